@@ -11,6 +11,27 @@ pub enum AgentRole {
     Director,
 }
 
+impl AgentRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgentRole::Coding => "coding",
+            AgentRole::Planner => "planner",
+            AgentRole::Manager => "manager",
+            AgentRole::Director => "director",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "coding" => Some(AgentRole::Coding),
+            "planner" => Some(AgentRole::Planner),
+            "manager" => Some(AgentRole::Manager),
+            "director" => Some(AgentRole::Director),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AgentState {
@@ -0,0 +1,371 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RetryConfig;
+
+/// How an agent's attempt at an issue ended.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptOutcome {
+    /// The agent finished with `agent done`.
+    Done,
+    /// The agent process exited without finishing.
+    Exited,
+    Aborted,
+    Deleted,
+    /// Handed back to the queue by the watchdog.
+    Requeued,
+    /// The agent claimed a different issue.
+    Abandoned,
+    /// The agent was gone after a daemon restart.
+    Lost,
+}
+
+impl AttemptOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AttemptOutcome::Done => "done",
+            AttemptOutcome::Exited => "exited",
+            AttemptOutcome::Aborted => "aborted",
+            AttemptOutcome::Deleted => "deleted",
+            AttemptOutcome::Requeued => "requeued",
+            AttemptOutcome::Abandoned => "abandoned",
+            AttemptOutcome::Lost => "lost",
+        }
+    }
+
+    pub fn is_failure(self) -> bool {
+        self != AttemptOutcome::Done
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub agent_id: String,
+    pub started_at_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<AttemptOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+}
+
+impl Attempt {
+    pub fn is_open(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    pub fn duration_ms(&self, now_ms: u64) -> u64 {
+        self.ended_at_ms
+            .unwrap_or(now_ms)
+            .saturating_sub(self.started_at_ms)
+    }
+
+    fn is_failure(&self) -> bool {
+        self.outcome.is_some_and(AttemptOutcome::is_failure)
+    }
+}
+
+/// Attempt history of one issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct IssueAttempts {
+    #[serde(default)]
+    pub attempts: Vec<Attempt>,
+    /// Attempts before this index were made before the last reset and do
+    /// not count toward `max-attempts`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub counted_from: usize,
+    /// When the issue was labelled as needing a human.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub needs_human_at_ms: Option<u64>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl IssueAttempts {
+    /// Failed attempts since the last reset.
+    pub fn failures(&self) -> usize {
+        self.attempts
+            .iter()
+            .skip(self.counted_from)
+            .filter(|a| a.is_failure())
+            .count()
+    }
+
+    /// Earliest time the issue may be picked up again, if it is backing off.
+    pub fn retry_at_ms(&self, policy: &RetryConfig) -> Option<u64> {
+        let last = self.attempts.iter().skip(self.counted_from).last()?;
+        if !last.is_failure() {
+            return None;
+        }
+        let ended = last.ended_at_ms?;
+        Some(ended.saturating_add(backoff_ms(policy, self.failures())))
+    }
+
+    pub fn exhausted(&self, policy: &RetryConfig) -> bool {
+        policy.max_attempts > 0 && self.failures() >= policy.max_attempts as usize
+    }
+}
+
+/// Whether an issue may be scheduled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RetryStatus {
+    Ready,
+    Backoff { until_ms: u64 },
+    NeedsHuman,
+}
+
+/// Delay before retrying after `failures` failed attempts: `backoff-secs`
+/// doubled for each failure after the first, capped at `max-backoff-secs`.
+pub fn backoff_ms(policy: &RetryConfig, failures: usize) -> u64 {
+    if failures == 0 {
+        return 0;
+    }
+    let exp = (failures - 1).min(32) as u32;
+    let secs = policy
+        .backoff_secs
+        .saturating_mul(2u64.saturating_pow(exp))
+        .min(policy.max_backoff_secs);
+    secs.saturating_mul(1000)
+}
+
+/// Attempt histories keyed by project, then issue id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct AttemptLog {
+    #[serde(default)]
+    pub projects: BTreeMap<String, BTreeMap<String, IssueAttempts>>,
+}
+
+impl AttemptLog {
+    pub fn get(&self, project: &str, issue_id: &str) -> Option<&IssueAttempts> {
+        self.projects.get(project)?.get(issue_id)
+    }
+
+    /// Records that `agent_id` started working on an issue. Any other open
+    /// attempt by the same agent ends as [`AttemptOutcome::Abandoned`].
+    pub fn start(&mut self, project: &str, issue_id: &str, agent_id: &str, now_ms: u64) {
+        if self.get(project, issue_id).is_some_and(|h| {
+            h.attempts
+                .iter()
+                .any(|a| a.is_open() && a.agent_id == agent_id)
+        }) {
+            return;
+        }
+        self.finish(agent_id, AttemptOutcome::Abandoned, None, now_ms);
+
+        self.projects
+            .entry(project.to_owned())
+            .or_default()
+            .entry(issue_id.to_owned())
+            .or_default()
+            .attempts
+            .push(Attempt {
+                agent_id: agent_id.to_owned(),
+                started_at_ms: now_ms,
+                ended_at_ms: None,
+                outcome: None,
+                exit_code: None,
+            });
+    }
+
+    /// Ends the open attempt of `agent_id`. Returns the project and issue it
+    /// was working on, or `None` if the agent had no open attempt.
+    pub fn finish(
+        &mut self,
+        agent_id: &str,
+        outcome: AttemptOutcome,
+        exit_code: Option<i32>,
+        now_ms: u64,
+    ) -> Option<(String, String)> {
+        for (project, issues) in self.projects.iter_mut() {
+            for (issue_id, history) in issues.iter_mut() {
+                let open = history
+                    .attempts
+                    .iter_mut()
+                    .find(|a| a.is_open() && a.agent_id == agent_id);
+                if let Some(attempt) = open {
+                    attempt.ended_at_ms = Some(now_ms);
+                    attempt.outcome = Some(outcome);
+                    attempt.exit_code = exit_code;
+                    return Some((project.clone(), issue_id.clone()));
+                }
+            }
+        }
+        None
+    }
+
+    /// Ends open attempts whose agent is not in `live_agents` as
+    /// [`AttemptOutcome::Lost`]. Returns the affected `(project, issue)` pairs.
+    pub fn close_lost(
+        &mut self,
+        live_agents: &BTreeSet<String>,
+        now_ms: u64,
+    ) -> Vec<(String, String)> {
+        let lost = self
+            .projects
+            .values()
+            .flat_map(|issues| issues.values())
+            .flat_map(|h| h.attempts.iter())
+            .filter(|a| a.is_open() && !live_agents.contains(&a.agent_id))
+            .map(|a| a.agent_id.clone())
+            .collect::<BTreeSet<_>>();
+        lost.iter()
+            .filter_map(|agent_id| self.finish(agent_id, AttemptOutcome::Lost, None, now_ms))
+            .collect()
+    }
+
+    pub fn mark_needs_human(&mut self, project: &str, issue_id: &str, now_ms: u64) {
+        if let Some(history) = self
+            .projects
+            .get_mut(project)
+            .and_then(|issues| issues.get_mut(issue_id))
+        {
+            history.needs_human_at_ms = Some(now_ms);
+        }
+    }
+
+    /// Starts counting attempts afresh, e.g. after a human cleared the
+    /// "needs human" label. Earlier attempts stay in the history.
+    pub fn reset(&mut self, project: &str, issue_id: &str) {
+        if let Some(history) = self
+            .projects
+            .get_mut(project)
+            .and_then(|issues| issues.get_mut(issue_id))
+        {
+            history.counted_from = history.attempts.len();
+            history.needs_human_at_ms = None;
+        }
+    }
+
+    pub fn remove_project(&mut self, project: &str) {
+        self.projects.remove(project);
+    }
+
+    pub fn retry_status(
+        &self,
+        project: &str,
+        issue_id: &str,
+        policy: &RetryConfig,
+        now_ms: u64,
+    ) -> RetryStatus {
+        let Some(history) = self.get(project, issue_id) else {
+            return RetryStatus::Ready;
+        };
+        if history.exhausted(policy) {
+            return RetryStatus::NeedsHuman;
+        }
+        match history.retry_at_ms(policy) {
+            Some(until_ms) if until_ms > now_ms => RetryStatus::Backoff { until_ms },
+            _ => RetryStatus::Ready,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC_MS: u64 = 1000;
+
+    fn policy() -> RetryConfig {
+        RetryConfig {
+            max_attempts: 3,
+            backoff_secs: 60,
+            max_backoff_secs: 150,
+            needs_human_label: "needs-human".to_owned(),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let policy = policy();
+        assert_eq!(backoff_ms(&policy, 0), 0);
+        assert_eq!(backoff_ms(&policy, 1), 60 * SEC_MS);
+        assert_eq!(backoff_ms(&policy, 2), 120 * SEC_MS);
+        assert_eq!(backoff_ms(&policy, 3), 150 * SEC_MS);
+        assert_eq!(backoff_ms(&policy, 99), 150 * SEC_MS);
+    }
+
+    #[test]
+    fn failures_back_off_then_need_a_human() {
+        let policy = policy();
+        let mut log = AttemptLog::default();
+
+        log.start("demo", "I-1", "a-1", 0);
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, 0),
+            RetryStatus::Ready
+        );
+
+        let ended = log.finish("a-1", AttemptOutcome::Exited, Some(1), 10 * SEC_MS);
+        assert_eq!(ended, Some(("demo".to_owned(), "I-1".to_owned())));
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, 20 * SEC_MS),
+            RetryStatus::Backoff {
+                until_ms: 70 * SEC_MS
+            }
+        );
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, 70 * SEC_MS),
+            RetryStatus::Ready
+        );
+
+        log.start("demo", "I-1", "a-2", 100 * SEC_MS);
+        log.finish("a-2", AttemptOutcome::Aborted, None, 110 * SEC_MS);
+        log.start("demo", "I-1", "a-3", 300 * SEC_MS);
+        log.finish("a-3", AttemptOutcome::Requeued, None, 310 * SEC_MS);
+
+        let history = log.get("demo", "I-1").unwrap();
+        assert_eq!(history.failures(), 3);
+        assert_eq!(history.attempts[0].duration_ms(0), 10 * SEC_MS);
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, 9_999 * SEC_MS),
+            RetryStatus::NeedsHuman
+        );
+
+        log.reset("demo", "I-1");
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, 9_999 * SEC_MS),
+            RetryStatus::Ready
+        );
+        assert_eq!(log.get("demo", "I-1").unwrap().attempts.len(), 3);
+    }
+
+    #[test]
+    fn finishing_successfully_does_not_back_off() {
+        let policy = policy();
+        let mut log = AttemptLog::default();
+        log.start("demo", "I-1", "a-1", 0);
+        log.finish("a-1", AttemptOutcome::Done, None, SEC_MS);
+        assert_eq!(
+            log.retry_status("demo", "I-1", &policy, SEC_MS),
+            RetryStatus::Ready
+        );
+        assert_eq!(
+            log.finish("a-1", AttemptOutcome::Exited, None, SEC_MS),
+            None
+        );
+    }
+
+    #[test]
+    fn switching_issues_abandons_and_restart_loses_attempts() {
+        let mut log = AttemptLog::default();
+        log.start("demo", "I-1", "a-1", 0);
+        log.start("demo", "I-1", "a-1", 5);
+        log.start("demo", "I-2", "a-1", 10);
+        log.start("demo", "I-3", "a-2", 10);
+
+        let first = &log.get("demo", "I-1").unwrap().attempts;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].outcome, Some(AttemptOutcome::Abandoned));
+
+        let live = BTreeSet::from(["a-2".to_owned()]);
+        let lost = log.close_lost(&live, 20);
+        assert_eq!(lost, vec![("demo".to_owned(), "I-2".to_owned())]);
+        assert!(log.get("demo", "I-3").unwrap().attempts[0].is_open());
+    }
+}
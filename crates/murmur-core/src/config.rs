@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::merge_message::{validate_merge_message_template, DEFAULT_MERGE_MESSAGE_TEMPLATE};
 use crate::paths::safe_join;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -136,6 +137,17 @@ pub struct OrchestrationConfig {
         alias = "silence_threshold_secs"
     )]
     pub silence_threshold_secs: u64,
+
+    /// Daemon-wide cap on concurrently running coding agents across all
+    /// projects. Free slots are split between projects by `weight`.
+    /// Unset or 0 means no global cap.
+    #[serde(
+        default,
+        rename = "max-total-agents",
+        alias = "max_total_agents",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_total_agents: Option<u32>,
 }
 
 impl OrchestrationConfig {
@@ -144,6 +156,333 @@ impl OrchestrationConfig {
     }
 }
 
+/// Per-project policy for ordering ready issues.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct SchedulingConfig {
+    /// Priority added to an issue for each matching label.
+    #[serde(
+        rename = "label-boosts",
+        alias = "label_boosts",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub label_boosts: BTreeMap<String, i32>,
+
+    /// Issues waiting longer than this are scheduled ahead of all others.
+    /// Set to 0 to disable starvation protection.
+    #[serde(rename = "starvation-secs", alias = "starvation_secs", default)]
+    pub starvation_secs: u64,
+}
+
+/// Step the watchdog takes against a stalled agent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatchdogAction {
+    /// Send the agent a message asking it to continue.
+    #[serde(rename = "nudge")]
+    Nudge,
+    /// Flag the agent as needing a human.
+    #[serde(rename = "needs-resolution")]
+    NeedsResolution,
+    /// Abort the agent and release its claim; the issue is held until restart.
+    #[serde(rename = "abort")]
+    Abort,
+    /// Remove the agent and hand its issue back to the ready queue.
+    #[serde(rename = "requeue")]
+    Requeue,
+}
+
+/// Per-project policy for agents that stop making progress.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchdogConfig {
+    /// Seconds a running agent may go without output. 0 disables the check.
+    #[serde(
+        rename = "running-timeout-secs",
+        alias = "running_timeout_secs",
+        default
+    )]
+    pub running_timeout_secs: u64,
+
+    /// Seconds an idle agent may hold a claim. 0 disables the check.
+    #[serde(rename = "idle-timeout-secs", alias = "idle_timeout_secs", default)]
+    pub idle_timeout_secs: u64,
+
+    /// Escalation steps; step `n` fires after `n` timeouts without activity.
+    #[serde(default = "WatchdogConfig::default_actions")]
+    pub actions: Vec<WatchdogAction>,
+
+    /// Message sent by the `nudge` action.
+    #[serde(
+        rename = "nudge-message",
+        alias = "nudge_message",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub nudge_message: Option<String>,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            running_timeout_secs: 0,
+            idle_timeout_secs: 0,
+            actions: Self::default_actions(),
+            nudge_message: None,
+        }
+    }
+}
+
+impl WatchdogConfig {
+    fn default_actions() -> Vec<WatchdogAction> {
+        vec![WatchdogAction::Nudge, WatchdogAction::NeedsResolution]
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        (self.running_timeout_secs > 0 || self.idle_timeout_secs > 0) && !self.actions.is_empty()
+    }
+}
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_RETRY_BACKOFF_SECS: u64 = 60;
+pub const DEFAULT_RETRY_MAX_BACKOFF_SECS: u64 = 3600;
+pub const DEFAULT_NEEDS_HUMAN_LABEL: &str = "needs-human";
+
+/// Per-project policy for retrying issues whose agents failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Failed attempts before the issue is handed to a human. 0 = unlimited.
+    #[serde(
+        rename = "max-attempts",
+        alias = "max_attempts",
+        default = "RetryConfig::default_max_attempts"
+    )]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry; doubled after each further failure.
+    #[serde(
+        rename = "backoff-secs",
+        alias = "backoff_secs",
+        default = "RetryConfig::default_backoff_secs"
+    )]
+    pub backoff_secs: u64,
+
+    #[serde(
+        rename = "max-backoff-secs",
+        alias = "max_backoff_secs",
+        default = "RetryConfig::default_max_backoff_secs"
+    )]
+    pub max_backoff_secs: u64,
+
+    /// Label added to the issue after the last attempt. Empty = comment only.
+    #[serde(
+        rename = "needs-human-label",
+        alias = "needs_human_label",
+        default = "RetryConfig::default_needs_human_label"
+    )]
+    pub needs_human_label: String,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff_secs: DEFAULT_RETRY_BACKOFF_SECS,
+            max_backoff_secs: DEFAULT_RETRY_MAX_BACKOFF_SECS,
+            needs_human_label: DEFAULT_NEEDS_HUMAN_LABEL.to_owned(),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn default_max_attempts() -> u32 {
+        DEFAULT_MAX_ATTEMPTS
+    }
+
+    fn default_backoff_secs() -> u64 {
+        DEFAULT_RETRY_BACKOFF_SECS
+    }
+
+    fn default_max_backoff_secs() -> u64 {
+        DEFAULT_RETRY_MAX_BACKOFF_SECS
+    }
+
+    fn default_needs_human_label() -> String {
+        DEFAULT_NEEDS_HUMAN_LABEL.to_owned()
+    }
+}
+
+pub const DEFAULT_VERIFY_MAX_ROUNDS: u32 = 3;
+pub const DEFAULT_VERIFY_TIMEOUT_SECS: u64 = 1800;
+
+/// Checks run in the agent worktree after rebase and before merging.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyConfig {
+    /// Shell commands, run in order; the first failure rejects the merge.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+
+    /// Failed rounds before the agent is marked `needs_resolution`.
+    #[serde(
+        rename = "max-rounds",
+        alias = "max_rounds",
+        default = "VerifyConfig::default_max_rounds"
+    )]
+    pub max_rounds: u32,
+
+    /// Time limit for each command.
+    #[serde(
+        rename = "timeout-secs",
+        alias = "timeout_secs",
+        default = "VerifyConfig::default_timeout_secs"
+    )]
+    pub timeout_secs: u64,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        Self {
+            commands: Vec::new(),
+            max_rounds: DEFAULT_VERIFY_MAX_ROUNDS,
+            timeout_secs: DEFAULT_VERIFY_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl VerifyConfig {
+    fn default_max_rounds() -> u32 {
+        DEFAULT_VERIFY_MAX_ROUNDS
+    }
+
+    fn default_timeout_secs() -> u64 {
+        DEFAULT_VERIFY_TIMEOUT_SECS
+    }
+}
+
+/// What happens when an agent's branch no longer rebases onto the base branch.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ConflictResolution {
+    /// Mark the agent `needs_resolution` and wait for a human.
+    #[serde(rename = "manual")]
+    #[default]
+    Manual,
+    /// Send the agent the conflict and let it rebase and resolve it.
+    #[serde(rename = "agent")]
+    Agent,
+}
+
+pub const DEFAULT_CONFLICT_MAX_ATTEMPTS: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictConfig {
+    #[serde(default)]
+    pub resolution: ConflictResolution,
+
+    /// Resolution turns before the agent is marked `needs_resolution`.
+    #[serde(
+        rename = "max-attempts",
+        alias = "max_attempts",
+        default = "ConflictConfig::default_max_attempts"
+    )]
+    pub max_attempts: u32,
+}
+
+impl Default for ConflictConfig {
+    fn default() -> Self {
+        Self {
+            resolution: ConflictResolution::default(),
+            max_attempts: DEFAULT_CONFLICT_MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl ConflictConfig {
+    fn default_max_attempts() -> u32 {
+        DEFAULT_CONFLICT_MAX_ATTEMPTS
+    }
+}
+
+/// Where agents' file-writing tools (`Write`, `Edit`, ...) may write. The
+/// hook always allows the agent's own worktree and the system temp dirs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfinementConfig {
+    /// Deny writes outside the allowed directories.
+    #[serde(default = "ConfinementConfig::default_enabled")]
+    pub enabled: bool,
+
+    /// Further directories writes may go to; a leading `~/` is the home
+    /// directory.
+    #[serde(
+        rename = "allowed-dirs",
+        alias = "allowed_dirs",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_dirs: Vec<String>,
+}
+
+impl Default for ConfinementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            allowed_dirs: Vec::new(),
+        }
+    }
+}
+
+impl ConfinementConfig {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+/// Agent role an issue can be routed to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RouteRole {
+    #[serde(rename = "coding")]
+    #[default]
+    Coding,
+    /// Plan and decompose the issue before any coding agent picks it up.
+    #[serde(rename = "planner")]
+    Planner,
+}
+
+/// Routes issues matching a label and/or type to a backend and role.
+/// Rules are evaluated in order; the first match wins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct RoutingRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(
+        rename = "type",
+        alias = "issue-type",
+        alias = "issue_type",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub issue_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<AgentBackend>,
+    #[serde(default)]
+    pub role: RouteRole,
+    /// Extra instructions appended to the kickoff prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kickoff: Option<String>,
+}
+
+impl RoutingRule {
+    pub fn matches(&self, labels: &[String], issue_type: &str) -> bool {
+        let label_ok = self
+            .label
+            .as_deref()
+            .is_none_or(|want| labels.iter().any(|l| l.eq_ignore_ascii_case(want)));
+        let type_ok = self
+            .issue_type
+            .as_deref()
+            .is_none_or(|want| issue_type.eq_ignore_ascii_case(want));
+        label_ok && type_ok
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     pub name: String,
@@ -158,12 +497,43 @@ pub struct ProjectConfig {
     )]
     pub max_agents: u16,
 
-    #[serde(rename = "issue-backend", alias = "issue_backend", default)]
-    pub issue_backend: IssueBackend,
+    /// Name of a registered issue backend, lowercased; built in are `tk`,
+    /// `github` (alias `gh`), `gitlab`, `gitea` (alias `forgejo`), `jira` and
+    /// `linear`.
+    #[serde(
+        rename = "issue-backend",
+        alias = "issue_backend",
+        default = "default_issue_backend",
+        deserialize_with = "deserialize_issue_backend"
+    )]
+    pub issue_backend: String,
 
     #[serde(rename = "permissions-checker", alias = "permissions_checker", default)]
     pub permissions_checker: PermissionsChecker,
 
+    /// Seconds a permission request waits for an answer before
+    /// `permission-timeout-action` applies (unset: wait indefinitely).
+    #[serde(
+        rename = "permission-timeout-secs",
+        alias = "permission_timeout_secs",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub permission_timeout_secs: Option<u64>,
+
+    #[serde(
+        rename = "permission-timeout-action",
+        alias = "permission_timeout_action",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub permission_timeout_action: Option<PermissionTimeoutAction>,
+
+    /// Tiers a permission request goes through after the rules, in order;
+    /// replaces `permissions-checker` when set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub escalation: Vec<EscalationStep>,
+
     #[serde(rename = "agent-backend", alias = "agent_backend", default)]
     pub agent_backend: AgentBackend,
 
@@ -176,9 +546,37 @@ pub struct ProjectConfig {
     #[serde(rename = "merge-strategy", alias = "merge_strategy", default)]
     pub merge_strategy: MergeStrategy,
 
+    /// Queued branches stacked, verified and landed together by a direct
+    /// merge (default 1: one at a time).
+    #[serde(
+        rename = "merge-batch-size",
+        alias = "merge_batch_size",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub merge_batch_size: Option<u32>,
+
+    /// Commit message for the squash and merge-commit strategies.
+    #[serde(
+        rename = "merge-message-template",
+        alias = "merge_message_template",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub merge_message_template: Option<String>,
+
     #[serde(rename = "allowed-authors", alias = "allowed_authors", default)]
     pub allowed_authors: Vec<String>,
 
+    /// When set, remote backends only fetch issues carrying this label for
+    /// `ready`, instead of the whole open backlog.
+    #[serde(
+        rename = "ready-label",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ready_label: Option<String>,
+
     #[serde(default)]
     pub autostart: bool,
 
@@ -188,6 +586,14 @@ pub struct ProjectConfig {
     #[serde(rename = "linear-project", default)]
     pub linear_project: Option<String>,
 
+    /// Jira project key, e.g. `APP`.
+    #[serde(rename = "jira-project", default)]
+    pub jira_project: Option<String>,
+
+    /// JQL selecting the issues the Jira backend reports as ready.
+    #[serde(rename = "jira-ready-jql", default)]
+    pub jira_ready_jql: Option<String>,
+
     /// Per-project silence threshold override in seconds.
     /// If set to a non-zero value, overrides the global orchestration setting.
     #[serde(
@@ -198,6 +604,31 @@ pub struct ProjectConfig {
     )]
     pub silence_threshold_secs: Option<u64>,
 
+    /// Share of the global agent pool relative to other projects (default 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduling: Option<SchedulingConfig>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routing: Vec<RoutingRule>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchdog: Option<WatchdogConfig>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryConfig>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<VerifyConfig>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<ConflictConfig>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confinement: Option<ConfinementConfig>,
+
     #[serde(flatten, default, skip_serializing_if = "BTreeMap::is_empty")]
     pub extra: BTreeMap<String, toml::Value>,
 }
@@ -210,23 +641,126 @@ impl ProjectConfig {
     pub fn effective_coding_backend(&self) -> AgentBackend {
         self.coding_backend.unwrap_or(self.agent_backend)
     }
+
+    pub fn effective_weight(&self) -> u32 {
+        self.weight.unwrap_or(1)
+    }
+
+    pub fn effective_merge_batch_size(&self) -> u32 {
+        self.merge_batch_size.unwrap_or(1)
+    }
+
+    pub fn effective_merge_message_template(&self) -> &str {
+        self.merge_message_template
+            .as_deref()
+            .unwrap_or(DEFAULT_MERGE_MESSAGE_TEMPLATE)
+    }
+
+    pub fn effective_scheduling(&self) -> SchedulingConfig {
+        self.scheduling.clone().unwrap_or_default()
+    }
+
+    pub fn effective_watchdog(&self) -> WatchdogConfig {
+        self.watchdog.clone().unwrap_or_default()
+    }
+
+    pub fn effective_retry(&self) -> RetryConfig {
+        self.retry.clone().unwrap_or_default()
+    }
+
+    pub fn effective_verify(&self) -> VerifyConfig {
+        self.verify.clone().unwrap_or_default()
+    }
+
+    pub fn effective_conflicts(&self) -> ConflictConfig {
+        self.conflicts.clone().unwrap_or_default()
+    }
+
+    pub fn effective_confinement(&self) -> ConfinementConfig {
+        self.confinement.clone().unwrap_or_default()
+    }
+
+    pub fn effective_permission_timeout_action(&self) -> PermissionTimeoutAction {
+        self.permission_timeout_action.unwrap_or_default()
+    }
+
+    /// The escalation chain with each tier's timeout filled in. Without an
+    /// `escalation` table, `permissions-checker` picks a one-tier chain.
+    pub fn effective_escalation(&self) -> Vec<EscalationStep> {
+        let chain = if self.escalation.is_empty() {
+            let tier = match self.permissions_checker {
+                PermissionsChecker::Manual => EscalationTier::Human,
+                PermissionsChecker::Llm => EscalationTier::Llm,
+            };
+            vec![EscalationStep {
+                tier,
+                timeout_secs: None,
+            }]
+        } else {
+            self.escalation.clone()
+        };
+        chain
+            .into_iter()
+            .map(|step| EscalationStep {
+                timeout_secs: match step.tier {
+                    EscalationTier::Llm => step.timeout_secs,
+                    EscalationTier::Manager => step
+                        .timeout_secs
+                        .or(Some(DEFAULT_MANAGER_ESCALATION_TIMEOUT_SECS)),
+                    EscalationTier::Human => step.timeout_secs.or(self.permission_timeout_secs),
+                },
+                ..step
+            })
+            .collect()
+    }
+
+    /// How long Claude lets the `PreToolUse` hook run: long enough for a
+    /// permission request to go through every tier in the daemon first.
+    pub fn permission_hook_timeout_secs(&self) -> u64 {
+        let budget: u64 = self
+            .effective_escalation()
+            .iter()
+            .filter_map(|step| step.timeout_secs)
+            .sum();
+        if budget == 0 {
+            return DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS;
+        }
+        (budget + 30).max(DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS)
+    }
+
+    /// Returns the first routing rule matching an issue's labels and type.
+    pub fn route_for(&self, labels: &[String], issue_type: &str) -> Option<&RoutingRule> {
+        self.routing.iter().find(|r| r.matches(labels, issue_type))
+    }
+
+    /// Backend an issue routed by `rule` runs on.
+    pub fn routed_backend(&self, rule: &RoutingRule) -> AgentBackend {
+        rule.backend.unwrap_or(match rule.role {
+            RouteRole::Coding => self.effective_coding_backend(),
+            RouteRole::Planner => self.effective_planner_backend(),
+        })
+    }
 }
 
 fn default_max_agents() -> u16 {
     3
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
-pub enum IssueBackend {
-    #[serde(rename = "tk")]
-    #[default]
-    Tk,
-    #[serde(rename = "github")]
-    Github,
-    #[serde(rename = "gh")]
-    Gh,
-    #[serde(rename = "linear")]
-    Linear,
+/// Issue backend of projects that do not name one.
+pub const DEFAULT_ISSUE_BACKEND: &str = "tk";
+
+fn default_issue_backend() -> String {
+    DEFAULT_ISSUE_BACKEND.to_owned()
+}
+
+/// Registry names are case-insensitive; storing them lowercased keeps
+/// comparisons such as the `linear` team check simple.
+fn deserialize_issue_backend<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    Ok(name.trim().to_ascii_lowercase())
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -238,6 +772,53 @@ pub enum PermissionsChecker {
     Llm,
 }
 
+/// What an unanswered permission request resolves to when
+/// `permission-timeout-secs` runs out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PermissionTimeoutAction {
+    #[serde(rename = "deny")]
+    #[default]
+    Deny,
+    #[serde(rename = "allow")]
+    Allow,
+}
+
+/// Seconds Claude gives the `PreToolUse` hook, which may be waiting on a
+/// permission request.
+pub const DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS: u64 = 5 * 60;
+
+/// Seconds the project manager agent has to answer an escalated request.
+pub const DEFAULT_MANAGER_ESCALATION_TIMEOUT_SECS: u64 = 2 * 60;
+
+/// Who a tier of the escalation chain asks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EscalationTier {
+    /// The `[llm_auth]` model; escalates when unsure or unreachable.
+    #[serde(rename = "llm")]
+    Llm,
+    /// The project's manager agent, over its chat.
+    #[serde(rename = "manager")]
+    Manager,
+    /// A pending request for a user to answer; always the last tier.
+    #[serde(rename = "human")]
+    Human,
+}
+
+/// One tier of a project's permission escalation chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationStep {
+    pub tier: EscalationTier,
+    /// Seconds the tier may take before the request moves on. For `human`
+    /// this defaults to `permission-timeout-secs`, whose action then applies.
+    #[serde(
+        rename = "timeout-secs",
+        alias = "timeout_secs",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_secs: Option<u64>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum AgentBackend {
     #[serde(rename = "claude")]
@@ -254,6 +835,21 @@ pub enum MergeStrategy {
     Direct,
     #[serde(rename = "pull-request")]
     PullRequest,
+    /// One commit per issue on the base branch; the agent branch keeps its
+    /// own commits.
+    #[serde(rename = "squash")]
+    Squash,
+    /// A merge commit joining the rebased agent branch.
+    #[serde(rename = "merge-commit")]
+    MergeCommit,
+}
+
+impl MergeStrategy {
+    /// Whether the daemon lands the branch on the base branch itself (as
+    /// opposed to opening a pull request).
+    pub fn is_direct(self) -> bool {
+        !matches!(self, MergeStrategy::PullRequest)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -270,12 +866,24 @@ pub enum ConfigError {
     RemoteUrlEmpty,
     #[error("max-agents must be > 0")]
     InvalidMaxAgents,
+    #[error("weight must be > 0")]
+    InvalidWeight,
+    #[error("merge-batch-size must be > 0")]
+    InvalidMergeBatchSize,
+    #[error("invalid merge-message-template: {reason}")]
+    InvalidMergeMessageTemplate { reason: String },
     #[error("unknown config key: {key}")]
     UnknownKey { key: String },
     #[error("invalid value for {key}: {value}")]
     InvalidValue { key: String, value: String },
     #[error("linear backend requires `linear-team`")]
     LinearTeamMissing,
+    #[error("jira backend requires `jira-project`")]
+    JiraProjectMissing,
+    #[error("routing rule {index}: {reason}")]
+    InvalidRoutingRule { index: usize, reason: String },
+    #[error("escalation tier {index}: {reason}")]
+    InvalidEscalationTier { index: usize, reason: String },
 }
 
 impl ConfigFile {
@@ -297,12 +905,41 @@ impl ConfigFile {
                 return Err(ConfigError::InvalidMaxAgents);
             }
 
-            if p.issue_backend == IssueBackend::Linear {
+            if p.weight == Some(0) {
+                return Err(ConfigError::InvalidWeight);
+            }
+
+            if p.merge_batch_size == Some(0) {
+                return Err(ConfigError::InvalidMergeBatchSize);
+            }
+
+            if let Some(template) = &p.merge_message_template {
+                validate_merge_message_template(template)
+                    .map_err(|reason| ConfigError::InvalidMergeMessageTemplate { reason })?;
+            }
+
+            if p.issue_backend == "linear" {
                 let has_team = p.linear_team.as_ref().is_some_and(|s| !s.trim().is_empty());
                 if !has_team {
                     return Err(ConfigError::LinearTeamMissing);
                 }
             }
+
+            if p.issue_backend == "jira" {
+                let has_project = p
+                    .jira_project
+                    .as_ref()
+                    .is_some_and(|s| !s.trim().is_empty());
+                if !has_project {
+                    return Err(ConfigError::JiraProjectMissing);
+                }
+            }
+
+            for (index, rule) in p.routing.iter().enumerate() {
+                validate_routing_rule(index, rule)?;
+            }
+
+            validate_escalation(&p.escalation)?;
         }
 
         Ok(())
@@ -317,6 +954,14 @@ impl ConfigFile {
         self.polling.clone().unwrap_or_default()
     }
 
+    /// Returns the global agent cap, or `None` when unlimited.
+    pub fn max_total_agents(&self) -> Option<u32> {
+        self.orchestration
+            .as_ref()
+            .and_then(|o| o.max_total_agents)
+            .filter(|n| *n > 0)
+    }
+
     /// Returns the effective silence threshold for a project.
     /// Checks project-level override first, then falls back to global config.
     pub fn silence_threshold_for_project(&self, project: &str) -> u64 {
@@ -393,19 +1038,48 @@ impl ConfigFile {
                 }
                 updated.max_agents = parsed;
             }
-            "autostart" => {
-                let parsed: bool = value.parse().map_err(|_| ConfigError::InvalidValue {
+            "weight" => {
+                let parsed: u32 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                if parsed == 0 {
+                    return Err(ConfigError::InvalidWeight);
+                }
+                updated.weight = Some(parsed);
+            }
+            "autostart" => {
+                let parsed: bool = value.parse().map_err(|_| ConfigError::InvalidValue {
                     key: key.clone(),
                     value: value.to_owned(),
                 })?;
                 updated.autostart = parsed;
             }
             "issue-backend" => {
-                updated.issue_backend = parse_enum::<IssueBackend>(&key, value)?;
+                // Any name: the daemon checks it against its registry.
+                let name = value.trim().to_ascii_lowercase();
+                if name.is_empty() {
+                    return Err(ConfigError::InvalidValue {
+                        key,
+                        value: value.to_owned(),
+                    });
+                }
+                updated.issue_backend = name;
             }
             "permissions-checker" => {
                 updated.permissions_checker = parse_enum::<PermissionsChecker>(&key, value)?;
             }
+            "permission-timeout-secs" => {
+                let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                updated.permission_timeout_secs = (parsed > 0).then_some(parsed);
+            }
+            "permission-timeout-action" => {
+                updated.permission_timeout_action =
+                    Some(parse_enum::<PermissionTimeoutAction>(&key, value)?);
+            }
             "agent-backend" => {
                 updated.agent_backend = parse_enum::<AgentBackend>(&key, value)?;
             }
@@ -418,6 +1092,21 @@ impl ConfigFile {
             "merge-strategy" => {
                 updated.merge_strategy = parse_enum::<MergeStrategy>(&key, value)?;
             }
+            "merge-batch-size" => {
+                let parsed: u32 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                if parsed == 0 {
+                    return Err(ConfigError::InvalidMergeBatchSize);
+                }
+                updated.merge_batch_size = Some(parsed);
+            }
+            "merge-message-template" => {
+                validate_merge_message_template(value)
+                    .map_err(|reason| ConfigError::InvalidMergeMessageTemplate { reason })?;
+                updated.merge_message_template = Some(value.to_owned());
+            }
             "allowed-authors" => {
                 let authors = value
                     .split(',')
@@ -427,12 +1116,22 @@ impl ConfigFile {
                     .collect::<Vec<_>>();
                 updated.allowed_authors = authors;
             }
+            "ready-label" => {
+                updated.ready_label = (!value.trim().is_empty()).then(|| value.trim().to_owned());
+            }
             "linear-team" => {
                 updated.linear_team = (!value.trim().is_empty()).then(|| value.to_owned());
             }
             "linear-project" => {
                 updated.linear_project = (!value.trim().is_empty()).then(|| value.to_owned());
             }
+            "jira-project" => {
+                updated.jira_project =
+                    (!value.trim().is_empty()).then(|| value.trim().to_ascii_uppercase());
+            }
+            "jira-ready-jql" => {
+                updated.jira_ready_jql = (!value.trim().is_empty()).then(|| value.to_owned());
+            }
             "silence-threshold-secs" => {
                 let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
                     key: key.clone(),
@@ -440,6 +1139,135 @@ impl ConfigFile {
                 })?;
                 updated.silence_threshold_secs = if parsed > 0 { Some(parsed) } else { None };
             }
+            "label-boosts" => {
+                let mut scheduling = updated.effective_scheduling();
+                scheduling.label_boosts = parse_label_boosts(&key, value)?;
+                updated.scheduling = Some(scheduling);
+            }
+            "starvation-secs" => {
+                let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut scheduling = updated.effective_scheduling();
+                scheduling.starvation_secs = parsed;
+                updated.scheduling = Some(scheduling);
+            }
+            "watchdog-running-secs" | "watchdog-idle-secs" => {
+                let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut watchdog = updated.effective_watchdog();
+                if key == "watchdog-running-secs" {
+                    watchdog.running_timeout_secs = parsed;
+                } else {
+                    watchdog.idle_timeout_secs = parsed;
+                }
+                updated.watchdog = Some(watchdog);
+            }
+            "watchdog-actions" => {
+                let actions = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| parse_enum::<WatchdogAction>(&key, s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut watchdog = updated.effective_watchdog();
+                watchdog.actions = actions;
+                updated.watchdog = Some(watchdog);
+            }
+            "max-attempts" => {
+                let parsed: u32 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut retry = updated.effective_retry();
+                retry.max_attempts = parsed;
+                updated.retry = Some(retry);
+            }
+            "retry-backoff-secs" | "retry-max-backoff-secs" => {
+                let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut retry = updated.effective_retry();
+                if key == "retry-backoff-secs" {
+                    retry.backoff_secs = parsed;
+                } else {
+                    retry.max_backoff_secs = parsed;
+                }
+                updated.retry = Some(retry);
+            }
+            "needs-human-label" => {
+                let mut retry = updated.effective_retry();
+                retry.needs_human_label = value.trim().to_owned();
+                updated.retry = Some(retry);
+            }
+            "verify-commands" => {
+                let mut verify = updated.effective_verify();
+                verify.commands = value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+                updated.verify = Some(verify);
+            }
+            "verify-max-rounds" => {
+                let parsed: u32 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut verify = updated.effective_verify();
+                verify.max_rounds = parsed;
+                updated.verify = Some(verify);
+            }
+            "verify-timeout-secs" => {
+                let parsed: u64 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut verify = updated.effective_verify();
+                verify.timeout_secs = parsed;
+                updated.verify = Some(verify);
+            }
+            "conflict-resolution" => {
+                let mut conflicts = updated.effective_conflicts();
+                conflicts.resolution = parse_enum::<ConflictResolution>(&key, value)?;
+                updated.conflicts = Some(conflicts);
+            }
+            "conflict-max-attempts" => {
+                let parsed: u32 = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut conflicts = updated.effective_conflicts();
+                conflicts.max_attempts = parsed;
+                updated.conflicts = Some(conflicts);
+            }
+            "write-confinement" => {
+                let parsed: bool = value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: key.clone(),
+                    value: value.to_owned(),
+                })?;
+                let mut confinement = updated.effective_confinement();
+                confinement.enabled = parsed;
+                updated.confinement = Some(confinement);
+            }
+            "escalation" => {
+                updated.escalation = parse_escalation(&key, value)?;
+            }
+            "write-allowed-dirs" => {
+                let mut confinement = updated.effective_confinement();
+                confinement.allowed_dirs = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned)
+                    .collect();
+                updated.confinement = Some(confinement);
+            }
             _ => {
                 return Err(ConfigError::UnknownKey { key });
             }
@@ -465,9 +1293,16 @@ impl ConfigFile {
             "name" => toml::Value::String(project.name.clone()),
             "remote-url" => toml::Value::String(project.remote_url.clone()),
             "max-agents" => toml::Value::Integer(project.max_agents as i64),
+            "weight" => toml::Value::Integer(project.effective_weight() as i64),
             "autostart" => toml::Value::Boolean(project.autostart),
-            "issue-backend" => toml::Value::String(format_enum(project.issue_backend)),
+            "issue-backend" => toml::Value::String(project.issue_backend.clone()),
             "permissions-checker" => toml::Value::String(format_enum(project.permissions_checker)),
+            "permission-timeout-secs" => {
+                toml::Value::Integer(project.permission_timeout_secs.unwrap_or(0) as i64)
+            }
+            "permission-timeout-action" => {
+                toml::Value::String(format_enum(project.effective_permission_timeout_action()))
+            }
             "agent-backend" => toml::Value::String(format_enum(project.agent_backend)),
             "planner-backend" => {
                 toml::Value::String(format_enum(project.effective_planner_backend()))
@@ -476,6 +1311,10 @@ impl ConfigFile {
                 toml::Value::String(format_enum(project.effective_coding_backend()))
             }
             "merge-strategy" => toml::Value::String(format_enum(project.merge_strategy)),
+            "merge-batch-size" => toml::Value::Integer(project.effective_merge_batch_size() as i64),
+            "merge-message-template" => {
+                toml::Value::String(project.effective_merge_message_template().to_owned())
+            }
             "allowed-authors" => toml::Value::Array(
                 project
                     .allowed_authors
@@ -483,13 +1322,56 @@ impl ConfigFile {
                     .map(|s| toml::Value::String(s.clone()))
                     .collect(),
             ),
+            "ready-label" => toml::Value::String(project.ready_label.clone().unwrap_or_default()),
             "linear-team" => toml::Value::String(project.linear_team.clone().unwrap_or_default()),
             "linear-project" => {
                 toml::Value::String(project.linear_project.clone().unwrap_or_default())
             }
+            "jira-project" => toml::Value::String(project.jira_project.clone().unwrap_or_default()),
+            "jira-ready-jql" => {
+                toml::Value::String(project.jira_ready_jql.clone().unwrap_or_default())
+            }
             "silence-threshold-secs" => {
                 toml::Value::Integer(project.silence_threshold_secs.unwrap_or(0) as i64)
             }
+            "label-boosts" => format_label_boosts(&project.effective_scheduling().label_boosts),
+            "starvation-secs" => {
+                toml::Value::Integer(project.effective_scheduling().starvation_secs as i64)
+            }
+            "routing" => {
+                toml::Value::try_from(&project.routing).unwrap_or(toml::Value::Array(vec![]))
+            }
+            "escalation" => format_escalation(&project.effective_escalation()),
+            "watchdog-running-secs" => {
+                toml::Value::Integer(project.effective_watchdog().running_timeout_secs as i64)
+            }
+            "watchdog-idle-secs" => {
+                toml::Value::Integer(project.effective_watchdog().idle_timeout_secs as i64)
+            }
+            "watchdog-actions" => format_watchdog_actions(&project.effective_watchdog().actions),
+            "max-attempts" => toml::Value::Integer(project.effective_retry().max_attempts as i64),
+            "retry-backoff-secs" => {
+                toml::Value::Integer(project.effective_retry().backoff_secs as i64)
+            }
+            "retry-max-backoff-secs" => {
+                toml::Value::Integer(project.effective_retry().max_backoff_secs as i64)
+            }
+            "needs-human-label" => toml::Value::String(project.effective_retry().needs_human_label),
+            "verify-commands" => format_strings(&project.effective_verify().commands),
+            "verify-max-rounds" => {
+                toml::Value::Integer(project.effective_verify().max_rounds as i64)
+            }
+            "verify-timeout-secs" => {
+                toml::Value::Integer(project.effective_verify().timeout_secs as i64)
+            }
+            "conflict-resolution" => {
+                toml::Value::String(format_enum(project.effective_conflicts().resolution))
+            }
+            "conflict-max-attempts" => {
+                toml::Value::Integer(project.effective_conflicts().max_attempts as i64)
+            }
+            "write-confinement" => toml::Value::Boolean(project.effective_confinement().enabled),
+            "write-allowed-dirs" => format_strings(&project.effective_confinement().allowed_dirs),
             _ => return Err(ConfigError::UnknownKey { key }),
         };
 
@@ -516,18 +1398,30 @@ pub fn project_config_map(project: &ProjectConfig) -> BTreeMap<String, toml::Val
             "max-agents".to_owned(),
             toml::Value::Integer(project.max_agents as i64),
         ),
+        (
+            "weight".to_owned(),
+            toml::Value::Integer(project.effective_weight() as i64),
+        ),
         (
             "autostart".to_owned(),
             toml::Value::Boolean(project.autostart),
         ),
         (
             "issue-backend".to_owned(),
-            toml::Value::String(format_enum(project.issue_backend)),
+            toml::Value::String(project.issue_backend.clone()),
         ),
         (
             "permissions-checker".to_owned(),
             toml::Value::String(format_enum(project.permissions_checker)),
         ),
+        (
+            "permission-timeout-secs".to_owned(),
+            toml::Value::Integer(project.permission_timeout_secs.unwrap_or(0) as i64),
+        ),
+        (
+            "permission-timeout-action".to_owned(),
+            toml::Value::String(format_enum(project.effective_permission_timeout_action())),
+        ),
         (
             "agent-backend".to_owned(),
             toml::Value::String(format_enum(project.agent_backend)),
@@ -544,6 +1438,14 @@ pub fn project_config_map(project: &ProjectConfig) -> BTreeMap<String, toml::Val
             "merge-strategy".to_owned(),
             toml::Value::String(format_enum(project.merge_strategy)),
         ),
+        (
+            "merge-batch-size".to_owned(),
+            toml::Value::Integer(project.effective_merge_batch_size() as i64),
+        ),
+        (
+            "merge-message-template".to_owned(),
+            toml::Value::String(project.effective_merge_message_template().to_owned()),
+        ),
         (
             "allowed-authors".to_owned(),
             toml::Value::Array(
@@ -554,6 +1456,10 @@ pub fn project_config_map(project: &ProjectConfig) -> BTreeMap<String, toml::Val
                     .collect(),
             ),
         ),
+        (
+            "ready-label".to_owned(),
+            toml::Value::String(project.ready_label.clone().unwrap_or_default()),
+        ),
         (
             "linear-team".to_owned(),
             toml::Value::String(project.linear_team.clone().unwrap_or_default()),
@@ -562,13 +1468,140 @@ pub fn project_config_map(project: &ProjectConfig) -> BTreeMap<String, toml::Val
             "linear-project".to_owned(),
             toml::Value::String(project.linear_project.clone().unwrap_or_default()),
         ),
+        (
+            "jira-project".to_owned(),
+            toml::Value::String(project.jira_project.clone().unwrap_or_default()),
+        ),
+        (
+            "jira-ready-jql".to_owned(),
+            toml::Value::String(project.jira_ready_jql.clone().unwrap_or_default()),
+        ),
         (
             "silence-threshold-secs".to_owned(),
             toml::Value::Integer(project.silence_threshold_secs.unwrap_or(0) as i64),
         ),
+        (
+            "label-boosts".to_owned(),
+            format_label_boosts(&project.effective_scheduling().label_boosts),
+        ),
+        (
+            "starvation-secs".to_owned(),
+            toml::Value::Integer(project.effective_scheduling().starvation_secs as i64),
+        ),
+        (
+            "routing".to_owned(),
+            toml::Value::try_from(&project.routing).unwrap_or(toml::Value::Array(vec![])),
+        ),
+        (
+            "escalation".to_owned(),
+            format_escalation(&project.effective_escalation()),
+        ),
+        (
+            "watchdog-running-secs".to_owned(),
+            toml::Value::Integer(project.effective_watchdog().running_timeout_secs as i64),
+        ),
+        (
+            "watchdog-idle-secs".to_owned(),
+            toml::Value::Integer(project.effective_watchdog().idle_timeout_secs as i64),
+        ),
+        (
+            "watchdog-actions".to_owned(),
+            format_watchdog_actions(&project.effective_watchdog().actions),
+        ),
+        (
+            "max-attempts".to_owned(),
+            toml::Value::Integer(project.effective_retry().max_attempts as i64),
+        ),
+        (
+            "retry-backoff-secs".to_owned(),
+            toml::Value::Integer(project.effective_retry().backoff_secs as i64),
+        ),
+        (
+            "retry-max-backoff-secs".to_owned(),
+            toml::Value::Integer(project.effective_retry().max_backoff_secs as i64),
+        ),
+        (
+            "needs-human-label".to_owned(),
+            toml::Value::String(project.effective_retry().needs_human_label),
+        ),
+        (
+            "verify-commands".to_owned(),
+            format_strings(&project.effective_verify().commands),
+        ),
+        (
+            "verify-max-rounds".to_owned(),
+            toml::Value::Integer(project.effective_verify().max_rounds as i64),
+        ),
+        (
+            "verify-timeout-secs".to_owned(),
+            toml::Value::Integer(project.effective_verify().timeout_secs as i64),
+        ),
+        (
+            "conflict-resolution".to_owned(),
+            toml::Value::String(format_enum(project.effective_conflicts().resolution)),
+        ),
+        (
+            "conflict-max-attempts".to_owned(),
+            toml::Value::Integer(project.effective_conflicts().max_attempts as i64),
+        ),
+        (
+            "write-confinement".to_owned(),
+            toml::Value::Boolean(project.effective_confinement().enabled),
+        ),
+        (
+            "write-allowed-dirs".to_owned(),
+            format_strings(&project.effective_confinement().allowed_dirs),
+        ),
     ])
 }
 
+/// Parses `label=boost` pairs, e.g. `urgent=10,frontend=2`.
+fn parse_label_boosts(key: &str, value: &str) -> Result<BTreeMap<String, i32>, ConfigError> {
+    let invalid = || ConfigError::InvalidValue {
+        key: key.to_owned(),
+        value: value.to_owned(),
+    };
+
+    let mut boosts = BTreeMap::new();
+    for pair in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (label, boost) = pair.split_once('=').ok_or_else(invalid)?;
+        let label = label.trim();
+        if label.is_empty() {
+            return Err(invalid());
+        }
+        let boost: i32 = boost.trim().parse().map_err(|_| invalid())?;
+        boosts.insert(label.to_owned(), boost);
+    }
+    Ok(boosts)
+}
+
+fn format_label_boosts(boosts: &BTreeMap<String, i32>) -> toml::Value {
+    toml::Value::Table(
+        boosts
+            .iter()
+            .map(|(label, boost)| (label.clone(), toml::Value::Integer(*boost as i64)))
+            .collect(),
+    )
+}
+
+fn format_watchdog_actions(actions: &[WatchdogAction]) -> toml::Value {
+    toml::Value::Array(
+        actions
+            .iter()
+            .map(|a| toml::Value::String(format_enum(*a)))
+            .collect(),
+    )
+}
+
+fn format_strings(values: &[String]) -> toml::Value {
+    toml::Value::Array(
+        values
+            .iter()
+            .map(|v| toml::Value::String(v.clone()))
+            .collect(),
+    )
+}
+
 fn validate_project_name(name: &str) -> Result<(), ConfigError> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
@@ -593,6 +1626,49 @@ fn validate_project_name(name: &str) -> Result<(), ConfigError> {
     Ok(())
 }
 
+fn validate_routing_rule(index: usize, rule: &RoutingRule) -> Result<(), ConfigError> {
+    let invalid = |reason: &str| ConfigError::InvalidRoutingRule {
+        index,
+        reason: reason.to_owned(),
+    };
+
+    let has_label = rule.label.as_ref().is_some_and(|s| !s.trim().is_empty());
+    let has_type = rule
+        .issue_type
+        .as_ref()
+        .is_some_and(|s| !s.trim().is_empty());
+    if !has_label && !has_type {
+        return Err(invalid("requires `label` or `type`"));
+    }
+    // The planner clears the label once it is done; without one the issue
+    // would be routed back to a planner forever.
+    if rule.role == RouteRole::Planner && !has_label {
+        return Err(invalid("planner routes require `label`"));
+    }
+    Ok(())
+}
+
+fn validate_escalation(chain: &[EscalationStep]) -> Result<(), ConfigError> {
+    for (index, step) in chain.iter().enumerate() {
+        let invalid = |reason: &str| ConfigError::InvalidEscalationTier {
+            index,
+            reason: reason.to_owned(),
+        };
+        if chain[..index].iter().any(|s| s.tier == step.tier) {
+            return Err(invalid("duplicate tier"));
+        }
+        // Nothing answers for a human who has not answered yet, so the
+        // tiers after one would never run.
+        if step.tier == EscalationTier::Human && index + 1 != chain.len() {
+            return Err(invalid("`human` must be the last tier"));
+        }
+        if step.timeout_secs == Some(0) {
+            return Err(invalid("timeout-secs must be > 0"));
+        }
+    }
+    Ok(())
+}
+
 fn normalize_key(key: &str) -> String {
     key.trim().replace('_', "-").to_ascii_lowercase()
 }
@@ -619,6 +1695,41 @@ where
     })
 }
 
+/// Parses `tier[:timeout-secs],...`, e.g. `llm:30,manager,human`. An empty
+/// value clears the chain.
+fn parse_escalation(key: &str, value: &str) -> Result<Vec<EscalationStep>, ConfigError> {
+    let invalid = || ConfigError::InvalidValue {
+        key: key.to_owned(),
+        value: value.to_owned(),
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|part| {
+            let (tier, secs) = match part.split_once(':') {
+                Some((tier, secs)) => (tier, Some(secs.trim().parse().map_err(|_| invalid())?)),
+                None => (part, None),
+            };
+            Ok(EscalationStep {
+                tier: parse_enum(key, tier)?,
+                timeout_secs: secs,
+            })
+        })
+        .collect()
+}
+
+fn format_escalation(chain: &[EscalationStep]) -> toml::Value {
+    let steps: Vec<String> = chain
+        .iter()
+        .map(|step| match step.timeout_secs {
+            Some(secs) => format!("{}:{secs}", format_enum(step.tier)),
+            None => format_enum(step.tier),
+        })
+        .collect();
+    toml::Value::String(steps.join(","))
+}
+
 fn format_enum<T>(value: T) -> String
 where
     T: Serialize,
@@ -639,17 +1750,33 @@ mod tests {
                     name: "demo".to_owned(),
                     remote_url: "file:///tmp/demo.git".to_owned(),
                     max_agents: 3,
-                    issue_backend: IssueBackend::Tk,
+                    issue_backend: DEFAULT_ISSUE_BACKEND.to_owned(),
                     permissions_checker: PermissionsChecker::Manual,
                     agent_backend: AgentBackend::Codex,
                     planner_backend: None,
                     coding_backend: None,
                     merge_strategy: MergeStrategy::Direct,
                     allowed_authors: vec![],
+                    ready_label: None,
                     autostart: false,
                     linear_team: None,
                     linear_project: None,
+                    jira_project: None,
+                    jira_ready_jql: None,
                     silence_threshold_secs: None,
+                    permission_timeout_secs: None,
+                    permission_timeout_action: None,
+                    escalation: Vec::new(),
+                    weight: None,
+                    merge_batch_size: None,
+                    merge_message_template: None,
+                    scheduling: None,
+                    routing: Vec::new(),
+                    watchdog: None,
+                    retry: None,
+                    verify: None,
+                    conflicts: None,
+                    confinement: None,
                     extra: BTreeMap::new(),
                 },
                 ProjectConfig {
@@ -686,7 +1813,7 @@ mod tests {
             projects: vec![ProjectConfig {
                 name: "linear".to_owned(),
                 remote_url: "file:///tmp/demo.git".to_owned(),
-                issue_backend: IssueBackend::Linear,
+                issue_backend: "linear".to_owned(),
                 linear_team: None,
                 ..default_project("linear")
             }],
@@ -695,6 +1822,50 @@ mod tests {
 
         let err = cfg.validate().unwrap_err();
         assert!(matches!(err, ConfigError::LinearTeamMissing));
+
+        let cfg: ConfigFile = toml::from_str(
+            r#"
+[[projects]]
+name = "linear"
+remote-url = "file:///tmp/demo.git"
+issue-backend = " Linear "
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.projects[0].issue_backend, "linear");
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(err, ConfigError::LinearTeamMissing));
+    }
+
+    #[test]
+    fn validate_rejects_jira_without_project() {
+        let mut cfg = ConfigFile {
+            projects: vec![ProjectConfig {
+                issue_backend: "jira".to_owned(),
+                ..default_project("jira")
+            }],
+            ..Default::default()
+        };
+
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(err, ConfigError::JiraProjectMissing));
+
+        cfg = cfg
+            .set_project_key("jira", "jira-project", " app ")
+            .unwrap();
+        cfg = cfg
+            .set_project_key(
+                "jira",
+                "jira-ready-jql",
+                "project = APP AND sprint in openSprints()",
+            )
+            .unwrap();
+        cfg.validate().unwrap();
+        assert_eq!(cfg.projects[0].jira_project.as_deref(), Some("APP"));
+        assert_eq!(
+            cfg.get_project_key_value("jira", "jira-ready-jql").unwrap(),
+            toml::Value::String("project = APP AND sprint in openSprints()".to_owned())
+        );
     }
 
     #[test]
@@ -711,6 +1882,8 @@ mod tests {
             .set_project_key("demo", "issue-backend", "github")
             .unwrap()
             .set_project_key("demo", "allowed-authors", "alice,bob")
+            .unwrap()
+            .set_project_key("demo", "ready-label", " agent-ready ")
             .unwrap();
 
         assert_eq!(
@@ -725,6 +1898,15 @@ mod tests {
             cfg.get_project_key_value("demo", "issue-backend").unwrap(),
             toml::Value::String("github".to_owned())
         );
+        // Backends are registered by name in the daemon, so any name goes.
+        let custom = cfg
+            .set_project_key("demo", "issue-backend", " Tracker ")
+            .unwrap();
+        assert_eq!(custom.project("demo").unwrap().issue_backend, "tracker");
+        let err = cfg
+            .set_project_key("demo", "issue-backend", " ")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
         assert_eq!(
             cfg.get_project_key_value("demo", "allowed-authors")
                 .unwrap(),
@@ -733,6 +1915,508 @@ mod tests {
                 toml::Value::String("bob".to_owned()),
             ])
         );
+        assert_eq!(
+            cfg.get_project_key_value("demo", "ready-label").unwrap(),
+            toml::Value::String("agent-ready".to_owned())
+        );
+    }
+
+    #[test]
+    fn scheduling_keys_round_trip_through_toml() {
+        let cfg = ConfigFile::default()
+            .add_project(default_project("demo"))
+            .unwrap()
+            .set_project_key("demo", "label-boosts", "urgent=10, frontend=2")
+            .unwrap()
+            .set_project_key("demo", "starvation-secs", "3600")
+            .unwrap();
+
+        let raw = toml::to_string(&cfg).unwrap();
+        let back: ConfigFile = toml::from_str(&raw).unwrap();
+        let scheduling = back.project("demo").unwrap().effective_scheduling();
+        assert_eq!(scheduling.starvation_secs, 3600);
+        assert_eq!(scheduling.label_boosts.get("urgent"), Some(&10));
+        assert_eq!(scheduling.label_boosts.get("frontend"), Some(&2));
+
+        let err = cfg
+            .set_project_key("demo", "label-boosts", "urgent")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn global_agent_cap_and_weight() {
+        let cfg: ConfigFile = toml::from_str(
+            r#"
+[orchestration]
+max-total-agents = 0
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.max_total_agents(), None);
+
+        let cfg: ConfigFile = toml::from_str(
+            r#"
+[orchestration]
+max-total-agents = 8
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.max_total_agents(), Some(8));
+
+        let cfg = cfg
+            .add_project(default_project("demo"))
+            .unwrap()
+            .set_project_key("demo", "weight", "3")
+            .unwrap();
+        assert_eq!(cfg.project("demo").unwrap().effective_weight(), 3);
+
+        let err = cfg.set_project_key("demo", "weight", "0").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidWeight));
+    }
+
+    #[test]
+    fn merge_batch_size_key() {
+        let cfg = ConfigFile::default()
+            .add_project(default_project("demo"))
+            .unwrap();
+        assert_eq!(cfg.project("demo").unwrap().effective_merge_batch_size(), 1);
+
+        let cfg = cfg
+            .set_project_key("demo", "merge_batch_size", "4")
+            .unwrap();
+        assert_eq!(cfg.project("demo").unwrap().effective_merge_batch_size(), 4);
+        assert_eq!(
+            cfg.get_project_key_value("demo", "merge-batch-size")
+                .unwrap(),
+            toml::Value::Integer(4)
+        );
+
+        let err = cfg
+            .set_project_key("demo", "merge-batch-size", "0")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidMergeBatchSize));
+    }
+
+    #[test]
+    fn merge_strategies_and_message_template() {
+        let cfg = ConfigFile::default()
+            .add_project(default_project("demo"))
+            .unwrap();
+        assert_eq!(
+            cfg.project("demo")
+                .unwrap()
+                .effective_merge_message_template(),
+            DEFAULT_MERGE_MESSAGE_TEMPLATE
+        );
+
+        let cfg = cfg
+            .set_project_key("demo", "merge-strategy", "squash")
+            .unwrap()
+            .set_project_key("demo", "merge_message_template", "{title} ({issue_id})")
+            .unwrap();
+        let project = cfg.project("demo").unwrap();
+        assert_eq!(project.merge_strategy, MergeStrategy::Squash);
+        assert!(project.merge_strategy.is_direct());
+        assert_eq!(
+            cfg.get_project_key_value("demo", "merge-message-template")
+                .unwrap(),
+            toml::Value::String("{title} ({issue_id})".to_owned())
+        );
+
+        let cfg = cfg
+            .set_project_key("demo", "merge-strategy", "merge-commit")
+            .unwrap();
+        assert_eq!(
+            cfg.project("demo").unwrap().merge_strategy,
+            MergeStrategy::MergeCommit
+        );
+        assert!(!MergeStrategy::PullRequest.is_direct());
+
+        let err = cfg
+            .set_project_key("demo", "merge-message-template", "{author}")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidMergeMessageTemplate { .. }
+        ));
+    }
+
+    #[test]
+    fn watchdog_table_and_keys() {
+        let cfg: ConfigFile = toml::from_str(
+            r#"
+[[projects]]
+name = "demo"
+remote-url = "https://example.com/repo.git"
+
+[projects.watchdog]
+running-timeout-secs = 3600
+actions = ["nudge", "abort"]
+"#,
+        )
+        .unwrap();
+        let watchdog = cfg.project("demo").unwrap().effective_watchdog();
+        assert!(watchdog.is_enabled());
+        assert_eq!(watchdog.idle_timeout_secs, 0);
+        assert_eq!(
+            watchdog.actions,
+            vec![WatchdogAction::Nudge, WatchdogAction::Abort]
+        );
+
+        let cfg = cfg
+            .set_project_key("demo", "watchdog-idle-secs", "600")
+            .unwrap()
+            .set_project_key(
+                "demo",
+                "watchdog_actions",
+                "nudge, needs-resolution, requeue",
+            )
+            .unwrap();
+        let s = toml::to_string(&cfg).unwrap();
+        let back: ConfigFile = toml::from_str(&s).unwrap();
+        let watchdog = back.project("demo").unwrap().effective_watchdog();
+        assert_eq!(watchdog.running_timeout_secs, 3600);
+        assert_eq!(watchdog.idle_timeout_secs, 600);
+        assert_eq!(watchdog.actions.last(), Some(&WatchdogAction::Requeue));
+
+        let err = cfg
+            .set_project_key("demo", "watchdog-actions", "nudge,explode")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+
+        assert!(!default_project("other").effective_watchdog().is_enabled());
+    }
+
+    #[test]
+    fn retry_defaults_and_keys() {
+        let retry = default_project("demo").effective_retry();
+        assert_eq!(retry.max_attempts, DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(retry.needs_human_label, "needs-human");
+
+        let cfg = ConfigFile::default()
+            .add_project(default_project("demo"))
+            .unwrap()
+            .set_project_key("demo", "max-attempts", "5")
+            .unwrap()
+            .set_project_key("demo", "retry-backoff-secs", "30")
+            .unwrap()
+            .set_project_key("demo", "needs-human-label", "")
+            .unwrap();
+        let s = toml::to_string(&cfg).unwrap();
+        let back: ConfigFile = toml::from_str(&s).unwrap();
+        let retry = back.project("demo").unwrap().effective_retry();
+        assert_eq!(retry.max_attempts, 5);
+        assert_eq!(retry.backoff_secs, 30);
+        assert_eq!(retry.max_backoff_secs, DEFAULT_RETRY_MAX_BACKOFF_SECS);
+        assert_eq!(retry.needs_human_label, "");
+
+        let err = cfg
+            .set_project_key("demo", "max-attempts", "-1")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn verify_table_and_keys() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+
+[projects.verify]
+commands = ["cargo test", "make lint"]
+max-rounds = 2
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        let verify = cfg.project("demo").unwrap().effective_verify();
+        assert_eq!(verify.commands, vec!["cargo test", "make lint"]);
+        assert_eq!(verify.max_rounds, 2);
+        assert_eq!(verify.timeout_secs, DEFAULT_VERIFY_TIMEOUT_SECS);
+
+        let cfg = cfg
+            .set_project_key("demo", "verify-commands", "cargo fmt --check; cargo test ;")
+            .unwrap()
+            .set_project_key("demo", "verify_timeout_secs", "60")
+            .unwrap();
+        let verify = cfg.project("demo").unwrap().effective_verify();
+        assert_eq!(verify.commands, vec!["cargo fmt --check", "cargo test"]);
+        assert_eq!(verify.max_rounds, 2);
+        assert_eq!(verify.timeout_secs, 60);
+        assert_eq!(
+            cfg.get_project_key_value("demo", "verify-commands")
+                .unwrap(),
+            toml::Value::Array(vec![
+                toml::Value::String("cargo fmt --check".to_owned()),
+                toml::Value::String("cargo test".to_owned()),
+            ])
+        );
+
+        assert!(default_project("other")
+            .effective_verify()
+            .commands
+            .is_empty());
+    }
+
+    #[test]
+    fn conflicts_table_and_keys() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+
+[projects.conflicts]
+resolution = "agent"
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        let conflicts = cfg.project("demo").unwrap().effective_conflicts();
+        assert_eq!(conflicts.resolution, ConflictResolution::Agent);
+        assert_eq!(conflicts.max_attempts, DEFAULT_CONFLICT_MAX_ATTEMPTS);
+
+        let cfg = cfg
+            .set_project_key("demo", "conflict-max-attempts", "4")
+            .unwrap()
+            .set_project_key("demo", "conflict_resolution", "manual")
+            .unwrap();
+        let conflicts = cfg.project("demo").unwrap().effective_conflicts();
+        assert_eq!(conflicts.resolution, ConflictResolution::Manual);
+        assert_eq!(conflicts.max_attempts, 4);
+        assert_eq!(
+            cfg.get_project_key_value("demo", "conflict-resolution")
+                .unwrap(),
+            toml::Value::String("manual".to_owned())
+        );
+
+        let err = cfg
+            .set_project_key("demo", "conflict-resolution", "auto")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn confinement_table_and_keys() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+
+[projects.confinement]
+allowed-dirs = ["~/.cache/demo"]
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        let confinement = cfg.project("demo").unwrap().effective_confinement();
+        assert!(confinement.enabled);
+        assert_eq!(confinement.allowed_dirs, ["~/.cache/demo"]);
+        assert!(default_project("other").effective_confinement().enabled);
+
+        let cfg = cfg
+            .set_project_key("demo", "write-confinement", "false")
+            .unwrap()
+            .set_project_key("demo", "write_allowed_dirs", "/opt/shared, ~/scratch")
+            .unwrap();
+        let confinement = cfg.project("demo").unwrap().effective_confinement();
+        assert!(!confinement.enabled);
+        assert_eq!(confinement.allowed_dirs, ["/opt/shared", "~/scratch"]);
+        assert_eq!(
+            cfg.get_project_key_value("demo", "write-confinement")
+                .unwrap(),
+            toml::Value::Boolean(false)
+        );
+
+        let err = cfg
+            .set_project_key("demo", "write-confinement", "maybe")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn permission_timeout_keys() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+permission-timeout-secs = 900
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        let project = cfg.project("demo").unwrap();
+        assert_eq!(project.permission_timeout_secs, Some(900));
+        assert_eq!(
+            project.effective_permission_timeout_action(),
+            PermissionTimeoutAction::Deny
+        );
+        assert_eq!(project.permission_hook_timeout_secs(), 930);
+        assert_eq!(
+            default_project("other").permission_hook_timeout_secs(),
+            DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS
+        );
+
+        let cfg = cfg
+            .set_project_key("demo", "permission-timeout-secs", "60")
+            .unwrap()
+            .set_project_key("demo", "permission-timeout-action", "allow")
+            .unwrap();
+        let project = cfg.project("demo").unwrap();
+        assert_eq!(project.permission_timeout_secs, Some(60));
+        assert_eq!(
+            project.permission_hook_timeout_secs(),
+            DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS
+        );
+        assert_eq!(
+            cfg.get_project_key_value("demo", "permission-timeout-action")
+                .unwrap(),
+            toml::Value::String("allow".to_owned())
+        );
+
+        let cfg = cfg
+            .set_project_key("demo", "permission-timeout-secs", "0")
+            .unwrap();
+        assert_eq!(cfg.project("demo").unwrap().permission_timeout_secs, None);
+        let err = cfg
+            .set_project_key("demo", "permission-timeout-action", "ask")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn routing_rules_parse_match_and_validate() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+coding-backend = "codex"
+planner-backend = "claude"
+
+[[projects.routing]]
+label = "needs-plan"
+role = "planner"
+
+[[projects.routing]]
+label = "frontend"
+backend = "claude"
+kickoff = "Run the storybook tests."
+
+[[projects.routing]]
+type = "chore"
+backend = "codex"
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        cfg.validate().unwrap();
+        let project = cfg.project("demo").unwrap();
+        assert_eq!(project.routing.len(), 3);
+
+        let labels = |ls: &[&str]| ls.iter().map(|l| (*l).to_owned()).collect::<Vec<_>>();
+
+        let rule = project
+            .route_for(&labels(&["Frontend", "needs-plan"]), "task")
+            .unwrap();
+        assert_eq!(rule.role, RouteRole::Planner);
+        assert_eq!(project.routed_backend(rule), AgentBackend::Claude);
+
+        let rule = project.route_for(&labels(&["frontend"]), "task").unwrap();
+        assert_eq!(project.routed_backend(rule), AgentBackend::Claude);
+        assert_eq!(rule.kickoff.as_deref(), Some("Run the storybook tests."));
+
+        let rule = project.route_for(&labels(&[]), "chore").unwrap();
+        assert_eq!(rule.backend, Some(AgentBackend::Codex));
+        assert!(project.route_for(&labels(&["backend"]), "task").is_none());
+
+        let mut bad = cfg.clone();
+        bad.projects[0].routing.push(RoutingRule {
+            issue_type: Some("bug".to_owned()),
+            role: RouteRole::Planner,
+            ..Default::default()
+        });
+        let err = bad.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidRoutingRule { index: 3, .. }
+        ));
+    }
+
+    #[test]
+    fn escalation_chain_defaults_and_validates() {
+        let raw = r#"
+[[projects]]
+name = "demo"
+remote-url = "git@github.com:o/r.git"
+permission-timeout-secs = 600
+
+[[projects.escalation]]
+tier = "llm"
+timeout-secs = 20
+
+[[projects.escalation]]
+tier = "manager"
+
+[[projects.escalation]]
+tier = "human"
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        cfg.validate().unwrap();
+        let project = cfg.project("demo").unwrap();
+        let step = |tier, timeout_secs| EscalationStep { tier, timeout_secs };
+        assert_eq!(
+            project.effective_escalation(),
+            [
+                step(EscalationTier::Llm, Some(20)),
+                step(
+                    EscalationTier::Manager,
+                    Some(DEFAULT_MANAGER_ESCALATION_TIMEOUT_SECS)
+                ),
+                step(EscalationTier::Human, Some(600)),
+            ]
+        );
+        assert_eq!(project.permission_hook_timeout_secs(), 20 + 120 + 600 + 30);
+
+        // Without a chain, `permissions-checker` picks the only tier.
+        let mut legacy = default_project("other");
+        assert_eq!(
+            legacy.effective_escalation(),
+            [step(EscalationTier::Human, None)]
+        );
+        legacy.permissions_checker = PermissionsChecker::Llm;
+        assert_eq!(
+            legacy.effective_escalation(),
+            [step(EscalationTier::Llm, None)]
+        );
+
+        assert_eq!(
+            cfg.get_project_key_value("demo", "escalation").unwrap(),
+            toml::Value::String("llm:20,manager:120,human:600".to_owned())
+        );
+        let set = cfg
+            .set_project_key("demo", "escalation", "manager:45, human")
+            .unwrap();
+        assert_eq!(
+            set.project("demo").unwrap().escalation,
+            [
+                step(EscalationTier::Manager, Some(45)),
+                step(EscalationTier::Human, None)
+            ]
+        );
+        let err = cfg
+            .set_project_key("demo", "escalation", "human,llm")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidEscalationTier { .. }));
+        let err = cfg
+            .set_project_key("demo", "escalation", "llm:soon")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+
+        let mut bad = cfg.clone();
+        bad.projects[0].escalation.swap(1, 2);
+        let err = bad.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidEscalationTier { index: 1, .. }
+        ));
+        let mut bad = cfg;
+        bad.projects[0].escalation[1] = step(EscalationTier::Llm, None);
+        let err = bad.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidEscalationTier { index: 1, .. }
+        ));
     }
 
     fn default_project(name: &str) -> ProjectConfig {
@@ -740,17 +2424,33 @@ mod tests {
             name: name.to_owned(),
             remote_url: "file:///tmp/demo.git".to_owned(),
             max_agents: 3,
-            issue_backend: IssueBackend::Tk,
+            issue_backend: DEFAULT_ISSUE_BACKEND.to_owned(),
             permissions_checker: PermissionsChecker::Manual,
             agent_backend: AgentBackend::Codex,
             planner_backend: None,
             coding_backend: None,
             merge_strategy: MergeStrategy::Direct,
             allowed_authors: vec![],
+            ready_label: None,
             autostart: false,
             linear_team: None,
             linear_project: None,
+            jira_project: None,
+            jira_ready_jql: None,
             silence_threshold_secs: None,
+            permission_timeout_secs: None,
+            permission_timeout_action: None,
+            escalation: Vec::new(),
+            weight: None,
+            merge_batch_size: None,
+            merge_message_template: None,
+            scheduling: None,
+            routing: Vec::new(),
+            watchdog: None,
+            retry: None,
+            verify: None,
+            conflicts: None,
+            confinement: None,
             extra: BTreeMap::new(),
         }
     }
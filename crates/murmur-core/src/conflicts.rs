@@ -0,0 +1,108 @@
+/// Upstream commits listed in a resolution prompt.
+pub const MAX_PROMPT_UPSTREAM_COMMITS: usize = 20;
+
+/// A rebase onto the base branch that stopped on conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub branch: String,
+    /// Ref the branch was rebased onto, e.g. `origin/main`.
+    pub upstream: String,
+    pub files: Vec<String>,
+    /// `<sha> <subject>` for upstream commits the branch lacks, newest first.
+    /// May hold one more than [`MAX_PROMPT_UPSTREAM_COMMITS`] to signal that
+    /// the list was cut.
+    pub upstream_commits: Vec<String>,
+    /// Git's own report, kept for the `needs_resolution` message.
+    pub error: String,
+}
+
+/// Whether `attempts` resolution turns use up the agent's `max-attempts`.
+pub fn conflict_attempts_exhausted(attempts: u32, max_attempts: u32) -> bool {
+    attempts >= max_attempts
+}
+
+/// Message asking the agent to rebase and resolve the conflict itself.
+pub fn conflict_resolution_prompt(
+    conflict: &MergeConflict,
+    attempt: u32,
+    max_attempts: u32,
+) -> String {
+    let mut out = format!(
+        "Merge rejected: {} no longer rebases cleanly onto {} \
+         (resolution attempt {attempt}/{max_attempts}).\n",
+        conflict.branch, conflict.upstream
+    );
+
+    out.push_str("\nConflicting files:\n");
+    if conflict.files.is_empty() {
+        out.push_str("- (git did not report any; see the rebase output)\n");
+    }
+    for file in &conflict.files {
+        out.push_str(&format!("- {file}\n"));
+    }
+
+    if !conflict.upstream_commits.is_empty() {
+        out.push_str("\nUpstream commits missing from your branch:\n");
+        for commit in conflict
+            .upstream_commits
+            .iter()
+            .take(MAX_PROMPT_UPSTREAM_COMMITS)
+        {
+            out.push_str(&format!("- {commit}\n"));
+        }
+        if conflict.upstream_commits.len() > MAX_PROMPT_UPSTREAM_COMMITS {
+            out.push_str("- (older commits not shown)\n");
+        }
+    }
+
+    out.push_str(&format!(
+        "\nRebase onto the latest upstream and resolve the conflicts:\n\n\
+         ```\n\
+         git fetch origin\n\
+         git rebase {}\n\
+         # fix each file, `git add` it, then `git rebase --continue`\n\
+         ```\n\n\
+         Keep the intent of both sides. Once the rebase has finished and the \
+         project still builds, run `mm agent done` again.",
+        conflict.upstream
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflict(commits: usize) -> MergeConflict {
+        MergeConflict {
+            branch: "murmur/a-1".to_owned(),
+            upstream: "origin/main".to_owned(),
+            files: vec!["src/lib.rs".to_owned(), "README.md".to_owned()],
+            upstream_commits: (0..commits).map(|i| format!("abc{i} change {i}")).collect(),
+            error: "CONFLICT (content)".to_owned(),
+        }
+    }
+
+    #[test]
+    fn prompt_lists_files_commits_and_steps() {
+        let msg = conflict_resolution_prompt(&conflict(2), 1, 2);
+        assert!(msg.contains("murmur/a-1 no longer rebases cleanly onto origin/main"));
+        assert!(msg.contains("(resolution attempt 1/2)"));
+        assert!(msg.contains("- src/lib.rs\n- README.md\n"));
+        assert!(msg.contains("- abc0 change 0\n- abc1 change 1\n"));
+        assert!(msg.contains("git rebase origin/main"));
+        assert!(!msg.contains("not shown"));
+
+        let long = conflict_resolution_prompt(&conflict(MAX_PROMPT_UPSTREAM_COMMITS + 1), 2, 2);
+        assert!(long.contains("(older commits not shown)"));
+        assert!(!long.contains(&format!("abc{MAX_PROMPT_UPSTREAM_COMMITS} ")));
+    }
+
+    #[test]
+    fn attempts_are_capped() {
+        assert!(!conflict_attempts_exhausted(0, 2));
+        assert!(!conflict_attempts_exhausted(1, 2));
+        assert!(conflict_attempts_exhausted(2, 2));
+        assert!(conflict_attempts_exhausted(0, 0));
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::{BTreeSet, VecDeque};
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -114,6 +115,94 @@ pub struct Comment {
     pub created_at_ms: u64,
 }
 
+/// Optional issue fields a backend can store.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    pub links: bool,
+    pub dependencies: bool,
+    pub labels: bool,
+}
+
+impl Capabilities {
+    /// The first field set in `params` the backend cannot store.
+    pub fn unsupported_create(&self, params: &CreateParams) -> Option<&'static str> {
+        self.unsupported(
+            !params.links.is_empty(),
+            !params.dependencies.is_empty(),
+            !params.labels.is_empty(),
+        )
+    }
+
+    /// The first field set in `params` the backend cannot store.
+    pub fn unsupported_update(&self, params: &UpdateParams) -> Option<&'static str> {
+        self.unsupported(
+            params.links.is_some(),
+            params.dependencies.is_some(),
+            params.labels.is_some(),
+        )
+    }
+
+    fn unsupported(&self, links: bool, dependencies: bool, labels: bool) -> Option<&'static str> {
+        if links && !self.links {
+            return Some("links");
+        }
+        if dependencies && !self.dependencies {
+            return Some("dependencies");
+        }
+        if labels && !self.labels {
+            return Some("labels");
+        }
+        None
+    }
+}
+
+/// An issue tracker a project's `issue-backend` names.
+///
+/// `now_ms` stamps changes for backends that keep their own records (tk);
+/// hosted trackers ignore it.
+#[async_trait]
+pub trait IssueBackend: Send + Sync {
+    fn capabilities(&self) -> Capabilities;
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue>;
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>>;
+
+    /// Open issues whose dependencies are all closed.
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>>;
+
+    async fn create(&self, now_ms: u64, params: CreateParams) -> anyhow::Result<Issue>;
+
+    async fn update(&self, now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue>;
+
+    async fn close(&self, now_ms: u64, id: &str) -> anyhow::Result<()>;
+
+    async fn comment(&self, now_ms: u64, id: &str, body: &str) -> anyhow::Result<()>;
+
+    /// Comments on an issue, oldest first; with `since_ms`, only newer ones.
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>>;
+
+    /// Publishes local changes (tk commits its ticket files); a no-op for
+    /// hosted trackers.
+    async fn commit(&self, message: &str) -> anyhow::Result<()>;
+
+    /// Replaces the `## Plan` section of an issue's description.
+    async fn plan(&self, now_ms: u64, id: &str, plan_content: &str) -> anyhow::Result<()> {
+        let issue = self.get(id).await?;
+        let description = upsert_plan_section(&issue.description, plan_content);
+        self.update(
+            now_ms,
+            id,
+            UpdateParams {
+                description: Some(description),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
 pub fn compute_ready_issues(open_issues: Vec<Issue>) -> Vec<Issue> {
     let open_ids: BTreeSet<String> = open_issues.iter().map(|i| i.id.clone()).collect();
     open_issues
@@ -490,6 +579,37 @@ mod tests {
         .matches(&issue));
     }
 
+    #[test]
+    fn capabilities_name_the_first_unsupported_field() {
+        let caps = Capabilities {
+            links: false,
+            dependencies: true,
+            labels: true,
+        };
+        let mut create = CreateParams {
+            title: "t".to_owned(),
+            description: String::new(),
+            issue_type: String::new(),
+            priority: 0,
+            labels: vec!["one".to_owned()],
+            dependencies: vec!["a".to_owned()],
+            links: vec![],
+        };
+        assert_eq!(caps.unsupported_create(&create), None);
+        create.links.push("https://example.com".to_owned());
+        assert_eq!(caps.unsupported_create(&create), Some("links"));
+
+        let update = UpdateParams {
+            labels: Some(vec![]),
+            ..Default::default()
+        };
+        assert_eq!(caps.unsupported_update(&update), None);
+        assert_eq!(
+            Capabilities::default().unsupported_update(&update),
+            Some("labels")
+        );
+    }
+
     #[test]
     fn tk_split_frontmatter_errors_on_missing_delimiters() {
         assert!(matches!(
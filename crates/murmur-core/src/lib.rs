@@ -1,11 +1,19 @@
 pub mod agent;
+pub mod attempts;
 pub mod claims;
 pub mod commits;
 pub mod config;
+pub mod conflicts;
 pub mod issue;
+pub mod merge_message;
+pub mod merge_queue;
 pub mod orchestration;
 pub mod paths;
+pub mod permission_log;
 pub mod permissions;
 pub mod project;
+pub mod shell;
 pub mod stream;
 pub mod usage;
+pub mod verify;
+pub mod watchdog;
@@ -0,0 +1,134 @@
+/// Commit message used by the squash and merge-commit strategies when a
+/// project sets no `merge-message-template`.
+pub const DEFAULT_MERGE_MESSAGE_TEMPLATE: &str = "{issue_id}: {title}\n\n{description}";
+
+/// Placeholders a merge message template may use.
+pub const MERGE_MESSAGE_PLACEHOLDERS: &[&str] = &["issue_id", "title", "agent_id", "description"];
+
+/// Values substituted into a merge message template.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeMessageVars {
+    pub issue_id: String,
+    pub title: String,
+    pub agent_id: String,
+    pub description: String,
+}
+
+impl MergeMessageVars {
+    fn get(&self, name: &str) -> Option<&str> {
+        match name {
+            "issue_id" => Some(&self.issue_id),
+            "title" => Some(&self.title),
+            "agent_id" => Some(&self.agent_id),
+            "description" => Some(&self.description),
+            _ => None,
+        }
+    }
+}
+
+/// Checks that every `{name}` in the template is a known placeholder.
+/// `{{` and `}}` stand for literal braces.
+pub fn validate_merge_message_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("template is empty".to_owned());
+    }
+    expand(template, |name| {
+        MERGE_MESSAGE_PLACEHOLDERS.contains(&name).then_some("")
+    })
+    .map(|_| ())
+}
+
+/// Renders the template, dropping runs of blank lines left by empty values.
+pub fn render_merge_message(template: &str, vars: &MergeMessageVars) -> Result<String, String> {
+    let raw = expand(template, |name| vars.get(name))?;
+
+    let mut out = String::new();
+    let mut blank = false;
+    for line in raw.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+            if blank {
+                out.push('\n');
+            }
+        }
+        blank = false;
+        out.push_str(line);
+    }
+    Ok(out)
+}
+
+fn expand<'a>(template: &str, lookup: impl Fn(&str) -> Option<&'a str>) -> Result<String, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(idx) = rest.find(['{', '}']) {
+        out.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if tail.starts_with('}') {
+            return Err("unmatched `}` (use `}}` for a literal brace)".to_owned());
+        }
+        let Some(end) = tail.find('}') else {
+            return Err("unclosed `{` (use `{{` for a literal brace)".to_owned());
+        };
+        let name = tail[1..end].trim();
+        let Some(value) = lookup(name) else {
+            return Err(format!(
+                "unknown placeholder {{{name}}} (expected one of: {})",
+                MERGE_MESSAGE_PLACEHOLDERS.join(", ")
+            ));
+        };
+        out.push_str(value);
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(description: &str) -> MergeMessageVars {
+        MergeMessageVars {
+            issue_id: "ISSUE-7".to_owned(),
+            title: "Fix login".to_owned(),
+            agent_id: "a-3".to_owned(),
+            description: description.to_owned(),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_and_collapses_blank_lines() {
+        let msg =
+            render_merge_message(DEFAULT_MERGE_MESSAGE_TEMPLATE, &vars("Body\n\n\nmore")).unwrap();
+        assert_eq!(msg, "ISSUE-7: Fix login\n\nBody\n\nmore");
+
+        let msg = render_merge_message(DEFAULT_MERGE_MESSAGE_TEMPLATE, &vars("")).unwrap();
+        assert_eq!(msg, "ISSUE-7: Fix login");
+
+        let msg = render_merge_message("{title} {{{agent_id}}}\n\nAgent: { agent_id }", &vars(""))
+            .unwrap();
+        assert_eq!(msg, "Fix login {a-3}\n\nAgent: a-3");
+    }
+
+    #[test]
+    fn rejects_unknown_or_unbalanced_placeholders() {
+        assert!(validate_merge_message_template(DEFAULT_MERGE_MESSAGE_TEMPLATE).is_ok());
+        assert!(validate_merge_message_template("{{literal}}").is_ok());
+        assert!(validate_merge_message_template("{author}")
+            .unwrap_err()
+            .contains("unknown placeholder {author}"));
+        assert!(validate_merge_message_template("{title").is_err());
+        assert!(validate_merge_message_template("title}").is_err());
+        assert!(validate_merge_message_template("  ").is_err());
+    }
+}
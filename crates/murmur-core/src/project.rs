@@ -26,7 +26,8 @@ mod tests {
     use tempfile::TempDir;
 
     use crate::config::{
-        AgentBackend, ConfigFile, IssueBackend, MergeStrategy, PermissionsChecker, ProjectConfig,
+        AgentBackend, ConfigFile, MergeStrategy, PermissionsChecker, ProjectConfig,
+        DEFAULT_ISSUE_BACKEND,
     };
     use crate::paths::{compute_paths, MurmurPaths, PathInputs};
 
@@ -45,17 +46,33 @@ mod tests {
             name: name.to_owned(),
             remote_url: "file:///tmp/demo.git".to_owned(),
             max_agents: 1,
-            issue_backend: IssueBackend::Tk,
+            issue_backend: DEFAULT_ISSUE_BACKEND.to_owned(),
             permissions_checker: PermissionsChecker::Manual,
             agent_backend: AgentBackend::Codex,
             planner_backend: None,
             coding_backend: None,
             merge_strategy: MergeStrategy::Direct,
             allowed_authors: vec![],
+            ready_label: None,
             autostart: false,
             linear_team: None,
             linear_project: None,
+            jira_project: None,
+            jira_ready_jql: None,
             silence_threshold_secs: None,
+            permission_timeout_secs: None,
+            permission_timeout_action: None,
+            escalation: Vec::new(),
+            weight: None,
+            merge_batch_size: None,
+            merge_message_template: None,
+            scheduling: None,
+            routing: Vec::new(),
+            watchdog: None,
+            retry: None,
+            verify: None,
+            conflicts: None,
+            confinement: None,
             extra: BTreeMap::new(),
         }
     }
@@ -0,0 +1,511 @@
+//! Just enough shell parsing to check each command a `Bash` call would run.
+
+/// The simple commands in a shell command line and the files it writes to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedCommand {
+    /// Each simple command as written (quoting kept), without leading
+    /// variable assignments or reserved words. Commands inside subshells,
+    /// `$(...)`, backticks and process substitutions are listed separately,
+    /// in the order they appear.
+    pub commands: Vec<String>,
+    /// Targets of output redirections (`>`, `>>`, `&>`, ...). Static targets
+    /// are unquoted; targets using `$` or backticks are kept as written.
+    pub writes: Vec<Redirect>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    pub target: String,
+    /// The target depends on expansion and cannot be resolved statically.
+    pub dynamic: bool,
+}
+
+/// Splits a command line on `;`, `&&`, `||`, `|`, `&` and newlines,
+/// descending into subshells, groups and substitutions. Fails on unbalanced
+/// quotes or parentheses.
+pub fn parse_command(src: &str) -> Result<ParsedCommand, String> {
+    let mut parser = Parser::new(src);
+    parser.parse_list(None)?;
+    Ok(parser.out)
+}
+
+const RESERVED: &[&str] = &[
+    "!", "{", "}", "if", "then", "elif", "else", "fi", "do", "done", "while", "until", "time",
+    "esac",
+];
+
+struct Heredoc {
+    delimiter: String,
+    strip_tabs: bool,
+    expand: bool,
+}
+
+struct Word {
+    raw: String,
+    unquoted: String,
+    dynamic: bool,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    heredocs: Vec<Heredoc>,
+    out: ParsedCommand,
+}
+
+impl Parser {
+    fn new(src: &str) -> Self {
+        Self {
+            chars: src.chars().collect(),
+            pos: 0,
+            heredocs: Vec::new(),
+            out: ParsedCommand::default(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    /// Parses commands until end of input, or through the matching `)`.
+    fn parse_list(&mut self, close: Option<char>) -> Result<(), String> {
+        let mut words: Vec<String> = Vec::new();
+        loop {
+            self.skip_blanks();
+            let Some(c) = self.peek() else {
+                if close.is_some() {
+                    return Err("unclosed `(`".to_owned());
+                }
+                self.finish(&mut words);
+                return Ok(());
+            };
+            match c {
+                '\n' => {
+                    self.pos += 1;
+                    self.finish(&mut words);
+                    self.read_heredoc_bodies()?;
+                }
+                '#' if words.is_empty() || self.chars[self.pos - 1].is_whitespace() => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                ')' if close.is_some() => {
+                    self.pos += 1;
+                    self.finish(&mut words);
+                    return Ok(());
+                }
+                ')' => return Err("unexpected `)`".to_owned()),
+                '(' if words.is_empty() => {
+                    self.pos += 1;
+                    self.parse_list(Some(')'))?;
+                }
+                '(' => return Err("unexpected `(`".to_owned()),
+                _ if self.at_redirect() => self.parse_redirect()?,
+                ';' | '&' | '|' => {
+                    self.pos += 1;
+                    if matches!(self.peek(), Some(n) if n == c || (c == '|' && n == '&')) {
+                        self.pos += 1;
+                    }
+                    self.finish(&mut words);
+                }
+                _ => {
+                    let word = self.read_word()?;
+                    words.push(word.raw);
+                }
+            }
+        }
+    }
+
+    fn skip_blanks(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t') => self.pos += 1,
+                Some('\\') if self.peek_at(1) == Some('\n') => self.pos += 2,
+                _ => return,
+            }
+        }
+    }
+
+    fn finish(&mut self, words: &mut Vec<String>) {
+        let mut rest = words.as_slice();
+        while let Some((first, tail)) = rest.split_first() {
+            if RESERVED.contains(&first.as_str()) || is_assignment(first) {
+                rest = tail;
+            } else {
+                break;
+            }
+        }
+        if !rest.is_empty() {
+            self.out.commands.push(rest.join(" "));
+        }
+        words.clear();
+    }
+
+    fn at_redirect(&self) -> bool {
+        let mut i = 0;
+        while self.peek_at(i).is_some_and(|c| c.is_ascii_digit()) {
+            i += 1;
+        }
+        match self.peek_at(i) {
+            // Process substitution, not a redirect.
+            Some('<' | '>') if self.peek_at(i + 1) == Some('(') => false,
+            Some('<' | '>') => true,
+            Some('&') if i == 0 => self.peek_at(1) == Some('>'),
+            _ => false,
+        }
+    }
+
+    fn parse_redirect(&mut self) -> Result<(), String> {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        let ops = [
+            "&>>", "&>", "<<<", "<<-", "<<", "<>", "<&", ">&", ">>", ">|", "<", ">",
+        ];
+        let op = ops
+            .iter()
+            .find(|op| self.starts_with(op))
+            .copied()
+            .unwrap_or(">");
+        self.pos += op.chars().count();
+        self.skip_blanks();
+
+        let target = self.read_word()?;
+        if target.raw.is_empty() {
+            return Err(format!("missing target after `{op}`"));
+        }
+
+        match op {
+            "<<" | "<<-" => self.heredocs.push(Heredoc {
+                delimiter: target.unquoted,
+                strip_tabs: op == "<<-",
+                expand: !target.raw.contains(['\'', '"', '\\']),
+            }),
+            "<" | "<<<" | "<&" => {}
+            // `>&2` and `>&-` duplicate or close a descriptor.
+            ">&" if target.raw == "-" || target.raw.chars().all(|c| c.is_ascii_digit()) => {}
+            _ => self.out.writes.push(Redirect {
+                target: if target.dynamic {
+                    target.raw
+                } else {
+                    target.unquoted
+                },
+                dynamic: target.dynamic,
+            }),
+        }
+        Ok(())
+    }
+
+    fn read_word(&mut self) -> Result<Word, String> {
+        let start = self.pos;
+        let mut unquoted = String::new();
+        let mut dynamic = false;
+
+        if matches!(self.peek(), Some('<' | '>')) && self.peek_at(1) == Some('(') {
+            self.pos += 2;
+            self.parse_list(Some(')'))?;
+            return Ok(Word {
+                raw: self.chars[start..self.pos].iter().collect(),
+                unquoted: String::new(),
+                dynamic: true,
+            });
+        }
+
+        while let Some(c) = self.peek() {
+            match c {
+                c if c.is_whitespace() => break,
+                ';' | '&' | '|' | '(' | ')' | '<' | '>' => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(c) => {
+                            unquoted.push(c);
+                            self.pos += 1;
+                        }
+                        None => {}
+                    }
+                }
+                '\'' => {
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => return Err("unclosed `'`".to_owned()),
+                            Some('\'') => break,
+                            Some(c) => unquoted.push(c),
+                        }
+                        self.pos += 1;
+                    }
+                    self.pos += 1;
+                }
+                '"' => {
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => return Err("unclosed `\"`".to_owned()),
+                            Some('"') => break,
+                            Some('\\') => {
+                                self.pos += 1;
+                                if let Some(c) = self.peek() {
+                                    unquoted.push(c);
+                                    self.pos += 1;
+                                }
+                            }
+                            Some('$' | '`') => {
+                                dynamic = true;
+                                self.read_expansion()?;
+                            }
+                            Some(c) => {
+                                unquoted.push(c);
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                    self.pos += 1;
+                }
+                '$' | '`' => {
+                    dynamic = true;
+                    self.read_expansion()?;
+                }
+                c => {
+                    unquoted.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        let raw: String = self.chars[start..self.pos].iter().collect();
+        Ok(Word {
+            // Line continuations join the word's pieces.
+            raw: raw.replace("\\\n", ""),
+            unquoted,
+            dynamic,
+        })
+    }
+
+    /// Consumes a `$...` or backtick expansion, recording the commands of any
+    /// command substitution.
+    fn read_expansion(&mut self) -> Result<(), String> {
+        if self.peek() == Some('`') {
+            self.pos += 1;
+            let mut inner = String::new();
+            loop {
+                match self.peek() {
+                    None => return Err("unclosed '`'".to_owned()),
+                    Some('`') => break,
+                    Some('\\') if matches!(self.peek_at(1), Some('`' | '\\' | '$')) => {
+                        inner.push(self.chars[self.pos + 1]);
+                        self.pos += 2;
+                        continue;
+                    }
+                    Some(c) => inner.push(c),
+                }
+                self.pos += 1;
+            }
+            self.pos += 1;
+            let nested = parse_command(&inner)?;
+            self.out.commands.extend(nested.commands);
+            self.out.writes.extend(nested.writes);
+            return Ok(());
+        }
+
+        // At `$`.
+        self.pos += 1;
+        if self.starts_with("((") {
+            return self.skip_balanced('(', ')', "unclosed `$((`");
+        }
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            return self.parse_list(Some(')'));
+        }
+        if self.peek() == Some('{') {
+            return self.skip_balanced('{', '}', "unclosed `${`");
+        }
+        Ok(())
+    }
+
+    fn skip_balanced(&mut self, open: char, close: char, err: &str) -> Result<(), String> {
+        let mut depth = 0usize;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+        }
+        Err(err.to_owned())
+    }
+
+    fn read_heredoc_bodies(&mut self) -> Result<(), String> {
+        for heredoc in std::mem::take(&mut self.heredocs) {
+            while self.pos < self.chars.len() {
+                let end = self.chars[self.pos..]
+                    .iter()
+                    .position(|&c| c == '\n')
+                    .map_or(self.chars.len(), |i| self.pos + i);
+                let line: String = self.chars[self.pos..end].iter().collect();
+                self.pos = (end + 1).min(self.chars.len());
+
+                let line = if heredoc.strip_tabs {
+                    line.trim_start_matches('\t')
+                } else {
+                    line.as_str()
+                };
+                if line == heredoc.delimiter {
+                    break;
+                }
+                if heredoc.expand {
+                    self.scan_expansions(line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn scan_expansions(&mut self, text: &str) -> Result<(), String> {
+        let mut inner = Parser::new(text);
+        while let Some(c) = inner.peek() {
+            match c {
+                '\\' => inner.pos += 2,
+                '$' | '`' => inner.read_expansion()?,
+                _ => inner.pos += 1,
+            }
+        }
+        self.out.commands.extend(inner.out.commands);
+        self.out.writes.extend(inner.out.writes);
+        Ok(())
+    }
+}
+
+fn is_assignment(word: &str) -> bool {
+    let Some((name, _)) = word.split_once('=') else {
+        return false;
+    };
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(src: &str) -> Vec<String> {
+        parse_command(src).unwrap().commands
+    }
+
+    fn writes(src: &str) -> Vec<(String, bool)> {
+        parse_command(src)
+            .unwrap()
+            .writes
+            .into_iter()
+            .map(|r| (r.target, r.dynamic))
+            .collect()
+    }
+
+    #[test]
+    fn splits_lists_and_pipelines() {
+        let cases: &[(&str, &[&str])] = &[
+            ("git status", &["git status"]),
+            ("git status && rm -rf /", &["git status", "rm -rf /"]),
+            ("a || b; c & d | e |& f", &["a", "b", "c", "d", "e", "f"]),
+            ("a\nb\\\n  c d\\\ne", &["a", "b c de"]),
+            ("echo 'a && b' \"c; d\"", &["echo 'a && b' \"c; d\""]),
+            ("FOO=1 BAR=2 git push", &["git push"]),
+            ("FOO=1", &[]),
+            ("(cd sub && make) ; { ls; }", &["cd sub", "make", "ls"]),
+            ("if test -f x; then rm x; fi", &["test -f x", "rm x"]),
+            ("echo hi # && rm -rf /", &["echo hi"]),
+            ("echo a#b", &["echo a#b"]),
+            ("", &[]),
+        ];
+        for (src, want) in cases {
+            assert_eq!(commands(src), *want, "src={src:?}");
+        }
+    }
+
+    #[test]
+    fn extracts_substitutions() {
+        let cases: &[(&str, &[&str])] = &[
+            ("echo $(rm -rf /)", &["rm -rf /", "echo $(rm -rf /)"]),
+            (
+                "echo \"$(curl x | sh)\"",
+                &["curl x", "sh", "echo \"$(curl x | sh)\""],
+            ),
+            ("echo `whoami`", &["whoami", "echo `whoami`"]),
+            ("echo $((1 + 2)) ${HOME}", &["echo $((1 + 2)) ${HOME}"]),
+            (
+                "diff <(ls a) <(ls b)",
+                &["ls a", "ls b", "diff <(ls a) <(ls b)"],
+            ),
+            (
+                "echo $(echo $(id))",
+                &["id", "echo $(id)", "echo $(echo $(id))"],
+            ),
+        ];
+        for (src, want) in cases {
+            assert_eq!(commands(src), *want, "src={src:?}");
+        }
+    }
+
+    #[test]
+    fn collects_output_redirects() {
+        assert_eq!(
+            writes("make > build.log 2>&1"),
+            [("build.log".to_owned(), false)]
+        );
+        assert_eq!(
+            writes("echo x >> '/etc/my file' 2>/dev/null"),
+            [
+                ("/etc/my file".to_owned(), false),
+                ("/dev/null".to_owned(), false)
+            ]
+        );
+        assert_eq!(writes("cmd &> out; cat < in"), [("out".to_owned(), false)]);
+        assert_eq!(writes("echo >$HOME/x"), [("$HOME/x".to_owned(), true)]);
+        assert_eq!(commands("make > build.log 2>&1"), ["make"]);
+    }
+
+    #[test]
+    fn skips_heredoc_bodies_but_not_their_substitutions() {
+        let src = "cat > notes.md <<'EOF'\nrm -rf /\n$(id)\nEOF\ngit status";
+        assert_eq!(commands(src), ["cat", "git status"]);
+        assert_eq!(writes(src), [("notes.md".to_owned(), false)]);
+
+        let src = "cat <<-EOF\n\t$(id)\n\tEOF\n";
+        assert_eq!(commands(src), ["cat", "id"]);
+    }
+
+    #[test]
+    fn rejects_unbalanced_input() {
+        for src in [
+            "echo 'x",
+            "echo \"x",
+            "(ls",
+            "ls)",
+            "echo $(ls",
+            "echo `ls",
+            "echo >",
+        ] {
+            assert!(parse_command(src).is_err(), "src={src:?}");
+        }
+    }
+}
@@ -148,10 +148,8 @@ impl StreamMessage {
         let mut out = Vec::new();
         for block in &message.content {
             match block.r#type.as_str() {
-                "text" => {
-                    if !block.text.is_empty() {
-                        out.push(ChatMessage::new(role, block.text.clone(), now_ms));
-                    }
+                "text" if !block.text.is_empty() => {
+                    out.push(ChatMessage::new(role, block.text.clone(), now_ms));
                 }
                 "tool_use" => {
                     let input = format_tool_input(&block.name, &block.input);
@@ -168,23 +166,21 @@ impl StreamMessage {
                         out.push(msg);
                     }
                 }
-                "tool_result" => {
-                    if !block.content.0.is_empty() {
-                        let tool_use_id = {
-                            let s = block.tool_use_id.trim();
-                            if s.is_empty() {
-                                None
-                            } else {
-                                Some(s.to_owned())
-                            }
-                        };
-                        out.push(ChatMessage::tool_result(
-                            block.content.0.clone(),
-                            tool_use_id,
-                            block.is_error,
-                            now_ms,
-                        ));
-                    }
+                "tool_result" if !block.content.0.is_empty() => {
+                    let tool_use_id = {
+                        let s = block.tool_use_id.trim();
+                        if s.is_empty() {
+                            None
+                        } else {
+                            Some(s.to_owned())
+                        }
+                    };
+                    out.push(ChatMessage::tool_result(
+                        block.content.0.clone(),
+                        tool_use_id,
+                        block.is_error,
+                        now_ms,
+                    ));
                 }
                 _ => {}
             }
@@ -0,0 +1,110 @@
+/// Output kept in feedback messages; verification logs are tailed to this.
+pub const MAX_FEEDBACK_OUTPUT_BYTES: usize = 8 * 1024;
+
+/// A verification command that rejected a merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyFailure {
+    pub command: String,
+    /// `None` if the command timed out or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    /// Combined stdout and stderr.
+    pub output: String,
+}
+
+impl VerifyFailure {
+    pub fn summary(&self) -> String {
+        if self.timed_out {
+            return format!("`{}` timed out", self.command);
+        }
+        match self.exit_code {
+            Some(code) => format!("`{}` exited with code {code}", self.command),
+            None => format!("`{}` was killed", self.command),
+        }
+    }
+}
+
+/// Whether `failed_rounds` failures use up the agent's `max-rounds`.
+pub fn verify_rounds_exhausted(failed_rounds: u32, max_rounds: u32) -> bool {
+    failed_rounds >= max_rounds
+}
+
+/// Message sent to the agent after a failed verification round.
+pub fn verify_feedback(failure: &VerifyFailure, round: u32, max_rounds: u32) -> String {
+    let output = tail_output(failure.output.trim_end(), MAX_FEEDBACK_OUTPUT_BYTES);
+    let truncated = if output.len() < failure.output.trim_end().len() {
+        "(output truncated)\n"
+    } else {
+        ""
+    };
+    format!(
+        "Merge rejected: verification failed (round {round}/{max_rounds}).\n\
+         {}.\n\n\
+         ```\n{truncated}{output}\n```\n\n\
+         Fix the problem, commit, and run `mm agent done` again.",
+        failure.summary()
+    )
+}
+
+/// Returns the last `max_bytes` of `output`, cut at a line start when possible.
+pub fn tail_output(output: &str, max_bytes: usize) -> &str {
+    if output.len() <= max_bytes {
+        return output;
+    }
+    let mut start = output.len() - max_bytes;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = &output[start..];
+    match tail.find('\n') {
+        Some(i) if i + 1 < tail.len() => &tail[i + 1..],
+        _ => tail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(output: &str) -> VerifyFailure {
+        VerifyFailure {
+            command: "cargo test".to_owned(),
+            exit_code: Some(101),
+            timed_out: false,
+            output: output.to_owned(),
+        }
+    }
+
+    #[test]
+    fn summary_describes_exit() {
+        assert_eq!(failure("").summary(), "`cargo test` exited with code 101");
+        let timed_out = VerifyFailure {
+            exit_code: None,
+            timed_out: true,
+            ..failure("")
+        };
+        assert_eq!(timed_out.summary(), "`cargo test` timed out");
+    }
+
+    #[test]
+    fn tail_output_keeps_whole_lines_and_char_boundaries() {
+        assert_eq!(tail_output("short", 100), "short");
+        assert_eq!(tail_output("one\ntwo\nthree", 9), "three");
+        assert_eq!(tail_output("ééé", 3), "é");
+    }
+
+    #[test]
+    fn feedback_includes_round_and_output() {
+        let msg = verify_feedback(&failure("test foo ... FAILED\n"), 1, 3);
+        assert!(msg.contains("round 1/3"));
+        assert!(msg.contains("`cargo test` exited with code 101"));
+        assert!(msg.contains("```\ntest foo ... FAILED\n```"));
+        assert!(!msg.contains("truncated"));
+
+        let long = "x\n".repeat(MAX_FEEDBACK_OUTPUT_BYTES);
+        assert!(verify_feedback(&failure(&long), 2, 3).contains("(output truncated)"));
+
+        assert!(!verify_rounds_exhausted(2, 3));
+        assert!(verify_rounds_exhausted(3, 3));
+    }
+}
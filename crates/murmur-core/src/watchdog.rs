@@ -0,0 +1,204 @@
+use crate::agent::AgentState;
+use crate::config::{WatchdogAction, WatchdogConfig};
+
+/// What the watchdog knows about one agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchedAgent {
+    pub state: AgentState,
+    pub holds_claim: bool,
+    /// Last time the agent produced output or received a user message.
+    pub last_activity_at_ms: u64,
+    pub last_idle_at_ms: Option<u64>,
+}
+
+/// Escalation progress since the agent's last activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WatchdogProgress {
+    /// Number of actions already taken.
+    pub fired: usize,
+    /// Timeout that started the escalation; kept so that later steps use the
+    /// same clock after an action changes the agent's state.
+    pub timeout_secs: u64,
+}
+
+/// An action the watchdog should take now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogFire {
+    pub action: WatchdogAction,
+    pub stalled_ms: u64,
+    pub progress: WatchdogProgress,
+}
+
+/// Decides whether a stalled agent has reached its next escalation step.
+///
+/// Running agents are measured against `running-timeout-secs`, idle agents
+/// holding a claim against `idle-timeout-secs`. Step `n` (zero-based) fires
+/// once the agent has been stalled for `(n + 1)` timeouts. Once escalation
+/// has started it continues through `NeedsResolution`, so later steps can
+/// still abort or requeue the agent.
+pub fn evaluate_watchdog(
+    policy: &WatchdogConfig,
+    agent: &WatchedAgent,
+    progress: WatchdogProgress,
+    now_ms: u64,
+) -> Option<WatchdogFire> {
+    let timeout_secs = if progress.fired > 0 {
+        match agent.state {
+            AgentState::Running | AgentState::Idle | AgentState::NeedsResolution => {
+                progress.timeout_secs
+            }
+            _ => 0,
+        }
+    } else {
+        match agent.state {
+            AgentState::Running => policy.running_timeout_secs,
+            AgentState::Idle if agent.holds_claim => policy.idle_timeout_secs,
+            _ => 0,
+        }
+    };
+    if timeout_secs == 0 {
+        return None;
+    }
+
+    let action = *policy.actions.get(progress.fired)?;
+
+    let since = agent
+        .last_idle_at_ms
+        .map_or(agent.last_activity_at_ms, |idle| {
+            idle.max(agent.last_activity_at_ms)
+        });
+    let stalled_ms = now_ms.saturating_sub(since);
+    let due_ms = timeout_secs
+        .saturating_mul(1000)
+        .saturating_mul(progress.fired as u64 + 1);
+    if stalled_ms < due_ms {
+        return None;
+    }
+
+    Some(WatchdogFire {
+        action,
+        stalled_ms,
+        progress: WatchdogProgress {
+            fired: progress.fired + 1,
+            timeout_secs,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_MS: u64 = 60_000;
+
+    fn policy() -> WatchdogConfig {
+        WatchdogConfig {
+            running_timeout_secs: 600,
+            idle_timeout_secs: 300,
+            actions: vec![
+                WatchdogAction::Nudge,
+                WatchdogAction::NeedsResolution,
+                WatchdogAction::Requeue,
+            ],
+            nudge_message: None,
+        }
+    }
+
+    fn agent(state: AgentState, holds_claim: bool) -> WatchedAgent {
+        WatchedAgent {
+            state,
+            holds_claim,
+            last_activity_at_ms: 0,
+            last_idle_at_ms: None,
+        }
+    }
+
+    #[test]
+    fn running_agent_escalates_once_per_timeout() {
+        let policy = policy();
+        let running = agent(AgentState::Running, true);
+
+        let progress = WatchdogProgress::default();
+        assert_eq!(
+            evaluate_watchdog(&policy, &running, progress, 9 * MIN_MS),
+            None
+        );
+
+        let fire = evaluate_watchdog(&policy, &running, progress, 10 * MIN_MS).unwrap();
+        assert_eq!(fire.action, WatchdogAction::Nudge);
+        assert_eq!(fire.stalled_ms, 10 * MIN_MS);
+
+        let progress = fire.progress;
+        assert_eq!(
+            evaluate_watchdog(&policy, &running, progress, 15 * MIN_MS),
+            None
+        );
+
+        let stuck = WatchedAgent {
+            state: AgentState::NeedsResolution,
+            ..running
+        };
+        let fire = evaluate_watchdog(&policy, &running, progress, 20 * MIN_MS).unwrap();
+        assert_eq!(fire.action, WatchdogAction::NeedsResolution);
+
+        let fire = evaluate_watchdog(&policy, &stuck, fire.progress, 30 * MIN_MS).unwrap();
+        assert_eq!(fire.action, WatchdogAction::Requeue);
+
+        assert_eq!(
+            evaluate_watchdog(&policy, &stuck, fire.progress, 99 * MIN_MS),
+            None
+        );
+    }
+
+    #[test]
+    fn idle_agents_are_only_watched_while_holding_a_claim() {
+        let policy = policy();
+        let mut idle = agent(AgentState::Idle, false);
+        idle.last_idle_at_ms = Some(2 * MIN_MS);
+        let progress = WatchdogProgress::default();
+
+        assert_eq!(
+            evaluate_watchdog(&policy, &idle, progress, 60 * MIN_MS),
+            None
+        );
+
+        idle.holds_claim = true;
+        assert_eq!(
+            evaluate_watchdog(&policy, &idle, progress, 6 * MIN_MS),
+            None
+        );
+        let fire = evaluate_watchdog(&policy, &idle, progress, 7 * MIN_MS).unwrap();
+        assert_eq!(fire.action, WatchdogAction::Nudge);
+        assert_eq!(fire.stalled_ms, 5 * MIN_MS);
+        assert_eq!(fire.progress.timeout_secs, 300);
+    }
+
+    #[test]
+    fn disabled_timeouts_and_other_states_are_ignored() {
+        let mut policy = policy();
+        policy.running_timeout_secs = 0;
+        let progress = WatchdogProgress::default();
+
+        let running = agent(AgentState::Running, true);
+        assert_eq!(
+            evaluate_watchdog(&policy, &running, progress, 99 * MIN_MS),
+            None
+        );
+
+        let exited = agent(AgentState::Exited, true);
+        let started = WatchdogProgress {
+            fired: 1,
+            timeout_secs: 60,
+        };
+        assert_eq!(
+            evaluate_watchdog(&policy, &exited, started, 99 * MIN_MS),
+            None
+        );
+
+        let unflagged = agent(AgentState::NeedsResolution, true);
+        assert_eq!(
+            evaluate_watchdog(&policy, &unflagged, progress, 99 * MIN_MS),
+            None
+        );
+    }
+}
@@ -252,7 +252,7 @@ mod tests {
         let resp = HostResponse::ok(msg::PING, "req-1");
         let json = serde_json::to_string(&resp).unwrap();
         let back: HostResponse = serde_json::from_str(&json).unwrap();
-        assert_eq!(back.success, true);
+        assert!(back.success);
         assert_eq!(back.error, None);
     }
 
@@ -261,7 +261,7 @@ mod tests {
         let resp = HostResponse::err(msg::PING, "req-1", "something went wrong");
         let json = serde_json::to_string(&resp).unwrap();
         let back: HostResponse = serde_json::from_str(&json).unwrap();
-        assert_eq!(back.success, false);
+        assert!(!back.success);
         assert_eq!(back.error.as_deref(), Some("something went wrong"));
     }
 
@@ -312,7 +312,7 @@ mod tests {
     fn stop_request_defaults() {
         let json = r#"{}"#;
         let req: StopRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.force, false);
+        assert!(!req.force);
         assert_eq!(req.timeout_secs, 0);
         assert_eq!(req.reason, None);
     }
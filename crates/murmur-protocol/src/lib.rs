@@ -48,6 +48,9 @@ pub const MSG_ORCHESTRATION_STATUS: &str = "orchestration.status";
 
 pub const MSG_CLAIM_LIST: &str = "claim.list";
 pub const MSG_COMMIT_LIST: &str = "commit.list";
+pub const MSG_MERGE_QUEUE: &str = "merge.queue";
+pub const MSG_MERGE_CANCEL: &str = "merge.cancel";
+pub const MSG_MERGE_PRIORITIZE: &str = "merge.prioritize";
 pub const MSG_STATS: &str = "stats";
 
 pub const MSG_PLAN_START: &str = "plan.start";
@@ -76,8 +79,10 @@ pub const EVT_AGENT_CHAT: &str = "agent.chat";
 pub const EVT_AGENT_CREATED: &str = "agent.created";
 pub const EVT_AGENT_DELETED: &str = "agent.deleted";
 pub const EVT_PERMISSION_REQUEST: &str = "permission.requested";
+pub const EVT_PERMISSION_EXPIRED: &str = "permission.expired";
 pub const EVT_USER_QUESTION: &str = "question.requested";
 pub const EVT_AGENT_IDLE: &str = "agent.idle";
+pub const EVT_AGENT_WATCHDOG: &str = "agent.watchdog";
 pub const EVT_ORCHESTRATION_TICK_REQUESTED: &str = "orchestration.tick_requested";
 
 pub const MSG_PERMISSION_REQUEST: &str = "permission.request";
@@ -166,6 +171,18 @@ pub struct AgentIdleEvent {
     pub state: AgentState,
 }
 
+/// Emitted when the watchdog acts on an agent that stopped making progress.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentWatchdogEvent {
+    pub agent_id: String,
+    pub project: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub issue_id: String,
+    /// `nudge`, `needs-resolution`, `abort` or `requeue`.
+    pub action: String,
+    pub stalled_secs: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProjectAddRequest {
     pub name: String,
@@ -267,6 +284,33 @@ pub struct ProjectStatusResponse {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub seconds_since_activity: Option<u64>,
     pub silence_threshold_secs: u64,
+
+    // Global agent pool (`[orchestration] max-total-agents`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_agents: Option<u32>,
+    #[serde(default)]
+    pub total_active_agents: u32,
+    #[serde(default)]
+    pub weight: u32,
+    /// Agents the project wanted on its last tick but could not get a slot for.
+    #[serde(default)]
+    pub waiting_for_slots: u32,
+
+    // Scheduling: ready issues in the order the orchestrator would take them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule: Vec<ScheduledIssue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledIssue {
+    pub issue_id: String,
+    pub title: String,
+    pub effective_priority: i32,
+    /// One of `next`, `waiting`, `claimed`, `completed`.
+    pub decision: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -451,6 +495,28 @@ pub struct PermissionRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tool_use_id: Option<String>,
     pub requested_at_ms: u64,
+    /// When `timeout_behavior` answers the request, if the project has a
+    /// `permission-timeout-secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_behavior: Option<PermissionBehavior>,
+}
+
+/// Emitted when a permission request goes unanswered for the project's
+/// `permission-timeout-secs`, or until just before the agent's hook limit,
+/// and its timeout action answers it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionExpiredEvent {
+    pub id: String,
+    pub agent_id: String,
+    pub project: String,
+    pub tool_name: String,
+    pub behavior: PermissionBehavior,
+    pub timeout_secs: u64,
+    /// The agent's hook limit, not `permission-timeout-secs`, ended the wait.
+    #[serde(default)]
+    pub hook_limit: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -463,6 +529,21 @@ pub struct PermissionResponse {
     pub interrupt: bool,
 }
 
+/// How far a permission response reaches beyond the request it answers.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionScope {
+    /// Only this request.
+    #[default]
+    Once,
+    /// Matching requests from the same agent, until the daemon restarts.
+    Agent,
+    /// A rule appended to the project's permissions file.
+    Project,
+    /// A rule appended to the global permissions file.
+    Global,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PermissionRespondPayload {
     pub id: String,
@@ -471,6 +552,8 @@ pub struct PermissionRespondPayload {
     pub message: Option<String>,
     #[serde(default)]
     pub interrupt: bool,
+    #[serde(default)]
+    pub scope: PermissionScope,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -744,6 +827,33 @@ pub struct IssueGetRequest {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IssueGetResponse {
     pub issue: Issue,
+    /// Agents that worked on the issue, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<IssueAttempt>,
+    /// Failed attempts counted toward `max_attempts` (0 = unlimited).
+    #[serde(default)]
+    pub failures: u32,
+    #[serde(default)]
+    pub max_attempts: u32,
+    #[serde(default)]
+    pub needs_human: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueAttempt {
+    pub agent_id: String,
+    pub started_at_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at_ms: Option<u64>,
+    /// `running` while the agent is still on it.
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Made before the last reset; does not count toward `max_attempts`.
+    #[serde(default)]
+    pub reset: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -918,6 +1028,41 @@ pub struct CommitListResponse {
     pub commits: Vec<CommitRecord>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct MergeQueueRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeQueueEntry {
+    pub project: String,
+    pub agent_id: String,
+    pub issue_id: String,
+    /// 1-based landing order within the project.
+    pub position: u32,
+    pub priority: i32,
+    /// `merging` or `queued`.
+    pub state: String,
+    pub enqueued_at_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeQueueResponse {
+    pub entries: Vec<MergeQueueEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeCancelRequest {
+    pub agent_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergePrioritizeRequest {
+    pub agent_id: String,
+    pub priority: i32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct StatsRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
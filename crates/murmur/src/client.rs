@@ -8,30 +8,33 @@ use murmur_protocol::{
     AgentDoneRequest, AgentIdleRequest, AgentListResponse, AgentSendMessageRequest,
     AgentSyncCommentsRequest, AgentSyncCommentsResponse, ClaimListRequest, ClaimListResponse,
     CommitListRequest, CommitListResponse, DirectorChatHistoryRequest, DirectorChatHistoryResponse,
-    DirectorSendMessageRequest, DirectorStartRequest, DirectorStartResponse, DirectorStatusResponse,
-    IssueCommentRequest, IssueCommitRequest, IssueCreateRequest, IssueCreateResponse,
-    IssueGetRequest, IssueGetResponse, IssueListRequest, IssueListResponse, IssuePlanRequest,
-    IssueReadyRequest, IssueReadyResponse, IssueUpdateRequest, IssueUpdateResponse,
-    ManagerChatHistoryRequest, ManagerChatHistoryResponse, ManagerClearHistoryRequest,
-    ManagerSendMessageRequest, ManagerStartRequest, ManagerStatusRequest, ManagerStatusResponse,
-    ManagerStopRequest, OrchestrationStartRequest, OrchestrationStatusRequest,
-    OrchestrationStatusResponse, OrchestrationStopRequest, PermissionListRequest,
-    PermissionListResponse, PermissionRequestPayload, PermissionRespondPayload, PermissionResponse,
-    PingResponse, PlanChatHistoryRequest, PlanChatHistoryResponse, PlanListRequest,
-    PlanListResponse, PlanSendMessageRequest, PlanShowRequest, PlanShowResponse, PlanStartRequest,
-    PlanStartResponse, PlanStopRequest, ProjectAddRequest, ProjectAddResponse,
-    ProjectConfigGetRequest, ProjectConfigGetResponse, ProjectConfigSetRequest,
-    ProjectConfigShowRequest, ProjectConfigShowResponse, ProjectListResponse, ProjectRemoveRequest,
-    ProjectStatusRequest, ProjectStatusResponse, Request, Response, StatsRequest, StatsResponse,
-    UserQuestionListRequest, UserQuestionListResponse, UserQuestionRequestPayload,
-    UserQuestionRespondPayload, UserQuestionResponse, MSG_AGENT_ABORT, MSG_AGENT_CHAT_HISTORY,
-    MSG_AGENT_CLAIM, MSG_AGENT_CREATE, MSG_AGENT_DELETE, MSG_AGENT_DESCRIBE, MSG_AGENT_DONE,
-    MSG_AGENT_IDLE, MSG_AGENT_LIST, MSG_AGENT_SEND_MESSAGE, MSG_AGENT_SYNC_COMMENTS, MSG_CLAIM_LIST,
-    MSG_COMMIT_LIST, MSG_DIRECTOR_CHAT_HISTORY, MSG_DIRECTOR_CLEAR_HISTORY, MSG_DIRECTOR_SEND_MESSAGE,
-    MSG_DIRECTOR_START, MSG_DIRECTOR_STATUS, MSG_DIRECTOR_STOP, MSG_ISSUE_CLOSE, MSG_ISSUE_COMMENT,
-    MSG_ISSUE_COMMIT, MSG_ISSUE_CREATE, MSG_ISSUE_GET, MSG_ISSUE_LIST, MSG_ISSUE_PLAN,
-    MSG_ISSUE_READY, MSG_ISSUE_UPDATE, MSG_MANAGER_CHAT_HISTORY, MSG_MANAGER_CLEAR_HISTORY,
-    MSG_MANAGER_SEND_MESSAGE, MSG_MANAGER_START, MSG_MANAGER_STATUS, MSG_MANAGER_STOP,
+    DirectorSendMessageRequest, DirectorStartRequest, DirectorStartResponse,
+    DirectorStatusResponse, IssueCommentRequest, IssueCommitRequest, IssueCreateRequest,
+    IssueCreateResponse, IssueGetRequest, IssueGetResponse, IssueListRequest, IssueListResponse,
+    IssuePlanRequest, IssueReadyRequest, IssueReadyResponse, IssueUpdateRequest,
+    IssueUpdateResponse, ManagerChatHistoryRequest, ManagerChatHistoryResponse,
+    ManagerClearHistoryRequest, ManagerSendMessageRequest, ManagerStartRequest,
+    ManagerStatusRequest, ManagerStatusResponse, ManagerStopRequest, MergeCancelRequest,
+    MergePrioritizeRequest, MergeQueueRequest, MergeQueueResponse, OrchestrationStartRequest,
+    OrchestrationStatusRequest, OrchestrationStatusResponse, OrchestrationStopRequest,
+    PermissionListRequest, PermissionListResponse, PermissionRequestPayload,
+    PermissionRespondPayload, PermissionResponse, PingResponse, PlanChatHistoryRequest,
+    PlanChatHistoryResponse, PlanListRequest, PlanListResponse, PlanSendMessageRequest,
+    PlanShowRequest, PlanShowResponse, PlanStartRequest, PlanStartResponse, PlanStopRequest,
+    ProjectAddRequest, ProjectAddResponse, ProjectConfigGetRequest, ProjectConfigGetResponse,
+    ProjectConfigSetRequest, ProjectConfigShowRequest, ProjectConfigShowResponse,
+    ProjectListResponse, ProjectRemoveRequest, ProjectStatusRequest, ProjectStatusResponse,
+    Request, Response, StatsRequest, StatsResponse, UserQuestionListRequest,
+    UserQuestionListResponse, UserQuestionRequestPayload, UserQuestionRespondPayload,
+    UserQuestionResponse, MSG_AGENT_ABORT, MSG_AGENT_CHAT_HISTORY, MSG_AGENT_CLAIM,
+    MSG_AGENT_CREATE, MSG_AGENT_DELETE, MSG_AGENT_DESCRIBE, MSG_AGENT_DONE, MSG_AGENT_IDLE,
+    MSG_AGENT_LIST, MSG_AGENT_SEND_MESSAGE, MSG_AGENT_SYNC_COMMENTS, MSG_CLAIM_LIST,
+    MSG_COMMIT_LIST, MSG_DIRECTOR_CHAT_HISTORY, MSG_DIRECTOR_CLEAR_HISTORY,
+    MSG_DIRECTOR_SEND_MESSAGE, MSG_DIRECTOR_START, MSG_DIRECTOR_STATUS, MSG_DIRECTOR_STOP,
+    MSG_ISSUE_CLOSE, MSG_ISSUE_COMMENT, MSG_ISSUE_COMMIT, MSG_ISSUE_CREATE, MSG_ISSUE_GET,
+    MSG_ISSUE_LIST, MSG_ISSUE_PLAN, MSG_ISSUE_READY, MSG_ISSUE_UPDATE, MSG_MANAGER_CHAT_HISTORY,
+    MSG_MANAGER_CLEAR_HISTORY, MSG_MANAGER_SEND_MESSAGE, MSG_MANAGER_START, MSG_MANAGER_STATUS,
+    MSG_MANAGER_STOP, MSG_MERGE_CANCEL, MSG_MERGE_PRIORITIZE, MSG_MERGE_QUEUE,
     MSG_ORCHESTRATION_START, MSG_ORCHESTRATION_STATUS, MSG_ORCHESTRATION_STOP, MSG_PERMISSION_LIST,
     MSG_PERMISSION_REQUEST, MSG_PERMISSION_RESPOND, MSG_PING, MSG_PLAN_CHAT_HISTORY, MSG_PLAN_LIST,
     MSG_PLAN_SEND_MESSAGE, MSG_PLAN_SHOW, MSG_PLAN_START, MSG_PLAN_STOP, MSG_PROJECT_ADD,
@@ -539,6 +542,63 @@ pub async fn commit_list(
     serde_json::from_value(resp.payload).context("parse commit.list payload")
 }
 
+pub async fn merge_queue(
+    paths: &MurmurPaths,
+    project: Option<String>,
+) -> anyhow::Result<MergeQueueResponse> {
+    let payload = MergeQueueRequest { project };
+    let req = Request {
+        r#type: MSG_MERGE_QUEUE.to_owned(),
+        id: new_request_id("merge-queue"),
+        payload: serde_json::to_value(payload).context("serialize payload")?,
+    };
+    let resp = request(paths, req).await?;
+    if !resp.success {
+        return Err(anyhow!(resp
+            .error
+            .unwrap_or_else(|| "merge.queue failed".to_owned())));
+    }
+    serde_json::from_value(resp.payload).context("parse merge.queue payload")
+}
+
+pub async fn merge_cancel(paths: &MurmurPaths, agent_id: String) -> anyhow::Result<()> {
+    let payload = MergeCancelRequest { agent_id };
+    let req = Request {
+        r#type: MSG_MERGE_CANCEL.to_owned(),
+        id: new_request_id("merge-cancel"),
+        payload: serde_json::to_value(payload).context("serialize payload")?,
+    };
+    let resp = request(paths, req).await?;
+    if resp.success {
+        Ok(())
+    } else {
+        Err(anyhow!(resp
+            .error
+            .unwrap_or_else(|| "merge.cancel failed".to_owned())))
+    }
+}
+
+pub async fn merge_prioritize(
+    paths: &MurmurPaths,
+    agent_id: String,
+    priority: i32,
+) -> anyhow::Result<()> {
+    let payload = MergePrioritizeRequest { agent_id, priority };
+    let req = Request {
+        r#type: MSG_MERGE_PRIORITIZE.to_owned(),
+        id: new_request_id("merge-prioritize"),
+        payload: serde_json::to_value(payload).context("serialize payload")?,
+    };
+    let resp = request(paths, req).await?;
+    if resp.success {
+        Ok(())
+    } else {
+        Err(anyhow!(resp
+            .error
+            .unwrap_or_else(|| "merge.prioritize failed".to_owned())))
+    }
+}
+
 pub async fn stats(paths: &MurmurPaths, project: Option<String>) -> anyhow::Result<StatsResponse> {
     let payload = StatsRequest { project };
     let req = Request {
@@ -727,13 +787,14 @@ pub async fn issue_commit(paths: &MurmurPaths, project: String) -> anyhow::Resul
 pub async fn permission_request(
     paths: &MurmurPaths,
     payload: PermissionRequestPayload,
+    read_timeout: Duration,
 ) -> anyhow::Result<PermissionResponse> {
     let req = Request {
         r#type: MSG_PERMISSION_REQUEST.to_owned(),
         id: new_request_id("permission-request"),
         payload: serde_json::to_value(payload).context("serialize payload")?,
     };
-    let resp = request_with_timeout(paths, req, Duration::from_secs(5 * 60)).await?;
+    let resp = request_with_timeout(paths, req, read_timeout).await?;
     if !resp.success {
         return Err(anyhow!(resp
             .error
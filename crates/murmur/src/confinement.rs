@@ -0,0 +1,234 @@
+//! Keeps agents' file-writing tools inside their worktree.
+
+use std::path::{Component, Path, PathBuf};
+
+use murmur_core::config::ConfinementConfig;
+use serde_json::Value;
+
+/// Tools whose target file is confined.
+pub const CONFINED_TOOLS: &[&str] = &["Write", "Edit", "MultiEdit", "NotebookEdit"];
+
+/// Writable without configuration, besides the worktree.
+const TEMP_DIRS: &[&str] = &["/tmp", "/var/tmp"];
+
+/// Symlinks followed while resolving one path, as in the kernel's limit.
+const MAX_SYMLINKS: u32 = 40;
+
+/// The file a confined tool call writes, or `None` for other tools.
+pub fn target_path(tool_name: &str, tool_input: &Value) -> Option<String> {
+    if !CONFINED_TOOLS.contains(&tool_name) {
+        return None;
+    }
+    let field = if tool_name == "NotebookEdit" {
+        "notebook_path"
+    } else {
+        "file_path"
+    };
+    tool_input
+        .get(field)
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+}
+
+/// The path a write to `path` lands on: relative to `cwd`, with `.`, `..`
+/// and symlinks (including ones to files that do not exist yet) resolved the
+/// way the kernel resolves them.
+pub fn resolve_path(path: &Path, cwd: &Path) -> PathBuf {
+    let mut links = 0;
+    resolve(&cwd.join(path), &mut links)
+}
+
+fn resolve(path: &Path, links: &mut u32) -> PathBuf {
+    let mut out = PathBuf::from("/");
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => out = PathBuf::from("/"),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::Normal(name) => {
+                out.push(name);
+                // `out` has no symlinks above this component, so following
+                // one here resolves relative targets against a real parent.
+                let is_link =
+                    std::fs::symlink_metadata(&out).is_ok_and(|meta| meta.file_type().is_symlink());
+                if is_link && *links < MAX_SYMLINKS {
+                    if let Ok(target) = std::fs::read_link(&out) {
+                        *links += 1;
+                        out.pop();
+                        let target = out.join(target);
+                        out = resolve(&target, links);
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Directories writes may go to, resolved: the worktree, the temp dirs and
+/// the configured extras.
+pub fn allowed_dirs(
+    worktree_dir: &Path,
+    config: &ConfinementConfig,
+    home_dir: &Path,
+) -> Vec<PathBuf> {
+    let mut dirs = vec![worktree_dir.to_path_buf()];
+    dirs.extend(TEMP_DIRS.iter().map(PathBuf::from));
+    dirs.push(std::env::temp_dir());
+    for dir in &config.allowed_dirs {
+        let dir = dir.trim();
+        if dir.is_empty() {
+            continue;
+        }
+        dirs.push(match dir.strip_prefix("~/") {
+            Some(rest) => home_dir.join(rest),
+            None if dir == "~" => home_dir.to_path_buf(),
+            None => PathBuf::from(dir),
+        });
+    }
+
+    let mut resolved: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        let dir = resolve_path(&dir, worktree_dir);
+        if !resolved.contains(&dir) {
+            resolved.push(dir);
+        }
+    }
+    resolved
+}
+
+/// Why the call may not write where it does, or `None` if it may.
+/// `allowed` comes from [`allowed_dirs`]; the first entry is the worktree.
+pub fn check(
+    tool_name: &str,
+    tool_input: &Value,
+    cwd: &Path,
+    allowed: &[PathBuf],
+) -> Option<String> {
+    let target = target_path(tool_name, tool_input)?;
+    let target = target.trim();
+    if target.is_empty() {
+        return None;
+    }
+    let resolved = resolve_path(Path::new(target), cwd);
+    if allowed.iter().any(|dir| resolved.starts_with(dir)) {
+        return None;
+    }
+
+    let via = if Path::new(target) == resolved {
+        String::new()
+    } else {
+        format!(" (resolves to {})", resolved.display())
+    };
+    let dirs: Vec<String> = allowed.iter().map(|d| d.display().to_string()).collect();
+    Some(format!(
+        "write confinement: {tool_name} to {target}{via} is outside this agent's worktree; \
+         writes are limited to {}",
+        dirs.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolves_dot_dot_and_symlinks_before_the_file_exists() {
+        let dir = TempDir::new().unwrap();
+        let base = resolve_path(dir.path(), Path::new("/"));
+        let worktree = base.join("wt");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(worktree.join("src")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, worktree.join("escape")).unwrap();
+        std::os::unix::fs::symlink("../outside/new.txt", worktree.join("dangling")).unwrap();
+
+        assert_eq!(
+            resolve_path(Path::new("src/../new/a.rs"), &worktree),
+            worktree.join("new/a.rs")
+        );
+        assert_eq!(
+            resolve_path(&worktree.join("escape/x/../y.txt"), Path::new("/")),
+            outside.join("y.txt")
+        );
+        assert_eq!(
+            resolve_path(&worktree.join("dangling"), Path::new("/")),
+            outside.join("new.txt")
+        );
+        // `..` after a symlink leaves the link's target, not the link.
+        assert_eq!(
+            resolve_path(&worktree.join("escape/../wt/src"), Path::new("/")),
+            worktree.join("src")
+        );
+    }
+
+    #[test]
+    fn check_denies_writes_outside_allowed_dirs() {
+        let dir = TempDir::new().unwrap();
+        let base = resolve_path(dir.path(), Path::new("/"));
+        let worktree = base.join("wt");
+        let sibling = base.join("wt-2");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::create_dir_all(&sibling).unwrap();
+        std::os::unix::fs::symlink(&sibling, worktree.join("link")).unwrap();
+
+        let config = ConfinementConfig {
+            enabled: true,
+            allowed_dirs: vec!["~/.cache/demo".to_owned()],
+        };
+        let allowed = allowed_dirs(&worktree, &config, Path::new("/home/u"));
+        assert_eq!(allowed[0], worktree);
+        let check = |tool: &str, input: Value| check(tool, &input, &worktree, &allowed);
+
+        assert_eq!(
+            check("Write", serde_json::json!({"file_path": "src/main.rs"})),
+            None
+        );
+        assert_eq!(
+            check("Edit", serde_json::json!({"file_path": "/tmp/notes"})),
+            None
+        );
+        assert_eq!(
+            check(
+                "Write",
+                serde_json::json!({"file_path": "/home/u/.cache/demo/out"})
+            ),
+            None
+        );
+        assert_eq!(
+            check("Read", serde_json::json!({"file_path": "/etc/passwd"})),
+            None
+        );
+
+        let reason = check(
+            "Write",
+            serde_json::json!({"file_path": "/home/u/.ssh/config"}),
+        )
+        .unwrap();
+        assert!(
+            reason.starts_with("write confinement: Write to /home/u/.ssh/config is outside"),
+            "{reason}"
+        );
+        assert!(reason.contains(&worktree.display().to_string()), "{reason}");
+
+        // The temp dir holds `sibling` too, so leave it out here.
+        let link = worktree.join("link/main.rs");
+        let reason = super::check(
+            "NotebookEdit",
+            &serde_json::json!({"notebook_path": link.to_string_lossy()}),
+            &worktree,
+            &allowed[..1],
+        )
+        .unwrap();
+        assert!(
+            reason.contains(&format!(
+                "(resolves to {})",
+                sibling.join("main.rs").display()
+            )),
+            "{reason}"
+        );
+    }
+}
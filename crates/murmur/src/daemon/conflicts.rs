@@ -0,0 +1,74 @@
+//! Rebase conflicts found while landing an agent's branch.
+
+use murmur_core::agent::{AgentEvent, AgentState, ChatMessage, ChatRole};
+use murmur_core::config::ConflictResolution;
+use murmur_core::conflicts::{
+    conflict_attempts_exhausted, conflict_resolution_prompt, MergeConflict,
+};
+
+use super::state::SharedState;
+use super::{deliver_user_message, emit_agent_chat_event, now_ms};
+
+/// With `resolution = "agent"`, sends the conflict back to the agent for a
+/// resolution turn until it has used up `max-attempts`; otherwise marks the
+/// agent `needs_resolution`. `kind` names the failure ("merge conflict",
+/// "rebase conflict"). Returns the error for the `agent.done` caller.
+pub(in crate::daemon) async fn report_conflict(
+    shared: &SharedState,
+    agent_id: &str,
+    project: &str,
+    kind: &'static str,
+    conflict: &MergeConflict,
+) -> String {
+    let policy = {
+        let cfg = shared.config.lock().await;
+        cfg.project(project)
+            .map(|p| p.effective_conflicts())
+            .unwrap_or_default()
+    };
+
+    if policy.resolution == ConflictResolution::Agent {
+        let attempt = {
+            let mut agents = shared.agents.lock().await;
+            match agents.agents.get_mut(agent_id) {
+                Some(rt)
+                    if rt.record.state != AgentState::Exited
+                        && !conflict_attempts_exhausted(
+                            rt.conflict_attempts,
+                            policy.max_attempts,
+                        ) =>
+                {
+                    rt.conflict_attempts += 1;
+                    Some(rt.conflict_attempts)
+                }
+                _ => None,
+            }
+        };
+        if let Some(attempt) = attempt {
+            let prompt = conflict_resolution_prompt(conflict, attempt, policy.max_attempts);
+            deliver_user_message(shared, agent_id, project, prompt).await;
+            return format!(
+                "{kind} on {} (resolution attempt {attempt}/{}; sent to agent)",
+                conflict.branch, policy.max_attempts
+            );
+        }
+    }
+
+    let now_ms = now_ms();
+    let msg = ChatMessage::new(
+        ChatRole::System,
+        format!("{kind} on {}: {}", conflict.branch, conflict.error),
+        now_ms,
+    );
+    {
+        let mut agents = shared.agents.lock().await;
+        if let Some(rt) = agents.agents.get_mut(agent_id) {
+            rt.record = rt
+                .record
+                .apply_event(AgentEvent::NeedsResolution { reason: kind }, now_ms);
+            rt.chat.push(msg.clone());
+        }
+    }
+    emit_agent_chat_event(shared, agent_id, project, msg);
+    format!("{kind} (agent needs resolution)")
+}
@@ -1,12 +1,16 @@
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{anyhow, Context as _};
+use murmur_core::config::{MergeStrategy, VerifyConfig};
+use murmur_core::conflicts::{MergeConflict, MAX_PROMPT_UPSTREAM_COMMITS};
+use murmur_core::merge_message::{render_merge_message, MergeMessageVars};
+use murmur_core::verify::VerifyFailure;
 
 use crate::git::{agent_branch_name, parse_default_branch_from_remote_show, Git};
 
-use super::{project_repo_dir, SharedState};
+use super::verify::run_verification;
+use super::{issue_backend_for_project, project_repo_dir, SharedState};
 
 async fn maybe_test_merge_delay() {
     if !cfg!(debug_assertions) {
@@ -27,10 +31,19 @@ pub(in crate::daemon) struct MergeSuccess {
     pub(in crate::daemon) branch: String,
 }
 
+/// How a rebased agent branch lands on the base branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::daemon) enum Landing {
+    FastForward,
+    Squash { message: String },
+    MergeCommit { message: String },
+}
+
 #[derive(Debug)]
 pub(in crate::daemon) enum MergeAttempt {
     Merged(MergeSuccess),
-    Conflict { branch: String, error: String },
+    Conflict(MergeConflict),
+    VerifyFailed(VerifyFailure),
 }
 
 #[derive(Debug)]
@@ -43,18 +56,23 @@ pub(in crate::daemon) struct PullRequestPrep {
 #[derive(Debug)]
 pub(in crate::daemon) enum PullRequestAttempt {
     Ready(PullRequestPrep),
-    Conflict { branch: String, error: String },
+    Conflict(MergeConflict),
 }
 
-pub(in crate::daemon) async fn merge_lock_for_project(
-    shared: &SharedState,
-    project: &str,
-) -> Arc<tokio::sync::Mutex<()>> {
-    let mut locks = shared.merge_locks.lock().await;
-    locks
-        .entry(project.to_owned())
-        .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
-        .clone()
+/// A queued branch landing as part of a batch.
+#[derive(Debug)]
+pub(in crate::daemon) struct BatchMember {
+    pub(in crate::daemon) agent_id: String,
+    pub(in crate::daemon) worktree_dir: PathBuf,
+}
+
+#[derive(Debug)]
+pub(in crate::daemon) enum BatchAttempt {
+    /// Every member landed; results are in member order.
+    Merged(Vec<MergeSuccess>),
+    /// The stack did not rebase or verify cleanly. Member worktrees are back
+    /// where they started.
+    Failed(String),
 }
 
 async fn determine_default_branch(git: &Git, repo_dir: &Path) -> anyhow::Result<String> {
@@ -79,11 +97,61 @@ async fn determine_default_branch(git: &Git, repo_dir: &Path) -> anyhow::Result<
     Err(anyhow!("could not determine default branch"))
 }
 
+/// Picks the landing for a direct-path strategy, rendering the project's
+/// merge message template for squash and merge-commit.
+pub(in crate::daemon) async fn landing_for(
+    shared: &SharedState,
+    project: &str,
+    strategy: MergeStrategy,
+    agent_id: &str,
+    issue_id: &str,
+) -> anyhow::Result<Landing> {
+    if !matches!(strategy, MergeStrategy::Squash | MergeStrategy::MergeCommit) {
+        return Ok(Landing::FastForward);
+    }
+
+    let template = {
+        let cfg = shared.config.lock().await;
+        cfg.project(project)
+            .map(|p| p.effective_merge_message_template().to_owned())
+            .ok_or_else(|| anyhow!("project not found: {project}"))?
+    };
+
+    let issue = match issue_backend_for_project(shared, project).await {
+        Ok(backend) => backend
+            .get(issue_id)
+            .await
+            .map_err(|err| format!("{err:#}")),
+        Err(msg) => Err(msg),
+    };
+    let (title, description) = match issue {
+        Ok(issue) => (issue.title, issue.description),
+        Err(msg) => {
+            tracing::warn!(project = %project, issue_id = %issue_id, error = %msg, "load issue for merge message failed");
+            (String::new(), String::new())
+        }
+    };
+
+    let vars = MergeMessageVars {
+        issue_id: issue_id.to_owned(),
+        title,
+        agent_id: agent_id.to_owned(),
+        description,
+    };
+    let message = render_merge_message(&template, &vars).map_err(|err| anyhow!(err))?;
+
+    Ok(match strategy {
+        MergeStrategy::Squash => Landing::Squash { message },
+        _ => Landing::MergeCommit { message },
+    })
+}
+
 pub(in crate::daemon) async fn merge_agent_branch_direct(
     shared: &SharedState,
     project: &str,
     agent_id: &str,
     worktree_dir: &Path,
+    landing: &Landing,
 ) -> anyhow::Result<MergeAttempt> {
     let repo_dir = project_repo_dir(&shared.paths, project);
     if !repo_dir.join(".git").exists() {
@@ -92,31 +160,26 @@ pub(in crate::daemon) async fn merge_agent_branch_direct(
 
     maybe_test_merge_delay().await;
 
-    shared.git.fetch_origin(&repo_dir).await?;
-
-    let base_branch = determine_default_branch(&shared.git, &repo_dir).await?;
-    let upstream = format!("origin/{base_branch}");
+    let (base_branch, upstream) = checkout_base_branch(&shared.git, &repo_dir).await?;
 
-    if shared.git.checkout(&repo_dir, &base_branch).await.is_err() {
-        shared
-            .git
-            .checkout_force(&repo_dir, &base_branch, &upstream)
-            .await?;
+    if let Err(err) = shared.git.rebase_onto(worktree_dir, &upstream).await {
+        let conflict =
+            abort_conflicted_rebase(&shared.git, agent_id, worktree_dir, &upstream, err).await;
+        return Ok(MergeAttempt::Conflict(conflict));
     }
-    shared.git.reset_hard(&repo_dir, &upstream).await?;
 
-    if let Err(err) = shared.git.rebase_onto(worktree_dir, &upstream).await {
-        shared.git.rebase_abort_best_effort(worktree_dir).await;
-        return Ok(MergeAttempt::Conflict {
-            branch: agent_branch_name(agent_id),
-            error: format!("{err:#}"),
-        });
+    let verify = verify_policy(shared, project).await;
+    if let Some(failure) = run_verification(&verify, worktree_dir).await? {
+        return Ok(MergeAttempt::VerifyFailed(failure));
     }
 
-    let sha = shared.git.rev_parse(worktree_dir, "HEAD").await?;
     let branch = agent_branch_name(agent_id);
 
-    shared.git.merge_ff_only(&repo_dir, &branch).await?;
+    if let Err(err) = land_branch(&shared.git, &repo_dir, &branch, landing).await {
+        let _ = shared.git.reset_hard(&repo_dir, &upstream).await;
+        return Err(err);
+    }
+    let sha = shared.git.rev_parse(&repo_dir, "HEAD").await?;
 
     if let Err(err) = shared.git.push_ref(&repo_dir, "origin", &base_branch).await {
         let _ = shared.git.reset_hard(&repo_dir, &upstream).await;
@@ -126,6 +189,158 @@ pub(in crate::daemon) async fn merge_agent_branch_direct(
     Ok(MergeAttempt::Merged(MergeSuccess { sha, branch }))
 }
 
+/// Rebases each member onto the one before it (the first onto the base
+/// branch), verifies the top of the stack once and fast-forwards the base
+/// branch to it, landing every member with a single push.
+pub(in crate::daemon) async fn merge_agent_branches_batched(
+    shared: &SharedState,
+    project: &str,
+    members: &[BatchMember],
+) -> anyhow::Result<BatchAttempt> {
+    let repo_dir = project_repo_dir(&shared.paths, project);
+    if !repo_dir.join(".git").exists() {
+        return Err(anyhow!("project repo not found: {}", repo_dir.display()));
+    }
+    let Some(top) = members.last() else {
+        return Ok(BatchAttempt::Merged(Vec::new()));
+    };
+
+    maybe_test_merge_delay().await;
+
+    let (base_branch, upstream) = checkout_base_branch(&shared.git, &repo_dir).await?;
+
+    let mut original = Vec::with_capacity(members.len());
+    for member in members {
+        original.push(shared.git.rev_parse(&member.worktree_dir, "HEAD").await?);
+    }
+
+    let mut onto = upstream.clone();
+    for member in members {
+        if let Err(err) = shared.git.rebase_onto(&member.worktree_dir, &onto).await {
+            shared
+                .git
+                .rebase_abort_best_effort(&member.worktree_dir)
+                .await;
+            restore_worktrees(&shared.git, members, &original).await;
+            return Ok(BatchAttempt::Failed(format!(
+                "{} does not rebase onto {onto}: {err:#}",
+                agent_branch_name(&member.agent_id)
+            )));
+        }
+        onto = agent_branch_name(&member.agent_id);
+    }
+
+    let verify = verify_policy(shared, project).await;
+    if let Some(failure) = run_verification(&verify, &top.worktree_dir).await? {
+        restore_worktrees(&shared.git, members, &original).await;
+        return Ok(BatchAttempt::Failed(format!(
+            "verification failed: {}",
+            failure.summary()
+        )));
+    }
+
+    let mut merged = Vec::with_capacity(members.len());
+    for member in members {
+        merged.push(MergeSuccess {
+            sha: shared.git.rev_parse(&member.worktree_dir, "HEAD").await?,
+            branch: agent_branch_name(&member.agent_id),
+        });
+    }
+
+    shared
+        .git
+        .merge_ff_only(&repo_dir, &agent_branch_name(&top.agent_id))
+        .await?;
+
+    if let Err(err) = shared.git.push_ref(&repo_dir, "origin", &base_branch).await {
+        let _ = shared.git.reset_hard(&repo_dir, &upstream).await;
+        restore_worktrees(&shared.git, members, &original).await;
+        return Err(err).context("push base branch");
+    }
+
+    Ok(BatchAttempt::Merged(merged))
+}
+
+/// Moves the checked-out base branch onto `branch`. The agent branch itself is
+/// left as it is, so a squash keeps its individual commits.
+async fn land_branch(
+    git: &Git,
+    repo_dir: &Path,
+    branch: &str,
+    landing: &Landing,
+) -> anyhow::Result<()> {
+    match landing {
+        Landing::FastForward => git.merge_ff_only(repo_dir, branch).await,
+        Landing::Squash { message } => {
+            git.merge_squash(repo_dir, branch).await?;
+            if git.diff_cached_has_changes(repo_dir).await? {
+                git.commit(repo_dir, message).await?;
+            }
+            Ok(())
+        }
+        Landing::MergeCommit { message } => git.merge_no_ff(repo_dir, branch, message).await,
+    }
+}
+
+/// Records what conflicted, then aborts the rebase so the worktree is back on
+/// the agent's commits.
+async fn abort_conflicted_rebase(
+    git: &Git,
+    agent_id: &str,
+    worktree_dir: &Path,
+    upstream: &str,
+    err: anyhow::Error,
+) -> MergeConflict {
+    let files = git.conflicted_files(worktree_dir).await.unwrap_or_default();
+    git.rebase_abort_best_effort(worktree_dir).await;
+    let upstream_commits = git
+        .log_oneline(
+            worktree_dir,
+            &format!("HEAD..{upstream}"),
+            MAX_PROMPT_UPSTREAM_COMMITS + 1,
+        )
+        .await
+        .unwrap_or_default();
+    MergeConflict {
+        branch: agent_branch_name(agent_id),
+        upstream: upstream.to_owned(),
+        files,
+        upstream_commits,
+        error: format!("{err:#}"),
+    }
+}
+
+async fn restore_worktrees(git: &Git, members: &[BatchMember], original: &[String]) {
+    for (member, sha) in members.iter().zip(original) {
+        if let Err(err) = git.reset_hard(&member.worktree_dir, sha).await {
+            tracing::warn!(agent_id = %member.agent_id, error = %err, "restore worktree failed");
+        }
+    }
+}
+
+/// Fetches and resets the project repo's base branch to its upstream.
+/// Returns the branch and upstream ref.
+async fn checkout_base_branch(git: &Git, repo_dir: &Path) -> anyhow::Result<(String, String)> {
+    git.fetch_origin(repo_dir).await?;
+
+    let base_branch = determine_default_branch(git, repo_dir).await?;
+    let upstream = format!("origin/{base_branch}");
+
+    if git.checkout(repo_dir, &base_branch).await.is_err() {
+        git.checkout_force(repo_dir, &base_branch, &upstream)
+            .await?;
+    }
+    git.reset_hard(repo_dir, &upstream).await?;
+    Ok((base_branch, upstream))
+}
+
+async fn verify_policy(shared: &SharedState, project: &str) -> VerifyConfig {
+    let cfg = shared.config.lock().await;
+    cfg.project(project)
+        .map(|p| p.effective_verify())
+        .unwrap_or_default()
+}
+
 pub(in crate::daemon) async fn prepare_agent_branch_pull_request(
     shared: &SharedState,
     project: &str,
@@ -145,11 +360,9 @@ pub(in crate::daemon) async fn prepare_agent_branch_pull_request(
     let upstream = format!("origin/{base_branch}");
 
     if let Err(err) = shared.git.rebase_onto(worktree_dir, &upstream).await {
-        shared.git.rebase_abort_best_effort(worktree_dir).await;
-        return Ok(PullRequestAttempt::Conflict {
-            branch: agent_branch_name(agent_id),
-            error: format!("{err:#}"),
-        });
+        let conflict =
+            abort_conflicted_rebase(&shared.git, agent_id, worktree_dir, &upstream, err).await;
+        return Ok(PullRequestAttempt::Conflict(conflict));
     }
 
     let sha = shared.git.rev_parse(worktree_dir, "HEAD").await?;
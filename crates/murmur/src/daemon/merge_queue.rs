@@ -0,0 +1,279 @@
+//! Per-project merge queue. `agent.done` callers wait here for their turn to
+//! land; the head of the queue may take the next few entries with it as a
+//! speculative batch.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use murmur_core::config::MergeStrategy;
+use murmur_core::merge_queue::{MergeQueue, MergeQueueEntry};
+use tokio::sync::oneshot;
+
+use super::merge::{
+    merge_agent_branch_direct, merge_agent_branches_batched, BatchAttempt, BatchMember, Landing,
+    MergeAttempt, MergeSuccess,
+};
+use super::now_ms;
+use super::state::SharedState;
+
+#[derive(Default)]
+pub(in crate::daemon) struct MergeQueues {
+    queues: BTreeMap<String, MergeQueue>,
+    waiters: BTreeMap<String, oneshot::Sender<MergeTurn>>,
+}
+
+pub(in crate::daemon) enum MergeTurn {
+    /// Merge now. `batch` holds queued agents to stack on top of this one.
+    Lead {
+        batch: Vec<MergeQueueEntry>,
+        guard: Arc<MergeTurnGuard>,
+    },
+    /// A batch led by another agent already landed this branch.
+    Landed {
+        merged: MergeSuccess,
+        guard: Arc<MergeTurnGuard>,
+    },
+    /// Removed from the queue before its turn came.
+    Cancelled,
+}
+
+/// Holds the project's turn. The next batch starts once the leader and every
+/// landed member have dropped their handle, so post-merge ticket commits never
+/// race the next merge in the project repo.
+pub(in crate::daemon) struct MergeTurnGuard {
+    shared: Arc<SharedState>,
+    project: String,
+    agent_id: String,
+    bookkeeping: tokio::sync::Mutex<()>,
+}
+
+impl MergeTurnGuard {
+    /// Agents that landed together close their issues one at a time: the tk
+    /// backend commits tickets in the project repo.
+    pub(in crate::daemon) async fn bookkeeping(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.bookkeeping.lock().await
+    }
+}
+
+impl Drop for MergeTurnGuard {
+    fn drop(&mut self) {
+        let shared = self.shared.clone();
+        let project = std::mem::take(&mut self.project);
+        let agent_id = std::mem::take(&mut self.agent_id);
+        tokio::spawn(async move {
+            {
+                let mut queues = shared.merge_queues.lock().await;
+                if let Some(queue) = queues.queues.get_mut(&project) {
+                    queue.remove(&agent_id);
+                    queue.requeue_merging();
+                }
+            }
+            pump(&shared, &project).await;
+        });
+    }
+}
+
+/// Queues the agent and waits until it may merge.
+pub(in crate::daemon) async fn wait_for_turn(
+    shared: &Arc<SharedState>,
+    project: &str,
+    agent_id: &str,
+    issue_id: &str,
+) -> Result<MergeTurn, String> {
+    let (tx, rx) = oneshot::channel();
+    {
+        let mut queues = shared.merge_queues.lock().await;
+        queues
+            .queues
+            .entry(project.to_owned())
+            .or_default()
+            .enqueue(agent_id, issue_id, now_ms())
+            .map_err(|err| err.to_string())?;
+        queues.waiters.insert(agent_id.to_owned(), tx);
+    }
+    pump(shared, project).await;
+    Ok(rx.await.unwrap_or(MergeTurn::Cancelled))
+}
+
+/// Starts the next batch if the project is idle.
+async fn pump(shared: &Arc<SharedState>, project: &str) {
+    let batch_size = {
+        let cfg = shared.config.lock().await;
+        match cfg.project(project) {
+            // Squash and merge-commit land one commit per issue.
+            Some(p) if p.merge_strategy == MergeStrategy::Direct => {
+                p.effective_merge_batch_size() as usize
+            }
+            _ => 1,
+        }
+    };
+
+    let mut queues = shared.merge_queues.lock().await;
+    let MergeQueues { queues, waiters } = &mut *queues;
+    loop {
+        let Some(queue) = queues.get_mut(project) else {
+            return;
+        };
+        if queue.is_empty() {
+            queues.remove(project);
+            return;
+        }
+        let mut batch = queue.start_batch(batch_size);
+        if batch.is_empty() {
+            return;
+        }
+        let leader = batch.remove(0);
+        let Some(tx) = waiters.remove(&leader.agent_id) else {
+            queue.remove(&leader.agent_id);
+            queue.requeue_merging();
+            continue;
+        };
+        let guard = Arc::new(MergeTurnGuard {
+            shared: shared.clone(),
+            project: project.to_owned(),
+            agent_id: leader.agent_id,
+            bookkeeping: tokio::sync::Mutex::new(()),
+        });
+        // If the caller went away, dropping the guard hands the turn on.
+        let _ = tx.send(MergeTurn::Lead { batch, guard });
+        return;
+    }
+}
+
+/// Lands the leader's branch, stacking the rest of its batch on top when
+/// there is one (fast-forward landings only). Landed members are handed their
+/// result; if the batch fails they go back in line and the leader merges
+/// alone.
+pub(in crate::daemon) async fn merge_turn_direct(
+    shared: &Arc<SharedState>,
+    project: &str,
+    agent_id: &str,
+    worktree_dir: &Path,
+    batch: &[MergeQueueEntry],
+    landing: &Landing,
+    guard: &Arc<MergeTurnGuard>,
+) -> anyhow::Result<MergeAttempt> {
+    let mut members = vec![BatchMember {
+        agent_id: agent_id.to_owned(),
+        worktree_dir: worktree_dir.to_owned(),
+    }];
+    {
+        let agents = shared.agents.lock().await;
+        for entry in batch {
+            if let Some(rt) = agents.agents.get(&entry.agent_id) {
+                members.push(BatchMember {
+                    agent_id: entry.agent_id.clone(),
+                    worktree_dir: PathBuf::from(&rt.record.worktree_dir),
+                });
+            }
+        }
+    }
+
+    if members.len() > 1 && *landing == Landing::FastForward {
+        match merge_agent_branches_batched(shared.as_ref(), project, &members).await {
+            Ok(BatchAttempt::Merged(merged)) => {
+                let mut merged = merged.into_iter();
+                let own = merged.next();
+                hand_over(shared, project, &members[1..], merged, guard).await;
+                if let Some(own) = own {
+                    return Ok(MergeAttempt::Merged(own));
+                }
+            }
+            Ok(BatchAttempt::Failed(reason)) => {
+                tracing::info!(project = %project, size = members.len(), reason = %reason, "batched merge failed; merging alone");
+            }
+            Err(err) => {
+                tracing::warn!(project = %project, size = members.len(), error = %err, "batched merge failed; merging alone");
+            }
+        }
+    }
+
+    {
+        let mut queues = shared.merge_queues.lock().await;
+        if let Some(queue) = queues.queues.get_mut(project) {
+            for entry in batch {
+                queue.requeue(&entry.agent_id);
+            }
+        }
+    }
+    merge_agent_branch_direct(shared.as_ref(), project, agent_id, worktree_dir, landing).await
+}
+
+async fn hand_over(
+    shared: &SharedState,
+    project: &str,
+    members: &[BatchMember],
+    merged: impl Iterator<Item = MergeSuccess>,
+    guard: &Arc<MergeTurnGuard>,
+) {
+    let mut queues = shared.merge_queues.lock().await;
+    let MergeQueues { queues, waiters } = &mut *queues;
+    for (member, merged) in members.iter().zip(merged) {
+        if let Some(queue) = queues.get_mut(project) {
+            queue.remove(&member.agent_id);
+        }
+        if let Some(tx) = waiters.remove(&member.agent_id) {
+            let _ = tx.send(MergeTurn::Landed {
+                merged,
+                guard: guard.clone(),
+            });
+        }
+    }
+}
+
+/// Queue entries in landing order, per project.
+pub(in crate::daemon) async fn list(
+    shared: &SharedState,
+    project: Option<&str>,
+) -> Vec<(String, MergeQueueEntry)> {
+    let queues = shared.merge_queues.lock().await;
+    queues
+        .queues
+        .iter()
+        .filter(|(name, _)| project.is_none_or(|p| p == name.as_str()))
+        .flat_map(|(name, queue)| {
+            queue
+                .entries()
+                .iter()
+                .map(move |entry| (name.clone(), entry.clone()))
+        })
+        .collect()
+}
+
+/// Removes a waiting agent; its `agent.done` call fails.
+pub(in crate::daemon) async fn cancel(shared: &SharedState, agent_id: &str) -> Result<(), String> {
+    let mut queues = shared.merge_queues.lock().await;
+    let MergeQueues { queues, waiters } = &mut *queues;
+    let Some(queue) = queues.values_mut().find(|q| q.get(agent_id).is_some()) else {
+        return Err(format!("agent not in merge queue: {agent_id}"));
+    };
+    queue.cancel(agent_id).map_err(|err| err.to_string())?;
+    if let Some(tx) = waiters.remove(agent_id) {
+        let _ = tx.send(MergeTurn::Cancelled);
+    }
+    Ok(())
+}
+
+pub(in crate::daemon) async fn prioritize(
+    shared: &SharedState,
+    agent_id: &str,
+    priority: i32,
+) -> Result<(), String> {
+    let mut queues = shared.merge_queues.lock().await;
+    let Some(queue) = queues
+        .queues
+        .values_mut()
+        .find(|q| q.get(agent_id).is_some())
+    else {
+        return Err(format!("agent not in merge queue: {agent_id}"));
+    };
+    queue
+        .set_priority(agent_id, priority)
+        .map_err(|err| err.to_string())
+}
+
+/// Drops a removed agent from the queue unless it is already merging.
+pub(in crate::daemon) async fn leave(shared: &SharedState, agent_id: &str) {
+    let _ = cancel(shared, agent_id).await;
+}
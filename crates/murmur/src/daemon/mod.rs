@@ -7,10 +7,12 @@ use anyhow::{anyhow, Context as _};
 use murmur_core::agent::{
     AgentEvent, AgentRecord, AgentRole, AgentState, ChatHistory, ChatMessage, ChatRole,
 };
+use murmur_core::attempts::AttemptOutcome;
 use murmur_core::claims::ClaimRegistry;
 use murmur_core::config::AgentBackend;
 use murmur_core::paths::MurmurPaths;
 use murmur_core::stream::{InputMessage, MessageBody, StreamMessage};
+use murmur_core::watchdog::WatchdogProgress;
 use murmur_protocol::{
     AgentChatEvent, AgentCreatedEvent, Event, EVT_AGENT_CHAT, EVT_AGENT_CREATED,
 };
@@ -21,20 +23,26 @@ use crate::config_store;
 use crate::dedup_store::DedupStore;
 use crate::git::Git;
 use crate::ipc::jsonl::{read_jsonl, write_jsonl};
+use crate::issue_backends::Registry;
 use crate::runtime_store;
 use crate::worktrees::WorktreeManager;
 
 mod claude;
 mod comment_poller;
+mod conflicts;
 pub mod host_manager;
 mod issue_backend;
 mod merge;
+mod merge_queue;
 mod orchestration;
 mod prompts;
 mod proto;
+mod retry;
 mod rpc;
 mod server;
 mod state;
+mod verify;
+mod watchdog;
 mod webhook;
 
 use issue_backend::issue_backend_for_project;
@@ -65,6 +73,14 @@ pub struct DaemonRuntime {
 }
 
 pub async fn run_foreground(paths: &MurmurPaths) -> anyhow::Result<()> {
+    run_foreground_with_backends(paths, Registry::default()).await
+}
+
+/// Runs the daemon with `issue_backends` as the backends projects can name.
+pub async fn run_foreground_with_backends(
+    paths: &MurmurPaths,
+    issue_backends: Registry,
+) -> anyhow::Result<()> {
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
     let handle = DaemonHandle { shutdown_tx };
 
@@ -93,6 +109,14 @@ pub async fn run_foreground(paths: &MurmurPaths) -> anyhow::Result<()> {
         }
     };
 
+    let attempts = match runtime_store::load_attempts(paths).await {
+        Ok(log) => log,
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to load attempt history, using empty");
+            murmur_core::attempts::AttemptLog::default()
+        }
+    };
+
     let shared = Arc::new(SharedState {
         pid,
         started_at,
@@ -105,15 +129,20 @@ pub async fn run_foreground(paths: &MurmurPaths) -> anyhow::Result<()> {
         next_plan_id: AtomicU64::new(next_plan_id),
         paths: paths.clone(),
         git,
-        config: tokio::sync::Mutex::new(config),
+        issue_backends,
+        config: tokio::sync::Mutex::new(Arc::new(config)),
         agents: tokio::sync::Mutex::new(AgentsState::default()),
         claims: tokio::sync::Mutex::new(ClaimRegistry::default()),
         pending_permissions: tokio::sync::Mutex::new(state::PendingPermissions::default()),
         pending_questions: tokio::sync::Mutex::new(state::PendingQuestions::default()),
         completed_issues: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
         orchestrators: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
-        merge_locks: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
+        merge_queues: tokio::sync::Mutex::new(merge_queue::MergeQueues::default()),
+        spawn_lock: tokio::sync::Mutex::new(()),
+        fair_share: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
+        schedules: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
         commits: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
+        attempts: tokio::sync::Mutex::new(attempts),
         dedup: dedup_store.clone(),
         user_activity: tokio::sync::Mutex::new(std::collections::BTreeMap::new()),
     });
@@ -122,8 +151,10 @@ pub async fn run_foreground(paths: &MurmurPaths) -> anyhow::Result<()> {
     if let Err(err) = rehydrate_agents(shared.clone()).await {
         tracing::warn!(error = %err, "failed to rehydrate agents from disk");
     }
+    retry::close_lost_attempts(&shared).await;
 
     tokio::spawn(server::heartbeat_loop(shared.clone(), shutdown_rx.clone()));
+    tokio::spawn(watchdog::watchdog_loop(shared.clone(), shutdown_rx.clone()));
     tokio::spawn(server::shutdown_signal_watcher(handle.clone()));
     tokio::spawn({
         let shared = shared.clone();
@@ -174,7 +205,15 @@ async fn spawn_agent(
     issue_id: String,
     backend_override: Option<AgentBackend>,
 ) -> anyhow::Result<AgentRecord> {
-    spawn_agent_with_kickoff(shared, project, issue_id, None, backend_override).await
+    spawn_agent_with_kickoff(
+        shared,
+        project,
+        issue_id,
+        AgentRole::Coding,
+        None,
+        backend_override,
+    )
+    .await
 }
 
 /// Spawn an agent without pre-assigning an issue.
@@ -219,6 +258,7 @@ async fn spawn_agent_without_issue(
     let (outbound_tx, outbound_rx) = mpsc::channel::<ChatMessage>(32);
     let (abort_tx, abort_rx) = watch::channel(false);
 
+    let hook_timeout_secs = shared.permission_hook_timeout_secs(&project).await;
     // CRITICAL: Register agent BEFORE spawning process to avoid race condition
     // where agent tries to call `mm agent claim` before it's registered.
     {
@@ -231,6 +271,12 @@ async fn spawn_agent_without_issue(
                 codex_thread_id: None,
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: created_at_ms,
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: (backend == AgentBackend::Claude).then_some(hook_timeout_secs),
                 claim_started_at_ms: None, // No issue assigned yet
                 outbound_tx: outbound_tx.clone(),
                 abort_tx: abort_tx.clone(),
@@ -253,8 +299,9 @@ async fn spawn_agent_without_issue(
             &shared.paths.murmur_dir,
             &shared.paths.socket_path,
             None,
-            false,
+            AgentRole::Coding,
             None,
+            hook_timeout_secs,
         )
         .await
         {
@@ -333,10 +380,13 @@ async fn spawn_agent_without_issue(
     Ok(record)
 }
 
+/// Spawn an agent with `issue_id` already claimed for it.
+/// `role` is `Coding` or, for issues routed to planning first, `Planner`.
 async fn spawn_agent_with_kickoff(
     shared: Arc<SharedState>,
     project: String,
     issue_id: String,
+    role: AgentRole,
     kickoff_message: Option<String>,
     backend_override: Option<AgentBackend>,
 ) -> anyhow::Result<AgentRecord> {
@@ -365,7 +415,10 @@ async fn spawn_agent_with_kickoff(
         None => {
             let cfg = shared.config.lock().await;
             cfg.project(&project)
-                .map(|p| p.effective_coding_backend())
+                .map(|p| match role {
+                    AgentRole::Planner => p.effective_planner_backend(),
+                    _ => p.effective_coding_backend(),
+                })
                 .unwrap_or_default()
         }
     };
@@ -373,7 +426,7 @@ async fn spawn_agent_with_kickoff(
     let record = AgentRecord::new(
         agent_id.clone(),
         project.clone(),
-        AgentRole::Coding,
+        role,
         issue_id.clone(),
         created_at_ms,
         wt.dir.to_string_lossy().to_string(),
@@ -382,6 +435,7 @@ async fn spawn_agent_with_kickoff(
     let (outbound_tx, outbound_rx) = mpsc::channel::<ChatMessage>(32);
     let (abort_tx, abort_rx) = watch::channel(false);
 
+    let hook_timeout_secs = shared.permission_hook_timeout_secs(&project).await;
     // CRITICAL: Register agent BEFORE spawning process to avoid race condition
     // where agent tries to call `mm agent claim` before it's registered.
     {
@@ -394,6 +448,12 @@ async fn spawn_agent_with_kickoff(
                 codex_thread_id: None,
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: created_at_ms,
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: (backend == AgentBackend::Claude).then_some(hook_timeout_secs),
                 claim_started_at_ms: Some(created_at_ms), // Track when issue was claimed
                 outbound_tx: outbound_tx.clone(),
                 abort_tx: abort_tx.clone(),
@@ -416,8 +476,9 @@ async fn spawn_agent_with_kickoff(
             &shared.paths.murmur_dir,
             &shared.paths.socket_path,
             None,
-            false,
+            role,
             None,
+            hook_timeout_secs,
         )
         .await
         {
@@ -493,6 +554,7 @@ async fn spawn_agent_with_kickoff(
         let _ = outbound_tx.send(msg).await;
     }
 
+    retry::start_attempt(shared.as_ref(), &project, &issue_id, &agent_id).await;
     persist_agents_runtime(shared).await;
     Ok(record)
 }
@@ -519,6 +581,7 @@ async fn cleanup_agent_runtime(
     shared: Arc<SharedState>,
     mut runtime: AgentRuntime,
 ) -> anyhow::Result<()> {
+    merge_queue::leave(&shared, &runtime.record.id).await;
     let _ = runtime.abort_tx.send(true);
     for task in runtime.tasks.drain(..) {
         let _ = tokio::time::timeout(Duration::from_secs(3), task).await;
@@ -541,6 +604,7 @@ async fn cleanup_agent_runtime(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_claude_agent_process(
     agent_id: &str,
     project: &str,
@@ -548,8 +612,9 @@ async fn spawn_claude_agent_process(
     murmur_dir: &Path,
     socket_path: &Path,
     permissions_allow: Option<&[String]>,
-    is_manager: bool,
+    role: AgentRole,
     append_system_prompt: Option<&str>,
+    hook_timeout_sec: u64,
 ) -> anyhow::Result<(
     tokio::process::Child,
     tokio::process::ChildStdin,
@@ -559,7 +624,6 @@ async fn spawn_claude_agent_process(
     let hook_exe_prefix = claude::hook_exe_prefix();
     let socket_path_str = socket_path.to_string_lossy();
 
-    let hook_timeout_sec = 5 * 60;
     let mut settings = serde_json::json!({
         "hooks": {
             "PreToolUse": [
@@ -624,6 +688,8 @@ async fn spawn_claude_agent_process(
         &settings_json,
     ])
     .env("MURMUR_AGENT_ID", agent_id)
+    .env("MURMUR_AGENT_ROLE", role.as_str())
+    .env("MURMUR_WORKTREE_DIR", worktree_dir)
     .env("MURMUR_DIR", murmur_dir)
     .env("MURMUR_PROJECT", project)
     .env("MURMUR_SOCKET_PATH", socket_path)
@@ -632,7 +698,7 @@ async fn spawn_claude_agent_process(
     .stdout(std::process::Stdio::piped())
     .stderr(std::process::Stdio::null());
 
-    if is_manager {
+    if matches!(role, AgentRole::Manager | AgentRole::Director) {
         cmd.env("FUGUE_MANAGER", "1").env("FAB_MANAGER", "1");
     }
 
@@ -740,7 +806,8 @@ async fn claude_reaper(
         return;
     };
 
-    if rt.record.state == murmur_core::agent::AgentState::Aborted {
+    let aborted = rt.record.state == murmur_core::agent::AgentState::Aborted;
+    if aborted {
         rt.record.exit_code = exit_code;
         rt.record.updated_at_ms = now_ms;
     } else {
@@ -750,6 +817,18 @@ async fn claude_reaper(
     }
 
     drop(agents);
+    if !aborted {
+        // An agent that exits without `agent done` gives its issue back
+        // so that it can be retried.
+        retry::finish_attempt(
+            shared.as_ref(),
+            &agent_id,
+            AttemptOutcome::Exited,
+            exit_code,
+        )
+        .await;
+        release_claims_for_agent(shared.as_ref(), &agent_id).await;
+    }
     persist_agents_runtime(shared).await;
 }
 
@@ -812,8 +891,8 @@ async fn codex_run_turn(
         )
     };
 
-    let is_manager = matches!(role, Some(AgentRole::Manager));
-    let prompt = if is_manager && thread_id.is_none() {
+    let role = role.unwrap_or(AgentRole::Coding);
+    let prompt = if role == AgentRole::Manager && thread_id.is_none() {
         let system_prompt = prompts::build_manager_prompt(&project);
         format!("{system_prompt}\n\n## User Message\n\n{prompt}")
     } else {
@@ -828,7 +907,7 @@ async fn codex_run_turn(
         &shared.paths.socket_path,
         thread_id.as_deref(),
         &prompt,
-        is_manager,
+        role,
     )
     .await?;
 
@@ -908,6 +987,7 @@ async fn codex_run_turn(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_codex_turn_process(
     agent_id: &str,
     project: &str,
@@ -916,7 +996,7 @@ async fn spawn_codex_turn_process(
     socket_path: &Path,
     thread_id: Option<&str>,
     prompt: &str,
-    is_manager: bool,
+    role: AgentRole,
 ) -> anyhow::Result<(tokio::process::Child, tokio::process::ChildStdout, u32)> {
     let mut cmd = tokio::process::Command::new("codex");
     cmd.arg("exec");
@@ -957,6 +1037,8 @@ async fn spawn_codex_turn_process(
     }
 
     cmd.env("MURMUR_AGENT_ID", agent_id)
+        .env("MURMUR_AGENT_ROLE", role.as_str())
+        .env("MURMUR_WORKTREE_DIR", worktree_dir)
         .env("MURMUR_DIR", murmur_dir)
         .env("MURMUR_PROJECT", project)
         .env("MURMUR_SOCKET_PATH", socket_path)
@@ -965,7 +1047,7 @@ async fn spawn_codex_turn_process(
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::null());
 
-    if is_manager {
+    if role == AgentRole::Manager {
         cmd.env("FUGUE_MANAGER", "1").env("FAB_MANAGER", "1");
     }
 
@@ -1001,6 +1083,10 @@ async fn apply_stream_message(shared: &SharedState, agent_id: &str, msg: StreamM
         for chat in &chat_messages {
             rt.chat.push(chat.clone());
         }
+        if !chat_messages.is_empty() {
+            rt.last_activity_at_ms = now_ms;
+            rt.watchdog = WatchdogProgress::default();
+        }
 
         rt.record.project.clone()
     };
@@ -1010,6 +1096,33 @@ async fn apply_stream_message(shared: &SharedState, agent_id: &str, msg: StreamM
     }
 }
 
+/// Sends `text` to the agent as a user message, waking it if idle.
+async fn deliver_user_message(shared: &SharedState, agent_id: &str, project: &str, text: String) {
+    let now_ms = now_ms();
+    let msg = ChatMessage::new(ChatRole::User, text, now_ms);
+
+    let (outbound_tx, resumed_from_idle) = {
+        let mut agents = shared.agents.lock().await;
+        let Some(rt) = agents.agents.get_mut(agent_id) else {
+            return;
+        };
+        let resumed_from_idle = rt.record.state == AgentState::Idle;
+        if resumed_from_idle {
+            rt.record = rt.record.apply_event(AgentEvent::ResumedFromIdle, now_ms);
+        }
+        rt.chat.push(msg.clone());
+        (rt.outbound_tx.clone(), resumed_from_idle)
+    };
+
+    emit_agent_chat_event(shared, agent_id, project, msg.clone());
+    if resumed_from_idle {
+        emit_agent_state_changed_event(shared, agent_id, project, AgentState::Running);
+    }
+    if outbound_tx.send(msg).await.is_err() {
+        tracing::warn!(agent_id = %agent_id, "deliver message: agent channel closed");
+    }
+}
+
 fn emit_agent_chat_event(shared: &SharedState, agent_id: &str, project: &str, msg: ChatMessage) {
     let payload = serde_json::to_value(AgentChatEvent {
         agent_id: agent_id.to_owned(),
@@ -1193,6 +1306,12 @@ async fn rehydrate_agents(shared: Arc<SharedState>) -> anyhow::Result<()> {
                 codex_thread_id: info.codex_thread_id.clone(),
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: now_ms(), // Watch from restart, not from original spawn
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: None,   // Spawned by an earlier daemon
                 claim_started_at_ms: None, // Will be set by comment poller on first poll
                 outbound_tx,
                 abort_tx,
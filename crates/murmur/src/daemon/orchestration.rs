@@ -1,13 +1,23 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
-use murmur_core::orchestration::orchestrator_tick;
+use murmur_core::agent::AgentRole;
+use murmur_core::config::{RouteRole, RoutingRule};
+use murmur_core::issue::Issue;
+use murmur_core::orchestration::{
+    fair_share, orchestrator_tick, rank_ready_issues, ProjectDemand, RankedIssue,
+};
 use tokio::sync::watch;
 
-use super::{issue_backend_for_project, spawn_agent_without_issue, SharedState};
+use super::retry::held_issues;
+use super::state::{FairShareSlot, TickSchedule};
+use super::{
+    issue_backend_for_project, now_ms, spawn_agent_with_kickoff, spawn_agent_without_issue,
+    SharedState,
+};
 
 pub(in crate::daemon) async fn request_orchestrator_tick(
     shared: Arc<SharedState>,
@@ -24,7 +34,12 @@ pub(in crate::daemon) async fn count_active_agents(shared: &SharedState, project
         .agents
         .values()
         .filter(|a| a.record.project == project)
-        .filter(|a| a.record.role == murmur_core::agent::AgentRole::Coding)
+        .filter(|a| match a.record.role {
+            AgentRole::Coding => true,
+            // Planners routed onto an issue hold a claim and take a slot.
+            AgentRole::Planner => a.claim_started_at_ms.is_some(),
+            _ => false,
+        })
         .filter(|a| {
             matches!(
                 a.record.state,
@@ -36,6 +51,22 @@ pub(in crate::daemon) async fn count_active_agents(shared: &SharedState, project
         .count() as u32
 }
 
+/// Active agents across all projects, as counted against `max-total-agents`.
+pub(in crate::daemon) async fn count_total_active_agents(shared: &SharedState) -> u32 {
+    let projects = {
+        let cfg = shared.config.lock().await;
+        cfg.projects
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut total = 0;
+    for project in projects {
+        total += count_active_agents(shared, &project).await;
+    }
+    total
+}
+
 pub(in crate::daemon) async fn count_active_claims(shared: &SharedState, project: &str) -> u32 {
     let claim_entries = {
         let claims = shared.claims.lock().await;
@@ -69,6 +100,93 @@ pub(in crate::daemon) async fn orchestrator_is_running(
     false
 }
 
+/// Orders ready issues using the project's scheduling policy.
+pub(in crate::daemon) async fn rank_ready_for_project(
+    shared: &SharedState,
+    project: &str,
+    ready: &[Issue],
+) -> Vec<RankedIssue> {
+    let policy = {
+        let cfg = shared.config.lock().await;
+        cfg.project(project)
+            .map(|p| p.effective_scheduling())
+            .unwrap_or_default()
+    };
+    rank_ready_issues(ready, &policy, now_ms())
+}
+
+/// Caps the `wanted` new agents for `project` at its fair share of the
+/// global pool and records the outcome for `mm project status`.
+///
+/// Callers must hold `shared.spawn_lock` until they finish spawning.
+async fn grant_global_slots(shared: &SharedState, project: &str, wanted: usize) -> usize {
+    let (max_total, weights) = {
+        let cfg = shared.config.lock().await;
+        let weights = cfg
+            .projects
+            .iter()
+            .map(|p| (p.name.clone(), p.effective_weight()))
+            .collect::<BTreeMap<_, _>>();
+        (cfg.max_total_agents(), weights)
+    };
+
+    let Some(max_total) = max_total else {
+        let mut slots = shared.fair_share.lock().await;
+        slots.insert(
+            project.to_owned(),
+            FairShareSlot {
+                wanted,
+                granted: wanted,
+            },
+        );
+        return wanted;
+    };
+
+    let running = {
+        let orchestrators = shared.orchestrators.lock().await;
+        orchestrators
+            .iter()
+            .filter(|(_, rt)| !rt.task.is_finished())
+            .map(|(name, _)| name.clone())
+            .collect::<BTreeSet<_>>()
+    };
+    let last_wanted = {
+        let slots = shared.fair_share.lock().await;
+        slots
+            .iter()
+            .map(|(name, slot)| (name.clone(), slot.wanted))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    // Every project's agents occupy the pool; only running orchestrators
+    // compete for free slots.
+    let mut demands = Vec::with_capacity(weights.len());
+    for (name, weight) in &weights {
+        let wanted = if name == project {
+            wanted
+        } else if running.contains(name) {
+            last_wanted.get(name).copied().unwrap_or(0)
+        } else {
+            0
+        };
+        demands.push(ProjectDemand {
+            project: name.clone(),
+            weight: *weight,
+            active_agents: count_active_agents(shared, name).await as usize,
+            wanted,
+        });
+    }
+
+    let granted = fair_share(max_total as usize, &demands)
+        .get(project)
+        .copied()
+        .unwrap_or(0);
+
+    let mut slots = shared.fair_share.lock().await;
+    slots.insert(project.to_owned(), FairShareSlot { wanted, granted });
+    granted
+}
+
 fn orchestration_interval() -> Duration {
     let ms = std::env::var("FUGUE_ORCHESTRATOR_INTERVAL_MS")
         .ok()
@@ -105,19 +223,26 @@ pub(in crate::daemon) async fn orchestrator_loop(
 }
 
 async fn orchestrator_tick_once(shared: Arc<SharedState>, project: &str) -> anyhow::Result<()> {
-    // Get config values: max_agents and silence_threshold
-    let (max_agents, silence_threshold) = {
+    // Get config values: project config and silence_threshold
+    let (project_cfg, silence_threshold) = {
         let cfg = shared.config.lock().await;
-        let max = cfg
+        let project_cfg = cfg
             .project(project)
-            .map(|p| p.max_agents as usize)
+            .cloned()
             .ok_or_else(|| anyhow!("project not found"))?;
         let threshold = cfg.silence_threshold_for_project(project);
-        (max, threshold)
+        (project_cfg, threshold)
     };
+    let max_agents = project_cfg.max_agents as usize;
 
     // Check user intervention before spawning
     if shared.is_user_intervening(project, silence_threshold).await {
+        // Leave the global pool to other projects while paused.
+        shared
+            .fair_share
+            .lock()
+            .await
+            .insert(project.to_owned(), FairShareSlot::default());
         let secs = shared.seconds_since_activity(project).await.unwrap_or(0);
         tracing::debug!(
             project = %project,
@@ -134,6 +259,7 @@ async fn orchestrator_tick_once(shared: Arc<SharedState>, project: &str) -> anyh
             .map_err(anyhow::Error::msg)?;
         backend.ready().await?
     };
+    let ranked = rank_ready_for_project(shared.as_ref(), project, &ready).await;
 
     let active_agents = count_active_agents(shared.as_ref(), project).await as usize;
     let claims = { shared.claims.lock().await.clone() };
@@ -141,22 +267,83 @@ async fn orchestrator_tick_once(shared: Arc<SharedState>, project: &str) -> anyh
         let completed = shared.completed_issues.lock().await;
         completed.get(project).cloned().unwrap_or_default()
     };
+    let held = held_issues(shared.as_ref(), project, &ready).await;
+    shared.schedules.lock().await.insert(
+        project.to_owned(),
+        TickSchedule {
+            ranked: ranked.clone(),
+            held: held.clone(),
+        },
+    );
 
     // Use orchestrator_tick to determine how many unclaimed issues exist
-    let plan = orchestrator_tick(
+    let mut plan = orchestrator_tick(
         project,
         active_agents,
         max_agents,
-        ready
+        ranked
             .iter()
-            .map(|i| i.id.as_str())
-            .filter(|id| !completed.contains(*id)),
+            .map(|i| i.issue_id.as_str())
+            .filter(|id| !completed.contains(*id) && !held.contains(*id)),
         &claims,
     );
 
-    // Spawn agents without pre-assigning issues.
+    // Apply the daemon-wide cap: this project only gets its fair share of
+    // the free slots. Hold the spawn lock so other projects see our agents.
+    let _spawn_guard = shared.spawn_lock.lock().await;
+    let granted = grant_global_slots(shared.as_ref(), project, plan.issue_ids.len()).await;
+    if granted < plan.issue_ids.len() {
+        tracing::debug!(
+            project = %project,
+            wanted = plan.issue_ids.len(),
+            granted,
+            "waiting for global agent slots"
+        );
+        plan.issue_ids.truncate(granted);
+    }
+
+    // Issues matching a routing rule get a dedicated agent with the issue
+    // pre-claimed. Spawn those first so pull-based agents cannot take them.
+    let mut routed = Vec::new();
+    let mut to_spawn = 0usize;
+    for issue_id in &plan.issue_ids {
+        let route = ready
+            .iter()
+            .find(|i| &i.id == issue_id)
+            .and_then(|i| Some((i, project_cfg.route_for(&i.labels, &i.issue_type)?)));
+        match route {
+            Some((issue, rule)) => routed.push((issue, rule)),
+            None => to_spawn += 1,
+        }
+    }
+
+    for (issue, rule) in routed {
+        let active = count_active_agents(shared.as_ref(), project).await as usize;
+        if active >= max_agents {
+            return Ok(());
+        }
+
+        let role = match rule.role {
+            RouteRole::Coding => AgentRole::Coding,
+            RouteRole::Planner => AgentRole::Planner,
+        };
+        let kickoff = build_routed_prompt(project, &shared.paths.socket_path, issue, rule);
+        if let Err(err) = spawn_agent_with_kickoff(
+            shared.clone(),
+            project.to_owned(),
+            issue.id.clone(),
+            role,
+            Some(kickoff),
+            Some(project_cfg.routed_backend(rule)),
+        )
+        .await
+        {
+            tracing::warn!(project = %project, issue = %issue.id, error = ?err, "spawn routed agent failed");
+        }
+    }
+
+    // Spawn the remaining agents without pre-assigning issues.
     // Agents will find and claim issues themselves using `mm issue ready` and `mm agent claim`.
-    let to_spawn = plan.issue_ids.len();
     let kickstart = build_kickstart_prompt(project, &shared.paths.socket_path);
 
     for _ in 0..to_spawn {
@@ -176,6 +363,81 @@ async fn orchestrator_tick_once(shared: Arc<SharedState>, project: &str) -> anyh
 }
 
 fn build_kickstart_prompt(project: &str, socket_path: &Path) -> String {
+    let find_work = r###"## Find Work
+
+1. List available issues: `mm issue ready` (listed in scheduling order, most important first)
+2. Claim the first one you can work on: `mm agent claim <issue-id>`
+3. Set your status: `mm agent describe "<short description>"`
+
+If the issue you picked is already claimed, pick the next one from `mm issue ready`.
+If no issues are available (or you did not claim anything), run `mm agent done` to exit; it should not create a PR or attempt merges.
+"###;
+    format!(
+        "{}\n{find_work}\n{}",
+        prompt_preamble(project, socket_path),
+        CODING_WORKFLOW
+    )
+}
+
+/// Kickoff prompt for an agent spawned onto an issue by a routing rule.
+fn build_routed_prompt(
+    project: &str,
+    socket_path: &Path,
+    issue: &Issue,
+    rule: &RoutingRule,
+) -> String {
+    let issue_id = &issue.id;
+    let title = &issue.title;
+    let mut prompt = match rule.role {
+        RouteRole::Coding => format!(
+            r###"{preamble}
+## Your Issue
+
+Issue `{issue_id}` ("{title}") has already been claimed for you; do not claim another one.
+
+1. Read it: `mm issue show {issue_id}`
+2. Set your status: `mm agent describe "<short description>"`
+
+{workflow}"###,
+            preamble = prompt_preamble(project, socket_path),
+            workflow = CODING_WORKFLOW,
+        ),
+        RouteRole::Planner => {
+            let label = rule.label.as_deref().unwrap_or_default();
+            format!(
+                r###"{preamble}
+You are acting as a **planner** for issue `{issue_id}` ("{title}"). The issue has already been claimed for you.
+
+## Task
+
+1. Read it: `mm issue show {issue_id}`
+2. Explore the codebase and work out how it should be implemented.
+3. Write the plan: `mm issue plan {issue_id} --body "## Steps\n- Step 1\n- Step 2"`
+4. If the work is large, create sub-issues: `mm issue create "Sub-task title" --depends-on {issue_id}`
+5. Run `mm agent done`.
+
+## Important Notes
+
+- Do NOT implement the issue, commit code, or close it.
+- When you run `mm agent done`, Murmur removes the `{label}` label so a coding agent picks the issue up next.
+"###,
+                preamble = prompt_preamble(project, socket_path),
+            )
+        }
+    };
+
+    if let Some(extra) = rule
+        .kickoff
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        prompt.push_str(&format!("\n## Additional Instructions\n\n{extra}\n"));
+    }
+    prompt
+}
+
+fn prompt_preamble(project: &str, socket_path: &Path) -> String {
     let socket_path = socket_path.to_string_lossy();
     format!(
         r###"The `mm` command is available on PATH (use `mm`, not `./mm`).
@@ -186,17 +448,11 @@ The Murmur daemon is already running and reachable at:
 `{socket_path}`
 
 (`MURMUR_SOCKET_PATH` is already set for you; you should not need `--socket-path`.)
+"###
+    )
+}
 
-## Find Work
-
-1. List available issues: `mm issue ready`
-2. Claim one: `mm agent claim <issue-id>`
-3. Set your status: `mm agent describe "<short description>"`
-
-If the issue you picked is already claimed, pick a different one from `mm issue ready`.
-If no issues are available (or you did not claim anything), run `mm agent done` to exit; it should not create a PR or attempt merges.
-
-## Workflow
+const CODING_WORKFLOW: &str = r###"## Workflow
 
 Read the issue carefully and decide how to proceed:
 
@@ -220,6 +476,4 @@ Read the issue carefully and decide how to proceed:
 - Do NOT run `git push` — merging and pushing happens automatically when you run `mm agent done`
 - Only close an issue when you have COMPLETED the implementation
 - Do NOT close if you only added comments, a plan, or created sub-issues
-"###,
-    )
-}
+"###;
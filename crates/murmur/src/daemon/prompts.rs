@@ -98,6 +98,14 @@ pub(in crate::daemon) fn build_manager_prompt(project: &str) -> String {
 - Do NOT implement code changes yourself; file issues and let coding agents do the work.
 - Work happens in git worktrees; PR numbers/links are not available until after merges.
 
+## Permission requests
+
+A project using the `manager` escalation tier sends you other agents' tool calls to approve. Each message starts with `Permission request <id>`; answer it with a single JSON object and nothing else:
+
+`{{"permission": "<id>", "decision": "allow", "reason": "..."}}`
+
+`decision` is `allow`, `deny`, or `escalate` to hand the request to the user when you are unsure.
+
 ## Using planner agents
 
 When the user asks for a project breakdown or plan, prefer starting a planner agent and reading back the generated Markdown plan.
@@ -162,3 +170,24 @@ IMPORTANT: The CLI binary is `mm`, not `murmur`. Always use `mm` for commands.
 "###
     )
 }
+
+/// What the manager agent is sent for a permission request escalated to it.
+pub(in crate::daemon) fn build_permission_escalation_message(
+    request_id: &str,
+    agent_id: &str,
+    tool_name: &str,
+    tool_input: &serde_json::Value,
+) -> String {
+    let tool_input = serde_json::to_string_pretty(tool_input).unwrap_or_else(|_| "{}".to_owned());
+    format!(
+        r###"Permission request {request_id}: agent {agent_id} wants to run {tool_name} with this input:
+
+```json
+{tool_input}
+```
+
+Reply with only this JSON object, with `decision` one of "allow", "deny" or "escalate" (hand it to the user):
+
+{{"permission": "{request_id}", "decision": "...", "reason": "..."}}"###
+    )
+}
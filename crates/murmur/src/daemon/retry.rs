@@ -0,0 +1,209 @@
+//! Per-issue attempt history, retry backoff and "needs human" escalation.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use murmur_core::attempts::{AttemptOutcome, IssueAttempts, RetryStatus};
+use murmur_core::config::RetryConfig;
+use murmur_core::issue::Issue;
+
+use crate::runtime_store;
+
+use super::state::SharedState;
+use super::{issue_backend_for_project, now_ms};
+
+/// Ends attempts whose agents did not survive a daemon restart.
+pub(in crate::daemon) async fn close_lost_attempts(shared: &Arc<SharedState>) {
+    let live: BTreeSet<String> = {
+        let agents = shared.agents.lock().await;
+        agents.agents.keys().cloned().collect()
+    };
+    let lost = {
+        let mut attempts = shared.attempts.lock().await;
+        attempts.close_lost(&live, now_ms())
+    };
+    if lost.is_empty() {
+        return;
+    }
+    persist_attempts(shared).await;
+    for (project, issue_id) in lost {
+        escalate_if_exhausted(shared, &project, &issue_id).await;
+    }
+}
+
+pub(in crate::daemon) async fn start_attempt(
+    shared: &SharedState,
+    project: &str,
+    issue_id: &str,
+    agent_id: &str,
+) {
+    {
+        let mut attempts = shared.attempts.lock().await;
+        attempts.start(project, issue_id, agent_id, now_ms());
+    }
+    persist_attempts(shared).await;
+}
+
+/// Ends the agent's open attempt, if any. A failure that uses up the
+/// project's `max-attempts` labels the issue and comments the history.
+pub(in crate::daemon) async fn finish_attempt(
+    shared: &SharedState,
+    agent_id: &str,
+    outcome: AttemptOutcome,
+    exit_code: Option<i32>,
+) {
+    let finished = {
+        let mut attempts = shared.attempts.lock().await;
+        attempts.finish(agent_id, outcome, exit_code, now_ms())
+    };
+    let Some((project, issue_id)) = finished else {
+        return;
+    };
+    persist_attempts(shared).await;
+    if outcome.is_failure() {
+        escalate_if_exhausted(shared, &project, &issue_id).await;
+    }
+}
+
+pub(in crate::daemon) async fn forget_project(shared: &SharedState, project: &str) {
+    shared.attempts.lock().await.remove_project(project);
+    persist_attempts(shared).await;
+}
+
+/// Ready issues that must not be scheduled yet: backing off after a failure
+/// or waiting for a human. An issue whose "needs human" label was removed
+/// starts counting attempts afresh.
+pub(in crate::daemon) async fn held_issues(
+    shared: &SharedState,
+    project: &str,
+    ready: &[Issue],
+) -> BTreeSet<String> {
+    let policy = retry_policy(shared, project).await;
+    let now_ms = now_ms();
+
+    let mut attempts = shared.attempts.lock().await;
+    let mut reset = false;
+    let mut held = BTreeSet::new();
+    for issue in ready {
+        let Some(history) = attempts.get(project, &issue.id) else {
+            continue;
+        };
+        if history.needs_human_at_ms.is_some() && !has_label(issue, &policy.needs_human_label) {
+            attempts.reset(project, &issue.id);
+            reset = true;
+        }
+        if attempts.retry_status(project, &issue.id, &policy, now_ms) != RetryStatus::Ready {
+            held.insert(issue.id.clone());
+        }
+    }
+    drop(attempts);
+
+    if reset {
+        persist_attempts(shared).await;
+    }
+    held
+}
+
+pub(in crate::daemon) async fn retry_policy(shared: &SharedState, project: &str) -> RetryConfig {
+    let cfg = shared.config.lock().await;
+    cfg.project(project)
+        .map(|p| p.effective_retry())
+        .unwrap_or_default()
+}
+
+fn has_label(issue: &Issue, label: &str) -> bool {
+    issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+}
+
+async fn escalate_if_exhausted(shared: &SharedState, project: &str, issue_id: &str) {
+    let policy = retry_policy(shared, project).await;
+    let history = {
+        let attempts = shared.attempts.lock().await;
+        attempts.get(project, issue_id).cloned()
+    };
+    let Some(history) = history.filter(|h| h.exhausted(&policy)) else {
+        return;
+    };
+    tracing::warn!(
+        project = %project,
+        issue = %issue_id,
+        failures = history.failures(),
+        "issue ran out of attempts; needs a human"
+    );
+    if policy.needs_human_label.trim().is_empty() {
+        return;
+    }
+
+    let backend = match issue_backend_for_project(shared, project).await {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(project = %project, issue = %issue_id, error = %err, "needs-human escalation failed");
+            return;
+        }
+    };
+    let issue = match backend.get(issue_id).await {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(project = %project, issue = %issue_id, error = %err, "needs-human escalation failed");
+            return;
+        }
+    };
+
+    let now_ms = now_ms();
+    let body = format_attempts_comment(&history, now_ms);
+    if let Err(err) = backend.comment(now_ms, issue_id, &body).await {
+        tracing::warn!(project = %project, issue = %issue_id, error = %err, "needs-human comment failed");
+    }
+
+    if !has_label(&issue, &policy.needs_human_label) {
+        let mut labels = issue.labels.clone();
+        labels.push(policy.needs_human_label.clone());
+        let params = murmur_core::issue::UpdateParams {
+            labels: Some(labels),
+            ..Default::default()
+        };
+        if let Err(err) = backend.update(now_ms, issue_id, params).await {
+            tracing::warn!(project = %project, issue = %issue_id, error = %err, "needs-human label failed");
+            return;
+        }
+    }
+    if let Err(err) = backend.commit("issue: update tickets").await {
+        tracing::warn!(project = %project, issue = %issue_id, error = %err, "needs-human commit failed");
+    }
+
+    // Only remember the escalation once the label is on the issue, so that
+    // removing it is what hands the issue back to the orchestrator.
+    {
+        let mut attempts = shared.attempts.lock().await;
+        attempts.mark_needs_human(project, issue_id, now_ms);
+    }
+    persist_attempts(shared).await;
+}
+
+fn format_attempts_comment(history: &IssueAttempts, now_ms: u64) -> String {
+    let mut out = format!(
+        "Murmur stopped retrying this issue after {} failed attempts:\n\n",
+        history.failures()
+    );
+    for attempt in history.attempts.iter().skip(history.counted_from) {
+        let outcome = attempt.outcome.map_or("running", AttemptOutcome::as_str);
+        let code = attempt
+            .exit_code
+            .map(|c| format!(" (code {c})"))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "- {}: {outcome}{code} after {}s\n",
+            attempt.agent_id,
+            attempt.duration_ms(now_ms) / 1000
+        ));
+    }
+    out.push_str("\nRemove the label to let agents try again.");
+    out
+}
+
+async fn persist_attempts(shared: &SharedState) {
+    let attempts = shared.attempts.lock().await.clone();
+    if let Err(err) = runtime_store::save_attempts(&shared.paths, &attempts).await {
+        tracing::debug!(error = %err, "persist attempts failed");
+    }
+}
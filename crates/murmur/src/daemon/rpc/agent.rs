@@ -3,8 +3,9 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use murmur_core::agent::{AgentEvent, AgentState, ChatMessage, ChatRole};
+use murmur_core::attempts::{AttemptOutcome, RetryStatus};
 use murmur_core::commits::{CommitLog, CommitRecord as CoreCommitRecord};
-use murmur_core::config::{AgentBackend, MergeStrategy};
+use murmur_core::config::{AgentBackend, MergeStrategy, RouteRole};
 use murmur_protocol::{
     AgentAbortRequest, AgentChatHistoryRequest, AgentChatHistoryResponse, AgentClaimRequest,
     AgentCreateRequest, AgentCreateResponse, AgentDeleteRequest, AgentDescribeRequest,
@@ -15,13 +16,18 @@ use murmur_protocol::{
     MSG_AGENT_SYNC_COMMENTS,
 };
 
+use crate::gitea::{parse_gitea_nwo, GiteaBackend};
 use crate::github::{parse_github_nwo, GithubBackend};
+use crate::gitlab::{parse_gitlab_project, GitlabBackend};
 use crate::providers;
 
+use super::super::conflicts::report_conflict;
 use super::super::merge::{
-    merge_agent_branch_direct, merge_lock_for_project, prepare_agent_branch_pull_request,
-    MergeAttempt, PullRequestAttempt,
+    landing_for, prepare_agent_branch_pull_request, MergeAttempt, PullRequestAttempt,
 };
+use super::super::merge_queue::{merge_turn_direct, wait_for_turn, MergeTurn};
+use super::super::retry::{finish_attempt, retry_policy, start_attempt};
+use super::super::verify::report_verify_failure;
 use super::super::{
     agent_info_from_record, cleanup_agent_runtime, emit_agent_chat_event, emit_agent_deleted_event,
     emit_agent_state_changed_event, issue_backend_for_project, mark_issue_completed, now_ms,
@@ -172,6 +178,7 @@ pub(in crate::daemon) async fn handle_agent_abort(
     } else {
         let _ = abort_tx.send(true);
     }
+    finish_attempt(&shared, &abort.agent_id, AttemptOutcome::Aborted, None).await;
     persist_agents_runtime(shared.clone()).await;
 
     Response {
@@ -208,6 +215,7 @@ pub(in crate::daemon) async fn handle_agent_delete(
     }
 
     release_claims_for_agent(&shared, &delete.agent_id).await;
+    finish_attempt(&shared, &delete.agent_id, AttemptOutcome::Deleted, None).await;
     persist_agents_runtime(shared.clone()).await;
     emit_agent_deleted_event(shared.as_ref(), &delete.agent_id, &project);
 
@@ -264,6 +272,8 @@ pub(in crate::daemon) async fn handle_agent_send_message(
         };
 
         rt.chat.push(msg.clone());
+        rt.last_activity_at_ms = now_ms;
+        rt.watchdog = Default::default();
         (rt.outbound_tx.clone(), rt.record.project.clone(), resumed_from_idle)
     };
 
@@ -315,6 +325,13 @@ pub(in crate::daemon) async fn handle_agent_claim(
         return error_response(req, "issue_id is required");
     }
 
+    if let Err(msg) = check_claim_route(&shared, agent_id, issue_id).await {
+        return error_response(req, &msg);
+    }
+    if let Err(msg) = check_claim_retry(&shared, agent_id, issue_id).await {
+        return error_response(req, &msg);
+    }
+
     let now_ms = now_ms();
 
     // Acquire both locks in a consistent order (agents first, then claims) to prevent deadlocks.
@@ -370,6 +387,7 @@ pub(in crate::daemon) async fn handle_agent_claim(
     drop(claims);
     drop(agents);
 
+    start_attempt(&shared, &project, issue_id, agent_id).await;
     persist_agents_runtime(shared.clone()).await;
 
     Response {
@@ -381,6 +399,140 @@ pub(in crate::daemon) async fn handle_agent_claim(
     }
 }
 
+/// Rejects claims on issues a routing rule reserves for another backend or
+/// for a planner. Only consults the issue backend when routing is configured.
+async fn check_claim_route(
+    shared: &SharedState,
+    agent_id: &str,
+    issue_id: &str,
+) -> Result<(), String> {
+    let Some((project, backend)) = ({
+        let agents = shared.agents.lock().await;
+        agents
+            .agents
+            .get(agent_id)
+            .map(|rt| (rt.record.project.clone(), rt.backend))
+    }) else {
+        return Ok(());
+    };
+
+    let project_cfg = {
+        let cfg = shared.config.lock().await;
+        cfg.project(&project).cloned()
+    };
+    let Some(project_cfg) = project_cfg.filter(|p| !p.routing.is_empty()) else {
+        return Ok(());
+    };
+
+    let issue = issue_backend_for_project(shared, &project)
+        .await?
+        .get(issue_id)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    let Some(rule) = project_cfg.route_for(&issue.labels, &issue.issue_type) else {
+        return Ok(());
+    };
+
+    if rule.role == RouteRole::Planner {
+        return Err(format!(
+            "issue {issue_id} is routed to a planner first; pick another issue"
+        ));
+    }
+    let routed_backend = project_cfg.routed_backend(rule);
+    if routed_backend != backend {
+        let name = match routed_backend {
+            AgentBackend::Claude => "claude",
+            AgentBackend::Codex => "codex",
+        };
+        return Err(format!(
+            "issue {issue_id} is routed to {name} agents; pick another issue"
+        ));
+    }
+    Ok(())
+}
+
+/// Rejects claims on issues that are backing off after a failed attempt or
+/// are waiting for a human.
+async fn check_claim_retry(
+    shared: &SharedState,
+    agent_id: &str,
+    issue_id: &str,
+) -> Result<(), String> {
+    let project = {
+        let agents = shared.agents.lock().await;
+        match agents.agents.get(agent_id) {
+            Some(rt) => rt.record.project.clone(),
+            None => return Ok(()),
+        }
+    };
+    let policy = retry_policy(shared, &project).await;
+    let status = {
+        let attempts = shared.attempts.lock().await;
+        attempts.retry_status(&project, issue_id, &policy, now_ms())
+    };
+    match status {
+        RetryStatus::Ready => Ok(()),
+        RetryStatus::Backoff { until_ms } => Err(format!(
+            "issue {issue_id} failed recently; retry in {}s",
+            until_ms.saturating_sub(now_ms()).div_ceil(1000)
+        )),
+        RetryStatus::NeedsHuman => Err(format!(
+            "issue {issue_id} ran out of attempts and needs a human"
+        )),
+    }
+}
+
+/// Once a routed planner finishes, drop the label that sent the issue to it
+/// so the next tick routes the issue to a coding agent.
+async fn clear_planner_route(shared: &SharedState, project: &str, issue_id: &str) {
+    let project_cfg = {
+        let cfg = shared.config.lock().await;
+        cfg.project(project).cloned()
+    };
+    let Some(project_cfg) = project_cfg else {
+        return;
+    };
+
+    let backend = match issue_backend_for_project(shared, project).await {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(project = %project, issue = %issue_id, error = %err, "clear planner route failed");
+            return;
+        }
+    };
+    let issue = match backend.get(issue_id).await {
+        Ok(v) => v,
+        Err(err) => {
+            tracing::warn!(project = %project, issue = %issue_id, error = %err, "clear planner route failed");
+            return;
+        }
+    };
+
+    let planner_labels: Vec<&str> = project_cfg
+        .routing
+        .iter()
+        .filter(|r| r.role == RouteRole::Planner && r.matches(&issue.labels, &issue.issue_type))
+        .filter_map(|r| r.label.as_deref())
+        .collect();
+    if planner_labels.is_empty() {
+        return;
+    }
+
+    let labels = issue
+        .labels
+        .iter()
+        .filter(|l| !planner_labels.iter().any(|p| l.eq_ignore_ascii_case(p)))
+        .cloned()
+        .collect();
+    let params = murmur_core::issue::UpdateParams {
+        labels: Some(labels),
+        ..Default::default()
+    };
+    if let Err(err) = backend.update(now_ms(), issue_id, params).await {
+        tracing::warn!(project = %project, issue = %issue_id, error = %err, "clear planner route failed");
+    }
+}
+
 pub(in crate::daemon) async fn handle_agent_describe(
     shared: Arc<SharedState>,
     mut req: Request,
@@ -500,6 +652,17 @@ pub(in crate::daemon) async fn handle_agent_done(
             return error_response(req, &format!("cleanup planner failed: {err:#}"));
         }
 
+        // Planners spawned by a routing rule hold the issue's claim.
+        let routed_issue = {
+            let claims = shared.claims.lock().await;
+            claims.agent_for(&project, &issue_id) == Some(agent_id.as_str())
+        };
+        if routed_issue {
+            clear_planner_route(shared.as_ref(), &project, &issue_id).await;
+            release_claims_for_agent(&shared, &agent_id).await;
+        }
+        finish_attempt(&shared, &agent_id, AttemptOutcome::Done, None).await;
+
         persist_agents_runtime(shared.clone()).await;
         emit_agent_deleted_event(shared.as_ref(), &agent_id, &project);
 
@@ -551,51 +714,74 @@ pub(in crate::daemon) async fn handle_agent_done(
             .unwrap_or(MergeStrategy::Direct)
     };
 
-    let lock = merge_lock_for_project(shared.as_ref(), &project).await;
-    let _guard = lock.lock().await;
+    let turn = match wait_for_turn(&shared, &project, &agent_id, &issue_id).await {
+        Ok(v) => v,
+        Err(msg) => return error_response(req, &msg),
+    };
+    let (batch, landed, guard) = match turn {
+        MergeTurn::Lead { batch, guard } => (batch, None, guard),
+        MergeTurn::Landed { merged, guard } => (Vec::new(), Some(merged), guard),
+        MergeTurn::Cancelled => return error_response(req, "removed from merge queue"),
+    };
 
     match merge_strategy {
-        MergeStrategy::Direct => {
-            let attempt = match merge_agent_branch_direct(
-                shared.as_ref(),
-                &project,
-                &agent_id,
-                Path::new(&worktree_dir),
-            )
-            .await
-            {
-                Ok(v) => v,
-                Err(err) => return error_response(req, &format!("merge failed: {err:#}")),
-            };
-
-            let merged = match attempt {
-                MergeAttempt::Conflict { branch, error } => {
-                    let now_ms = now_ms();
-                    let msg = ChatMessage::new(
-                        ChatRole::System,
-                        format!("merge conflict on {branch}: {error}"),
-                        now_ms,
-                    );
-
+        MergeStrategy::Direct | MergeStrategy::Squash | MergeStrategy::MergeCommit => {
+            let attempt = match landed {
+                Some(merged) => MergeAttempt::Merged(merged),
+                None => {
+                    let landing = match landing_for(
+                        shared.as_ref(),
+                        &project,
+                        merge_strategy,
+                        &agent_id,
+                        &issue_id,
+                    )
+                    .await
                     {
-                        let mut agents = shared.agents.lock().await;
-                        if let Some(rt) = agents.agents.get_mut(&agent_id) {
-                            rt.record = rt.record.apply_event(
-                                AgentEvent::NeedsResolution {
-                                    reason: "merge conflict",
-                                },
-                                now_ms,
-                            );
-                            rt.chat.push(msg.clone());
-                        }
+                        Ok(v) => v,
+                        Err(err) => return error_response(req, &format!("merge failed: {err:#}")),
+                    };
+                    match merge_turn_direct(
+                        &shared,
+                        &project,
+                        &agent_id,
+                        Path::new(&worktree_dir),
+                        &batch,
+                        &landing,
+                        &guard,
+                    )
+                    .await
+                    {
+                        Ok(v) => v,
+                        Err(err) => return error_response(req, &format!("merge failed: {err:#}")),
                     }
+                }
+            };
 
-                    emit_agent_chat_event(shared.as_ref(), &agent_id, &project, msg);
+            let merged = match attempt {
+                MergeAttempt::Conflict(conflict) => {
+                    let msg =
+                        report_conflict(&shared, &agent_id, &project, "merge conflict", &conflict)
+                            .await;
+                    persist_agents_runtime(shared.clone()).await;
+                    return error_response(req, &msg);
+                }
+                MergeAttempt::VerifyFailed(failure) => {
+                    let policy = {
+                        let cfg = shared.config.lock().await;
+                        cfg.project(&project)
+                            .map(|p| p.effective_verify())
+                            .unwrap_or_default()
+                    };
+                    let msg =
+                        report_verify_failure(&shared, &agent_id, &project, &policy, &failure)
+                            .await;
                     persist_agents_runtime(shared.clone()).await;
-                    return error_response(req, "merge conflict (agent needs resolution)");
+                    return error_response(req, &msg);
                 }
                 MergeAttempt::Merged(merged) => merged,
             };
+            let _bookkeeping = guard.bookkeeping().await;
 
             let backend = match issue_backend_for_project(shared.as_ref(), &project).await {
                 Ok(v) => v,
@@ -700,6 +886,7 @@ pub(in crate::daemon) async fn handle_agent_done(
 
             mark_issue_completed(&shared, &project, &issue_id).await;
             release_claims_for_agent(&shared, &agent_id).await;
+            finish_attempt(&shared, &agent_id, AttemptOutcome::Done, None).await;
             persist_agents_runtime(shared.clone()).await;
             emit_agent_deleted_event(shared.as_ref(), &agent_id, &project);
 
@@ -727,30 +914,12 @@ pub(in crate::daemon) async fn handle_agent_done(
             };
 
             let prep = match pr_attempt {
-                PullRequestAttempt::Conflict { branch, error } => {
-                    let now_ms = now_ms();
-                    let msg = ChatMessage::new(
-                        ChatRole::System,
-                        format!("rebase conflict on {branch}: {error}"),
-                        now_ms,
-                    );
-
-                    {
-                        let mut agents = shared.agents.lock().await;
-                        if let Some(rt) = agents.agents.get_mut(&agent_id) {
-                            rt.record = rt.record.apply_event(
-                                AgentEvent::NeedsResolution {
-                                    reason: "rebase conflict",
-                                },
-                                now_ms,
-                            );
-                            rt.chat.push(msg.clone());
-                        }
-                    }
-
-                    emit_agent_chat_event(shared.as_ref(), &agent_id, &project, msg);
+                PullRequestAttempt::Conflict(conflict) => {
+                    let msg =
+                        report_conflict(&shared, &agent_id, &project, "rebase conflict", &conflict)
+                            .await;
                     persist_agents_runtime(shared.clone()).await;
-                    return error_response(req, "rebase conflict (agent needs resolution)");
+                    return error_response(req, &msg);
                 }
                 PullRequestAttempt::Ready(prep) => prep,
             };
@@ -776,78 +945,18 @@ pub(in crate::daemon) async fn handle_agent_done(
                 format!("Changes from agent {agent_id}")
             };
 
-            let (token, graphql_url, allowed_authors, remote_url) = {
-                let cfg = shared.config.lock().await;
-                let token = providers::github_token(&cfg).ok_or_else(|| {
-                    anyhow::anyhow!(
-                        "github token not set (set GITHUB_TOKEN/GH_TOKEN or [providers.github].token)"
-                    )
-                });
-                let graphql_url = providers::github_graphql_url(&cfg);
-                let project_cfg = cfg.project(&project).cloned();
-                let allowed_authors = project_cfg
-                    .as_ref()
-                    .map(|p| p.allowed_authors.clone())
-                    .unwrap_or_default();
-                let remote_url = project_cfg
-                    .as_ref()
-                    .map(|p| p.remote_url.clone())
-                    .unwrap_or_default();
-                (token, graphql_url, allowed_authors, remote_url)
-            };
-            let token = match token {
-                Ok(v) => v,
-                Err(err) => return error_response(req, &format!("{err:#}")),
-            };
-
-            let (owner, repo) = match parse_github_nwo(&remote_url) {
-                Some(v) => v,
-                None => {
-                    let now_ms = now_ms();
-                    let msg = ChatMessage::new(
-                        ChatRole::System,
-                        format!(
-                            "failed to initialize github client: not a github remote: {remote_url}"
-                        ),
-                        now_ms,
-                    );
-                    {
-                        let mut agents = shared.agents.lock().await;
-                        if let Some(rt) = agents.agents.get_mut(&agent_id) {
-                            rt.record = rt.record.apply_event(
-                                AgentEvent::NeedsResolution {
-                                    reason: "github init",
-                                },
-                                now_ms,
-                            );
-                            rt.chat.push(msg.clone());
-                        }
-                    }
-                    emit_agent_chat_event(shared.as_ref(), &agent_id, &project, msg);
-                    persist_agents_runtime(shared.clone()).await;
-                    return error_response(req, "pull request creation failed");
-                }
-            };
-
-            let github = match GithubBackend::new(owner, repo, token, allowed_authors, graphql_url)
-            {
+            let host = match pull_request_host(shared.as_ref(), &project).await {
                 Ok(v) => v,
-                Err(err) => {
+                Err(PullRequestHostError::Config(err)) => return error_response(req, &err),
+                Err(PullRequestHostError::Init { reason, message }) => {
                     let now_ms = now_ms();
-                    let msg = ChatMessage::new(
-                        ChatRole::System,
-                        format!("failed to initialize github client: {err:#}"),
-                        now_ms,
-                    );
+                    let msg = ChatMessage::new(ChatRole::System, message, now_ms);
                     {
                         let mut agents = shared.agents.lock().await;
                         if let Some(rt) = agents.agents.get_mut(&agent_id) {
-                            rt.record = rt.record.apply_event(
-                                AgentEvent::NeedsResolution {
-                                    reason: "github init",
-                                },
-                                now_ms,
-                            );
+                            rt.record = rt
+                                .record
+                                .apply_event(AgentEvent::NeedsResolution { reason }, now_ms);
                             rt.chat.push(msg.clone());
                         }
                     }
@@ -857,7 +966,7 @@ pub(in crate::daemon) async fn handle_agent_done(
                 }
             };
 
-            let pr_url = match github
+            let pr_url = match host
                 .create_pull_request(&prep.base_branch, &prep.branch, &pr_title, &pr_body)
                 .await
             {
@@ -874,7 +983,7 @@ pub(in crate::daemon) async fn handle_agent_done(
                         if let Some(rt) = agents.agents.get_mut(&agent_id) {
                             rt.record = rt.record.apply_event(
                                 AgentEvent::NeedsResolution {
-                                    reason: "github pr",
+                                    reason: host.create_reason(),
                                 },
                                 now_ms,
                             );
@@ -924,6 +1033,7 @@ pub(in crate::daemon) async fn handle_agent_done(
 
             mark_issue_completed(&shared, &project, &issue_id).await;
             release_claims_for_agent(&shared, &agent_id).await;
+            finish_attempt(&shared, &agent_id, AttemptOutcome::Done, None).await;
             persist_agents_runtime(shared.clone()).await;
             emit_agent_state_changed_event(
                 shared.as_ref(),
@@ -943,6 +1053,130 @@ pub(in crate::daemon) async fn handle_agent_done(
     }
 }
 
+/// Where the pull request merge strategy opens its pull requests.
+enum PullRequestHost {
+    Github(GithubBackend),
+    Gitlab(GitlabBackend),
+    Gitea(GiteaBackend),
+}
+
+enum PullRequestHostError {
+    /// Missing credentials; reported to the caller only.
+    Config(String),
+    /// The agent needs resolution.
+    Init {
+        reason: &'static str,
+        message: String,
+    },
+}
+
+impl PullRequestHost {
+    async fn create_pull_request(
+        &self,
+        base_branch: &str,
+        head_branch: &str,
+        title: &str,
+        body: &str,
+    ) -> anyhow::Result<String> {
+        match self {
+            Self::Github(github) => {
+                github
+                    .create_pull_request(base_branch, head_branch, title, body)
+                    .await
+            }
+            Self::Gitlab(gitlab) => {
+                gitlab
+                    .create_merge_request(base_branch, head_branch, title, body)
+                    .await
+            }
+            Self::Gitea(gitea) => {
+                gitea
+                    .create_pull_request(base_branch, head_branch, title, body)
+                    .await
+            }
+        }
+    }
+
+    fn create_reason(&self) -> &'static str {
+        match self {
+            Self::Github(_) => "github pr",
+            Self::Gitlab(_) => "gitlab mr",
+            Self::Gitea(_) => "gitea pr",
+        }
+    }
+}
+
+/// GitLab or Gitea when the project's remote is on the configured instance,
+/// GitHub otherwise.
+async fn pull_request_host(
+    shared: &SharedState,
+    project: &str,
+) -> Result<PullRequestHost, PullRequestHostError> {
+    let cfg = shared.config.lock().await;
+    let project_cfg = cfg.project(project).cloned();
+    let allowed_authors = project_cfg
+        .as_ref()
+        .map(|p| p.allowed_authors.clone())
+        .unwrap_or_default();
+    let remote_url = project_cfg
+        .as_ref()
+        .map(|p| p.remote_url.clone())
+        .unwrap_or_default();
+
+    let gitlab_url = providers::gitlab_url(&cfg);
+    if let Some(gitlab_project) = parse_gitlab_project(&remote_url, &gitlab_url) {
+        let token = providers::gitlab_token(&cfg).ok_or_else(|| {
+            PullRequestHostError::Config(
+                "gitlab token not set (set GITLAB_TOKEN or [providers.gitlab].token)".to_owned(),
+            )
+        })?;
+        return GitlabBackend::new(gitlab_project, token, allowed_authors, gitlab_url)
+            .map(PullRequestHost::Gitlab)
+            .map_err(|err| PullRequestHostError::Init {
+                reason: "gitlab init",
+                message: format!("failed to initialize gitlab client: {err:#}"),
+            });
+    }
+
+    let gitea = providers::gitea_url(&cfg)
+        .and_then(|url| parse_gitea_nwo(&remote_url, &url).map(|(owner, repo)| (url, owner, repo)));
+    if let Some((gitea_url, owner, repo)) = gitea {
+        let token = providers::gitea_token(&cfg).ok_or_else(|| {
+            PullRequestHostError::Config(
+                "gitea token not set (set GITEA_TOKEN or [providers.gitea].token)".to_owned(),
+            )
+        })?;
+        return GiteaBackend::new(owner, repo, token, allowed_authors, gitea_url)
+            .map(PullRequestHost::Gitea)
+            .map_err(|err| PullRequestHostError::Init {
+                reason: "gitea init",
+                message: format!("failed to initialize gitea client: {err:#}"),
+            });
+    }
+
+    let token = providers::github_token(&cfg).ok_or_else(|| {
+        PullRequestHostError::Config(
+            "github token not set (set GITHUB_TOKEN/GH_TOKEN or [providers.github].token)"
+                .to_owned(),
+        )
+    })?;
+    let graphql_url = providers::github_graphql_url(&cfg);
+    let Some((owner, repo)) = parse_github_nwo(&remote_url) else {
+        return Err(PullRequestHostError::Init {
+            reason: "github init",
+            message: format!(
+                "failed to initialize github client: not a github remote: {remote_url}"
+            ),
+        });
+    };
+    GithubBackend::new(owner, repo, token, allowed_authors, graphql_url)
+        .map(PullRequestHost::Github)
+        .map_err(|err| PullRequestHostError::Init {
+            reason: "github init",
+            message: format!("failed to initialize github client: {err:#}"),
+        })
+}
+
 pub(in crate::daemon) async fn handle_agent_idle(
     shared: Arc<SharedState>,
     mut req: Request,
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use murmur_core::agent::{AgentEvent, AgentRecord, AgentRole, ChatHistory, ChatMessage, ChatRole};
 use murmur_core::config::AgentBackend;
+use murmur_core::watchdog::WatchdogProgress;
 use murmur_protocol::{
     DirectorChatHistoryRequest, DirectorChatHistoryResponse, DirectorSendMessageRequest,
     DirectorStartRequest, DirectorStartResponse, DirectorStatusResponse, Request, Response,
@@ -130,6 +131,7 @@ pub(in crate::daemon) async fn handle_director_start(
     let (outbound_tx, outbound_rx) = mpsc::channel::<ChatMessage>(32);
     let (abort_tx, abort_rx) = watch::channel(false);
 
+    let hook_timeout_secs = shared.permission_hook_timeout_secs("").await;
     let mut pending_claude = None;
     if backend == AgentBackend::Claude {
         let system_prompt = build_director_system_prompt(&projects);
@@ -140,8 +142,9 @@ pub(in crate::daemon) async fn handle_director_start(
             &shared.paths.murmur_dir,
             &shared.paths.socket_path,
             Some(&allowed_tools),
-            true,
+            AgentRole::Director,
             Some(&system_prompt),
+            hook_timeout_secs,
         )
         .await
         {
@@ -165,6 +168,12 @@ pub(in crate::daemon) async fn handle_director_start(
                 codex_thread_id: None,
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: created_at_ms,
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: (backend == AgentBackend::Claude).then_some(hook_timeout_secs),
                 claim_started_at_ms: None,
                 outbound_tx: outbound_tx.clone(),
                 abort_tx,
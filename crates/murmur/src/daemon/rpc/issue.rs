@@ -1,12 +1,19 @@
+use std::collections::HashMap;
+
+use murmur_core::attempts::{AttemptOutcome, RetryStatus};
+use murmur_core::issue::Issue;
 use murmur_protocol::{
-    IssueComment, IssueCommentRequest, IssueCommitRequest, IssueCreateRequest, IssueCreateResponse,
-    IssueGetRequest, IssueGetResponse, IssueListCommentsRequest, IssueListCommentsResponse,
-    IssueListRequest, IssueListResponse, IssuePlanRequest, IssueReadyRequest, IssueReadyResponse,
-    IssueUpdateRequest, IssueUpdateResponse, Request, Response, MSG_ISSUE_CLOSE, MSG_ISSUE_COMMENT,
-    MSG_ISSUE_COMMIT, MSG_ISSUE_CREATE, MSG_ISSUE_GET, MSG_ISSUE_LIST, MSG_ISSUE_LIST_COMMENTS,
-    MSG_ISSUE_PLAN, MSG_ISSUE_READY, MSG_ISSUE_UPDATE,
+    IssueAttempt, IssueComment, IssueCommentRequest, IssueCommitRequest, IssueCreateRequest,
+    IssueCreateResponse, IssueGetRequest, IssueGetResponse, IssueListCommentsRequest,
+    IssueListCommentsResponse, IssueListRequest, IssueListResponse, IssuePlanRequest,
+    IssueReadyRequest, IssueReadyResponse, IssueUpdateRequest, IssueUpdateResponse, Request,
+    Response, MSG_ISSUE_CLOSE, MSG_ISSUE_COMMENT, MSG_ISSUE_COMMIT, MSG_ISSUE_CREATE,
+    MSG_ISSUE_GET, MSG_ISSUE_LIST, MSG_ISSUE_LIST_COMMENTS, MSG_ISSUE_PLAN, MSG_ISSUE_READY,
+    MSG_ISSUE_UPDATE,
 };
 
+use super::super::orchestration::rank_ready_for_project;
+use super::super::retry::{held_issues, retry_policy};
 use super::super::{
     from_proto_issue_status, issue_backend_for_project, now_ms, to_proto_issue,
     to_proto_issue_summary, SharedState,
@@ -72,7 +79,43 @@ pub(in crate::daemon) async fn handle_issue_get(
         Err(err) => return error_response(req, &format!("{err:#}")),
     };
 
+    let policy = retry_policy(shared, &get.project).await;
+    let now_ms = now_ms();
+    let (history, status) = {
+        let attempts = shared.attempts.lock().await;
+        (
+            attempts
+                .get(&get.project, &issue.id)
+                .cloned()
+                .unwrap_or_default(),
+            attempts.retry_status(&get.project, &issue.id, &policy, now_ms),
+        )
+    };
+
     let payload = IssueGetResponse {
+        attempts: history
+            .attempts
+            .iter()
+            .enumerate()
+            .map(|(i, a)| IssueAttempt {
+                agent_id: a.agent_id.clone(),
+                started_at_ms: a.started_at_ms,
+                ended_at_ms: a.ended_at_ms,
+                outcome: a
+                    .outcome
+                    .map_or("running", AttemptOutcome::as_str)
+                    .to_owned(),
+                exit_code: a.exit_code,
+                reset: i < history.counted_from,
+            })
+            .collect(),
+        failures: history.failures() as u32,
+        max_attempts: policy.max_attempts,
+        needs_human: status == RetryStatus::NeedsHuman,
+        retry_at_ms: match status {
+            RetryStatus::Backoff { until_ms } => Some(until_ms),
+            _ => None,
+        },
         issue: to_proto_issue(issue),
     };
 
@@ -101,14 +144,23 @@ pub(in crate::daemon) async fn handle_issue_ready(
         Err(msg) => return error_response(req, &msg),
     };
 
-    let mut issues = match backend.ready().await {
+    let issues = match backend.ready().await {
         Ok(v) => v,
         Err(err) => return error_response(req, &format!("{err:#}")),
     };
-    issues.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // Present issues in the order the orchestrator would schedule them.
+    let ranked = rank_ready_for_project(shared, &ready.project, &issues).await;
+    let held = held_issues(shared, &ready.project, &issues).await;
+    let by_id: HashMap<&str, &Issue> = issues.iter().map(|i| (i.id.as_str(), i)).collect();
 
     let payload = IssueReadyResponse {
-        issues: issues.iter().map(to_proto_issue_summary).collect(),
+        issues: ranked
+            .iter()
+            .filter(|r| !held.contains(&r.issue_id))
+            .filter_map(|r| by_id.get(r.issue_id.as_str()))
+            .map(|i| to_proto_issue_summary(i))
+            .collect(),
     };
 
     Response {
@@ -145,6 +197,13 @@ pub(in crate::daemon) async fn handle_issue_create(
         dependencies: create.dependencies,
         links: create.links,
     };
+    if let Some(field) = backend.capabilities().unsupported_create(&params) {
+        let msg = format!(
+            "issue backend for {} does not support `{field}`",
+            create.project
+        );
+        return error_response(req, &msg);
+    }
 
     let issue = match backend.create(now_ms(), params).await {
         Ok(v) => v,
@@ -190,6 +249,13 @@ pub(in crate::daemon) async fn handle_issue_update(
         dependencies: update.dependencies,
         links: update.links,
     };
+    if let Some(field) = backend.capabilities().unsupported_update(&params) {
+        let msg = format!(
+            "issue backend for {} does not support `{field}`",
+            update.project
+        );
+        return error_response(req, &msg);
+    }
 
     let issue = match backend.update(now_ms(), &update.id, params).await {
         Ok(v) => v,
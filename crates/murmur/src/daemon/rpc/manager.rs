@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use murmur_core::agent::{AgentEvent, AgentRecord, AgentRole, ChatHistory, ChatMessage, ChatRole};
 use murmur_core::config::AgentBackend;
+use murmur_core::watchdog::WatchdogProgress;
 use murmur_protocol::{
     ManagerChatHistoryRequest, ManagerChatHistoryResponse, ManagerClearHistoryRequest,
     ManagerSendMessageRequest, ManagerStartRequest, ManagerStatusRequest, ManagerStatusResponse,
@@ -130,6 +131,7 @@ pub(in crate::daemon) async fn handle_manager_start(
     let (outbound_tx, outbound_rx) = mpsc::channel::<ChatMessage>(32);
     let (abort_tx, abort_rx) = watch::channel(false);
 
+    let hook_timeout_secs = shared.permission_hook_timeout_secs(project).await;
     let mut pending_claude = None;
     if backend == AgentBackend::Claude {
         let system_prompt = build_manager_prompt(project);
@@ -140,8 +142,9 @@ pub(in crate::daemon) async fn handle_manager_start(
             &shared.paths.murmur_dir,
             &shared.paths.socket_path,
             Some(&allowed_tools),
-            true,
+            AgentRole::Manager,
             Some(&system_prompt),
+            hook_timeout_secs,
         )
         .await
         {
@@ -165,6 +168,12 @@ pub(in crate::daemon) async fn handle_manager_start(
                 codex_thread_id: None,
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: created_at_ms,
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: (backend == AgentBackend::Claude).then_some(hook_timeout_secs),
                 claim_started_at_ms: None, // Manager doesn't have an issue
                 outbound_tx: outbound_tx.clone(),
                 abort_tx,
@@ -466,7 +475,7 @@ pub(in crate::daemon) async fn handle_manager_clear_history(
     }
 }
 
-fn manager_agent_id(project: &str) -> String {
+pub(super) fn manager_agent_id(project: &str) -> String {
     format!("manager-{project}")
 }
 
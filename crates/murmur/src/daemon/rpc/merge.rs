@@ -0,0 +1,95 @@
+use murmur_protocol::{
+    MergeCancelRequest, MergePrioritizeRequest, MergeQueueEntry as ProtoMergeQueueEntry,
+    MergeQueueRequest, MergeQueueResponse, Request, Response, MSG_MERGE_CANCEL,
+    MSG_MERGE_PRIORITIZE, MSG_MERGE_QUEUE,
+};
+
+use super::super::merge_queue;
+use super::super::SharedState;
+use super::error_response;
+
+pub(in crate::daemon) async fn handle_merge_queue(
+    shared: &SharedState,
+    mut req: Request,
+) -> Response {
+    let payload = std::mem::take(&mut req.payload);
+    let parsed: Result<MergeQueueRequest, _> = serde_json::from_value(payload);
+    let list = match parsed {
+        Ok(v) => v,
+        Err(err) => return error_response(req, &format!("invalid payload: {err}")),
+    };
+
+    let mut entries: Vec<ProtoMergeQueueEntry> = Vec::new();
+    for (project, entry) in merge_queue::list(shared, list.project.as_deref()).await {
+        let position = entries.iter().filter(|e| e.project == project).count() as u32 + 1;
+        entries.push(ProtoMergeQueueEntry {
+            project,
+            agent_id: entry.agent_id,
+            issue_id: entry.issue_id,
+            position,
+            priority: entry.priority,
+            state: if entry.merging { "merging" } else { "queued" }.to_owned(),
+            enqueued_at_ms: entry.enqueued_at_ms,
+        });
+    }
+
+    let payload = MergeQueueResponse { entries };
+
+    Response {
+        r#type: MSG_MERGE_QUEUE.to_owned(),
+        id: req.id,
+        success: true,
+        error: None,
+        payload: serde_json::to_value(payload).unwrap_or(serde_json::Value::Null),
+    }
+}
+
+pub(in crate::daemon) async fn handle_merge_cancel(
+    shared: &SharedState,
+    mut req: Request,
+) -> Response {
+    let payload = std::mem::take(&mut req.payload);
+    let parsed: Result<MergeCancelRequest, _> = serde_json::from_value(payload);
+    let cancel = match parsed {
+        Ok(v) => v,
+        Err(err) => return error_response(req, &format!("invalid payload: {err}")),
+    };
+
+    if let Err(msg) = merge_queue::cancel(shared, cancel.agent_id.trim()).await {
+        return error_response(req, &msg);
+    }
+
+    Response {
+        r#type: MSG_MERGE_CANCEL.to_owned(),
+        id: req.id,
+        success: true,
+        error: None,
+        payload: serde_json::Value::Null,
+    }
+}
+
+pub(in crate::daemon) async fn handle_merge_prioritize(
+    shared: &SharedState,
+    mut req: Request,
+) -> Response {
+    let payload = std::mem::take(&mut req.payload);
+    let parsed: Result<MergePrioritizeRequest, _> = serde_json::from_value(payload);
+    let prioritize = match parsed {
+        Ok(v) => v,
+        Err(err) => return error_response(req, &format!("invalid payload: {err}")),
+    };
+
+    if let Err(msg) =
+        merge_queue::prioritize(shared, prioritize.agent_id.trim(), prioritize.priority).await
+    {
+        return error_response(req, &msg);
+    }
+
+    Response {
+        r#type: MSG_MERGE_PRIORITIZE.to_owned(),
+        id: req.id,
+        success: true,
+        error: None,
+        payload: serde_json::Value::Null,
+    }
+}
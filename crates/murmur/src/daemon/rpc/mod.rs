@@ -6,6 +6,7 @@ mod commit;
 mod director;
 mod issue;
 mod manager;
+mod merge;
 mod orchestration;
 mod permission;
 mod ping;
@@ -34,6 +35,7 @@ pub(super) use manager::{
     handle_manager_chat_history, handle_manager_clear_history, handle_manager_send_message,
     handle_manager_start, handle_manager_status, handle_manager_stop,
 };
+pub(super) use merge::{handle_merge_cancel, handle_merge_prioritize, handle_merge_queue};
 pub(super) use orchestration::{
     handle_orchestration_start, handle_orchestration_status, handle_orchestration_stop,
 };
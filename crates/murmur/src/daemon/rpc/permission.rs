@@ -1,20 +1,47 @@
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-
-use murmur_core::agent::ChatRole;
-use murmur_core::config::PermissionsChecker;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use directories::BaseDirs;
+use murmur_core::agent::{AgentState, ChatRole};
+use murmur_core::config::{
+    EscalationStep, EscalationTier, PermissionTimeoutAction,
+    DEFAULT_MANAGER_ESCALATION_TIMEOUT_SECS,
+};
+use murmur_core::permission_log::{Decider, PermissionLogEntry};
+use murmur_core::permissions::{evaluate_rules, grant_rules, resolve_primary_field, Action};
 use murmur_protocol::{
-    PermissionListRequest, PermissionListResponse, PermissionRequest, PermissionRequestPayload,
-    PermissionRespondPayload, PermissionResponse, Request, Response, EVT_PERMISSION_REQUEST,
-    MSG_PERMISSION_LIST, MSG_PERMISSION_REQUEST, MSG_PERMISSION_RESPOND,
+    AgentChatEvent, PermissionBehavior, PermissionExpiredEvent, PermissionListRequest,
+    PermissionListResponse, PermissionRequest, PermissionRequestPayload, PermissionRespondPayload,
+    PermissionResponse, PermissionScope, Request, Response, EVT_AGENT_CHAT, EVT_PERMISSION_EXPIRED,
+    EVT_PERMISSION_REQUEST, MSG_PERMISSION_LIST, MSG_PERMISSION_REQUEST, MSG_PERMISSION_RESPOND,
 };
-use tokio::sync::oneshot;
+use serde::Deserialize;
+use tokio::sync::{broadcast, oneshot};
 
 use crate::llm_auth;
+use crate::permission_log;
+use crate::permissions;
 use crate::providers;
 
-use super::super::{now_ms, SharedState};
+use super::super::prompts::build_permission_escalation_message;
+use super::super::{deliver_user_message, now_ms, SharedState};
 use super::error_response;
+use super::manager::manager_agent_id;
+
+/// Seconds left between the daemon's answer and Claude's hook timeout, for
+/// the reply to reach the hook.
+const HOOK_REPLY_MARGIN_SECS: u64 = 5;
+
+/// What ends the wait for an unanswered request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaitLimit {
+    /// The project's `permission-timeout-secs`.
+    Configured,
+    /// The hook timeout the agent was spawned with, in seconds.
+    Hook(u64),
+}
 
 fn generate_request_id() -> String {
     let mut buf = [0u8; 4];
@@ -54,38 +81,107 @@ pub(in crate::daemon) async fn handle_permission_request(
         return error_response(req, "agent_id is required");
     }
 
-    let (project, issue_id, agent_description, chat_tail) = {
+    let received = Instant::now();
+    let (project, issue_id, agent_description, chat_tail, granted, hook_timeout_secs) = {
         let agents = shared.agents.lock().await;
         let Some(rt) = agents.agents.get(&agent_id) else {
             return error_response(req, "agent not found");
         };
+        let granted = evaluate_rules(
+            &rt.permission_grants,
+            &request.tool_name,
+            &request.tool_input,
+            &rt.record.worktree_dir,
+            &home_dir(),
+        );
         (
             rt.record.project.clone(),
             rt.record.issue_id.clone(),
             rt.record.description.clone(),
             rt.chat.tail(10),
+            granted,
+            rt.hook_timeout_secs,
         )
     };
 
-    let checker = {
+    if let (action @ (Action::Allow | Action::Deny), true) = granted {
+        let (behavior, message) = if action == Action::Allow {
+            (PermissionBehavior::Allow, None)
+        } else {
+            (
+                PermissionBehavior::Deny,
+                Some("denied by an earlier response for this agent".to_owned()),
+            )
+        };
+        log_decision(
+            &shared,
+            &agent_id,
+            &project,
+            &request.tool_name,
+            &request.tool_input,
+            behavior,
+            Decider::Rule,
+            Some("agent-scoped grant".to_owned()),
+        )
+        .await;
+        return Response {
+            r#type: MSG_PERMISSION_REQUEST.to_owned(),
+            id: req.id,
+            success: true,
+            error: None,
+            payload: serde_json::to_value(PermissionResponse {
+                id: generate_request_id(),
+                behavior,
+                message,
+                interrupt: false,
+            })
+            .unwrap_or(serde_json::Value::Null),
+        };
+    }
+
+    let (chain, timeout_action) = {
         let cfg = shared.config.lock().await;
-        cfg.project(&project)
-            .map(|p| p.permissions_checker)
-            .unwrap_or(PermissionsChecker::Manual)
+        match cfg.project(&project) {
+            Some(p) => {
+                let action = match p.effective_permission_timeout_action() {
+                    PermissionTimeoutAction::Deny => PermissionBehavior::Deny,
+                    PermissionTimeoutAction::Allow => PermissionBehavior::Allow,
+                };
+                (p.effective_escalation(), action)
+            }
+            None => (
+                vec![EscalationStep {
+                    tier: EscalationTier::Human,
+                    timeout_secs: None,
+                }],
+                PermissionBehavior::Deny,
+            ),
+        }
     };
 
-    if checker == PermissionsChecker::Llm {
-        match try_llm_authorize(
-            shared.as_ref(),
-            &project,
-            &issue_id,
-            agent_description.as_deref(),
-            &chat_tail,
-            &request,
-        )
-        .await
-        {
-            LlmAuthorizeOutcome::Decision(resp) => {
+    let ctx = Escalation {
+        agent_id: &agent_id,
+        project: &project,
+        issue_id: &issue_id,
+        agent_description: agent_description.as_deref(),
+        chat_tail: &chat_tail,
+        request: &request,
+    };
+    let mut escalated = None;
+    for step in &chain {
+        let outcome = match step.tier {
+            EscalationTier::Llm => llm_tier(&shared, &ctx, step.timeout_secs).await,
+            EscalationTier::Manager => {
+                let secs = step
+                    .timeout_secs
+                    .unwrap_or(DEFAULT_MANAGER_ESCALATION_TIMEOUT_SECS);
+                manager_tier(&shared, &ctx, secs).await
+            }
+            // Validation keeps `human` last.
+            EscalationTier::Human => break,
+        };
+        match outcome {
+            TierOutcome::Decided(resp) => {
                 return Response {
                     r#type: MSG_PERMISSION_REQUEST.to_owned(),
                     id: req.id,
@@ -94,24 +190,52 @@ pub(in crate::daemon) async fn handle_permission_request(
                     payload: serde_json::to_value(resp).unwrap_or(serde_json::Value::Null),
                 };
             }
-            LlmAuthorizeOutcome::Blocked(message) => {
-                return Response {
-                    r#type: MSG_PERMISSION_REQUEST.to_owned(),
-                    id: req.id,
-                    success: true,
-                    error: None,
-                    payload: serde_json::to_value(PermissionResponse {
-                        id: generate_request_id(),
-                        behavior: murmur_protocol::PermissionBehavior::Deny,
-                        message: Some(message),
-                        interrupt: false,
-                    })
-                    .unwrap_or(serde_json::Value::Null),
-                };
+            TierOutcome::Escalated(decider, reason) => {
+                tracing::info!(project = %project, agent_id = %agent_id, tier = decider.as_str(), reason = %reason, "permission escalated");
+                escalated = Some((decider, reason));
             }
         }
     }
 
+    let human = chain.last().filter(|s| s.tier == EscalationTier::Human);
+    let Some(human) = human else {
+        // No tier decided and nobody is left to ask: fail closed.
+        let (decider, message) = escalated.unwrap_or((
+            Decider::Rule,
+            "no permission tier configured - operation blocked".to_owned(),
+        ));
+        log_decision(
+            &shared,
+            &agent_id,
+            &project,
+            &request.tool_name,
+            &request.tool_input,
+            PermissionBehavior::Deny,
+            decider,
+            Some(message.clone()),
+        )
+        .await;
+        return Response {
+            r#type: MSG_PERMISSION_REQUEST.to_owned(),
+            id: req.id,
+            success: true,
+            error: None,
+            payload: serde_json::to_value(PermissionResponse {
+                id: generate_request_id(),
+                behavior: PermissionBehavior::Deny,
+                message: Some(message),
+                interrupt: false,
+            })
+            .unwrap_or(serde_json::Value::Null),
+        };
+    };
+    let timeout = wait_limit(
+        human.timeout_secs,
+        hook_timeout_secs,
+        received.elapsed().as_secs(),
+    )
+    .map(|(secs, limit)| (secs, timeout_action, limit));
+
     let id = generate_request_id();
     let requested_at_ms = now_ms();
     let stored = PermissionRequest {
@@ -122,9 +246,11 @@ pub(in crate::daemon) async fn handle_permission_request(
         tool_input: request.tool_input,
         tool_use_id: request.tool_use_id,
         requested_at_ms,
+        expires_at_ms: timeout.map(|(secs, ..)| requested_at_ms + secs * 1000),
+        timeout_behavior: timeout.map(|(_, behavior, _)| behavior),
     };
 
-    let (tx, rx) = oneshot::channel::<PermissionResponse>();
+    let (tx, mut rx) = oneshot::channel::<PermissionResponse>();
     {
         let mut pending = shared.pending_permissions.lock().await;
         pending.insert(stored.clone(), tx);
@@ -138,7 +264,20 @@ pub(in crate::daemon) async fn handle_permission_request(
         payload: evt_payload,
     });
 
-    let response = match rx.await {
+    let answered = match timeout {
+        Some((secs, behavior, limit)) => {
+            match tokio::time::timeout(Duration::from_secs(secs), &mut rx).await {
+                Ok(answered) => answered,
+                Err(_) => match expire_request(&shared, &id, secs, behavior, limit).await {
+                    Some(response) => Ok(response),
+                    // Answered while the timeout fired.
+                    None => rx.await,
+                },
+            }
+        }
+        None => rx.await,
+    };
+    let response = match answered {
         Ok(v) => v,
         Err(_) => return error_response(req, "permission request canceled"),
     };
@@ -152,8 +291,275 @@ pub(in crate::daemon) async fn handle_permission_request(
     }
 }
 
+/// How long to wait for an answer, given `permission-timeout-secs` and the
+/// hook timeout the agent was spawned with. Claude stops waiting for the hook
+/// at the latter even if `permission-timeout-secs` has since been raised, so
+/// the wait ends before it, `elapsed_secs` into the request.
+fn wait_limit(
+    configured: Option<u64>,
+    hook_timeout_secs: Option<u64>,
+    elapsed_secs: u64,
+) -> Option<(u64, WaitLimit)> {
+    let hook = hook_timeout_secs.map(|secs| {
+        let left = secs
+            .saturating_sub(elapsed_secs)
+            .saturating_sub(HOOK_REPLY_MARGIN_SECS);
+        (left, WaitLimit::Hook(secs))
+    });
+    match (configured, hook) {
+        (Some(configured), Some(hook)) if hook.0 < configured => Some(hook),
+        (Some(configured), _) => Some((configured, WaitLimit::Configured)),
+        (None, hook) => hook,
+    }
+}
+
+/// Answers a request that reached `limit` with the project's timeout action.
+/// `None` if it was answered in the meantime.
+async fn expire_request(
+    shared: &SharedState,
+    id: &str,
+    timeout_secs: u64,
+    behavior: PermissionBehavior,
+    limit: WaitLimit,
+) -> Option<PermissionResponse> {
+    let request = {
+        let mut pending = shared.pending_permissions.lock().await;
+        pending.expire(id)?
+    };
+
+    let rationale = match limit {
+        WaitLimit::Configured => format!("no response within {timeout_secs}s"),
+        WaitLimit::Hook(hook) => format!("no response before the agent's hook limit ({hook}s)"),
+    };
+    log_decision(
+        shared,
+        &request.agent_id,
+        &request.project,
+        &request.tool_name,
+        &request.tool_input,
+        behavior,
+        Decider::Timeout,
+        Some(rationale.clone()),
+    )
+    .await;
+
+    let evt = PermissionExpiredEvent {
+        id: request.id.clone(),
+        agent_id: request.agent_id,
+        project: request.project,
+        tool_name: request.tool_name,
+        behavior,
+        timeout_secs,
+        hook_limit: matches!(limit, WaitLimit::Hook(_)),
+    };
+    let evt_id = shared.next_event_id.fetch_add(1, Ordering::Relaxed);
+    let _ = shared.events_tx.send(murmur_protocol::Event {
+        r#type: EVT_PERMISSION_EXPIRED.to_owned(),
+        id: format!("evt-{evt_id}"),
+        payload: serde_json::to_value(evt).unwrap_or(serde_json::Value::Null),
+    });
+
+    Some(PermissionResponse {
+        id: request.id,
+        behavior,
+        message: (behavior == PermissionBehavior::Deny).then(|| match limit {
+            WaitLimit::Configured => format!("{rationale}; denied by permission-timeout-secs"),
+            WaitLimit::Hook(_) => format!("{rationale}; denied by permission-timeout-action"),
+        }),
+        interrupt: false,
+    })
+}
+
+/// A permission request on its way through the escalation chain.
+struct Escalation<'a> {
+    agent_id: &'a str,
+    project: &'a str,
+    issue_id: &'a str,
+    agent_description: Option<&'a str>,
+    chat_tail: &'a [murmur_core::agent::ChatMessage],
+    request: &'a PermissionRequestPayload,
+}
+
+enum TierOutcome {
+    /// Answered and logged.
+    Decided(PermissionResponse),
+    /// Passed on to the next tier, with who passed it on and why.
+    Escalated(Decider, String),
+}
+
+async fn llm_tier(
+    shared: &SharedState,
+    ctx: &Escalation<'_>,
+    timeout_secs: Option<u64>,
+) -> TierOutcome {
+    let authorize = try_llm_authorize(
+        shared,
+        ctx.project,
+        ctx.issue_id,
+        ctx.agent_description,
+        ctx.chat_tail,
+        ctx.request,
+    );
+    let outcome = match timeout_secs {
+        Some(secs) => match tokio::time::timeout(Duration::from_secs(secs), authorize).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                return TierOutcome::Escalated(
+                    Decider::Llm,
+                    format!("LLM authorization timed out after {secs}s - operation blocked"),
+                );
+            }
+        },
+        None => authorize.await,
+    };
+    match outcome {
+        LlmAuthorizeOutcome::Decision(resp, rationale) => {
+            log_decision(
+                shared,
+                ctx.agent_id,
+                ctx.project,
+                &ctx.request.tool_name,
+                &ctx.request.tool_input,
+                resp.behavior,
+                Decider::Llm,
+                Some(rationale).filter(|s| !s.trim().is_empty()),
+            )
+            .await;
+            TierOutcome::Decided(resp)
+        }
+        LlmAuthorizeOutcome::Blocked(message) => TierOutcome::Escalated(Decider::Llm, message),
+    }
+}
+
+/// Asks the project's manager agent in its chat and waits for a reply
+/// carrying a verdict for this request.
+async fn manager_tier(
+    shared: &SharedState,
+    ctx: &Escalation<'_>,
+    timeout_secs: u64,
+) -> TierOutcome {
+    let escalate = |reason: &str| TierOutcome::Escalated(Decider::Manager, reason.to_owned());
+    let manager_id = manager_agent_id(ctx.project);
+    // The manager would be waiting on its own answer.
+    if ctx.agent_id == manager_id {
+        return escalate("the manager's own request");
+    }
+    let available = {
+        let agents = shared.agents.lock().await;
+        agents
+            .agents
+            .get(&manager_id)
+            .is_some_and(|rt| rt.record.state != AgentState::Aborted)
+    };
+    if !available {
+        return escalate("no manager agent running");
+    }
+
+    let id = generate_request_id();
+    // Subscribe first so a quick reply is not missed.
+    let mut events = shared.events_tx.subscribe();
+    let text = build_permission_escalation_message(
+        &id,
+        ctx.agent_id,
+        &ctx.request.tool_name,
+        &ctx.request.tool_input,
+    );
+    deliver_user_message(shared, &manager_id, ctx.project, text).await;
+
+    let reply = async {
+        loop {
+            match events.recv().await {
+                Ok(evt) if evt.r#type == EVT_AGENT_CHAT => {
+                    let Ok(chat) = serde_json::from_value::<AgentChatEvent>(evt.payload) else {
+                        continue;
+                    };
+                    if chat.agent_id != manager_id
+                        || chat.message.role != murmur_protocol::ChatRole::Assistant
+                    {
+                        continue;
+                    }
+                    if let Some(verdict) = parse_manager_verdict(&chat.message.content, &id) {
+                        return Some(verdict);
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    };
+    let verdict = match tokio::time::timeout(Duration::from_secs(timeout_secs), reply).await {
+        Ok(Some(verdict)) => verdict,
+        Ok(None) => return escalate("daemon shutting down"),
+        Err(_) => return escalate(&format!("manager did not answer within {timeout_secs}s")),
+    };
+
+    let reason = verdict.reason.trim().to_owned();
+    let behavior = match verdict.decision {
+        ManagerDecision::Allow => PermissionBehavior::Allow,
+        ManagerDecision::Deny => PermissionBehavior::Deny,
+        ManagerDecision::Escalate if reason.is_empty() => {
+            return escalate("escalated by the manager")
+        }
+        ManagerDecision::Escalate => return escalate(&reason),
+    };
+    log_decision(
+        shared,
+        ctx.agent_id,
+        ctx.project,
+        &ctx.request.tool_name,
+        &ctx.request.tool_input,
+        behavior,
+        Decider::Manager,
+        Some(reason.clone()).filter(|s| !s.is_empty()),
+    )
+    .await;
+    TierOutcome::Decided(PermissionResponse {
+        id,
+        behavior,
+        message: (behavior == PermissionBehavior::Deny).then(|| {
+            if reason.is_empty() {
+                "denied by the project manager".to_owned()
+            } else {
+                reason
+            }
+        }),
+        interrupt: false,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ManagerVerdict {
+    #[serde(default)]
+    permission: String,
+    decision: ManagerDecision,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ManagerDecision {
+    Allow,
+    Deny,
+    Escalate,
+}
+
+/// The first JSON object in `content` that answers request `id`; the reply
+/// may wrap it in prose or a code fence.
+fn parse_manager_verdict(content: &str, id: &str) -> Option<ManagerVerdict> {
+    content.match_indices('{').find_map(|(start, _)| {
+        let mut objects =
+            serde_json::Deserializer::from_str(&content[start..]).into_iter::<ManagerVerdict>();
+        match objects.next() {
+            Some(Ok(verdict)) if verdict.permission.trim() == id => Some(verdict),
+            _ => None,
+        }
+    })
+}
+
 enum LlmAuthorizeOutcome {
-    Decision(PermissionResponse),
+    /// The verdict and the model's rationale.
+    Decision(PermissionResponse, String),
     Blocked(String),
 }
 
@@ -168,7 +574,7 @@ async fn try_llm_authorize(
     const DEFAULT_PROVIDER: &str = "anthropic";
     const DEFAULT_MODEL: &str = "claude-haiku-4-5";
 
-    let (provider, model, api_key, api_url) = {
+    let (provider, model, api_key, api_url, constraint, timeout, retries) = {
         let cfg = shared.config.lock().await;
         let provider =
             providers::llm_auth_provider(&cfg).unwrap_or_else(|| DEFAULT_PROVIDER.to_owned());
@@ -179,16 +585,31 @@ async fn try_llm_authorize(
                 "LLM authorization failed - operation blocked".to_owned(),
             );
         };
+        let constraint = match providers::llm_auth_constraint(&cfg) {
+            Some(raw) => match llm_auth::Constraint::parse(&raw) {
+                Some(c) => c,
+                None => {
+                    tracing::warn!(project = %project, constraint = %raw, "llm permissions: unknown constraint");
+                    return LlmAuthorizeOutcome::Blocked(
+                        "LLM authorization failed - operation blocked".to_owned(),
+                    );
+                }
+            },
+            None => llm_auth::Constraint::default(),
+        };
+        let timeout = providers::llm_auth_timeout_secs(&cfg)
+            .map(Duration::from_secs)
+            .unwrap_or(llm_auth::DEFAULT_TIMEOUT);
+        let retries = providers::llm_auth_retries(&cfg).unwrap_or(0);
 
-        match provider_parsed {
+        let (api_key, api_url) = match provider_parsed {
             llm_auth::Provider::Anthropic => {
                 let Some(api_key) = providers::anthropic_api_key(&cfg) else {
                     return LlmAuthorizeOutcome::Blocked(
                         "LLM authorization failed - operation blocked".to_owned(),
                     );
                 };
-                let api_url = providers::anthropic_api_url(&cfg);
-                (provider_parsed, model, api_key, api_url)
+                (api_key, providers::anthropic_api_url(&cfg))
             }
             llm_auth::Provider::OpenAI => {
                 let Some(api_key) = providers::openai_api_key(&cfg) else {
@@ -196,10 +617,34 @@ async fn try_llm_authorize(
                         "LLM authorization failed - operation blocked".to_owned(),
                     );
                 };
-                let api_url = providers::openai_api_url(&cfg);
-                (provider_parsed, model, api_key, api_url)
+                (api_key, providers::openai_api_url(&cfg))
             }
-        }
+            llm_auth::Provider::Local => {
+                // A local server has no default endpoint or model.
+                let (Some(api_url), true) = (
+                    providers::local_llm_api_url(&cfg),
+                    providers::llm_auth_model(&cfg).is_some(),
+                ) else {
+                    tracing::warn!(project = %project, "llm permissions: local provider needs [providers.local].api-url and [llm_auth].model");
+                    return LlmAuthorizeOutcome::Blocked(
+                        "LLM authorization failed - operation blocked".to_owned(),
+                    );
+                };
+                (
+                    providers::local_llm_api_key(&cfg).unwrap_or_default(),
+                    api_url,
+                )
+            }
+        };
+        (
+            provider_parsed,
+            model,
+            api_key,
+            api_url,
+            constraint,
+            timeout,
+            retries,
+        )
     };
 
     let tool_input = serde_json::to_string(&request.tool_input).unwrap_or_else(|_| "{}".to_owned());
@@ -235,7 +680,10 @@ async fn try_llm_authorize(
         .collect::<Vec<_>>();
 
     let auth = match llm_auth::Authorizer::new(provider, model, api_key, api_url) {
-        Ok(v) => v,
+        Ok(v) => v
+            .with_constraint(constraint)
+            .with_timeout(timeout)
+            .with_retries(retries),
         Err(err) => {
             tracing::warn!(project = %project, error = %err, "llm permissions: failed to initialize authorizer");
             return LlmAuthorizeOutcome::Blocked(
@@ -263,18 +711,24 @@ async fn try_llm_authorize(
     };
 
     match result.decision {
-        llm_auth::Decision::Safe => LlmAuthorizeOutcome::Decision(PermissionResponse {
-            id: generate_request_id(),
-            behavior: murmur_protocol::PermissionBehavior::Allow,
-            message: None,
-            interrupt: false,
-        }),
-        llm_auth::Decision::Unsafe => LlmAuthorizeOutcome::Decision(PermissionResponse {
-            id: generate_request_id(),
-            behavior: murmur_protocol::PermissionBehavior::Deny,
-            message: (!result.rationale.trim().is_empty()).then_some(result.rationale),
-            interrupt: false,
-        }),
+        llm_auth::Decision::Safe => LlmAuthorizeOutcome::Decision(
+            PermissionResponse {
+                id: generate_request_id(),
+                behavior: murmur_protocol::PermissionBehavior::Allow,
+                message: None,
+                interrupt: false,
+            },
+            result.rationale,
+        ),
+        llm_auth::Decision::Unsafe => LlmAuthorizeOutcome::Decision(
+            PermissionResponse {
+                id: generate_request_id(),
+                behavior: murmur_protocol::PermissionBehavior::Deny,
+                message: (!result.rationale.trim().is_empty()).then(|| result.rationale.clone()),
+                interrupt: false,
+            },
+            result.rationale,
+        ),
         llm_auth::Decision::Unsure => LlmAuthorizeOutcome::Blocked(
             "Blocked by LLM authorization: unable to determine safety".to_owned(),
         ),
@@ -330,26 +784,55 @@ pub(in crate::daemon) async fn handle_permission_respond(
         return error_response(req, "id is required");
     }
 
-    let (ok, project) = {
+    // Get the request before responding (respond() removes the item)
+    let request = {
+        let pending = shared.pending_permissions.lock().await;
+        pending.pending.get(&respond.id).map(|p| p.request.clone())
+    };
+    let Some(request) = request else {
+        return error_response(req, "permission request not found");
+    };
+
+    if respond.scope != PermissionScope::Once {
+        if let Err(err) = save_grant(shared, &request, respond.behavior, respond.scope).await {
+            let scope = scope_name(respond.scope);
+            return error_response(req, &format!("save {scope} grant: {err:#}"));
+        }
+    }
+
+    let scope = (respond.scope != PermissionScope::Once)
+        .then(|| format!("scope: {}", scope_name(respond.scope)));
+    let rationale = match (respond.message.as_deref(), scope) {
+        (Some(message), Some(scope)) => Some(format!("{message} ({scope})")),
+        (message, scope) => message.map(str::to_owned).or(scope),
+    };
+    let ok = {
         let mut pending = shared.pending_permissions.lock().await;
-        // Get the project before responding (respond() removes the item)
-        let project = pending.pending.get(&respond.id).map(|p| p.request.project.clone());
-        let ok = pending.respond(PermissionResponse {
+        pending.respond(PermissionResponse {
             id: respond.id,
             behavior: respond.behavior,
             message: respond.message,
             interrupt: respond.interrupt,
-        });
-        (ok, project)
+        })
     };
     if !ok {
         return error_response(req, "permission request not found");
     }
 
+    log_decision(
+        shared,
+        &request.agent_id,
+        &request.project,
+        &request.tool_name,
+        &request.tool_input,
+        respond.behavior,
+        Decider::Human,
+        rationale,
+    )
+    .await;
+
     // Record user activity for intervention detection
-    if let Some(project) = project {
-        shared.record_user_activity(&project).await;
-    }
+    shared.record_user_activity(&request.project).await;
 
     Response {
         r#type: MSG_PERMISSION_RESPOND.to_owned(),
@@ -359,3 +842,159 @@ pub(in crate::daemon) async fn handle_permission_respond(
         payload: serde_json::Value::Null,
     }
 }
+
+/// Records a response that reaches beyond its request: as in-memory rules
+/// on the agent, or as rules appended to a permissions file.
+async fn save_grant(
+    shared: &SharedState,
+    request: &PermissionRequest,
+    behavior: PermissionBehavior,
+    scope: PermissionScope,
+) -> anyhow::Result<()> {
+    let action = match behavior {
+        PermissionBehavior::Allow => Action::Allow,
+        PermissionBehavior::Deny => Action::Deny,
+    };
+    let (worktree_dir, role) = {
+        let agents = shared.agents.lock().await;
+        agents
+            .agents
+            .get(&request.agent_id)
+            .map(|rt| (rt.record.worktree_dir.clone(), rt.record.role))
+            .ok_or_else(|| anyhow!("agent not found"))?
+    };
+    let existing = permissions::load_rules(&shared.paths, Some(&request.project), role).await?;
+    let rules = grant_rules(
+        &existing,
+        &request.tool_name,
+        &request.tool_input,
+        &worktree_dir,
+        &home_dir(),
+        action,
+    )
+    .map_err(|err| anyhow!("{err}"))?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let verb = if action == Action::Allow {
+        "Allowed"
+    } else {
+        "Denied"
+    };
+    let note = format!(
+        "{verb} for {} ({}) via permission.respond",
+        request.agent_id, request.project
+    );
+    match scope {
+        PermissionScope::Once => {}
+        PermissionScope::Agent => {
+            let mut agents = shared.agents.lock().await;
+            if let Some(rt) = agents.agents.get_mut(&request.agent_id) {
+                rt.permission_grants.extend(rules);
+            }
+        }
+        PermissionScope::Project => {
+            permissions::append_rules(&shared.paths, Some(&request.project), &rules, &note).await?;
+        }
+        PermissionScope::Global => {
+            permissions::append_rules(&shared.paths, None, &rules, &note).await?;
+        }
+    }
+    Ok(())
+}
+
+fn scope_name(scope: PermissionScope) -> &'static str {
+    match scope {
+        PermissionScope::Once => "once",
+        PermissionScope::Agent => "agent",
+        PermissionScope::Project => "project",
+        PermissionScope::Global => "global",
+    }
+}
+
+/// Appends a decision to the permission log. Failures are only logged: the
+/// agent still gets its answer.
+#[allow(clippy::too_many_arguments)]
+async fn log_decision(
+    shared: &SharedState,
+    agent_id: &str,
+    project: &str,
+    tool_name: &str,
+    tool_input: &serde_json::Value,
+    behavior: PermissionBehavior,
+    decider: Decider,
+    rationale: Option<String>,
+) {
+    let entry = PermissionLogEntry {
+        at_ms: now_ms(),
+        agent_id: agent_id.to_owned(),
+        project: project.to_owned(),
+        tool_name: tool_name.to_owned(),
+        primary_field: resolve_primary_field(tool_name, tool_input),
+        action: match behavior {
+            PermissionBehavior::Allow => Action::Allow,
+            PermissionBehavior::Deny => Action::Deny,
+        },
+        decider,
+        rationale,
+    };
+    if let Err(err) = permission_log::append(&shared.paths, &entry).await {
+        tracing::warn!(project = %project, error = %err, "permission log: append failed");
+    }
+}
+
+fn home_dir() -> String {
+    BaseDirs::new()
+        .map(|d| d.home_dir().to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_manager_verdict_for_the_request() {
+        let reply = r#"Looks routine.
+
+```json
+{"permission": "ab12cd34", "decision": "deny", "reason": "pushes to main"}
+```"#;
+        let verdict = parse_manager_verdict(reply, "ab12cd34").unwrap();
+        assert_eq!(verdict.decision, ManagerDecision::Deny);
+        assert_eq!(verdict.reason, "pushes to main");
+
+        // A verdict for another request, or none at all, is not an answer.
+        assert!(parse_manager_verdict(reply, "ffffffff").is_none());
+        assert!(parse_manager_verdict("{not json} allow", "ab12cd34").is_none());
+
+        let reply =
+            r#"{"permission":"1","decision":"allow"} {"permission":"2","decision":"escalate"}"#;
+        let verdict = parse_manager_verdict(reply, "2").unwrap();
+        assert_eq!(verdict.decision, ManagerDecision::Escalate);
+        assert!(verdict.reason.is_empty());
+    }
+
+    #[test]
+    fn wait_ends_before_the_agent_hook_limit() {
+        assert_eq!(wait_limit(None, None, 0), None);
+        assert_eq!(
+            wait_limit(Some(60), None, 0),
+            Some((60, WaitLimit::Configured))
+        );
+        assert_eq!(
+            wait_limit(Some(60), Some(300), 10),
+            Some((60, WaitLimit::Configured))
+        );
+        // Raised after the agent was spawned.
+        assert_eq!(
+            wait_limit(Some(600), Some(300), 10),
+            Some((285, WaitLimit::Hook(300)))
+        );
+        assert_eq!(
+            wait_limit(None, Some(300), 0),
+            Some((295, WaitLimit::Hook(300)))
+        );
+    }
+}
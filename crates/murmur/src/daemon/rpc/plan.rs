@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use murmur_core::agent::{AgentEvent, AgentRecord, AgentRole, ChatHistory, ChatMessage, ChatRole};
 use murmur_core::config::AgentBackend;
+use murmur_core::watchdog::WatchdogProgress;
 use murmur_protocol::{
     AgentInfo, PlanChatHistoryRequest, PlanChatHistoryResponse, PlanListRequest, PlanListResponse,
     PlanSendMessageRequest, PlanShowRequest, PlanShowResponse, PlanStartRequest, PlanStartResponse,
@@ -116,6 +117,7 @@ pub(in crate::daemon) async fn handle_plan_start(
     let (outbound_tx, outbound_rx) = mpsc::channel::<ChatMessage>(32);
     let (abort_tx, abort_rx) = watch::channel(false);
 
+    let hook_timeout_secs = shared.permission_hook_timeout_secs(&project).await;
     let mut pending_claude = None;
     if backend == AgentBackend::Claude {
         let (child, stdin, stdout, pid) = match spawn_claude_agent_process(
@@ -125,8 +127,9 @@ pub(in crate::daemon) async fn handle_plan_start(
             &shared.paths.murmur_dir,
             &shared.paths.socket_path,
             None,
-            false,
+            AgentRole::Planner,
             None,
+            hook_timeout_secs,
         )
         .await
         {
@@ -156,6 +159,12 @@ pub(in crate::daemon) async fn handle_plan_start(
                 codex_thread_id: None,
                 chat: ChatHistory::new(DEFAULT_CHAT_CAPACITY),
                 last_idle_at_ms: None,
+                last_activity_at_ms: created_at_ms,
+                watchdog: WatchdogProgress::default(),
+                verify_failures: 0,
+                conflict_attempts: 0,
+                permission_grants: Vec::new(),
+                hook_timeout_secs: (backend == AgentBackend::Claude).then_some(hook_timeout_secs),
                 claim_started_at_ms: None, // Planner doesn't have an issue
                 outbound_tx: outbound_tx.clone(),
                 abort_tx,
@@ -1,17 +1,23 @@
 use std::sync::Arc;
 
 use murmur_core::config::{
-    AgentBackend, IssueBackend, MergeStrategy, PermissionsChecker, ProjectConfig,
+    AgentBackend, MergeStrategy, PermissionsChecker, ProjectConfig, DEFAULT_ISSUE_BACKEND,
 };
+use murmur_core::orchestration::explain_schedule;
 use murmur_protocol::{
     ProjectAddRequest, ProjectAddResponse, ProjectConfigGetRequest, ProjectConfigGetResponse,
     ProjectConfigSetRequest, ProjectConfigShowRequest, ProjectConfigShowResponse, ProjectInfo,
     ProjectListResponse, ProjectRemoveRequest, ProjectStatusRequest, ProjectStatusResponse,
-    Request, Response, MSG_PROJECT_ADD, MSG_PROJECT_CONFIG_GET, MSG_PROJECT_CONFIG_SET,
-    MSG_PROJECT_CONFIG_SHOW, MSG_PROJECT_LIST, MSG_PROJECT_REMOVE, MSG_PROJECT_STATUS,
+    Request, Response, ScheduledIssue, MSG_PROJECT_ADD, MSG_PROJECT_CONFIG_GET,
+    MSG_PROJECT_CONFIG_SET, MSG_PROJECT_CONFIG_SHOW, MSG_PROJECT_LIST, MSG_PROJECT_REMOVE,
+    MSG_PROJECT_STATUS,
 };
 
-use super::super::orchestration::orchestrator_is_running;
+use super::super::orchestration::{
+    count_active_agents, count_total_active_agents, orchestrator_is_running,
+};
+use super::super::retry::forget_project;
+use super::super::state::TickSchedule;
 use super::super::{persist_agents_runtime, project_dir, project_repo_dir, SharedState};
 use super::error_response;
 
@@ -106,17 +112,33 @@ pub(in crate::daemon) async fn handle_project_add(
         name: add.name.clone(),
         remote_url: add.remote_url.clone(),
         max_agents,
-        issue_backend: IssueBackend::Tk,
+        issue_backend: DEFAULT_ISSUE_BACKEND.to_owned(),
         permissions_checker: PermissionsChecker::Manual,
         agent_backend,
         planner_backend: None,
         coding_backend: None,
         merge_strategy: MergeStrategy::Direct,
         allowed_authors: vec![],
+        ready_label: None,
         autostart,
         linear_team: None,
         linear_project: None,
+        jira_project: None,
+        jira_ready_jql: None,
         silence_threshold_secs: None,
+        permission_timeout_secs: None,
+        permission_timeout_action: None,
+        escalation: Vec::new(),
+        weight: None,
+        merge_batch_size: None,
+        merge_message_template: None,
+        scheduling: None,
+        routing: Vec::new(),
+        watchdog: None,
+        retry: None,
+        verify: None,
+        conflicts: None,
+        confinement: None,
         extra: Default::default(),
     };
 
@@ -185,7 +207,7 @@ pub(in crate::daemon) async fn handle_project_add(
         return error_response(req, &format!("save config failed: {err:#}"));
     }
 
-    *cfg = next_cfg;
+    *cfg = Arc::new(next_cfg);
 
     let payload = ProjectAddResponse {
         name: add.name,
@@ -289,6 +311,7 @@ pub(in crate::daemon) async fn handle_project_remove(
         let mut completed = shared.completed_issues.lock().await;
         completed.remove(&remove.name);
     }
+    forget_project(&shared, &remove.name).await;
     {
         let mut commits = shared.commits.lock().await;
         commits.remove(&remove.name);
@@ -352,7 +375,7 @@ pub(in crate::daemon) async fn handle_project_remove(
         return error_response(req, &format!("save config failed: {err:#}"));
     }
 
-    *cfg = next_cfg;
+    *cfg = Arc::new(next_cfg);
 
     Response {
         r#type: MSG_PROJECT_REMOVE.to_owned(),
@@ -449,11 +472,29 @@ pub(in crate::daemon) async fn handle_project_config_set(
         Err(err) => return error_response(req, &err.to_string()),
     };
 
+    // Only a changed name is checked, so a backend that has since gone from
+    // the registry does not block setting other keys.
+    let backend = next_cfg
+        .project(&set.name)
+        .map(|p| p.issue_backend.as_str());
+    let previous = cfg.project(&set.name).map(|p| p.issue_backend.as_str());
+    if let Some(name) = backend.filter(|name| Some(*name) != previous) {
+        if !shared.issue_backends.contains(name) {
+            return error_response(
+                req,
+                &format!(
+                    "unknown issue backend: {name} (registered: {})",
+                    shared.issue_backends.names().join(", ")
+                ),
+            );
+        }
+    }
+
     if let Err(err) = config_store::save(&shared.paths, &next_cfg).await {
         return error_response(req, &format!("save config failed: {err:#}"));
     }
 
-    *cfg = next_cfg;
+    *cfg = Arc::new(next_cfg);
 
     Response {
         r#type: MSG_PROJECT_CONFIG_SET.to_owned(),
@@ -475,14 +516,20 @@ pub(in crate::daemon) async fn handle_project_status(
         Err(err) => return error_response(req, &format!("invalid payload: {err}")),
     };
 
-    let (configured, silence_threshold_secs) = {
+    let (configured, max_agents, weight, max_total_agents, silence_threshold_secs) = {
         let cfg = shared.config.lock().await;
         let Some(project) = cfg.project(&status.name) else {
             return error_response(req, "project not found");
         };
         let configured = project.remote_url.clone();
         let threshold = cfg.silence_threshold_for_project(&status.name);
-        (configured, threshold)
+        (
+            configured,
+            project.max_agents as usize,
+            project.effective_weight(),
+            cfg.max_total_agents(),
+            threshold,
+        )
     };
 
     let repo_dir = project_repo_dir(&shared.paths, &status.name);
@@ -509,6 +556,25 @@ pub(in crate::daemon) async fn handle_project_status(
         .is_user_intervening(&status.name, silence_threshold_secs)
         .await;
 
+    // Global pool standing, as of the orchestrator's last tick
+    let total_active_agents = count_total_active_agents(shared).await;
+    let slot = if orchestrator_running {
+        let slots = shared.fair_share.lock().await;
+        slots.get(&status.name).copied().unwrap_or_default()
+    } else {
+        Default::default()
+    };
+    let waiting_for_slots = slot.wanted.saturating_sub(slot.granted);
+
+    let (schedule, schedule_error) = if orchestrator_running {
+        match project_schedule(shared, &status.name, max_agents, waiting_for_slots).await {
+            Ok(v) => (v, None),
+            Err(err) => (Vec::new(), Some(err)),
+        }
+    } else {
+        (Vec::new(), Some("orchestrator not running".to_owned()))
+    };
+
     let payload = ProjectStatusResponse {
         name: status.name,
         repo_dir: repo_dir.to_string_lossy().to_string(),
@@ -522,6 +588,12 @@ pub(in crate::daemon) async fn handle_project_status(
         user_intervening,
         seconds_since_activity,
         silence_threshold_secs,
+        max_total_agents,
+        total_active_agents,
+        weight,
+        waiting_for_slots: waiting_for_slots as u32,
+        schedule,
+        schedule_error,
     };
 
     Response {
@@ -532,3 +604,48 @@ pub(in crate::daemon) async fn handle_project_status(
         payload: serde_json::to_value(payload).unwrap_or(serde_json::Value::Null),
     }
 }
+
+/// Ready issues in the order the orchestrator's last tick ranked them,
+/// annotated with what it would do with each on its next tick. Reuses that
+/// tick's `ready()` rather than walking the tracker again.
+async fn project_schedule(
+    shared: &SharedState,
+    project: &str,
+    max_agents: usize,
+    waiting_for_slots: usize,
+) -> Result<Vec<ScheduledIssue>, String> {
+    let Some(TickSchedule { ranked, held }) = shared.schedules.lock().await.get(project).cloned()
+    else {
+        return Err("waiting for the first orchestrator tick".to_owned());
+    };
+
+    let active_agents = count_active_agents(shared, project).await as usize;
+    let claims = { shared.claims.lock().await.clone() };
+    let completed = {
+        let completed = shared.completed_issues.lock().await;
+        completed.get(project).cloned().unwrap_or_default()
+    };
+    // Slots the global cap withheld are not available to this project.
+    let max_agents = max_agents.saturating_sub(waiting_for_slots);
+    let decisions = explain_schedule(
+        project,
+        active_agents,
+        max_agents,
+        &ranked,
+        &claims,
+        &completed,
+        &held,
+    );
+
+    Ok(ranked
+        .into_iter()
+        .zip(decisions)
+        .map(|(issue, decision)| ScheduledIssue {
+            effective_priority: issue.effective_priority(),
+            reason: issue.reason(),
+            decision: decision.as_str().to_owned(),
+            issue_id: issue.issue_id,
+            title: issue.title,
+        })
+        .collect())
+}
@@ -4,10 +4,14 @@ use std::sync::Arc;
 use std::time::{Instant, SystemTime};
 
 use murmur_core::agent::{AgentRecord, ChatHistory, ChatMessage};
+use murmur_core::attempts::AttemptLog;
 use murmur_core::claims::ClaimRegistry;
 use murmur_core::commits::CommitLog;
-use murmur_core::config::{AgentBackend, ConfigFile};
+use murmur_core::config::{AgentBackend, ConfigFile, DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS};
+use murmur_core::orchestration::RankedIssue;
 use murmur_core::paths::MurmurPaths;
+use murmur_core::permissions::Rule;
+use murmur_core::watchdog::WatchdogProgress;
 use murmur_protocol::{
     Event, PermissionRequest, PermissionResponse, UserQuestion, UserQuestionResponse,
 };
@@ -15,7 +19,9 @@ use tokio::sync::{broadcast, mpsc, oneshot, watch};
 
 use crate::dedup_store::DedupStore;
 use crate::git::Git;
+use crate::issue_backends::Registry;
 
+use super::merge_queue::MergeQueues;
 use super::DaemonHandle;
 
 pub(super) const DEFAULT_CHAT_CAPACITY: usize = 200;
@@ -26,6 +32,20 @@ pub(super) struct AgentRuntime {
     pub(super) codex_thread_id: Option<String>,
     pub(super) chat: ChatHistory,
     pub(super) last_idle_at_ms: Option<u64>,
+    /// Last output from, or user message to, the agent (for the watchdog).
+    pub(super) last_activity_at_ms: u64,
+    pub(super) watchdog: WatchdogProgress,
+    /// Pre-merge verification rounds this agent has failed.
+    pub(super) verify_failures: u32,
+    /// Conflict-resolution turns this agent has been given.
+    pub(super) conflict_attempts: u32,
+    /// Rules from `agent`-scoped permission responses.
+    pub(super) permission_grants: Vec<Rule>,
+    /// `PreToolUse` hook timeout the Claude process was spawned with; Claude
+    /// gives up on a permission request after it, whatever the config says now,
+    /// so permission waits end just before it. Read from the config at each
+    /// spawn site; `None` for other backends and agents from an earlier daemon.
+    pub(super) hook_timeout_secs: Option<u64>,
     /// Timestamp when the agent claimed the issue (for comment polling).
     pub(super) claim_started_at_ms: Option<u64>,
     pub(super) outbound_tx: mpsc::Sender<ChatMessage>,
@@ -80,6 +100,12 @@ impl PendingPermissions {
         true
     }
 
+    /// Drops a request nobody answered in time. `None` if a response got
+    /// there first.
+    pub(super) fn expire(&mut self, id: &str) -> Option<PermissionRequest> {
+        self.pending.remove(id).map(|p| p.request)
+    }
+
     pub(super) fn cancel_for_project(&mut self, project: &str) -> usize {
         let ids = self
             .pending
@@ -147,6 +173,24 @@ impl PendingQuestions {
     }
 }
 
+/// A project's standing in the global agent pool, as of its last tick.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FairShareSlot {
+    /// New agents the project wanted to spawn.
+    pub(super) wanted: usize,
+    /// New agents the global cap allowed it to spawn.
+    pub(super) granted: usize,
+}
+
+/// A project's ready issues as its last tick ranked them, for
+/// `mm project status`.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TickSchedule {
+    pub(super) ranked: Vec<RankedIssue>,
+    /// Issues held back by the retry policy.
+    pub(super) held: BTreeSet<String>,
+}
+
 pub(super) struct SharedState {
     pub(super) pid: u32,
     pub(super) started_at: SystemTime,
@@ -159,15 +203,23 @@ pub(super) struct SharedState {
     pub(super) next_plan_id: AtomicU64,
     pub(super) paths: MurmurPaths,
     pub(super) git: Git,
-    pub(super) config: tokio::sync::Mutex<ConfigFile>,
+    pub(super) issue_backends: Registry,
+    /// Replaced whole on every change, so a reader can keep a cheap snapshot.
+    pub(super) config: tokio::sync::Mutex<Arc<ConfigFile>>,
     pub(super) agents: tokio::sync::Mutex<AgentsState>,
     pub(super) claims: tokio::sync::Mutex<ClaimRegistry>,
     pub(super) pending_permissions: tokio::sync::Mutex<PendingPermissions>,
     pub(super) pending_questions: tokio::sync::Mutex<PendingQuestions>,
     pub(super) completed_issues: tokio::sync::Mutex<BTreeMap<String, BTreeSet<String>>>,
     pub(super) orchestrators: tokio::sync::Mutex<BTreeMap<String, OrchestratorRuntime>>,
-    pub(super) merge_locks: tokio::sync::Mutex<BTreeMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    pub(super) merge_queues: tokio::sync::Mutex<MergeQueues>,
+    /// Serializes spawning across projects so the global agent cap holds.
+    pub(super) spawn_lock: tokio::sync::Mutex<()>,
+    pub(super) fair_share: tokio::sync::Mutex<BTreeMap<String, FairShareSlot>>,
+    pub(super) schedules: tokio::sync::Mutex<BTreeMap<String, TickSchedule>>,
     pub(super) commits: tokio::sync::Mutex<BTreeMap<String, CommitLog>>,
+    /// Per-issue attempt history, persisted in `runtime/attempts.json`.
+    pub(super) attempts: tokio::sync::Mutex<AttemptLog>,
     pub(super) dedup: Arc<tokio::sync::Mutex<DedupStore>>,
     /// Tracks the last user activity timestamp for each project.
     /// Used by the orchestrator to pause spawning when users are active.
@@ -199,4 +251,13 @@ impl SharedState {
             None => false,
         }
     }
+
+    /// Hook timeout for a project's Claude agents; see
+    /// `ProjectConfig::permission_hook_timeout_secs`.
+    pub(super) async fn permission_hook_timeout_secs(&self, project: &str) -> u64 {
+        let cfg = self.config.lock().await;
+        cfg.project(project)
+            .map(|p| p.permission_hook_timeout_secs())
+            .unwrap_or(DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS)
+    }
 }
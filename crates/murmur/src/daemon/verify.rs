@@ -0,0 +1,191 @@
+//! Pre-merge verification: project commands run in the agent worktree.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use murmur_core::agent::{AgentEvent, AgentState, ChatMessage, ChatRole};
+use murmur_core::config::VerifyConfig;
+use murmur_core::verify::{verify_feedback, verify_rounds_exhausted, VerifyFailure};
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::Pid;
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+
+use super::state::SharedState;
+use super::{deliver_user_message, emit_agent_chat_event, emit_agent_state_changed_event, now_ms};
+
+/// Runs the commands in order and returns the first failure.
+pub(in crate::daemon) async fn run_verification(
+    policy: &VerifyConfig,
+    worktree_dir: &Path,
+) -> anyhow::Result<Option<VerifyFailure>> {
+    for command in &policy.commands {
+        tracing::info!(dir = %worktree_dir.display(), command = %command, "running verification");
+        // Own process group, so a timeout also takes down whatever the shell
+        // started (test runners, servers).
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(worktree_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("spawn verification command: {command}"))?;
+        let stdout = child.stdout.take().context("verification stdout")?;
+        let stderr = child.stderr.take().context("verification stderr")?;
+
+        let timeout = Duration::from_secs(policy.timeout_secs.max(1));
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let finished = tokio::time::timeout(timeout, async {
+            let (_, _, status) = tokio::join!(
+                drain(stdout, &mut out),
+                drain(stderr, &mut err),
+                child.wait()
+            );
+            status
+        })
+        .await;
+        let status = match finished {
+            Ok(status) => {
+                Some(status.with_context(|| format!("run verification command: {command}"))?)
+            }
+            Err(_) => {
+                if let Some(pid) = child.id() {
+                    let _ = killpg(Pid::from_raw(pid as i32), Signal::SIGKILL);
+                }
+                let _ = child.wait().await;
+                None
+            }
+        };
+        if status.is_some_and(|s| s.success()) {
+            continue;
+        }
+
+        let mut combined = String::from_utf8_lossy(&out).into_owned();
+        combined.push_str(&String::from_utf8_lossy(&err));
+        return Ok(Some(VerifyFailure {
+            command: command.clone(),
+            exit_code: status.and_then(|s| s.code()),
+            timed_out: status.is_none(),
+            output: combined,
+        }));
+    }
+    Ok(None)
+}
+
+/// Appends everything read from `pipe` to `buf`. Each chunk lands in `buf`
+/// as soon as it is read, so a cancelled drain keeps the partial output.
+async fn drain(mut pipe: impl AsyncRead + Unpin, buf: &mut Vec<u8>) {
+    let mut chunk = [0u8; 8192];
+    while let Ok(n) = pipe.read(&mut chunk).await {
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Sends the failure back to the agent so it can fix it, or marks the agent
+/// `needs_resolution` once it has used up `max-rounds`. Returns the error for
+/// the `agent.done` caller.
+pub(in crate::daemon) async fn report_verify_failure(
+    shared: &SharedState,
+    agent_id: &str,
+    project: &str,
+    policy: &VerifyConfig,
+    failure: &VerifyFailure,
+) -> String {
+    let round = {
+        let mut agents = shared.agents.lock().await;
+        match agents.agents.get_mut(agent_id) {
+            Some(rt) => {
+                rt.verify_failures += 1;
+                rt.verify_failures
+            }
+            None => return format!("verification failed: {}", failure.summary()),
+        }
+    };
+    let feedback = verify_feedback(failure, round, policy.max_rounds);
+
+    if !verify_rounds_exhausted(round, policy.max_rounds) {
+        deliver_user_message(shared, agent_id, project, feedback).await;
+        return format!(
+            "verification failed: {} (round {round}/{}; output sent to agent)",
+            failure.summary(),
+            policy.max_rounds
+        );
+    }
+
+    let now_ms = now_ms();
+    let msg = ChatMessage::new(ChatRole::System, feedback, now_ms);
+    {
+        let mut agents = shared.agents.lock().await;
+        if let Some(rt) = agents.agents.get_mut(agent_id) {
+            rt.record = rt.record.apply_event(
+                AgentEvent::NeedsResolution {
+                    reason: "verification failed",
+                },
+                now_ms,
+            );
+            rt.chat.push(msg.clone());
+        }
+    }
+    emit_agent_chat_event(shared, agent_id, project, msg);
+    emit_agent_state_changed_event(shared, agent_id, project, AgentState::NeedsResolution);
+    format!(
+        "verification failed: {} (agent needs resolution)",
+        failure.summary()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn timeout_kills_the_process_group_and_keeps_partial_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = VerifyConfig {
+            commands: vec!["echo started; sleep 30 & sleep 30; wait".to_owned()],
+            timeout_secs: 1,
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let failure = run_verification(&policy, dir.path())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(failure.timed_out);
+        assert_eq!(failure.exit_code, None);
+        assert_eq!(failure.output, "started\n");
+    }
+
+    #[tokio::test]
+    async fn failing_command_reports_exit_code_and_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = VerifyConfig {
+            commands: vec![
+                "true".to_owned(),
+                "echo out; echo err >&2; exit 3".to_owned(),
+            ],
+            ..Default::default()
+        };
+
+        let failure = run_verification(&policy, dir.path())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(failure.command, "echo out; echo err >&2; exit 3");
+        assert_eq!(failure.exit_code, Some(3));
+        assert!(!failure.timed_out);
+        assert_eq!(failure.output, "out\nerr\n");
+    }
+}
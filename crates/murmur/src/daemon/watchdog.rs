@@ -0,0 +1,275 @@
+//! Background task that acts on agents that stop making progress.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use murmur_core::agent::{AgentEvent, AgentRole, AgentState, ChatMessage, ChatRole};
+use murmur_core::attempts::AttemptOutcome;
+use murmur_core::config::{WatchdogAction, WatchdogConfig};
+use murmur_core::watchdog::{evaluate_watchdog, WatchdogFire, WatchedAgent};
+use murmur_protocol::{AgentWatchdogEvent, Event, EVT_AGENT_WATCHDOG};
+use tokio::sync::watch;
+
+use super::retry::finish_attempt;
+use super::state::SharedState;
+use super::{
+    cleanup_agent_runtime, deliver_user_message, emit_agent_chat_event, emit_agent_deleted_event,
+    emit_agent_state_changed_event, mark_issue_completed, now_ms, persist_agents_runtime,
+    release_claims_for_agent,
+};
+
+const DEFAULT_NUDGE_MESSAGE: &str = "You have not made progress in a while. \
+If you are blocked, explain what you need; otherwise continue with the task, \
+or run `mm agent done` if it is finished.";
+
+fn watchdog_interval() -> Duration {
+    let ms = std::env::var("FUGUE_WATCHDOG_INTERVAL_MS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(30_000);
+    Duration::from_millis(ms.clamp(50, 600_000))
+}
+
+pub(in crate::daemon) async fn watchdog_loop(
+    shared: Arc<SharedState>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let mut tick = tokio::time::interval(watchdog_interval());
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = shutdown_rx.changed() => {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+            _ = tick.tick() => {
+                run_watchdog(&shared).await;
+            }
+        }
+    }
+}
+
+struct Stalled {
+    agent_id: String,
+    project: String,
+    issue_id: String,
+    fire: WatchdogFire,
+    nudge_message: Option<String>,
+}
+
+async fn run_watchdog(shared: &Arc<SharedState>) {
+    let policies: BTreeMap<String, WatchdogConfig> = {
+        let cfg = shared.config.lock().await;
+        cfg.projects
+            .iter()
+            .map(|p| (p.name.clone(), p.effective_watchdog()))
+            .filter(|(_, w)| w.is_enabled())
+            .collect()
+    };
+    if policies.is_empty() {
+        return;
+    }
+
+    let claimed: BTreeMap<String, String> = shared
+        .claims
+        .lock()
+        .await
+        .list()
+        .into_iter()
+        .map(|c| (c.agent_id, c.issue_id))
+        .collect();
+
+    let now_ms = now_ms();
+    let stalled = {
+        let mut agents = shared.agents.lock().await;
+        let mut stalled = Vec::new();
+        for (agent_id, rt) in agents.agents.iter_mut() {
+            let Some(policy) = policies.get(&rt.record.project) else {
+                continue;
+            };
+            let claim = claimed.get(agent_id);
+            let watched_role = match rt.record.role {
+                AgentRole::Coding => true,
+                AgentRole::Planner => claim.is_some(),
+                AgentRole::Manager | AgentRole::Director => false,
+            };
+            if !watched_role {
+                continue;
+            }
+
+            let agent = WatchedAgent {
+                state: rt.record.state,
+                holds_claim: claim.is_some(),
+                last_activity_at_ms: rt.last_activity_at_ms,
+                last_idle_at_ms: rt.last_idle_at_ms,
+            };
+            let Some(fire) = evaluate_watchdog(policy, &agent, rt.watchdog, now_ms) else {
+                continue;
+            };
+            rt.watchdog = fire.progress;
+            stalled.push(Stalled {
+                agent_id: agent_id.clone(),
+                project: rt.record.project.clone(),
+                issue_id: claim.cloned().unwrap_or_else(|| rt.record.issue_id.clone()),
+                fire,
+                nudge_message: policy.nudge_message.clone(),
+            });
+        }
+        stalled
+    };
+
+    for s in stalled {
+        tracing::warn!(
+            agent_id = %s.agent_id,
+            project = %s.project,
+            action = ?s.fire.action,
+            stalled_secs = s.fire.stalled_ms / 1000,
+            "watchdog acting on stalled agent"
+        );
+        apply_action(shared, &s).await;
+        emit_watchdog_event(shared.as_ref(), &s);
+    }
+}
+
+async fn apply_action(shared: &Arc<SharedState>, s: &Stalled) {
+    match s.fire.action {
+        WatchdogAction::Nudge => nudge(shared, s).await,
+        WatchdogAction::NeedsResolution => flag_needs_resolution(shared, s).await,
+        WatchdogAction::Abort => abort(shared, s).await,
+        WatchdogAction::Requeue => requeue(shared, s).await,
+    }
+}
+
+async fn nudge(shared: &Arc<SharedState>, s: &Stalled) {
+    let text = s
+        .nudge_message
+        .clone()
+        .unwrap_or_else(|| DEFAULT_NUDGE_MESSAGE.to_owned());
+    deliver_user_message(shared.as_ref(), &s.agent_id, &s.project, text).await;
+}
+
+async fn flag_needs_resolution(shared: &Arc<SharedState>, s: &Stalled) {
+    let now_ms = now_ms();
+    let reason = format!(
+        "Watchdog: no progress for {}",
+        format_stalled(s.fire.stalled_ms)
+    );
+    let msg = ChatMessage::new(ChatRole::System, reason.clone(), now_ms);
+
+    {
+        let mut agents = shared.agents.lock().await;
+        let Some(rt) = agents.agents.get_mut(&s.agent_id) else {
+            return;
+        };
+        rt.record = rt
+            .record
+            .apply_event(AgentEvent::NeedsResolution { reason: &reason }, now_ms);
+        rt.chat.push(msg.clone());
+    }
+
+    emit_agent_chat_event(shared.as_ref(), &s.agent_id, &s.project, msg);
+    emit_agent_state_changed_event(
+        shared.as_ref(),
+        &s.agent_id,
+        &s.project,
+        AgentState::NeedsResolution,
+    );
+    persist_agents_runtime(shared.clone()).await;
+}
+
+/// Mirrors `mm agent abort`, but keeps the issue out of the queue so the
+/// worktree can be inspected before anyone retries it.
+async fn abort(shared: &Arc<SharedState>, s: &Stalled) {
+    let now_ms = now_ms();
+    let quit = ChatMessage::new(ChatRole::User, "/quit".to_owned(), now_ms);
+
+    let (abort_tx, outbound_tx) = {
+        let mut agents = shared.agents.lock().await;
+        let mut claims = shared.claims.lock().await;
+        let Some(rt) = agents.agents.get_mut(&s.agent_id) else {
+            return;
+        };
+        rt.chat.push(quit.clone());
+        rt.record = rt
+            .record
+            .apply_event(AgentEvent::Aborted { by: "watchdog" }, now_ms);
+        *claims = claims.release_by_agent(&s.agent_id);
+        (rt.abort_tx.clone(), rt.outbound_tx.clone())
+    };
+
+    if !s.issue_id.is_empty() {
+        mark_issue_completed(shared.as_ref(), &s.project, &s.issue_id).await;
+    }
+    finish_attempt(shared.as_ref(), &s.agent_id, AttemptOutcome::Aborted, None).await;
+
+    emit_agent_chat_event(shared.as_ref(), &s.agent_id, &s.project, quit.clone());
+    emit_agent_state_changed_event(
+        shared.as_ref(),
+        &s.agent_id,
+        &s.project,
+        AgentState::Aborted,
+    );
+    let _ = outbound_tx.send(quit).await;
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let _ = abort_tx.send(true);
+    });
+    persist_agents_runtime(shared.clone()).await;
+}
+
+/// Removes the agent like `mm agent delete`, leaving its issue ready for a
+/// fresh agent on the next orchestrator tick.
+async fn requeue(shared: &Arc<SharedState>, s: &Stalled) {
+    let runtime = {
+        let mut agents = shared.agents.lock().await;
+        let Some(rt) = agents.agents.remove(&s.agent_id) else {
+            return;
+        };
+        rt
+    };
+
+    if let Err(err) = cleanup_agent_runtime(shared.clone(), runtime).await {
+        tracing::warn!(agent_id = %s.agent_id, error = %err, "watchdog requeue: cleanup failed");
+    }
+    release_claims_for_agent(shared.as_ref(), &s.agent_id).await;
+    finish_attempt(shared.as_ref(), &s.agent_id, AttemptOutcome::Requeued, None).await;
+    persist_agents_runtime(shared.clone()).await;
+    emit_agent_deleted_event(shared.as_ref(), &s.agent_id, &s.project);
+}
+
+fn emit_watchdog_event(shared: &SharedState, s: &Stalled) {
+    let action = match s.fire.action {
+        WatchdogAction::Nudge => "nudge",
+        WatchdogAction::NeedsResolution => "needs-resolution",
+        WatchdogAction::Abort => "abort",
+        WatchdogAction::Requeue => "requeue",
+    };
+    let payload = serde_json::to_value(AgentWatchdogEvent {
+        agent_id: s.agent_id.clone(),
+        project: s.project.clone(),
+        issue_id: s.issue_id.clone(),
+        action: action.to_owned(),
+        stalled_secs: s.fire.stalled_ms / 1000,
+    })
+    .unwrap_or(serde_json::Value::Null);
+
+    let id = shared.next_event_id.fetch_add(1, Ordering::Relaxed);
+    let _ = shared.events_tx.send(Event {
+        r#type: EVT_AGENT_WATCHDOG.to_owned(),
+        id: format!("evt-{id}"),
+        payload,
+    });
+}
+
+fn format_stalled(ms: u64) -> String {
+    let mins = ms / 60_000;
+    if mins >= 60 {
+        format!("{}h{}m", mins / 60, mins % 60)
+    } else {
+        format!("{mins}m")
+    }
+}
@@ -306,6 +306,36 @@ impl Git {
         ensure_success(output, "git merge --ff-only")
     }
 
+    /// Stages `rev`'s changes on top of HEAD without committing.
+    pub async fn merge_squash(&self, repo_dir: &Path, rev: &str) -> anyhow::Result<()> {
+        let output = tokio::process::Command::new(&self.exe)
+            .arg("-C")
+            .arg(repo_dir)
+            .args(["merge", "--squash", rev])
+            .output()
+            .await
+            .context("spawn git merge --squash")?;
+
+        ensure_success(output, "git merge --squash")
+    }
+
+    pub async fn merge_no_ff(
+        &self,
+        repo_dir: &Path,
+        rev: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        let output = tokio::process::Command::new(&self.exe)
+            .arg("-C")
+            .arg(repo_dir)
+            .args(["merge", "--no-ff", "-m", message, rev])
+            .output()
+            .await
+            .context("spawn git merge --no-ff")?;
+
+        ensure_success(output, "git merge --no-ff")
+    }
+
     pub async fn push_ref(
         &self,
         repo_dir: &Path,
@@ -352,6 +382,51 @@ impl Git {
         ensure_success_with_output(output, "git rebase")
     }
 
+    /// Paths left unmerged by a stopped rebase or merge.
+    pub async fn conflicted_files(&self, worktree_dir: &Path) -> anyhow::Result<Vec<String>> {
+        let output = tokio::process::Command::new(&self.exe)
+            .arg("-C")
+            .arg(worktree_dir)
+            .args(["diff", "--name-only", "--diff-filter=U"])
+            .output()
+            .await
+            .context("spawn git diff")?;
+
+        let stdout = ensure_success_with_stdout(output, "git diff --name-only")?;
+        Ok(stdout
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_owned())
+            .collect())
+    }
+
+    /// `<short-sha> <subject>` for each commit in `range`, newest first.
+    pub async fn log_oneline(
+        &self,
+        repo_dir: &Path,
+        range: &str,
+        max_count: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        let output = tokio::process::Command::new(&self.exe)
+            .arg("-C")
+            .arg(repo_dir)
+            .args(["log", "--format=%h %s"])
+            .arg(format!("--max-count={max_count}"))
+            .arg(range)
+            .output()
+            .await
+            .context("spawn git log")?;
+
+        let stdout = ensure_success_with_stdout(output, "git log")?;
+        Ok(stdout
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_owned())
+            .collect())
+    }
+
     pub async fn rebase_abort_best_effort(&self, worktree_dir: &Path) {
         let _ = tokio::process::Command::new(&self.exe)
             .arg("-C")
@@ -0,0 +1,862 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
+use murmur_core::issue::{
+    Capabilities, Comment, CreateParams, Issue, IssueBackend, ListFilter, Status, UpdateParams,
+};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use url::Url;
+
+use crate::git::Git;
+use crate::github::split_owner_repo;
+
+/// Items asked for per page; the default server maximum
+/// (`MAX_RESPONSE_ITEMS`), which instances can lower.
+const PAGE_SIZE: usize = 50;
+
+/// How long `ready()` trusts an issue's cached dependencies. A closed blocker
+/// does not touch the issues it blocks, so entries expire rather than wait
+/// for the issue to change.
+const DEPENDENCY_TTL: Duration = Duration::from_secs(60);
+
+/// Dependencies `ready()` fetched, kept across backend instances so that
+/// orchestrator ticks do not ask for every candidate's dependencies again.
+/// Clones share the entries.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyCache {
+    entries: Arc<Mutex<BTreeMap<String, CachedDependencies>>>,
+}
+
+#[derive(Debug)]
+struct CachedDependencies {
+    /// The issue's `updated_at` when fetched; any change refetches.
+    updated_at: String,
+    fetched_at: Instant,
+    deps: Vec<GiteaDependency>,
+}
+
+impl DependencyCache {
+    fn get(&self, key: &str, updated_at: &str) -> Option<Vec<GiteaDependency>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(key)
+            .filter(|e| e.updated_at == updated_at && e.fetched_at.elapsed() < DEPENDENCY_TTL)
+            .map(|e| e.deps.clone())
+    }
+
+    fn insert(&self, key: String, updated_at: &str, deps: &[GiteaDependency]) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, e| e.fetched_at.elapsed() < DEPENDENCY_TTL);
+        entries.insert(
+            key,
+            CachedDependencies {
+                updated_at: updated_at.to_owned(),
+                fetched_at: Instant::now(),
+                deps: deps.to_vec(),
+            },
+        );
+    }
+}
+
+/// Issues and pull requests of one Gitea or Forgejo repository, over the
+/// REST API (v1).
+#[derive(Debug, Clone)]
+pub struct GiteaBackend {
+    client: reqwest::Client,
+    api_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+    allowed_authors: Vec<String>,
+    ready_label: Option<String>,
+    dependency_cache: DependencyCache,
+}
+
+impl GiteaBackend {
+    /// `base_url` is the instance root, e.g. `https://gitea.example.com`.
+    pub fn new(
+        owner: String,
+        repo: String,
+        token: String,
+        mut allowed_authors: Vec<String>,
+        base_url: String,
+    ) -> anyhow::Result<Self> {
+        if owner.trim().is_empty() || repo.trim().is_empty() {
+            return Err(anyhow!("invalid gitea repo: {owner}/{repo}"));
+        }
+        if token.trim().is_empty() {
+            return Err(anyhow!("missing gitea token"));
+        }
+
+        if allowed_authors.is_empty() {
+            allowed_authors.push(owner.clone());
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let client = reqwest::Client::builder()
+            .user_agent(format!("murmur/{}", env!("CARGO_PKG_VERSION")))
+            .default_headers(headers)
+            .build()
+            .context("build reqwest client")?;
+
+        Ok(Self {
+            client,
+            api_url: format!("{}/api/v1", base_url.trim().trim_end_matches('/')),
+            token,
+            owner,
+            repo,
+            allowed_authors,
+            ready_label: None,
+            dependency_cache: DependencyCache::default(),
+        })
+    }
+
+    /// Limits `ready()` to open issues with `label`, filtered by the server.
+    pub fn with_ready_label(mut self, label: Option<String>) -> Self {
+        self.ready_label = label.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    /// Shares `ready()`'s dependency lookups with other backends holding a
+    /// clone of `cache`.
+    pub fn with_dependency_cache(mut self, cache: DependencyCache) -> Self {
+        self.dependency_cache = cache;
+        self
+    }
+
+    pub async fn from_repo(
+        git: &Git,
+        repo_dir: &Path,
+        token: String,
+        allowed_authors: Vec<String>,
+        base_url: String,
+    ) -> anyhow::Result<Self> {
+        let remote = git
+            .remote_origin_url(repo_dir)
+            .await
+            .context("git remote get-url origin")?;
+
+        let (owner, repo) = parse_gitea_nwo(&remote, &base_url)
+            .ok_or_else(|| anyhow!("not a gitea remote for {base_url}: {remote}"))?;
+
+        Self::new(owner, repo, token, allowed_authors, base_url)
+    }
+
+    pub fn nwo(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    pub async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        let num = parse_issue_number(id)?;
+        let gt: GiteaIssue = self
+            .request(Method::GET, &format!("issues/{num}"), None)
+            .await
+            .context("gitea get issue")?;
+        let deps = self.blocked_by(num).await?;
+        Ok(to_issue(&gt, &deps))
+    }
+
+    pub async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        let states: BTreeSet<&str> = filter
+            .status
+            .iter()
+            .map(|s| match s {
+                Status::Open | Status::Blocked => "open",
+                Status::Closed => "closed",
+            })
+            .collect();
+        let state = match states.len() {
+            1 => states.into_iter().next().unwrap_or("all"),
+            _ => "all",
+        };
+
+        let mut path = format!("issues?type=issues&state={state}");
+        if !filter.labels.is_empty() {
+            path.push_str("&labels=");
+            path.push_str(&encode(&filter.labels.join(",")));
+        }
+
+        let issues: Vec<GiteaIssue> = self.pages(&path).await.context("gitea list issues")?;
+
+        let mut out = Vec::with_capacity(issues.len());
+        for gt in issues {
+            let deps = self.blocked_by(gt.number).await?;
+            out.push(to_issue(&gt, &deps));
+        }
+        Ok(out)
+    }
+
+    pub async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        let allowed: BTreeSet<String> = self
+            .allowed_authors
+            .iter()
+            .map(|s| s.trim().to_ascii_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let mut path = "issues?type=issues&state=open".to_owned();
+        if let Some(label) = self.ready_label.as_ref() {
+            path.push_str("&labels=");
+            path.push_str(&encode(label));
+        }
+        let issues: Vec<GiteaIssue> = self.pages(&path).await.context("gitea ready query")?;
+
+        let mut ready = Vec::new();
+        for gt in issues {
+            if gt.labels.iter().any(|l| l.name == "blocked") {
+                continue;
+            }
+
+            if !allowed.is_empty() {
+                let is_allowed = gt
+                    .user
+                    .as_ref()
+                    .is_some_and(|u| allowed.contains(&u.login.trim().to_ascii_lowercase()));
+                if !is_allowed {
+                    continue;
+                }
+            }
+
+            let deps = self.cached_blocked_by(&gt).await?;
+            if deps.iter().any(|d| d.state == "open") {
+                continue;
+            }
+
+            ready.push(to_issue(&gt, &deps));
+        }
+
+        Ok(ready)
+    }
+
+    pub async fn create(&self, params: CreateParams) -> anyhow::Result<Issue> {
+        if !params.links.is_empty() {
+            return Err(anyhow!("gitea backend does not support `links`"));
+        }
+
+        let issue_type = if params.issue_type.trim().is_empty() {
+            "task".to_owned()
+        } else {
+            params.issue_type
+        };
+
+        let mut label_names = Vec::new();
+        label_names.push(format!("type:{issue_type}"));
+        label_names.push(format!("priority:{}", params.priority));
+        label_names.extend(params.labels);
+        let label_ids = self.label_ids(&label_names).await?;
+
+        let mut body = serde_json::Map::new();
+        body.insert("title".to_owned(), serde_json::Value::String(params.title));
+        if !params.description.trim().is_empty() {
+            body.insert(
+                "body".to_owned(),
+                serde_json::Value::String(params.description),
+            );
+        }
+        body.insert("labels".to_owned(), serde_json::json!(label_ids));
+
+        let created: GiteaIssue = self
+            .request(
+                Method::POST,
+                "issues",
+                Some(serde_json::Value::Object(body)),
+            )
+            .await
+            .context("gitea create issue")?;
+
+        for dep in params.dependencies {
+            if let Err(err) = self.add_blocked_by(created.number, &dep).await {
+                tracing::warn!(dep = %dep, error = %err, "failed to add gitea dependency");
+            }
+        }
+
+        let deps = self.blocked_by(created.number).await.unwrap_or_default();
+        Ok(to_issue(&created, &deps))
+    }
+
+    pub async fn update(&self, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        if params.dependencies.is_some() {
+            return Err(anyhow!(
+                "gitea backend does not support updating dependencies"
+            ));
+        }
+        if params.links.is_some() {
+            return Err(anyhow!("gitea backend does not support `links`"));
+        }
+
+        let UpdateParams {
+            title,
+            description,
+            status,
+            priority,
+            issue_type,
+            labels,
+            dependencies: _,
+            links: _,
+        } = params;
+
+        let num = parse_issue_number(id)?;
+        let current: GiteaIssue = self
+            .request(Method::GET, &format!("issues/{num}"), None)
+            .await
+            .context("gitea get issue for update")?;
+        let current_issue = to_issue(&current, &[]);
+
+        let labels_provided = labels.is_some();
+        if labels_provided || issue_type.is_some() || priority.is_some() || status.is_some() {
+            let mut new_labels = if let Some(v) = labels {
+                v
+            } else {
+                current_issue.labels.clone()
+            };
+
+            if let Some(v) = issue_type {
+                new_labels.retain(|l| !l.starts_with("type:"));
+                new_labels.push(format!("type:{v}"));
+            } else if !current_issue.issue_type.trim().is_empty() && !labels_provided {
+                new_labels.push(format!("type:{}", current_issue.issue_type));
+            }
+
+            if let Some(v) = priority {
+                new_labels.retain(|l| !l.starts_with("priority:"));
+                new_labels.push(format!("priority:{v}"));
+            } else if !labels_provided {
+                new_labels.push(format!("priority:{}", current_issue.priority));
+            }
+
+            let blocked = match status {
+                Some(status) => status == Status::Blocked,
+                None => current_issue.status == Status::Blocked,
+            };
+            new_labels.retain(|l| l != "blocked");
+            if blocked {
+                new_labels.push("blocked".to_owned());
+            }
+
+            // Labels are not part of the issue edit; they are replaced
+            // separately.
+            let label_ids = self.label_ids(&new_labels).await?;
+            let _: serde_json::Value = self
+                .request(
+                    Method::PUT,
+                    &format!("issues/{num}/labels"),
+                    Some(serde_json::json!({ "labels": label_ids })),
+                )
+                .await
+                .context("gitea replace labels")?;
+        }
+
+        let mut body = serde_json::Map::new();
+        if let Some(v) = title {
+            body.insert("title".to_owned(), serde_json::Value::String(v));
+        }
+        if let Some(v) = description {
+            body.insert("body".to_owned(), serde_json::Value::String(v));
+        }
+        if let Some(status) = status {
+            let state = match status {
+                Status::Closed => "closed",
+                Status::Open | Status::Blocked => "open",
+            };
+            body.insert(
+                "state".to_owned(),
+                serde_json::Value::String(state.to_owned()),
+            );
+        }
+
+        let updated: GiteaIssue = self
+            .request(
+                Method::PATCH,
+                &format!("issues/{num}"),
+                Some(serde_json::Value::Object(body)),
+            )
+            .await
+            .context("gitea update issue")?;
+
+        let deps = self.blocked_by(num).await?;
+        Ok(to_issue(&updated, &deps))
+    }
+
+    pub async fn close(&self, id: &str) -> anyhow::Result<()> {
+        let _ = self
+            .update(
+                id,
+                UpdateParams {
+                    status: Some(Status::Closed),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn comment(&self, id: &str, body: &str) -> anyhow::Result<()> {
+        let num = parse_issue_number(id)?;
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issues/{num}/comments"),
+                Some(serde_json::json!({ "body": body })),
+            )
+            .await
+            .context("gitea add comment")?;
+        Ok(())
+    }
+
+    /// List comments on an issue, optionally filtering to those created after `since_ms`.
+    pub async fn list_comments(
+        &self,
+        id: &str,
+        since_ms: Option<u64>,
+    ) -> anyhow::Result<Vec<Comment>> {
+        let num = parse_issue_number(id)?;
+
+        #[derive(Debug, Deserialize)]
+        struct GiteaComment {
+            id: i64,
+            #[serde(default)]
+            body: String,
+            created_at: String,
+            #[serde(default)]
+            user: Option<GiteaUser>,
+        }
+
+        let raw: Vec<GiteaComment> = self
+            .request(Method::GET, &format!("issues/{num}/comments"), None)
+            .await
+            .context("gitea list comments")?;
+
+        let mut comments = Vec::new();
+        for c in raw {
+            let created_at_ms = parse_rfc3339_ms(&c.created_at).unwrap_or(0);
+            if let Some(since) = since_ms {
+                if created_at_ms <= since {
+                    continue;
+                }
+            }
+            comments.push(Comment {
+                id: c.id.to_string(),
+                author: c.user.map(|u| u.login).unwrap_or_default(),
+                body: c.body,
+                created_at_ms,
+            });
+        }
+
+        Ok(comments)
+    }
+
+    pub async fn create_pull_request(
+        &self,
+        base_branch: &str,
+        head_branch: &str,
+        title: &str,
+        body: &str,
+    ) -> anyhow::Result<String> {
+        let base = base_branch.trim();
+        let head = head_branch.trim();
+        if base.is_empty() || head.is_empty() {
+            return Err(anyhow!("pull request base/head is empty"));
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct PullRequest {
+            html_url: String,
+        }
+
+        let pr: PullRequest = self
+            .request(
+                Method::POST,
+                "pulls",
+                Some(serde_json::json!({
+                    "base": base,
+                    "head": head,
+                    "title": title,
+                    "body": body,
+                })),
+            )
+            .await
+            .context("gitea create pull request")?;
+
+        Ok(pr.html_url)
+    }
+
+    pub async fn commit(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// `blocked_by` through the dependency cache, for `ready()` candidates.
+    async fn cached_blocked_by(&self, gt: &GiteaIssue) -> anyhow::Result<Vec<GiteaDependency>> {
+        let key = format!(
+            "{}/repos/{}/{}#{}",
+            self.api_url, self.owner, self.repo, gt.number
+        );
+        if let Some(deps) = self.dependency_cache.get(&key, &gt.updated_at) {
+            return Ok(deps);
+        }
+        let deps = self.blocked_by(gt.number).await?;
+        self.dependency_cache.insert(key, &gt.updated_at, &deps);
+        Ok(deps)
+    }
+
+    /// Issues blocking `num`. Empty when the repository has dependencies
+    /// turned off, which the server reports as 404.
+    async fn blocked_by(&self, num: i64) -> anyhow::Result<Vec<GiteaDependency>> {
+        let (status, text) = self
+            .send(Method::GET, &format!("issues/{num}/dependencies"), None)
+            .await
+            .context("gitea list dependencies")?;
+        if status == StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+        if !status.is_success() {
+            return Err(anyhow!("gitea api error ({status}): {text}"));
+        }
+        serde_json::from_str(&text).context("parse gitea response")
+    }
+
+    async fn add_blocked_by(&self, blocked: i64, blocking: &str) -> anyhow::Result<()> {
+        let blocking = parse_issue_number(blocking)?;
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issues/{blocked}/dependencies"),
+                Some(serde_json::json!({
+                    "owner": self.owner,
+                    "repo": self.repo,
+                    "index": blocking,
+                })),
+            )
+            .await
+            .context("gitea add dependency")?;
+        Ok(())
+    }
+
+    /// Label ids for `names`, creating the labels that do not exist yet.
+    async fn label_ids(&self, names: &[String]) -> anyhow::Result<Vec<i64>> {
+        #[derive(Debug, Deserialize)]
+        struct Label {
+            id: i64,
+            name: String,
+        }
+
+        let existing: Vec<Label> = self.pages("labels").await.context("gitea list labels")?;
+        let existing: BTreeMap<String, i64> =
+            existing.into_iter().map(|l| (l.name, l.id)).collect();
+
+        let mut ids = Vec::new();
+        for name in names {
+            if let Some(id) = existing.get(name) {
+                ids.push(*id);
+                continue;
+            }
+
+            let color = match name.as_str() {
+                s if s.starts_with("type:") => "#0366d6",
+                s if s.starts_with("priority:") => "#fbca04",
+                "blocked" => "#d73a4a",
+                _ => "#ededed",
+            };
+            let created: anyhow::Result<Label> = self
+                .request(
+                    Method::POST,
+                    "labels",
+                    Some(serde_json::json!({ "name": name, "color": color })),
+                )
+                .await;
+            match created {
+                Ok(label) => ids.push(label.id),
+                Err(err) => {
+                    tracing::warn!(label = %name, error = %err, "failed to create gitea label")
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// GETs every page of a list endpoint.
+    async fn pages<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<Vec<T>> {
+        let sep = if path.contains('?') { '&' } else { '?' };
+        let mut out = Vec::new();
+        for page in 1.. {
+            let items: Vec<T> = self
+                .request(
+                    Method::GET,
+                    &format!("{path}{sep}limit={PAGE_SIZE}&page={page}"),
+                    None,
+                )
+                .await?;
+            // The server may cap `limit` below PAGE_SIZE, so a short page is
+            // not the last one; only an empty page is.
+            if items.is_empty() {
+                break;
+            }
+            out.extend(items);
+        }
+        Ok(out)
+    }
+
+    /// Sends a request to `repos/:owner/:repo/<path>`.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<T> {
+        let (status, text) = self.send(method, path, body).await?;
+        if !status.is_success() {
+            return Err(anyhow!("gitea api error ({status}): {text}"));
+        }
+        serde_json::from_str(&text).context("parse gitea response")
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<(StatusCode, String)> {
+        let url = format!(
+            "{}/repos/{}/{}/{path}",
+            self.api_url,
+            encode(&self.owner),
+            encode(&self.repo)
+        );
+
+        let mut req = self.client.request(method, &url).header(
+            reqwest::header::AUTHORIZATION,
+            format!("token {}", self.token),
+        );
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+
+        let resp = req.send().await.context("send request")?;
+        let status = resp.status();
+        let text = resp.text().await.context("read response")?;
+        Ok((status, text))
+    }
+}
+
+#[async_trait]
+impl IssueBackend for GiteaBackend {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: false,
+            // Set on create only, when the repository has dependencies on.
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        GiteaBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        GiteaBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        GiteaBackend::ready(self).await
+    }
+
+    async fn create(&self, _now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        GiteaBackend::create(self, params).await
+    }
+
+    async fn update(&self, _now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        GiteaBackend::update(self, id, params).await
+    }
+
+    async fn close(&self, _now_ms: u64, id: &str) -> anyhow::Result<()> {
+        GiteaBackend::close(self, id).await
+    }
+
+    async fn comment(&self, _now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        GiteaBackend::comment(self, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        GiteaBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+        GiteaBackend::commit(self).await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaIssue {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    state: String,
+    created_at: String,
+    #[serde(default)]
+    updated_at: String,
+    #[serde(default)]
+    user: Option<GiteaUser>,
+    #[serde(default)]
+    labels: Vec<GiteaLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaLabel {
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GiteaDependency {
+    number: i64,
+    state: String,
+}
+
+fn to_issue(gt: &GiteaIssue, blocked_by: &[GiteaDependency]) -> Issue {
+    let created_at_ms = parse_rfc3339_ms(&gt.created_at).unwrap_or(0);
+
+    let mut issue_type = String::new();
+    let mut priority = 0;
+    let mut labels = Vec::new();
+
+    let mut status = if gt.state == "closed" {
+        Status::Closed
+    } else {
+        Status::Open
+    };
+
+    for label in &gt.labels {
+        let name = label.name.as_str();
+        if let Some(rest) = name.strip_prefix("type:") {
+            issue_type = rest.to_owned();
+            continue;
+        }
+        if let Some(rest) = name.strip_prefix("priority:") {
+            priority = rest.parse::<i32>().unwrap_or(priority);
+            continue;
+        }
+        if name == "blocked" {
+            status = Status::Blocked;
+            continue;
+        }
+        labels.push(name.to_owned());
+    }
+
+    if issue_type.is_empty() {
+        issue_type = "task".to_owned();
+    }
+
+    Issue {
+        id: gt.number.to_string(),
+        title: gt.title.clone(),
+        description: gt.body.clone(),
+        status,
+        priority,
+        issue_type,
+        dependencies: blocked_by.iter().map(|d| d.number.to_string()).collect(),
+        labels,
+        links: vec![],
+        created_at_ms,
+    }
+}
+
+fn parse_rfc3339_ms(s: &str) -> anyhow::Result<u64> {
+    let dt = time::OffsetDateTime::parse(s.trim(), &time::format_description::well_known::Rfc3339)
+        .map_err(|e| anyhow!("parse rfc3339: {e}"))?;
+    Ok(dt.unix_timestamp_nanos() as u64 / 1_000_000)
+}
+
+fn parse_issue_number(s: &str) -> anyhow::Result<i64> {
+    let trimmed = s.trim().trim_start_matches('#');
+    if trimmed.is_empty() {
+        return Err(anyhow!("invalid issue id: empty"));
+    }
+    trimmed
+        .parse::<i64>()
+        .map_err(|_| anyhow!("invalid gitea issue number: {trimmed}"))
+}
+
+fn encode(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
+
+/// `owner/repo` of `remote_url` if it lives on the Gitea instance at
+/// `base_url`. Only hosts are compared, so SSH remotes match an HTTPS base.
+pub fn parse_gitea_nwo(remote_url: &str, base_url: &str) -> Option<(String, String)> {
+    let base = Url::parse(base_url.trim()).ok()?;
+    let host = base.host_str()?;
+    let url = remote_url.trim();
+
+    if let Some(rest) = url.strip_prefix("git@") {
+        let (remote_host, path) = rest.split_once(':')?;
+        if remote_host != host {
+            return None;
+        }
+        return split_owner_repo(path);
+    }
+
+    let parsed = Url::parse(url).ok()?;
+    if parsed.host_str()? != host {
+        return None;
+    }
+    let path = parsed.path().trim_start_matches('/');
+    // An instance served under a path prefix clones from under it too.
+    let prefix = base.path().trim_matches('/');
+    if matches!(parsed.scheme(), "http" | "https") && !prefix.is_empty() {
+        return split_owner_repo(path.strip_prefix(prefix)?.trim_start_matches('/'));
+    }
+    split_owner_repo(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gitea_nwo_parses_expected_variants() {
+        let base = "https://git.example.com";
+        let nwo = Some(("owner".to_owned(), "repo".to_owned()));
+        assert_eq!(
+            parse_gitea_nwo("git@git.example.com:owner/repo.git", base),
+            nwo
+        );
+        assert_eq!(
+            parse_gitea_nwo("https://git.example.com/owner/repo.git", base),
+            nwo
+        );
+        assert_eq!(
+            parse_gitea_nwo("ssh://git@git.example.com:2222/owner/repo.git", base),
+            nwo
+        );
+        assert_eq!(
+            parse_gitea_nwo(
+                "https://git.example.com/gitea/owner/repo",
+                "https://git.example.com/gitea"
+            ),
+            nwo
+        );
+        assert_eq!(parse_gitea_nwo("git@github.com:owner/repo.git", base), None);
+        assert_eq!(
+            parse_gitea_nwo("https://git.example.com/group/sub/repo", base),
+            None
+        );
+        assert_eq!(parse_gitea_nwo("not-a-url", base), None);
+    }
+}
@@ -2,7 +2,10 @@ use std::collections::BTreeSet;
 use std::path::Path;
 
 use anyhow::{anyhow, Context as _};
-use murmur_core::issue::{Comment, CreateParams, Issue, ListFilter, Status, UpdateParams};
+use async_trait::async_trait;
+use murmur_core::issue::{
+    Capabilities, Comment, CreateParams, Issue, IssueBackend, ListFilter, Status, UpdateParams,
+};
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -10,6 +13,9 @@ use url::Url;
 
 use crate::git::Git;
 
+/// Connection page size; GitHub caps `first` at 100.
+const PAGE_SIZE: i64 = 100;
+
 #[derive(Debug, Clone)]
 pub struct GithubBackend {
     client: reqwest::Client,
@@ -18,6 +24,7 @@ pub struct GithubBackend {
     owner: String,
     repo: String,
     allowed_authors: Vec<String>,
+    ready_label: Option<String>,
 }
 
 impl GithubBackend {
@@ -58,9 +65,16 @@ impl GithubBackend {
             owner,
             repo,
             allowed_authors,
+            ready_label: None,
         })
     }
 
+    /// Limits `ready()` to open issues with `label`, filtered by GitHub.
+    pub fn with_ready_label(mut self, label: Option<String>) -> Self {
+        self.ready_label = label.filter(|s| !s.trim().is_empty());
+        self
+    }
+
     pub async fn from_repo(
         git: &Git,
         repo_dir: &Path,
@@ -145,9 +159,10 @@ impl GithubBackend {
         };
 
         let query = r#"
-            query ListIssues($owner: String!, $repo: String!, $states: [IssueState!], $first: Int!) {
+            query ListIssues($owner: String!, $repo: String!, $states: [IssueState!], $labels: [String!], $first: Int!, $after: String) {
                 repository(owner: $owner, name: $repo) {
-                    issues(states: $states, first: $first, orderBy: {field: UPDATED_AT, direction: DESC}) {
+                    issues(states: $states, labels: $labels, first: $first, after: $after, orderBy: {field: UPDATED_AT, direction: DESC}) {
+                        pageInfo { hasNextPage endCursor }
                         nodes {
                             id
                             number
@@ -164,41 +179,22 @@ impl GithubBackend {
             }
         "#;
 
-        let variables = if states.is_empty() {
-            serde_json::json!({
-                "owner": self.owner,
-                "repo": self.repo,
-                "first": 100,
-            })
-        } else {
-            serde_json::json!({
-                "owner": self.owner,
-                "repo": self.repo,
-                "states": states,
-                "first": 100,
-            })
-        };
-
-        #[derive(Debug, Deserialize)]
-        struct Data {
-            repository: Repository,
-        }
-        #[derive(Debug, Deserialize)]
-        struct Repository {
-            issues: IssueConn,
+        let mut variables = serde_json::Map::new();
+        if !states.is_empty() {
+            variables.insert("states".to_owned(), serde_json::json!(states));
         }
-        #[derive(Debug, Deserialize)]
-        struct IssueConn {
-            nodes: Vec<GithubIssue>,
+        // GitHub matches any of the labels; all of them are checked below.
+        if !filter.labels.is_empty() {
+            variables.insert("labels".to_owned(), serde_json::json!(filter.labels));
         }
 
-        let data: Data = self
-            .graphql(query, Some(variables), None)
+        let nodes = self
+            .issue_pages(query, variables)
             .await
             .context("github list issues")?;
 
         let mut out = Vec::new();
-        for iss in data.repository.issues.nodes {
+        for iss in nodes {
             if !filter.labels.is_empty() {
                 let labels: BTreeSet<&str> =
                     iss.labels.nodes.iter().map(|l| l.name.as_str()).collect();
@@ -220,9 +216,10 @@ impl GithubBackend {
             .collect();
 
         let query = r#"
-            query ListIssuesForReady($owner: String!, $repo: String!) {
+            query ListIssuesForReady($owner: String!, $repo: String!, $labels: [String!], $first: Int!, $after: String) {
                 repository(owner: $owner, name: $repo) {
-                    issues(states: [OPEN], first: 100, orderBy: {field: UPDATED_AT, direction: DESC}) {
+                    issues(states: [OPEN], labels: $labels, first: $first, after: $after, orderBy: {field: CREATED_AT, direction: ASC}) {
+                        pageInfo { hasNextPage endCursor }
                         nodes {
                             id
                             number
@@ -239,33 +236,18 @@ impl GithubBackend {
             }
         "#;
 
-        #[derive(Debug, Deserialize)]
-        struct Data {
-            repository: Repository,
-        }
-        #[derive(Debug, Deserialize)]
-        struct Repository {
-            issues: IssueConn,
-        }
-        #[derive(Debug, Deserialize)]
-        struct IssueConn {
-            nodes: Vec<GithubIssue>,
+        let mut variables = serde_json::Map::new();
+        if let Some(label) = self.ready_label.as_ref() {
+            variables.insert("labels".to_owned(), serde_json::json!([label]));
         }
 
-        let data: Data = self
-            .graphql(
-                query,
-                Some(serde_json::json!({
-                    "owner": self.owner,
-                    "repo": self.repo,
-                })),
-                None,
-            )
+        let nodes = self
+            .issue_pages(query, variables)
             .await
             .context("github ready query")?;
 
         let mut ready = Vec::new();
-        for gh in data.repository.issues.nodes {
+        for gh in nodes {
             let iss = to_issue(&gh);
             if iss.status == Status::Blocked {
                 continue;
@@ -588,10 +570,11 @@ impl GithubBackend {
         let num = parse_issue_number(id)?;
 
         let query = r#"
-            query GetIssueComments($owner: String!, $repo: String!, $number: Int!, $first: Int!) {
+            query GetIssueComments($owner: String!, $repo: String!, $number: Int!, $first: Int!, $after: String) {
                 repository(owner: $owner, name: $repo) {
                     issue(number: $number) {
-                        comments(first: $first, orderBy: {field: UPDATED_AT, direction: ASC}) {
+                        comments(first: $first, after: $after, orderBy: {field: UPDATED_AT, direction: ASC}) {
+                            pageInfo { hasNextPage endCursor }
                             nodes {
                                 id
                                 body
@@ -619,6 +602,8 @@ impl GithubBackend {
         #[derive(Debug, Deserialize)]
         struct CommentsConnection {
             nodes: Vec<CommentNode>,
+            #[serde(default, rename = "pageInfo")]
+            page_info: PageInfo,
         }
         #[derive(Debug, Deserialize)]
         struct CommentNode {
@@ -633,22 +618,34 @@ impl GithubBackend {
             login: String,
         }
 
-        let data: Data = self
-            .graphql(
-                query,
-                Some(serde_json::json!({
-                    "owner": self.owner,
-                    "repo": self.repo,
-                    "number": num,
-                    "first": 100,
-                })),
-                None,
-            )
-            .await
-            .context("github list comments")?;
+        let mut nodes = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data: Data = self
+                .graphql(
+                    query,
+                    Some(serde_json::json!({
+                        "owner": self.owner,
+                        "repo": self.repo,
+                        "number": num,
+                        "first": PAGE_SIZE,
+                        "after": after,
+                    })),
+                    None,
+                )
+                .await
+                .context("github list comments")?;
+
+            let conn = data.repository.issue.comments;
+            nodes.extend(conn.nodes);
+            match conn.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
 
         let mut comments = Vec::new();
-        for node in data.repository.issue.comments.nodes {
+        for node in nodes {
             let created_at_ms = parse_rfc3339_ms(&node.created_at).unwrap_or(0);
 
             // Filter by since_ms if provided
@@ -980,6 +977,59 @@ impl GithubBackend {
         Ok(data.repository.issue)
     }
 
+    /// Runs an issues query page by page. `query` takes `$owner`, `$repo`,
+    /// `$first` and `$after` and selects `repository.issues` with `pageInfo`.
+    async fn issue_pages(
+        &self,
+        query: &str,
+        mut variables: serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<Vec<GithubIssue>> {
+        #[derive(Debug, Deserialize)]
+        struct Data {
+            repository: Repository,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Repository {
+            issues: IssueConn,
+        }
+        #[derive(Debug, Deserialize)]
+        struct IssueConn {
+            nodes: Vec<GithubIssue>,
+            #[serde(default, rename = "pageInfo")]
+            page_info: PageInfo,
+        }
+
+        variables.insert("owner".to_owned(), serde_json::json!(self.owner));
+        variables.insert("repo".to_owned(), serde_json::json!(self.repo));
+        variables.insert("first".to_owned(), serde_json::json!(PAGE_SIZE));
+
+        let mut issues = Vec::new();
+        // Under `list`'s UPDATED_AT order, an issue updated mid-walk jumps to
+        // the first page, which was already read, so it is missed; and the
+        // issue it pushes across a page boundary comes back twice, which
+        // `seen` drops. `ready` orders by creation, which edits don't change.
+        let mut seen = BTreeSet::new();
+        let mut after: Option<String> = None;
+        loop {
+            variables.insert("after".to_owned(), serde_json::json!(after));
+            let data: Data = self
+                .graphql(
+                    query,
+                    Some(serde_json::Value::Object(variables.clone())),
+                    None,
+                )
+                .await?;
+
+            let conn = data.repository.issues;
+            issues.extend(conn.nodes.into_iter().filter(|i| seen.insert(i.number)));
+            match conn.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        Ok(issues)
+    }
+
     async fn graphql<T: DeserializeOwned>(
         &self,
         query: &str,
@@ -1039,6 +1089,68 @@ impl GithubBackend {
     }
 }
 
+#[async_trait]
+impl IssueBackend for GithubBackend {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: false,
+            // Set on create only, as "blocked by" relationships.
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        GithubBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        GithubBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        GithubBackend::ready(self).await
+    }
+
+    async fn create(&self, _now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        GithubBackend::create(self, params).await
+    }
+
+    async fn update(&self, _now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        GithubBackend::update(self, id, params).await
+    }
+
+    async fn close(&self, _now_ms: u64, id: &str) -> anyhow::Result<()> {
+        GithubBackend::close(self, id).await
+    }
+
+    async fn comment(&self, _now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        GithubBackend::comment(self, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        GithubBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+        GithubBackend::commit(self).await
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PageInfo {
+    #[serde(default, rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(default, rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    fn next_cursor(self) -> Option<String> {
+        self.end_cursor.filter(|_| self.has_next_page)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct GithubIssue {
     id: String,
@@ -1174,7 +1286,7 @@ pub fn parse_github_nwo(remote_url: &str) -> Option<(String, String)> {
     None
 }
 
-fn split_owner_repo(path: &str) -> Option<(String, String)> {
+pub(crate) fn split_owner_repo(path: &str) -> Option<(String, String)> {
     let path = path.trim_end_matches('/');
     let path = path.strip_suffix(".git").unwrap_or(path);
     let mut parts = path.split('/');
@@ -0,0 +1,959 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
+use murmur_core::issue::{
+    Capabilities, Comment, CreateParams, Issue, IssueBackend, ListFilter, Status, UpdateParams,
+};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use url::Url;
+
+use crate::git::Git;
+
+/// Items per page; GitLab caps `per_page` and `first` at 100.
+const PAGE_SIZE: usize = 100;
+
+/// Issues with their blockers inline (`BLOCKERS`), so listing costs one
+/// request per page rather than one per issue.
+const ISSUES_QUERY: &str = r#"
+    query Issues($project: ID!, $state: IssuableState, $labels: [String!], $sort: IssueSort, $first: Int!, $after: String) {
+        project(fullPath: $project) {
+            issues(state: $state, labelName: $labels, sort: $sort, first: $first, after: $after) {
+                pageInfo { hasNextPage endCursor }
+                nodes {
+                    iid
+                    projectId
+                    title
+                    description
+                    state
+                    createdAt
+                    author { username }
+                    labels(first: 100) { nodes { title } }
+                    BLOCKERS
+                }
+            }
+        }
+    }
+"#;
+
+/// Each issue's blockers, spliced into `ISSUES_QUERY`. Blocking links are an
+/// EE feature: the CE schema has no such field and rejects the query.
+const BLOCKERS_FIELD: &str = "blockedByIssues(first: 100) { nodes { iid state } }";
+
+/// Issues and merge requests of one GitLab project, over the REST API (v4).
+/// Issue lists go through GraphQL.
+#[derive(Debug, Clone)]
+pub struct GitlabBackend {
+    client: reqwest::Client,
+    api_url: String,
+    graphql_url: String,
+    token: String,
+    project: String,
+    allowed_authors: Vec<String>,
+    ready_label: Option<String>,
+}
+
+impl GitlabBackend {
+    /// `project` is the full path (`group/subgroup/repo`); `base_url` is the
+    /// instance root, e.g. `https://gitlab.com`.
+    pub fn new(
+        project: String,
+        token: String,
+        mut allowed_authors: Vec<String>,
+        base_url: String,
+    ) -> anyhow::Result<Self> {
+        let project = project.trim().trim_matches('/').to_owned();
+        let Some((namespace, _)) = project.split_once('/') else {
+            return Err(anyhow!("invalid gitlab project: {project}"));
+        };
+        if token.trim().is_empty() {
+            return Err(anyhow!("missing gitlab token"));
+        }
+
+        if allowed_authors.is_empty() {
+            allowed_authors.push(namespace.to_owned());
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let client = reqwest::Client::builder()
+            .user_agent(format!("murmur/{}", env!("CARGO_PKG_VERSION")))
+            .default_headers(headers)
+            .build()
+            .context("build reqwest client")?;
+
+        let base_url = base_url.trim().trim_end_matches('/');
+        Ok(Self {
+            client,
+            api_url: format!("{base_url}/api/v4"),
+            graphql_url: format!("{base_url}/api/graphql"),
+            token,
+            project,
+            allowed_authors,
+            ready_label: None,
+        })
+    }
+
+    /// Limits `ready()` to open issues with `label`, filtered by GitLab.
+    pub fn with_ready_label(mut self, label: Option<String>) -> Self {
+        self.ready_label = label.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    pub async fn from_repo(
+        git: &Git,
+        repo_dir: &Path,
+        token: String,
+        allowed_authors: Vec<String>,
+        base_url: String,
+    ) -> anyhow::Result<Self> {
+        let remote = git
+            .remote_origin_url(repo_dir)
+            .await
+            .context("git remote get-url origin")?;
+
+        let project = parse_gitlab_project(&remote, &base_url)
+            .ok_or_else(|| anyhow!("not a gitlab remote for {base_url}: {remote}"))?;
+
+        Self::new(project, token, allowed_authors, base_url)
+    }
+
+    pub fn project_path(&self) -> &str {
+        &self.project
+    }
+
+    pub async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        let iid = parse_issue_iid(id)?;
+        let gl: GitlabIssue = self
+            .request(Method::GET, &format!("issues/{iid}"), None)
+            .await
+            .context("gitlab get issue")?;
+        let links = self.blocked_by(iid).await?;
+        Ok(to_issue(&gl, &links))
+    }
+
+    pub async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        let states: BTreeSet<&str> = filter
+            .status
+            .iter()
+            .map(|s| match s {
+                Status::Open | Status::Blocked => "opened",
+                Status::Closed => "closed",
+            })
+            .collect();
+        let state = match states.len() {
+            1 => states.into_iter().next().unwrap_or("all"),
+            _ => "all",
+        };
+
+        let issues = self
+            .issue_pages(state, &filter.labels, "UPDATED_DESC")
+            .await
+            .context("gitlab list issues")?;
+        Ok(issues
+            .iter()
+            .map(|(gl, links)| to_issue(gl, links))
+            .collect())
+    }
+
+    pub async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        let allowed: BTreeSet<String> = self
+            .allowed_authors
+            .iter()
+            .map(|s| s.trim().to_ascii_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        // Creation order does not change while paging, so no issue is skipped.
+        let labels: Vec<String> = self.ready_label.iter().cloned().collect();
+        let issues = self
+            .issue_pages("opened", &labels, "CREATED_ASC")
+            .await
+            .context("gitlab ready query")?;
+
+        let mut ready = Vec::new();
+        for (gl, links) in issues {
+            if gl.labels.iter().any(|l| l == "blocked") {
+                continue;
+            }
+
+            if !allowed.is_empty() {
+                let is_allowed = gl
+                    .author
+                    .as_ref()
+                    .is_some_and(|a| allowed.contains(&a.username.trim().to_ascii_lowercase()));
+                if !is_allowed {
+                    continue;
+                }
+            }
+
+            if links.iter().any(|l| l.state == "opened") {
+                continue;
+            }
+
+            ready.push(to_issue(&gl, &links));
+        }
+
+        Ok(ready)
+    }
+
+    pub async fn create(&self, params: CreateParams) -> anyhow::Result<Issue> {
+        if !params.links.is_empty() {
+            return Err(anyhow!("gitlab backend does not support `links`"));
+        }
+
+        let issue_type = if params.issue_type.trim().is_empty() {
+            "task".to_owned()
+        } else {
+            params.issue_type
+        };
+
+        let mut labels = Vec::new();
+        labels.push(format!("type:{issue_type}"));
+        labels.push(format!("priority:{}", params.priority));
+        labels.extend(params.labels);
+
+        let mut body = serde_json::Map::new();
+        body.insert("title".to_owned(), serde_json::Value::String(params.title));
+        if !params.description.trim().is_empty() {
+            body.insert(
+                "description".to_owned(),
+                serde_json::Value::String(params.description),
+            );
+        }
+        body.insert(
+            "labels".to_owned(),
+            serde_json::Value::String(labels.join(",")),
+        );
+
+        let created: GitlabIssue = self
+            .request(
+                Method::POST,
+                "issues",
+                Some(serde_json::Value::Object(body)),
+            )
+            .await
+            .context("gitlab create issue")?;
+
+        for dep in params.dependencies {
+            if let Err(err) = self.add_blocked_by(&created, &dep).await {
+                tracing::warn!(dep = %dep, error = %err, "failed to add gitlab dependency");
+            }
+        }
+
+        let links = self.blocked_by(created.iid).await.unwrap_or_default();
+        Ok(to_issue(&created, &links))
+    }
+
+    pub async fn update(&self, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        if params.dependencies.is_some() {
+            return Err(anyhow!(
+                "gitlab backend does not support updating dependencies"
+            ));
+        }
+        if params.links.is_some() {
+            return Err(anyhow!("gitlab backend does not support `links`"));
+        }
+
+        let UpdateParams {
+            title,
+            description,
+            status,
+            priority,
+            issue_type,
+            labels,
+            dependencies: _,
+            links: _,
+        } = params;
+
+        let iid = parse_issue_iid(id)?;
+        let current: GitlabIssue = self
+            .request(Method::GET, &format!("issues/{iid}"), None)
+            .await
+            .context("gitlab get issue for update")?;
+        let current_issue = to_issue(&current, &[]);
+
+        let mut body = serde_json::Map::new();
+        if let Some(v) = title {
+            body.insert("title".to_owned(), serde_json::Value::String(v));
+        }
+        if let Some(v) = description {
+            body.insert("description".to_owned(), serde_json::Value::String(v));
+        }
+
+        if let Some(status) = status {
+            let state_event = match status {
+                Status::Closed if current.state != "closed" => Some("close"),
+                Status::Open | Status::Blocked if current.state == "closed" => Some("reopen"),
+                _ => None,
+            };
+            if let Some(v) = state_event {
+                body.insert(
+                    "state_event".to_owned(),
+                    serde_json::Value::String(v.to_owned()),
+                );
+            }
+        }
+
+        let labels_provided = labels.is_some();
+        if labels_provided || issue_type.is_some() || priority.is_some() || status.is_some() {
+            let mut new_labels = if let Some(v) = labels {
+                v
+            } else {
+                current_issue.labels.clone()
+            };
+
+            if let Some(v) = issue_type {
+                new_labels.retain(|l| !l.starts_with("type:"));
+                new_labels.push(format!("type:{v}"));
+            } else if !current_issue.issue_type.trim().is_empty() && !labels_provided {
+                new_labels.push(format!("type:{}", current_issue.issue_type));
+            }
+
+            if let Some(v) = priority {
+                new_labels.retain(|l| !l.starts_with("priority:"));
+                new_labels.push(format!("priority:{v}"));
+            } else if !labels_provided {
+                new_labels.push(format!("priority:{}", current_issue.priority));
+            }
+
+            let blocked = match status {
+                Some(status) => status == Status::Blocked,
+                None => current_issue.status == Status::Blocked,
+            };
+            new_labels.retain(|l| l != "blocked");
+            if blocked {
+                new_labels.push("blocked".to_owned());
+            }
+
+            body.insert(
+                "labels".to_owned(),
+                serde_json::Value::String(new_labels.join(",")),
+            );
+        }
+
+        let updated: GitlabIssue = self
+            .request(
+                Method::PUT,
+                &format!("issues/{iid}"),
+                Some(serde_json::Value::Object(body)),
+            )
+            .await
+            .context("gitlab update issue")?;
+
+        let links = self.blocked_by(iid).await?;
+        Ok(to_issue(&updated, &links))
+    }
+
+    pub async fn close(&self, id: &str) -> anyhow::Result<()> {
+        let _ = self
+            .update(
+                id,
+                UpdateParams {
+                    status: Some(Status::Closed),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn comment(&self, id: &str, body: &str) -> anyhow::Result<()> {
+        let iid = parse_issue_iid(id)?;
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issues/{iid}/notes"),
+                Some(serde_json::json!({ "body": body })),
+            )
+            .await
+            .context("gitlab add comment")?;
+        Ok(())
+    }
+
+    /// List comments on an issue, optionally filtering to those created after `since_ms`.
+    /// System notes (label changes, state changes, ...) are skipped.
+    pub async fn list_comments(
+        &self,
+        id: &str,
+        since_ms: Option<u64>,
+    ) -> anyhow::Result<Vec<Comment>> {
+        let iid = parse_issue_iid(id)?;
+
+        #[derive(Debug, Deserialize)]
+        struct Note {
+            id: i64,
+            #[serde(default)]
+            body: String,
+            created_at: String,
+            #[serde(default)]
+            author: Option<GitlabAuthor>,
+            #[serde(default)]
+            system: bool,
+        }
+
+        let notes: Vec<Note> = self
+            .pages(&format!("issues/{iid}/notes?sort=asc&order_by=created_at"))
+            .await
+            .context("gitlab list comments")?;
+
+        let mut comments = Vec::new();
+        for note in notes {
+            if note.system {
+                continue;
+            }
+            let created_at_ms = parse_rfc3339_ms(&note.created_at).unwrap_or(0);
+            if let Some(since) = since_ms {
+                if created_at_ms <= since {
+                    continue;
+                }
+            }
+            comments.push(Comment {
+                id: note.id.to_string(),
+                author: note.author.map(|a| a.username).unwrap_or_default(),
+                body: note.body,
+                created_at_ms,
+            });
+        }
+
+        Ok(comments)
+    }
+
+    /// Opens a merge request from `source_branch` into `target_branch` and
+    /// returns its URL.
+    pub async fn create_merge_request(
+        &self,
+        target_branch: &str,
+        source_branch: &str,
+        title: &str,
+        description: &str,
+    ) -> anyhow::Result<String> {
+        let target = target_branch.trim();
+        let source = source_branch.trim();
+        if target.is_empty() || source.is_empty() {
+            return Err(anyhow!("merge request source/target is empty"));
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct MergeRequest {
+            web_url: String,
+        }
+
+        let mr: MergeRequest = self
+            .request(
+                Method::POST,
+                "merge_requests",
+                Some(serde_json::json!({
+                    "source_branch": source,
+                    "target_branch": target,
+                    "title": title,
+                    "description": description,
+                })),
+            )
+            .await
+            .context("gitlab create merge request")?;
+
+        Ok(mr.web_url)
+    }
+
+    pub async fn commit(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Issues linked to `iid` as blocking it.
+    async fn blocked_by(&self, iid: i64) -> anyhow::Result<Vec<GitlabLink>> {
+        let links: Vec<GitlabLink> = self
+            .request(Method::GET, &format!("issues/{iid}/links"), None)
+            .await
+            .context("gitlab list issue links")?;
+        Ok(links
+            .into_iter()
+            .filter(|l| l.link_type == "is_blocked_by")
+            .collect())
+    }
+
+    async fn add_blocked_by(&self, blocked: &GitlabIssue, blocking: &str) -> anyhow::Result<()> {
+        let blocking_iid = parse_issue_iid(blocking)?;
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issues/{}/links", blocked.iid),
+                Some(serde_json::json!({
+                    "target_project_id": blocked.project_id,
+                    "target_issue_iid": blocking_iid,
+                    "link_type": "is_blocked_by",
+                })),
+            )
+            .await
+            .context("gitlab add blocked-by link")?;
+        Ok(())
+    }
+
+    /// Runs `ISSUES_QUERY` page by page; each issue comes with the issues
+    /// blocking it. On CE, which cannot block issues, it runs without
+    /// `BLOCKERS_FIELD` and every issue comes with none.
+    async fn issue_pages(
+        &self,
+        state: &str,
+        labels: &[String],
+        sort: &str,
+    ) -> anyhow::Result<Vec<(GitlabIssue, Vec<GitlabLink>)>> {
+        #[derive(Debug, Deserialize)]
+        struct Data {
+            project: Option<Project>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Project {
+            issues: IssueConn,
+        }
+        #[derive(Debug, Deserialize)]
+        struct IssueConn {
+            nodes: Vec<GraphqlIssue>,
+            #[serde(default, rename = "pageInfo")]
+            page_info: PageInfo,
+        }
+
+        let mut variables = serde_json::json!({
+            "project": self.project,
+            "state": state,
+            "sort": sort,
+            "first": PAGE_SIZE,
+        });
+        if !labels.is_empty() {
+            variables["labels"] = serde_json::json!(labels);
+        }
+
+        let mut query = ISSUES_QUERY.replace("BLOCKERS", BLOCKERS_FIELD);
+        let mut blockers = true;
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            variables["after"] = serde_json::json!(after);
+            let data: Data = match self.graphql(&query, variables.clone()).await {
+                Ok(data) => data,
+                Err(err) if blockers && err.to_string().contains("blockedByIssues") => {
+                    tracing::debug!(error = %err, "gitlab without blocking links");
+                    query = ISSUES_QUERY.replace("BLOCKERS", "");
+                    blockers = false;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+            let Some(project) = data.project else {
+                return Err(anyhow!("gitlab project not found: {}", self.project));
+            };
+
+            let conn = project.issues;
+            for node in conn.nodes {
+                issues.push(node.into_rest()?);
+            }
+            match conn.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> anyhow::Result<T> {
+        #[derive(Debug, Deserialize)]
+        struct GraphqlResponse<T> {
+            data: Option<T>,
+            #[serde(default)]
+            errors: Vec<GraphqlError>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct GraphqlError {
+            message: String,
+        }
+
+        let resp = self
+            .client
+            .post(&self.graphql_url)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.token),
+            )
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .context("send request")?;
+        let status = resp.status();
+        let text = resp.text().await.context("read response")?;
+
+        if !status.is_success() {
+            return Err(anyhow!("gitlab api error ({status}): {text}"));
+        }
+
+        let parsed: GraphqlResponse<T> =
+            serde_json::from_str(&text).context("parse graphql response")?;
+        if let Some(first) = parsed.errors.first() {
+            return Err(anyhow!("gitlab graphql error: {}", first.message));
+        }
+        parsed
+            .data
+            .ok_or_else(|| anyhow!("gitlab graphql response missing data"))
+    }
+
+    /// GETs every page of a list endpoint.
+    async fn pages<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<Vec<T>> {
+        let sep = if path.contains('?') { '&' } else { '?' };
+        let mut out = Vec::new();
+        for page in 1.. {
+            let items: Vec<T> = self
+                .request(
+                    Method::GET,
+                    &format!("{path}{sep}per_page={PAGE_SIZE}&page={page}"),
+                    None,
+                )
+                .await?;
+            let last = items.len() < PAGE_SIZE;
+            out.extend(items);
+            if last {
+                break;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Sends a request to `projects/:project/<path>`.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<T> {
+        let url = format!("{}/projects/{}/{path}", self.api_url, encode(&self.project));
+
+        let mut req = self
+            .client
+            .request(method, &url)
+            .header("PRIVATE-TOKEN", &self.token);
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+
+        let resp = req.send().await.context("send request")?;
+        let status = resp.status();
+        let text = resp.text().await.context("read response")?;
+
+        if !status.is_success() {
+            return Err(anyhow!("gitlab api error ({status}): {text}"));
+        }
+
+        serde_json::from_str(&text).context("parse gitlab response")
+    }
+}
+
+#[async_trait]
+impl IssueBackend for GitlabBackend {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: false,
+            // Set on create only, as "is blocked by" issue links.
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        GitlabBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        GitlabBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        GitlabBackend::ready(self).await
+    }
+
+    async fn create(&self, _now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        GitlabBackend::create(self, params).await
+    }
+
+    async fn update(&self, _now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        GitlabBackend::update(self, id, params).await
+    }
+
+    async fn close(&self, _now_ms: u64, id: &str) -> anyhow::Result<()> {
+        GitlabBackend::close(self, id).await
+    }
+
+    async fn comment(&self, _now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        GitlabBackend::comment(self, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        GitlabBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+        GitlabBackend::commit(self).await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabIssue {
+    iid: i64,
+    project_id: i64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+    created_at: String,
+    #[serde(default)]
+    author: Option<GitlabAuthor>,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabAuthor {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabLink {
+    iid: i64,
+    state: String,
+    link_type: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PageInfo {
+    #[serde(default, rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(default, rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    fn next_cursor(self) -> Option<String> {
+        self.end_cursor.filter(|_| self.has_next_page)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+/// An issue as GraphQL returns it: `iid` is a string and labels and blockers
+/// are connections.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphqlIssue {
+    iid: String,
+    project_id: i64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+    created_at: String,
+    #[serde(default)]
+    author: Option<GitlabAuthor>,
+    #[serde(default)]
+    labels: Option<Nodes<GraphqlLabel>>,
+    #[serde(default)]
+    blocked_by_issues: Option<Nodes<GraphqlBlocker>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlLabel {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphqlBlocker {
+    iid: String,
+    state: String,
+}
+
+impl GraphqlIssue {
+    /// The REST shapes, with the blockers as "is blocked by" links.
+    fn into_rest(self) -> anyhow::Result<(GitlabIssue, Vec<GitlabLink>)> {
+        let links = self
+            .blocked_by_issues
+            .map(|b| b.nodes)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|b| {
+                Ok(GitlabLink {
+                    iid: parse_issue_iid(&b.iid)?,
+                    state: b.state,
+                    link_type: "is_blocked_by".to_owned(),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let issue = GitlabIssue {
+            iid: parse_issue_iid(&self.iid)?,
+            project_id: self.project_id,
+            title: self.title,
+            description: self.description,
+            state: self.state,
+            created_at: self.created_at,
+            author: self.author,
+            labels: self
+                .labels
+                .map(|l| l.nodes.into_iter().map(|l| l.title).collect())
+                .unwrap_or_default(),
+        };
+        Ok((issue, links))
+    }
+}
+
+fn to_issue(gl: &GitlabIssue, blocked_by: &[GitlabLink]) -> Issue {
+    let created_at_ms = parse_rfc3339_ms(&gl.created_at).unwrap_or(0);
+
+    let mut issue_type = String::new();
+    let mut priority = 0;
+    let mut labels = Vec::new();
+
+    let mut status = if gl.state == "closed" {
+        Status::Closed
+    } else {
+        Status::Open
+    };
+
+    for name in &gl.labels {
+        if let Some(rest) = name.strip_prefix("type:") {
+            issue_type = rest.to_owned();
+            continue;
+        }
+        if let Some(rest) = name.strip_prefix("priority:") {
+            priority = rest.parse::<i32>().unwrap_or(priority);
+            continue;
+        }
+        if name == "blocked" {
+            status = Status::Blocked;
+            continue;
+        }
+        labels.push(name.clone());
+    }
+
+    if issue_type.is_empty() {
+        issue_type = "task".to_owned();
+    }
+
+    Issue {
+        id: gl.iid.to_string(),
+        title: gl.title.clone(),
+        description: gl.description.clone().unwrap_or_default(),
+        status,
+        priority,
+        issue_type,
+        dependencies: blocked_by.iter().map(|l| l.iid.to_string()).collect(),
+        labels,
+        links: vec![],
+        created_at_ms,
+    }
+}
+
+fn parse_rfc3339_ms(s: &str) -> anyhow::Result<u64> {
+    let dt = time::OffsetDateTime::parse(s.trim(), &time::format_description::well_known::Rfc3339)
+        .map_err(|e| anyhow!("parse rfc3339: {e}"))?;
+    Ok(dt.unix_timestamp_nanos() as u64 / 1_000_000)
+}
+
+fn parse_issue_iid(s: &str) -> anyhow::Result<i64> {
+    let trimmed = s.trim().trim_start_matches('#');
+    if trimmed.is_empty() {
+        return Err(anyhow!("invalid issue id: empty"));
+    }
+    trimmed
+        .parse::<i64>()
+        .map_err(|_| anyhow!("invalid gitlab issue iid: {trimmed}"))
+}
+
+fn encode(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
+
+/// The project path of `remote_url` if it lives on the GitLab instance at
+/// `base_url`. Only hosts are compared, so SSH remotes match an HTTPS base.
+pub fn parse_gitlab_project(remote_url: &str, base_url: &str) -> Option<String> {
+    let base = Url::parse(base_url.trim()).ok()?;
+    let host = base.host_str()?;
+    let url = remote_url.trim();
+
+    let path = if let Some(rest) = url.strip_prefix("git@") {
+        let (remote_host, path) = rest.split_once(':')?;
+        if remote_host != host {
+            return None;
+        }
+        path.to_owned()
+    } else {
+        let parsed = Url::parse(url).ok()?;
+        if parsed.host_str()? != host {
+            return None;
+        }
+        let path = parsed.path().trim_start_matches('/');
+        // An instance served under a path prefix clones from under it too.
+        let prefix = base.path().trim_matches('/');
+        if matches!(parsed.scheme(), "http" | "https") && !prefix.is_empty() {
+            path.strip_prefix(prefix)?
+                .trim_start_matches('/')
+                .to_owned()
+        } else {
+            path.to_owned()
+        }
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').collect();
+    if segments.len() < 2 || segments.iter().any(|s| s.trim().is_empty()) {
+        return None;
+    }
+    Some(path.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gitlab_project_parses_expected_variants() {
+        let base = "https://gitlab.com";
+        assert_eq!(
+            parse_gitlab_project("git@gitlab.com:group/repo.git", base).as_deref(),
+            Some("group/repo")
+        );
+        assert_eq!(
+            parse_gitlab_project("https://gitlab.com/group/sub/repo.git", base).as_deref(),
+            Some("group/sub/repo")
+        );
+        assert_eq!(
+            parse_gitlab_project("ssh://git@gitlab.com:2222/group/repo.git", base).as_deref(),
+            Some("group/repo")
+        );
+        assert_eq!(
+            parse_gitlab_project(
+                "https://git.example.com/gitlab/group/repo",
+                "https://git.example.com/gitlab/"
+            )
+            .as_deref(),
+            Some("group/repo")
+        );
+        assert_eq!(
+            parse_gitlab_project("git@github.com:owner/repo.git", base),
+            None
+        );
+        assert_eq!(parse_gitlab_project("https://gitlab.com/repo", base), None);
+        assert_eq!(parse_gitlab_project("not-a-url", base), None);
+    }
+}
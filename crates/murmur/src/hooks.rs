@@ -1,17 +1,22 @@
 use std::collections::BTreeMap;
 use std::env;
 use std::io::Read as _;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Context as _};
 use directories::BaseDirs;
+use murmur_core::agent::AgentRole;
+use murmur_core::config::DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS;
 use murmur_core::paths::MurmurPaths;
-use murmur_core::permissions::{evaluate_rules, Action};
+use murmur_core::permission_log::{Decider, PermissionLogEntry};
+use murmur_core::permissions::{resolve_primary_field, Action};
 use murmur_protocol::{
     PermissionBehavior, PermissionRequestPayload, QuestionItem, UserQuestionRequestPayload,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{client, permissions};
+use crate::{client, config_store, confinement, permission_log, permissions};
 
 #[derive(Debug, Deserialize)]
 struct HookInput {
@@ -59,7 +64,9 @@ struct AskUserQuestionInput {
 }
 
 pub async fn handle_pre_tool_use(paths: &MurmurPaths) -> anyhow::Result<()> {
-    let hook_input = read_hook_input()?;
+    let raw_input = read_hook_input_json()?;
+    let hook_input: HookInput =
+        serde_json::from_value(raw_input.clone()).context("parse hook input JSON")?;
 
     if hook_input.tool_name == "AskUserQuestion" {
         return handle_ask_user_question(paths, hook_input).await;
@@ -75,21 +82,45 @@ pub async fn handle_pre_tool_use(paths: &MurmurPaths) -> anyhow::Result<()> {
         .ok()
         .map(|s| s.trim().to_owned())
         .filter(|s| !s.is_empty());
-    let rules = permissions::load_rules(paths, project.as_deref()).await?;
 
-    let (action, matched) = evaluate_rules(
+    if let Some(reason) =
+        confinement_denial(paths, &hook_input, project.as_deref(), &home_dir).await?
+    {
+        let decision = permissions::Decision {
+            action: Action::Deny,
+            matched: true,
+            reason: Some(reason),
+            rules: Vec::new(),
+        };
+        log_rule_decision(paths, &hook_input, project.as_deref(), &decision).await;
+        return output_pre_tool_use("deny", decision.reason.as_deref(), None);
+    }
+
+    let rules = permissions::load_rules(paths, project.as_deref(), agent_role_from_env()).await?;
+
+    let decision = permissions::evaluate(
         &rules,
         &hook_input.tool_name,
         &hook_input.tool_input,
         &hook_input.cwd,
         &home_dir,
-    );
+        &raw_input,
+    )
+    .await;
+
+    if decision.matched && decision.action != Action::Pass {
+        log_rule_decision(paths, &hook_input, project.as_deref(), &decision).await;
+    }
 
-    if matched {
-        match action {
-            Action::Allow => return output_pre_tool_use("allow", None, None),
+    if decision.matched {
+        match decision.action {
+            Action::Allow => return output_pre_tool_use("allow", decision.reason.as_deref(), None),
             Action::Deny => {
-                return output_pre_tool_use("deny", Some("blocked by permission rule"), None);
+                let reason = decision
+                    .reason
+                    .as_deref()
+                    .unwrap_or("blocked by permission rule");
+                return output_pre_tool_use("deny", Some(reason), None);
             }
             Action::Pass => {}
         }
@@ -105,6 +136,13 @@ pub async fn handle_pre_tool_use(paths: &MurmurPaths) -> anyhow::Result<()> {
         Some(tool_use_id)
     };
 
+    // Claude stops the hook after the timeout the daemon gave it, which
+    // leaves room for the project's permission-timeout-secs.
+    let wait_secs = config_store::load(paths)
+        .await?
+        .project(project.as_deref().unwrap_or_default())
+        .map(|p| p.permission_hook_timeout_secs())
+        .unwrap_or(DEFAULT_PERMISSION_HOOK_TIMEOUT_SECS);
     let resp = match client::permission_request(
         paths,
         PermissionRequestPayload {
@@ -113,6 +151,7 @@ pub async fn handle_pre_tool_use(paths: &MurmurPaths) -> anyhow::Result<()> {
             tool_input: hook_input.tool_input,
             tool_use_id,
         },
+        Duration::from_secs(wait_secs),
     )
     .await
     {
@@ -138,6 +177,92 @@ pub async fn handle_stop(paths: &MurmurPaths) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Checks a file-writing call against the project's confinement policy.
+/// The worktree is `MURMUR_WORKTREE_DIR`, or the hook's `cwd` for agents
+/// started without it.
+async fn confinement_denial(
+    paths: &MurmurPaths,
+    hook_input: &HookInput,
+    project: Option<&str>,
+    home_dir: &str,
+) -> anyhow::Result<Option<String>> {
+    if confinement::target_path(&hook_input.tool_name, &hook_input.tool_input).is_none() {
+        return Ok(None);
+    }
+    let Some(worktree_dir) = env::var("MURMUR_WORKTREE_DIR")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .or_else(|| Some(hook_input.cwd.clone()).filter(|s| !s.trim().is_empty()))
+    else {
+        return Ok(None);
+    };
+
+    let config = config_store::load(paths).await?;
+    let policy = project
+        .and_then(|name| config.project(name))
+        .map(|p| p.effective_confinement())
+        .unwrap_or_default();
+    if !policy.enabled {
+        return Ok(None);
+    }
+
+    let worktree_dir = Path::new(&worktree_dir);
+    let cwd = match hook_input.cwd.trim() {
+        "" => worktree_dir,
+        cwd => Path::new(cwd),
+    };
+    let allowed = confinement::allowed_dirs(worktree_dir, &policy, Path::new(home_dir));
+    let reason = confinement::check(
+        &hook_input.tool_name,
+        &hook_input.tool_input,
+        cwd,
+        &allowed,
+    )
+    .map(|reason| match project {
+        Some(project) => format!(
+            "{reason} (set write-allowed-dirs or write-confinement for project {project} to change this)"
+        ),
+        None => reason,
+    });
+    Ok(reason)
+}
+
+/// Records a decision made by the rules. The hook still answers if the log
+/// cannot be written.
+async fn log_rule_decision(
+    paths: &MurmurPaths,
+    hook_input: &HookInput,
+    project: Option<&str>,
+    decision: &permissions::Decision,
+) {
+    let rules = decision.rules.join("; ");
+    let rationale = match decision.reason.as_deref() {
+        Some(reason) if rules.is_empty() => reason.to_owned(),
+        Some(reason) => format!("{rules}: {reason}"),
+        None => rules,
+    };
+    let entry = PermissionLogEntry {
+        at_ms: now_ms(),
+        agent_id: agent_id_from_env().unwrap_or_default(),
+        project: project.unwrap_or_default().to_owned(),
+        tool_name: hook_input.tool_name.clone(),
+        primary_field: resolve_primary_field(&hook_input.tool_name, &hook_input.tool_input),
+        action: decision.action,
+        decider: Decider::Rule,
+        rationale: Some(rationale).filter(|s| !s.is_empty()),
+    };
+    if let Err(err) = permission_log::append(paths, &entry).await {
+        eprintln!("murmur: could not write permission log: {err:#}");
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 fn agent_id_from_env() -> Option<String> {
     env::var("MURMUR_AGENT_ID")
         .ok()
@@ -145,7 +270,16 @@ fn agent_id_from_env() -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
-fn read_hook_input() -> anyhow::Result<HookInput> {
+/// The agent's role from `MURMUR_AGENT_ROLE`. Agents started without it are
+/// coding agents.
+fn agent_role_from_env() -> AgentRole {
+    env::var("MURMUR_AGENT_ROLE")
+        .ok()
+        .and_then(|s| AgentRole::parse(&s))
+        .unwrap_or(AgentRole::Coding)
+}
+
+fn read_hook_input_json() -> anyhow::Result<serde_json::Value> {
     let mut buf = String::new();
     std::io::stdin()
         .read_to_string(&mut buf)
@@ -207,6 +207,8 @@ impl Manager {
             &settings_json,
         ])
         .env("MURMUR_AGENT_ID", &self.config.agent_id)
+        .env("MURMUR_AGENT_ROLE", self.config.role.as_str())
+        .env("MURMUR_WORKTREE_DIR", &self.config.worktree)
         .env("MURMUR_DIR", &self.config.murmur_dir)
         .env("MURMUR_PROJECT", &self.config.project)
         .env("MURMUR_SOCKET_PATH", &self.config.socket_path)
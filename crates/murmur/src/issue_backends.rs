@@ -0,0 +1,396 @@
+//! Issue backends a project's `issue-backend` can name.
+//!
+//! The daemon opens backends through a [`Registry`]. It starts with the
+//! built-in `tk`, `github` (alias `gh`), `gitlab`, `gitea` (alias `forgejo`),
+//! `jira` and `linear`; a binary embedding the
+//! daemon can register its own with [`Registry::register`] and pass the
+//! registry to [`crate::daemon::run_foreground_with_backends`].
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use murmur_core::config::{ConfigFile, ProjectConfig};
+use murmur_core::issue::IssueBackend;
+
+use crate::git::Git;
+use crate::gitea::{DependencyCache, GiteaBackend};
+use crate::github::GithubBackend;
+use crate::gitlab::GitlabBackend;
+use crate::issues::TkBackend;
+use crate::jira::JiraBackend;
+use crate::linear::LinearBackend;
+use crate::providers;
+
+/// What a factory gets to open a project's backend.
+pub struct BackendContext<'a> {
+    pub git: &'a Git,
+    /// The project's clone of its remote.
+    pub repo_dir: PathBuf,
+    pub project: ProjectConfig,
+    /// Snapshot of the whole config, for `[providers.*]` credentials.
+    pub config: Arc<ConfigFile>,
+}
+
+/// Opens a backend for one project.
+#[async_trait]
+pub trait BackendFactory: Send + Sync {
+    async fn open<'a>(&self, ctx: BackendContext<'a>)
+        -> anyhow::Result<Box<dyn IssueBackend + 'a>>;
+}
+
+/// Backend factories by name. The default registry holds the built-in
+/// backends.
+#[derive(Clone)]
+pub struct Registry {
+    factories: BTreeMap<String, Arc<dyn BackendFactory>>,
+}
+
+impl Registry {
+    /// A registry without even the built-in backends.
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a backend under `name` (case-insensitive), replacing any
+    /// registered under that name before.
+    pub fn register(&mut self, name: &str, factory: impl BackendFactory + 'static) -> &mut Self {
+        self.factories
+            .insert(name.trim().to_ascii_lowercase(), Arc::new(factory));
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories
+            .contains_key(&name.trim().to_ascii_lowercase())
+    }
+
+    /// Registered names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Opens the backend registered as `name`.
+    pub async fn open<'a>(
+        &self,
+        name: &str,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(factory) = self.factories.get(&name.trim().to_ascii_lowercase()) else {
+            return Err(anyhow!(
+                "unknown issue backend: {name} (registered: {})",
+                self.names().join(", ")
+            ));
+        };
+        factory.open(ctx).await
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let gitea = GiteaFactory::default();
+        let mut registry = Self::empty();
+        registry
+            .register("tk", TkFactory)
+            .register("github", GithubFactory)
+            .register("gh", GithubFactory)
+            .register("gitlab", GitlabFactory)
+            .register("gitea", gitea.clone())
+            .register("forgejo", gitea)
+            .register("jira", JiraFactory)
+            .register("linear", LinearFactory);
+        registry
+    }
+}
+
+struct TkFactory;
+
+#[async_trait]
+impl BackendFactory for TkFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let backend = TkBackend::new(ctx.git, ctx.repo_dir).await?;
+        Ok(Box::new(backend))
+    }
+}
+
+struct GithubFactory;
+
+#[async_trait]
+impl BackendFactory for GithubFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(token) = providers::github_token(&ctx.config) else {
+            return Err(anyhow!(
+                "github token not set (set GITHUB_TOKEN/GH_TOKEN or [providers.github].token)"
+            ));
+        };
+        let backend = GithubBackend::from_repo(
+            ctx.git,
+            &ctx.repo_dir,
+            token,
+            ctx.project.allowed_authors,
+            providers::github_graphql_url(&ctx.config),
+        )
+        .await?
+        .with_ready_label(ctx.project.ready_label);
+        Ok(Box::new(backend))
+    }
+}
+
+struct GitlabFactory;
+
+#[async_trait]
+impl BackendFactory for GitlabFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(token) = providers::gitlab_token(&ctx.config) else {
+            return Err(anyhow!(
+                "gitlab token not set (set GITLAB_TOKEN or [providers.gitlab].token)"
+            ));
+        };
+        let backend = GitlabBackend::from_repo(
+            ctx.git,
+            &ctx.repo_dir,
+            token,
+            ctx.project.allowed_authors,
+            providers::gitlab_url(&ctx.config),
+        )
+        .await?
+        .with_ready_label(ctx.project.ready_label);
+        Ok(Box::new(backend))
+    }
+}
+
+/// Holds the dependency cache, which outlives the backend opened each tick.
+#[derive(Clone, Default)]
+struct GiteaFactory {
+    dependencies: DependencyCache,
+}
+
+#[async_trait]
+impl BackendFactory for GiteaFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(base_url) = providers::gitea_url(&ctx.config) else {
+            return Err(anyhow!(
+                "gitea url not set (set GITEA_URL or [providers.gitea].url)"
+            ));
+        };
+        let Some(token) = providers::gitea_token(&ctx.config) else {
+            return Err(anyhow!(
+                "gitea token not set (set GITEA_TOKEN or [providers.gitea].token)"
+            ));
+        };
+        let backend = GiteaBackend::from_repo(
+            ctx.git,
+            &ctx.repo_dir,
+            token,
+            ctx.project.allowed_authors,
+            base_url,
+        )
+        .await?
+        .with_ready_label(ctx.project.ready_label)
+        .with_dependency_cache(self.dependencies.clone());
+        Ok(Box::new(backend))
+    }
+}
+
+struct JiraFactory;
+
+#[async_trait]
+impl BackendFactory for JiraFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(base_url) = providers::jira_url(&ctx.config) else {
+            return Err(anyhow!(
+                "jira url not set (set JIRA_URL or [providers.jira].url)"
+            ));
+        };
+        let Some(token) = providers::jira_token(&ctx.config) else {
+            return Err(anyhow!(
+                "jira api token not set (set JIRA_API_TOKEN or [providers.jira].token)"
+            ));
+        };
+        let Some(project) = ctx.project.jira_project.filter(|s| !s.trim().is_empty()) else {
+            return Err(anyhow!("jira-project is required for jira backend"));
+        };
+        let backend = JiraBackend::new(
+            base_url,
+            project,
+            ctx.project.jira_ready_jql,
+            providers::jira_email(&ctx.config),
+            token,
+        )?
+        .with_ready_label(ctx.project.ready_label);
+        Ok(Box::new(backend))
+    }
+}
+
+struct LinearFactory;
+
+#[async_trait]
+impl BackendFactory for LinearFactory {
+    async fn open<'a>(
+        &self,
+        ctx: BackendContext<'a>,
+    ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+        let Some(api_key) = providers::linear_api_key(&ctx.config) else {
+            return Err(anyhow!(
+                "linear api key not set (set LINEAR_API_KEY or [providers.linear].api-key)"
+            ));
+        };
+        let Some(team_id) = ctx.project.linear_team.filter(|s| !s.trim().is_empty()) else {
+            return Err(anyhow!("linear-team is required for linear backend"));
+        };
+        let backend = LinearBackend::new(
+            team_id,
+            ctx.project.linear_project,
+            api_key,
+            ctx.project.allowed_authors,
+            providers::linear_graphql_url(&ctx.config),
+        )?
+        .with_ready_label(ctx.project.ready_label);
+        Ok(Box::new(backend))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use murmur_core::config::DEFAULT_ISSUE_BACKEND;
+    use murmur_core::issue::{
+        Capabilities, Comment, CreateParams, Issue, ListFilter, Status, UpdateParams,
+    };
+
+    /// A tracker with one fixed issue.
+    struct Fixed;
+
+    #[async_trait]
+    impl IssueBackend for Fixed {
+        fn capabilities(&self) -> Capabilities {
+            Capabilities::default()
+        }
+
+        async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+            Ok(Issue {
+                id: id.to_owned(),
+                title: "fixed".to_owned(),
+                description: String::new(),
+                status: Status::Open,
+                priority: 0,
+                issue_type: "task".to_owned(),
+                dependencies: vec![],
+                labels: vec![],
+                links: vec![],
+                created_at_ms: 0,
+            })
+        }
+
+        async fn list(&self, _filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+            Ok(vec![self.get("FIX-1").await?])
+        }
+
+        async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+            self.list(ListFilter::default()).await
+        }
+
+        async fn create(&self, _now_ms: u64, _params: CreateParams) -> anyhow::Result<Issue> {
+            Err(anyhow!("read-only"))
+        }
+
+        async fn update(
+            &self,
+            _now_ms: u64,
+            _id: &str,
+            _params: UpdateParams,
+        ) -> anyhow::Result<Issue> {
+            Err(anyhow!("read-only"))
+        }
+
+        async fn close(&self, _now_ms: u64, _id: &str) -> anyhow::Result<()> {
+            Err(anyhow!("read-only"))
+        }
+
+        async fn comment(&self, _now_ms: u64, _id: &str, _body: &str) -> anyhow::Result<()> {
+            Err(anyhow!("read-only"))
+        }
+
+        async fn list_comments(
+            &self,
+            _id: &str,
+            _since_ms: Option<u64>,
+        ) -> anyhow::Result<Vec<Comment>> {
+            Ok(vec![])
+        }
+
+        async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FixedFactory;
+
+    #[async_trait]
+    impl BackendFactory for FixedFactory {
+        async fn open<'a>(
+            &self,
+            _ctx: BackendContext<'a>,
+        ) -> anyhow::Result<Box<dyn IssueBackend + 'a>> {
+            Ok(Box::new(Fixed))
+        }
+    }
+
+    fn context(git: &Git) -> BackendContext<'_> {
+        let toml = r#"
+[[projects]]
+name = "demo"
+remote-url = "file:///tmp/demo.git"
+"#;
+        let config: ConfigFile = toml::from_str(toml).unwrap();
+        BackendContext {
+            git,
+            repo_dir: PathBuf::from("/nonexistent"),
+            project: config.projects[0].clone(),
+            config: Arc::new(config),
+        }
+    }
+
+    #[tokio::test]
+    async fn opens_registered_backends_by_name() {
+        let mut registry = Registry::default();
+        assert_eq!(
+            registry.names(),
+            ["forgejo", "gh", "gitea", "github", "gitlab", "jira", "linear", "tk"]
+        );
+        assert!(registry.contains(DEFAULT_ISSUE_BACKEND));
+
+        registry.register("Fixed", FixedFactory);
+        let git = Git::default();
+        let backend = registry.open("fixed", context(&git)).await.unwrap();
+        assert_eq!(backend.ready().await.unwrap()[0].id, "FIX-1");
+
+        let err = match registry.open("youtrack", context(&git)).await {
+            Ok(_) => panic!("opened an unregistered backend"),
+            Err(err) => err.to_string(),
+        };
+        assert_eq!(
+            err,
+            "unknown issue backend: youtrack (registered: fixed, forgejo, gh, gitea, github, gitlab, jira, linear, tk)"
+        );
+    }
+}
@@ -1,10 +1,11 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
 use fs2::FileExt as _;
 use murmur_core::issue::{
-    compute_ready_issues, tk_format_issue, tk_parse_issue, tk_upsert_comment, Comment, CreateParams, Issue,
-    ListFilter, Status, UpdateParams,
+    compute_ready_issues, tk_format_issue, tk_parse_issue, tk_upsert_comment, Capabilities,
+    Comment, CreateParams, Issue, IssueBackend, ListFilter, Status, UpdateParams,
 };
 use murmur_core::paths::safe_join;
 
@@ -278,6 +279,53 @@ impl<'a> TkBackend<'a> {
     }
 }
 
+#[async_trait]
+impl IssueBackend for TkBackend<'_> {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: true,
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        TkBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        TkBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        TkBackend::ready(self).await
+    }
+
+    async fn create(&self, now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        TkBackend::create(self, now_ms, params).await
+    }
+
+    async fn update(&self, now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        TkBackend::update(self, now_ms, id, params).await
+    }
+
+    async fn close(&self, now_ms: u64, id: &str) -> anyhow::Result<()> {
+        TkBackend::close(self, now_ms, id).await
+    }
+
+    async fn comment(&self, now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        TkBackend::comment(self, now_ms, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        TkBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, message: &str) -> anyhow::Result<()> {
+        TkBackend::commit(self, message).await
+    }
+}
+
 async fn detect_prefix(tickets_dir: &Path) -> Option<String> {
     let mut dir = match tokio::fs::read_dir(tickets_dir).await {
         Ok(v) => v,
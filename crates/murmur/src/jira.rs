@@ -0,0 +1,819 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
+use murmur_core::issue::{
+    Capabilities, Comment, CreateParams, Issue, IssueBackend, ListFilter, Status, UpdateParams,
+};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Results per page; Jira caps `maxResults` at 100 for searches.
+const PAGE_SIZE: usize = 100;
+
+const SEARCH_FIELDS: &[&str] = &[
+    "summary",
+    "description",
+    "status",
+    "priority",
+    "issuetype",
+    "labels",
+    "issuelinks",
+    "created",
+];
+
+/// Issues of one Jira project, over the REST API (v2), which takes plain-text
+/// descriptions and comments on both Cloud and Data Center.
+#[derive(Debug, Clone)]
+pub struct JiraBackend {
+    client: reqwest::Client,
+    api_url: String,
+    project: String,
+    ready_jql: Option<String>,
+    email: Option<String>,
+    token: String,
+    ready_label: Option<String>,
+}
+
+impl JiraBackend {
+    /// Cloud authenticates with `email` and an API token; without an email
+    /// the token is sent as a bearer token (Data Center personal access
+    /// token).
+    pub fn new(
+        base_url: String,
+        project: String,
+        ready_jql: Option<String>,
+        email: Option<String>,
+        token: String,
+    ) -> anyhow::Result<Self> {
+        let project = project.trim().to_ascii_uppercase();
+        if project.is_empty() {
+            return Err(anyhow!("missing jira project key"));
+        }
+        if token.trim().is_empty() {
+            return Err(anyhow!("missing jira api token"));
+        }
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let client = reqwest::Client::builder()
+            .user_agent(format!("murmur/{}", env!("CARGO_PKG_VERSION")))
+            .default_headers(headers)
+            .build()
+            .context("build reqwest client")?;
+
+        Ok(Self {
+            client,
+            api_url: format!("{}/rest/api/2", base_url.trim().trim_end_matches('/')),
+            project,
+            ready_jql: ready_jql.filter(|s| !s.trim().is_empty()),
+            email: email.filter(|s| !s.trim().is_empty()),
+            token,
+            ready_label: None,
+        })
+    }
+
+    /// Adds a label clause to the default ready JQL; a configured
+    /// `jira-ready-jql` is used as written.
+    pub fn with_ready_label(mut self, label: Option<String>) -> Self {
+        self.ready_label = label.filter(|s| !s.trim().is_empty());
+        self
+    }
+
+    /// The JQL `ready()` runs: the configured query, or every issue of the
+    /// project not in the Done category.
+    pub fn ready_jql(&self) -> String {
+        self.ready_jql.clone().unwrap_or_else(|| {
+            let label = self
+                .ready_label
+                .as_ref()
+                .map(|l| format!(" AND labels = {}", jql_string(l)))
+                .unwrap_or_default();
+            format!(
+                "project = {}{label} AND statusCategory != Done ORDER BY priority DESC, created ASC",
+                jql_string(&self.project)
+            )
+        })
+    }
+
+    pub async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        let key = self.issue_key(id)?;
+        let ji: JiraIssue = self
+            .request(
+                Method::GET,
+                &format!("issue/{key}?fields={}", SEARCH_FIELDS.join(",")),
+                None,
+            )
+            .await
+            .context("jira get issue")?;
+        Ok(to_issue(&ji))
+    }
+
+    pub async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        let mut clauses = vec![format!("project = {}", jql_string(&self.project))];
+
+        let done: BTreeSet<bool> = filter.status.iter().map(|s| *s == Status::Closed).collect();
+        if done.len() == 1 {
+            if done.contains(&true) {
+                clauses.push("statusCategory = Done".to_owned());
+            } else {
+                clauses.push("statusCategory != Done".to_owned());
+            }
+        }
+        for label in &filter.labels {
+            clauses.push(format!("labels = {}", jql_string(label)));
+        }
+
+        let jql = format!("{} ORDER BY updated DESC", clauses.join(" AND "));
+        let issues = self.search(&jql).await.context("jira list issues")?;
+        Ok(issues.iter().map(to_issue).collect())
+    }
+
+    /// Issues matching the ready JQL, minus blocked ones and those with an
+    /// "is blocked by" link to an issue that is not done.
+    pub async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        let issues = self
+            .search(&self.ready_jql())
+            .await
+            .context("jira ready query")?;
+
+        let mut ready = Vec::new();
+        for ji in &issues {
+            let iss = to_issue(ji);
+            if iss.status != Status::Open {
+                continue;
+            }
+            if blockers(ji).any(|b| !b.is_done()) {
+                continue;
+            }
+            ready.push(iss);
+        }
+        Ok(ready)
+    }
+
+    pub async fn create(&self, params: CreateParams) -> anyhow::Result<Issue> {
+        if !params.links.is_empty() {
+            return Err(anyhow!("jira backend does not support `links`"));
+        }
+
+        let issue_type = if params.issue_type.trim().is_empty() {
+            "task"
+        } else {
+            params.issue_type.trim()
+        };
+
+        let mut fields = serde_json::Map::new();
+        fields.insert(
+            "project".to_owned(),
+            serde_json::json!({ "key": self.project }),
+        );
+        fields.insert(
+            "summary".to_owned(),
+            serde_json::Value::String(params.title),
+        );
+        if !params.description.trim().is_empty() {
+            fields.insert(
+                "description".to_owned(),
+                serde_json::Value::String(params.description),
+            );
+        }
+        fields.insert(
+            "issuetype".to_owned(),
+            serde_json::json!({ "name": jira_issue_type(issue_type) }),
+        );
+        fields.insert(
+            "priority".to_owned(),
+            serde_json::json!({ "name": map_priority_to_jira(params.priority) }),
+        );
+        if !params.labels.is_empty() {
+            fields.insert("labels".to_owned(), serde_json::json!(params.labels));
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Created {
+            key: String,
+        }
+
+        let created: Created = self
+            .request(
+                Method::POST,
+                "issue",
+                Some(serde_json::json!({ "fields": fields })),
+            )
+            .await
+            .context("jira create issue")?;
+
+        for dep in params.dependencies {
+            if let Err(err) = self.add_blocked_by(&created.key, &dep).await {
+                tracing::warn!(dep = %dep, error = %err, "failed to add jira dependency");
+            }
+        }
+
+        self.get(&created.key).await
+    }
+
+    pub async fn update(&self, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        if params.dependencies.is_some() {
+            return Err(anyhow!(
+                "jira backend does not support updating dependencies"
+            ));
+        }
+        if params.links.is_some() {
+            return Err(anyhow!("jira backend does not support `links`"));
+        }
+
+        let UpdateParams {
+            title,
+            description,
+            status,
+            priority,
+            issue_type,
+            labels,
+            dependencies: _,
+            links: _,
+        } = params;
+
+        let key = self.issue_key(id)?;
+        let current = self.get(&key).await.context("jira get issue for update")?;
+
+        let mut fields = serde_json::Map::new();
+        if let Some(v) = title {
+            fields.insert("summary".to_owned(), serde_json::Value::String(v));
+        }
+        if let Some(v) = description {
+            fields.insert("description".to_owned(), serde_json::Value::String(v));
+        }
+        if let Some(v) = issue_type {
+            fields.insert(
+                "issuetype".to_owned(),
+                serde_json::json!({ "name": jira_issue_type(&v) }),
+            );
+        }
+        if let Some(v) = priority {
+            fields.insert(
+                "priority".to_owned(),
+                serde_json::json!({ "name": map_priority_to_jira(v) }),
+            );
+        }
+        // "Blocked" is a label, so it is rewritten only when it flips.
+        let was_blocked = current.status == Status::Blocked;
+        let blocked = status.map_or(was_blocked, |s| s == Status::Blocked);
+        if labels.is_some() || blocked != was_blocked {
+            let mut new_labels = labels.unwrap_or_else(|| current.labels.clone());
+            new_labels.retain(|l| l != "blocked");
+            if blocked {
+                new_labels.push("blocked".to_owned());
+            }
+            fields.insert("labels".to_owned(), serde_json::json!(new_labels));
+        }
+
+        if !fields.is_empty() {
+            let _: serde_json::Value = self
+                .request(
+                    Method::PUT,
+                    &format!("issue/{key}"),
+                    Some(serde_json::json!({ "fields": fields })),
+                )
+                .await
+                .context("jira update issue")?;
+        }
+
+        if let Some(status) = status {
+            let want_done = status == Status::Closed;
+            if want_done != (current.status == Status::Closed) {
+                self.transition(&key, want_done).await?;
+            }
+        }
+
+        self.get(&key).await
+    }
+
+    /// Moves the issue to the Done category through the first transition
+    /// that leads there.
+    pub async fn close(&self, id: &str) -> anyhow::Result<()> {
+        let _ = self
+            .update(
+                id,
+                UpdateParams {
+                    status: Some(Status::Closed),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn comment(&self, id: &str, body: &str) -> anyhow::Result<()> {
+        let key = self.issue_key(id)?;
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issue/{key}/comment"),
+                Some(serde_json::json!({ "body": body })),
+            )
+            .await
+            .context("jira add comment")?;
+        Ok(())
+    }
+
+    /// List comments on an issue, optionally filtering to those created after `since_ms`.
+    pub async fn list_comments(
+        &self,
+        id: &str,
+        since_ms: Option<u64>,
+    ) -> anyhow::Result<Vec<Comment>> {
+        let key = self.issue_key(id)?;
+
+        #[derive(Debug, Deserialize)]
+        struct Comments {
+            comments: Vec<JiraComment>,
+            #[serde(default)]
+            total: usize,
+        }
+        #[derive(Debug, Deserialize)]
+        struct JiraComment {
+            id: String,
+            #[serde(default)]
+            body: String,
+            created: String,
+            #[serde(default)]
+            author: Option<JiraUser>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct JiraUser {
+            #[serde(rename = "displayName")]
+            display_name: String,
+        }
+
+        let mut raw = Vec::new();
+        loop {
+            let data: Comments = self
+                .request(
+                    Method::GET,
+                    &format!(
+                        "issue/{key}/comment?orderBy=created&startAt={}&maxResults={PAGE_SIZE}",
+                        raw.len()
+                    ),
+                    None,
+                )
+                .await
+                .context("jira list comments")?;
+
+            let done = data.comments.is_empty();
+            raw.extend(data.comments);
+            if done || raw.len() >= data.total {
+                break;
+            }
+        }
+
+        let mut comments = Vec::new();
+        for c in raw {
+            let created_at_ms = parse_jira_time_ms(&c.created).unwrap_or(0);
+            if let Some(since) = since_ms {
+                if created_at_ms <= since {
+                    continue;
+                }
+            }
+            comments.push(Comment {
+                id: c.id,
+                author: c.author.map(|a| a.display_name).unwrap_or_default(),
+                body: c.body,
+                created_at_ms,
+            });
+        }
+
+        Ok(comments)
+    }
+
+    pub async fn commit(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Runs a JQL search page by page. Jira Cloud serves `search/jql`,
+    /// paged by token; Data Center only has the older `search`, paged by
+    /// offset.
+    async fn search(&self, jql: &str) -> anyhow::Result<Vec<JiraIssue>> {
+        #[derive(Debug, Deserialize)]
+        struct SearchResults {
+            issues: Vec<JiraIssue>,
+            #[serde(default, rename = "nextPageToken")]
+            next_page_token: Option<String>,
+            #[serde(default)]
+            total: usize,
+        }
+
+        let mut issues = Vec::new();
+        let mut legacy = false;
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut body = serde_json::json!({
+                "jql": jql,
+                "maxResults": PAGE_SIZE,
+                "fields": SEARCH_FIELDS,
+            });
+            if legacy {
+                body["startAt"] = serde_json::json!(issues.len());
+            } else if let Some(token) = page_token.take() {
+                body["nextPageToken"] = serde_json::json!(token);
+            }
+
+            let path = if legacy { "search" } else { "search/jql" };
+            let (status, text) = self.send(Method::POST, path, Some(body)).await?;
+            if status == StatusCode::NOT_FOUND && !legacy && issues.is_empty() {
+                legacy = true;
+                continue;
+            }
+            if !status.is_success() {
+                return Err(anyhow!("jira api error ({status}): {text}"));
+            }
+
+            let page: SearchResults = serde_json::from_str(&text).context("parse jira response")?;
+            let empty = page.issues.is_empty();
+            issues.extend(page.issues);
+            if legacy {
+                if empty || issues.len() >= page.total {
+                    break;
+                }
+            } else {
+                match page.next_page_token {
+                    Some(token) if !empty => page_token = Some(token),
+                    _ => break,
+                }
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn transition(&self, key: &str, to_done: bool) -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize)]
+        struct Transitions {
+            transitions: Vec<Transition>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Transition {
+            id: String,
+            to: JiraStatus,
+        }
+
+        let data: Transitions = self
+            .request(Method::GET, &format!("issue/{key}/transitions"), None)
+            .await
+            .context("jira list transitions")?;
+
+        // Reopening prefers "To Do" over "In Progress".
+        let wanted: &[&str] = if to_done {
+            &["done"]
+        } else {
+            &["new", "indeterminate"]
+        };
+        let Some(transition) = wanted.iter().find_map(|category| {
+            data.transitions
+                .iter()
+                .find(|t| t.to.status_category.key == *category)
+        }) else {
+            return Err(anyhow!(
+                "no jira transition for {key} leads to {}",
+                if to_done { "done" } else { "an open status" }
+            ));
+        };
+
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                &format!("issue/{key}/transitions"),
+                Some(serde_json::json!({ "transition": { "id": transition.id } })),
+            )
+            .await
+            .context("jira transition issue")?;
+        Ok(())
+    }
+
+    async fn add_blocked_by(&self, blocked: &str, blocking: &str) -> anyhow::Result<()> {
+        let blocking = self.issue_key(blocking)?;
+        // Jira reads this as "<inwardIssue> blocks <outwardIssue>".
+        let _: serde_json::Value = self
+            .request(
+                Method::POST,
+                "issueLink",
+                Some(serde_json::json!({
+                    "type": { "name": "Blocks" },
+                    "inwardIssue": { "key": blocking },
+                    "outwardIssue": { "key": blocked },
+                })),
+            )
+            .await
+            .context("jira add blocks link")?;
+        Ok(())
+    }
+
+    /// `APP-12` as given, or a bare `12` in this backend's project.
+    fn issue_key(&self, id: &str) -> anyhow::Result<String> {
+        let trimmed = id.trim().trim_start_matches('#');
+        if trimmed.is_empty() {
+            return Err(anyhow!("invalid issue id: empty"));
+        }
+        if trimmed.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(format!("{}-{trimmed}", self.project));
+        }
+        if !trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(anyhow!("invalid jira issue key: {trimmed}"));
+        }
+        Ok(trimmed.to_ascii_uppercase())
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<T> {
+        let (status, text) = self.send(method, path, body).await?;
+        if !status.is_success() {
+            return Err(anyhow!("jira api error ({status}): {text}"));
+        }
+        // Edits and transitions answer 204 with no body.
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).context("parse jira response")
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> anyhow::Result<(StatusCode, String)> {
+        let url = format!("{}/{path}", self.api_url);
+        let mut req = self.client.request(method, &url);
+        req = match &self.email {
+            Some(email) => req.basic_auth(email, Some(&self.token)),
+            None => req.bearer_auth(&self.token),
+        };
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+
+        let resp = req.send().await.context("send request")?;
+        let status = resp.status();
+        let text = resp.text().await.context("read response")?;
+        Ok((status, text))
+    }
+}
+
+#[async_trait]
+impl IssueBackend for JiraBackend {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: false,
+            // Set on create only, as "is blocked by" issue links.
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        JiraBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        JiraBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        JiraBackend::ready(self).await
+    }
+
+    async fn create(&self, _now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        JiraBackend::create(self, params).await
+    }
+
+    async fn update(&self, _now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        JiraBackend::update(self, id, params).await
+    }
+
+    async fn close(&self, _now_ms: u64, id: &str) -> anyhow::Result<()> {
+        JiraBackend::close(self, id).await
+    }
+
+    async fn comment(&self, _now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        JiraBackend::comment(self, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        JiraBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+        JiraBackend::commit(self).await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraFields {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    description: Option<String>,
+    status: JiraStatus,
+    #[serde(default)]
+    priority: Option<JiraNamed>,
+    #[serde(default)]
+    issuetype: Option<JiraNamed>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    issuelinks: Vec<JiraLink>,
+    #[serde(default)]
+    created: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatus {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "statusCategory")]
+    status_category: JiraStatusCategory,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    /// `new`, `indeterminate` or `done`.
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraNamed {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraLink {
+    #[serde(rename = "type")]
+    link_type: JiraLinkType,
+    #[serde(default, rename = "inwardIssue")]
+    inward_issue: Option<JiraLinkedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraLinkType {
+    #[serde(default)]
+    inward: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraLinkedIssue {
+    key: String,
+    #[serde(default)]
+    fields: Option<JiraLinkedFields>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraLinkedFields {
+    status: JiraStatus,
+}
+
+impl JiraLinkedIssue {
+    fn is_done(&self) -> bool {
+        self.fields
+            .as_ref()
+            .is_some_and(|f| f.status.status_category.key == "done")
+    }
+}
+
+/// Issues `ji` "is blocked by".
+fn blockers(ji: &JiraIssue) -> impl Iterator<Item = &JiraLinkedIssue> {
+    ji.fields
+        .issuelinks
+        .iter()
+        .filter(|l| l.link_type.inward.eq_ignore_ascii_case("is blocked by"))
+        .filter_map(|l| l.inward_issue.as_ref())
+}
+
+fn to_issue(ji: &JiraIssue) -> Issue {
+    let fields = &ji.fields;
+    let blocked = fields.labels.iter().any(|l| l == "blocked")
+        || fields.status.name.eq_ignore_ascii_case("blocked");
+    let status = if fields.status.status_category.key == "done" {
+        Status::Closed
+    } else if blocked {
+        Status::Blocked
+    } else {
+        Status::Open
+    };
+
+    Issue {
+        id: ji.key.clone(),
+        title: fields.summary.clone(),
+        description: fields.description.clone().unwrap_or_default(),
+        status,
+        priority: map_priority_from_jira(fields.priority.as_ref().map(|p| p.name.as_str())),
+        issue_type: fields
+            .issuetype
+            .as_ref()
+            .map(|t| t.name.to_ascii_lowercase())
+            .unwrap_or_else(|| "task".to_owned()),
+        dependencies: blockers(ji).map(|b| b.key.clone()).collect(),
+        labels: fields
+            .labels
+            .iter()
+            .filter(|l| *l != "blocked")
+            .cloned()
+            .collect(),
+        links: vec![],
+        created_at_ms: parse_jira_time_ms(&fields.created).unwrap_or(0),
+    }
+}
+
+/// Murmur priorities are "higher is more important", with 1 as the usual
+/// default; Jira's default scheme is five named levels.
+fn map_priority_to_jira(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=0 => "Low",
+        1 => "Medium",
+        2 => "High",
+        _ => "Highest",
+    }
+}
+
+fn map_priority_from_jira(name: Option<&str>) -> i32 {
+    match name.map(str::to_ascii_lowercase).as_deref() {
+        Some("highest") | Some("blocker") => 3,
+        Some("high") | Some("critical") => 2,
+        Some("low") | Some("lowest") | Some("minor") | Some("trivial") => 0,
+        _ => 1,
+    }
+}
+
+/// `task` -> `Task`; Jira matches issue type names as configured.
+fn jira_issue_type(issue_type: &str) -> String {
+    let mut chars = issue_type.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Task".to_owned(),
+    }
+}
+
+/// Quotes a value for JQL.
+fn jql_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Jira timestamps look like `2026-01-20T10:00:00.000+0000`: RFC 3339
+/// except for the colon in the offset.
+fn parse_jira_time_ms(s: &str) -> anyhow::Result<u64> {
+    let s = s.trim();
+    let normalized = match s.len().checked_sub(5).map(|i| s.split_at(i)) {
+        Some((head, offset))
+            if (offset.starts_with('+') || offset.starts_with('-'))
+                && offset[1..].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            format!("{head}{}:{}", &offset[..3], &offset[3..])
+        }
+        _ => s.to_owned(),
+    };
+    let dt =
+        time::OffsetDateTime::parse(&normalized, &time::format_description::well_known::Rfc3339)
+            .map_err(|e| anyhow!("parse jira time: {e}"))?;
+    Ok(dt.unix_timestamp_nanos() as u64 / 1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_priorities_and_timestamps() {
+        for p in 0..=3 {
+            assert_eq!(map_priority_from_jira(Some(map_priority_to_jira(p))), p);
+        }
+        assert_eq!(map_priority_from_jira(None), 1);
+
+        assert_eq!(
+            parse_jira_time_ms("2026-01-20T00:00:01.000+0000").unwrap(),
+            parse_jira_time_ms("2026-01-20T00:00:01Z").unwrap()
+        );
+        assert_eq!(
+            parse_jira_time_ms("2026-01-20T02:00:00.000+0200").unwrap(),
+            parse_jira_time_ms("2026-01-20T00:00:00.000+0000").unwrap()
+        );
+        assert_eq!(jql_string(r#"a "b""#), r#""a \"b\"""#);
+    }
+}
@@ -1,15 +1,21 @@
 pub mod client;
 pub mod config_store;
+pub mod confinement;
 pub mod daemon;
 pub mod dedup_store;
 pub mod git;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
 pub mod hooks;
 pub mod host;
 pub mod ipc;
+pub mod issue_backends;
 pub mod issues;
+pub mod jira;
 pub mod linear;
 pub mod llm_auth;
+pub mod permission_log;
 pub mod permissions;
 pub mod providers;
 pub mod runtime_store;
@@ -1,8 +1,10 @@
 use std::collections::BTreeSet;
 
 use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
 use murmur_core::issue::{
-    compute_ready_issues, Comment, CreateParams, Issue, ListFilter, Status, UpdateParams,
+    compute_ready_issues, Capabilities, Comment, CreateParams, Issue, IssueBackend, ListFilter,
+    Status, UpdateParams,
 };
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde::de::DeserializeOwned;
@@ -17,8 +19,12 @@ pub struct LinearBackend {
     project_id: Option<String>,
     #[allow(dead_code)]
     allowed_authors: Vec<String>,
+    ready_label: Option<String>,
 }
 
+/// Connection page size; Linear caps `first` at 250.
+const PAGE_SIZE: i64 = 100;
+
 impl LinearBackend {
     pub fn new(
         team_id: String,
@@ -53,9 +59,16 @@ impl LinearBackend {
             team_id,
             project_id: project_id.filter(|s| !s.trim().is_empty()),
             allowed_authors,
+            ready_label: None,
         })
     }
 
+    /// Limits `ready()` to open issues with `label`, filtered by Linear.
+    pub fn with_ready_label(mut self, label: Option<String>) -> Self {
+        self.ready_label = label.filter(|s| !s.trim().is_empty());
+        self
+    }
+
     pub async fn get(&self, id: &str) -> anyhow::Result<Issue> {
         let query = r#"
             query Issue($id: String!) {
@@ -86,18 +99,7 @@ impl LinearBackend {
     }
 
     pub async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
-        let mut filter_obj = serde_json::Map::new();
-        if let Some(project_id) = self.project_id.as_ref() {
-            filter_obj.insert(
-                "project".to_owned(),
-                serde_json::json!({ "id": { "eq": project_id } }),
-            );
-        } else {
-            filter_obj.insert(
-                "team".to_owned(),
-                serde_json::json!({ "id": { "eq": self.team_id } }),
-            );
-        }
+        let mut filter_obj = self.scope_filter();
 
         if !filter.status.is_empty() {
             let mut types = Vec::new();
@@ -125,45 +127,17 @@ impl LinearBackend {
                 );
             }
         }
-
-        let query = r#"
-            query Issues($filter: IssueFilter) {
-                issues(filter: $filter, first: 100) {
-                    nodes {
-                        identifier
-                        title
-                        description
-                        priority
-                        createdAt
-                        state { type }
-                        labels { nodes { name } }
-                        parent { identifier }
-                    }
-                }
-            }
-        "#;
-
-        #[derive(Debug, Deserialize)]
-        struct Data {
-            issues: IssueConn,
-        }
-        #[derive(Debug, Deserialize)]
-        struct IssueConn {
-            nodes: Vec<LinearIssue>,
+        if !filter.labels.is_empty() {
+            filter_obj.insert("and".to_owned(), labels_filter(&filter.labels));
         }
 
-        let data: Data = self
-            .graphql(
-                query,
-                Some(serde_json::json!({
-                    "filter": serde_json::Value::Object(filter_obj),
-                })),
-            )
+        let nodes = self
+            .issue_pages(filter_obj)
             .await
             .context("linear list issues")?;
 
         let mut issues = Vec::new();
-        for li in data.issues.nodes {
+        for li in nodes {
             let iss = to_issue(&li);
 
             if !filter.labels.is_empty() {
@@ -180,12 +154,27 @@ impl LinearBackend {
     }
 
     pub async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
-        let open = self
-            .list(ListFilter {
-                status: vec![Status::Open],
-                labels: vec![],
-            })
-            .await?;
+        let mut filter_obj = self.scope_filter();
+        filter_obj.insert(
+            "state".to_owned(),
+            serde_json::json!({ "type": { "in": ["backlog", "started", "unstarted"] } }),
+        );
+        if let Some(label) = self.ready_label.as_ref() {
+            filter_obj.insert("and".to_owned(), labels_filter(std::slice::from_ref(label)));
+        }
+
+        let nodes = self
+            .issue_pages(filter_obj)
+            .await
+            .context("linear ready query")?;
+
+        // With a ready label the parent may be outside the fetched set, so
+        // its own state is checked as well.
+        let open = nodes
+            .iter()
+            .filter(|li| !li.parent.as_ref().is_some_and(LinearParent::is_open))
+            .map(to_issue)
+            .collect();
 
         let ready = compute_ready_issues(open)
             .into_iter()
@@ -541,9 +530,10 @@ impl LinearBackend {
         let issue_id = self.resolve_issue_id(id).await?;
 
         let query = r#"
-            query IssueComments($issueId: String!, $first: Int!) {
+            query IssueComments($issueId: String!, $first: Int!, $after: String) {
                 issue(id: $issueId) {
-                    comments(first: $first, orderBy: createdAt) {
+                    comments(first: $first, after: $after, orderBy: createdAt) {
+                        pageInfo { hasNextPage endCursor }
                         nodes {
                             id
                             body
@@ -568,6 +558,8 @@ impl LinearBackend {
         #[derive(Debug, Deserialize)]
         struct CommentsConnection {
             nodes: Vec<CommentNode>,
+            #[serde(default, rename = "pageInfo")]
+            page_info: PageInfo,
         }
         #[derive(Debug, Deserialize)]
         struct CommentNode {
@@ -582,19 +574,31 @@ impl LinearBackend {
             name: String,
         }
 
-        let data: Data = self
-            .graphql(
-                query,
-                Some(serde_json::json!({
-                    "issueId": issue_id,
-                    "first": 100,
-                })),
-            )
-            .await
-            .context("linear list comments")?;
+        let mut nodes = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data: Data = self
+                .graphql(
+                    query,
+                    Some(serde_json::json!({
+                        "issueId": issue_id,
+                        "first": PAGE_SIZE,
+                        "after": after,
+                    })),
+                )
+                .await
+                .context("linear list comments")?;
+
+            let conn = data.issue.comments;
+            nodes.extend(conn.nodes);
+            match conn.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
 
         let mut comments = Vec::new();
-        for node in data.issue.comments.nodes {
+        for node in nodes {
             let created_at_ms = parse_rfc3339_ms(&node.created_at).unwrap_or(0);
 
             // Filter by since_ms if provided
@@ -730,6 +734,81 @@ impl LinearBackend {
         Ok(data.issue_labels.nodes.into_iter().next().map(|l| l.id))
     }
 
+    /// The team, or the project when one is configured.
+    fn scope_filter(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut filter_obj = serde_json::Map::new();
+        if let Some(project_id) = self.project_id.as_ref() {
+            filter_obj.insert(
+                "project".to_owned(),
+                serde_json::json!({ "id": { "eq": project_id } }),
+            );
+        } else {
+            filter_obj.insert(
+                "team".to_owned(),
+                serde_json::json!({ "id": { "eq": self.team_id } }),
+            );
+        }
+        filter_obj
+    }
+
+    /// Runs the issues query for `filter` page by page.
+    async fn issue_pages(
+        &self,
+        filter: serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<Vec<LinearIssue>> {
+        let query = r#"
+            query Issues($filter: IssueFilter, $first: Int!, $after: String) {
+                issues(filter: $filter, first: $first, after: $after) {
+                    pageInfo { hasNextPage endCursor }
+                    nodes {
+                        identifier
+                        title
+                        description
+                        priority
+                        createdAt
+                        state { type }
+                        labels { nodes { name } }
+                        parent { identifier state { type } }
+                    }
+                }
+            }
+        "#;
+
+        #[derive(Debug, Deserialize)]
+        struct Data {
+            issues: IssueConn,
+        }
+        #[derive(Debug, Deserialize)]
+        struct IssueConn {
+            nodes: Vec<LinearIssue>,
+            #[serde(default, rename = "pageInfo")]
+            page_info: PageInfo,
+        }
+
+        let filter = serde_json::Value::Object(filter);
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data: Data = self
+                .graphql(
+                    query,
+                    Some(serde_json::json!({
+                        "filter": filter,
+                        "first": PAGE_SIZE,
+                        "after": after,
+                    })),
+                )
+                .await?;
+
+            issues.extend(data.issues.nodes);
+            match data.issues.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        Ok(issues)
+    }
+
     async fn graphql<T: DeserializeOwned>(
         &self,
         query: &str,
@@ -780,6 +859,54 @@ impl LinearBackend {
     }
 }
 
+#[async_trait]
+impl IssueBackend for LinearBackend {
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            links: false,
+            // The first dependency becomes the parent issue.
+            dependencies: true,
+            labels: true,
+        }
+    }
+
+    async fn get(&self, id: &str) -> anyhow::Result<Issue> {
+        LinearBackend::get(self, id).await
+    }
+
+    async fn list(&self, filter: ListFilter) -> anyhow::Result<Vec<Issue>> {
+        LinearBackend::list(self, filter).await
+    }
+
+    async fn ready(&self) -> anyhow::Result<Vec<Issue>> {
+        LinearBackend::ready(self).await
+    }
+
+    async fn create(&self, _now_ms: u64, params: CreateParams) -> anyhow::Result<Issue> {
+        LinearBackend::create(self, params).await
+    }
+
+    async fn update(&self, _now_ms: u64, id: &str, params: UpdateParams) -> anyhow::Result<Issue> {
+        LinearBackend::update(self, id, params).await
+    }
+
+    async fn close(&self, _now_ms: u64, id: &str) -> anyhow::Result<()> {
+        LinearBackend::close(self, id).await
+    }
+
+    async fn comment(&self, _now_ms: u64, id: &str, body: &str) -> anyhow::Result<()> {
+        LinearBackend::comment(self, id, body).await
+    }
+
+    async fn list_comments(&self, id: &str, since_ms: Option<u64>) -> anyhow::Result<Vec<Comment>> {
+        LinearBackend::list_comments(self, id, since_ms).await
+    }
+
+    async fn commit(&self, _message: &str) -> anyhow::Result<()> {
+        LinearBackend::commit(self).await
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct LinearIssue {
     identifier: String,
@@ -814,6 +941,38 @@ struct LinearLabel {
 #[derive(Debug, Deserialize)]
 struct LinearParent {
     identifier: String,
+    #[serde(default)]
+    state: Option<LinearState>,
+}
+
+impl LinearParent {
+    fn is_open(&self) -> bool {
+        self.state
+            .as_ref()
+            .is_some_and(|s| !matches!(s.kind.as_str(), "completed" | "canceled"))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PageInfo {
+    #[serde(default, rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(default, rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    fn next_cursor(self) -> Option<String> {
+        self.end_cursor.filter(|_| self.has_next_page)
+    }
+}
+
+/// Issues carrying every one of `labels`.
+fn labels_filter(labels: &[String]) -> serde_json::Value {
+    labels
+        .iter()
+        .map(|l| serde_json::json!({ "labels": { "some": { "name": { "eq": l } } } }))
+        .collect()
 }
 
 fn to_issue(li: &LinearIssue) -> Issue {
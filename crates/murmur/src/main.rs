@@ -122,7 +122,7 @@ enum Command {
     },
 
     // === Issue Tracking ===
-    /// Manage issues (tk, github, linear backends)
+    /// Manage issues (tk, github, gitlab, gitea, jira, linear backends)
     Issue(IssueArgs),
 
     // === Planning ===
@@ -166,6 +166,12 @@ enum Command {
         command: CommitCommand,
     },
 
+    /// Inspect and reorder the merge queue
+    Merge {
+        #[command(subcommand)]
+        command: MergeCommand,
+    },
+
     /// Manage agent host processes (advanced)
     #[command(
         long_about = "Manage agent host processes that wrap agent subprocesses.\n\n\
@@ -369,16 +375,33 @@ enum ProjectConfigCommand {
     #[command(after_help = "\
 KEYS AND VALUES:
   max-agents          Number of concurrent agents (1-10)
-  issue-backend       Issue source: tk, github, linear
+  issue-backend       Issue source: tk, github, gitlab, gitea, jira, linear,
+                      or a backend registered with the daemon
   agent-backend       AI backend: claude, codex
   planner-backend     Override for planners: claude, codex
   coding-backend      Override for coding agents: claude, codex
   permissions-checker How to handle permissions: manual, llm
-  merge-strategy      How to merge completed work: direct, pull-request
+  permission-timeout-secs
+                      Seconds before an unanswered permission request gets
+                      the timeout action (0 = wait indefinitely)
+  permission-timeout-action
+                      Applied when a request times out: deny, allow
+  escalation          Permission tiers after the rules, as tier[:secs] from
+                      llm, manager, human (e.g. llm:30,manager,human)
+  merge-strategy      How to merge completed work: direct, squash,
+                      merge-commit, pull-request
+  merge-message-template
+                      Squash/merge-commit message; placeholders {issue_id},
+                      {title}, {agent_id}, {description}
   autostart           Start orchestration on daemon start: true, false
   allowed-authors     Filter issues by author (comma-separated)
+  ready-label         Only fetch ready issues with this label (remote backends)
   linear-team         Linear team UUID (required for linear backend)
   linear-project      Linear project UUID (optional filter)
+  jira-project        Jira project key (required for jira backend)
+  jira-ready-jql      JQL selecting ready Jira issues (optional)
+  write-confinement   Deny writes outside the agent's worktree: true, false
+  write-allowed-dirs  Extra writable directories (comma-separated)
 
 EXAMPLES:
   mm project config set myproj max-agents 5
@@ -755,6 +778,29 @@ enum CommitCommand {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum MergeCommand {
+    /// Show finished agents waiting to merge, in landing order
+    Queue {
+        /// Filter by project
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+    },
+    /// Remove an agent from the merge queue (its `agent done` fails)
+    Cancel {
+        /// Agent ID
+        agent_id: String,
+    },
+    /// Change an agent's merge priority (higher lands first, default 0)
+    Prioritize {
+        /// Agent ID
+        agent_id: String,
+        /// New priority
+        #[arg(allow_negative_numbers = true)]
+        priority: i32,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum HostCommand {
     /// List running agent hosts
@@ -927,6 +973,62 @@ enum PermissionCommand {
     Respond {
         request_id: String,
         decision: String,
+        /// Also apply the decision to matching future requests
+        /// [possible values: once, agent, project, global]
+        #[arg(long, value_name = "SCOPE", default_value = "once")]
+        scope: String,
+    },
+    /// Show recorded permission decisions, oldest first
+    Log {
+        /// Filter by agent
+        #[arg(short = 'a', long)]
+        agent: Option<String>,
+        /// Filter by project
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+        /// Filter by tool name
+        #[arg(long)]
+        tool: Option<String>,
+        /// Filter by decision [possible values: allow, deny]
+        #[arg(long)]
+        decision: Option<String>,
+        /// Filter by decider [possible values: rule, llm, manager, human, timeout]
+        #[arg(long)]
+        decider: Option<String>,
+        /// Only decisions newer than this age (e.g. 30m, 2h, 7d)
+        #[arg(long, value_name = "AGE")]
+        since: Option<String>,
+        /// Only calls whose command or path contains this text
+        #[arg(long, value_name = "TEXT")]
+        grep: Option<String>,
+        /// Show only the most recent N decisions
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    /// Show which rules decide a tool call, without asking the daemon
+    Test {
+        /// Tool name (e.g. Bash, Edit, WebFetch)
+        #[arg(long)]
+        tool: String,
+        /// Tool input as JSON (e.g. '{"command":"git push"}')
+        #[arg(long, value_name = "JSON")]
+        input: String,
+        /// Apply this project's rules ahead of the global ones
+        #[arg(short = 'p', long)]
+        project: Option<String>,
+        /// Role of the agent making the call
+        /// [possible values: coding, planner, manager, director]
+        #[arg(long, default_value = "coding")]
+        role: String,
+        /// Directory the call runs in [default: current directory]
+        #[arg(long)]
+        cwd: Option<PathBuf>,
+    },
+    /// Report rules that never apply or are misconfigured
+    Lint {
+        /// Lint only this project's rules [default: global and every project]
+        #[arg(short = 'p', long)]
+        project: Option<String>,
     },
 }
 
@@ -1083,6 +1185,7 @@ async fn dispatch(command: Command, paths: &MurmurPaths) -> anyhow::Result<()> {
         }
         Command::Branch { command } => dispatch_branch(command).await,
         Command::Commit { command } => dispatch_commit(command, paths).await,
+        Command::Merge { command } => dispatch_merge(command, paths).await,
         Command::Host { command } => dispatch_host(command, paths).await,
         Command::Hook { command } => dispatch_hook(command, paths).await,
         Command::Plan { command } => dispatch_plan(command, paths).await,
@@ -1333,12 +1436,21 @@ async fn dispatch_permission(
     match command {
         PermissionCommand::List => {
             let resp = client::permission_list(paths, None).await?;
+            let now = now_ms();
             for r in resp.requests {
                 let primary =
                     murmur_core::permissions::resolve_primary_field(&r.tool_name, &r.tool_input);
+                let expiry = match (r.expires_at_ms, r.timeout_behavior) {
+                    (Some(at_ms), Some(behavior)) => format!(
+                        "{} in {}",
+                        format_permission_behavior(behavior),
+                        format_duration_ms(at_ms.saturating_sub(now))
+                    ),
+                    _ => "-".to_owned(),
+                };
                 println!(
-                    "{}\t{}\t{}\t{}\t{}",
-                    r.id, r.agent_id, r.project, r.tool_name, primary
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    r.id, r.agent_id, r.project, r.tool_name, primary, expiry
                 );
             }
             Ok(())
@@ -1346,8 +1458,10 @@ async fn dispatch_permission(
         PermissionCommand::Respond {
             request_id,
             decision,
+            scope,
         } => {
             let behavior = parse_permission_behavior(&decision)?;
+            let scope = parse_permission_scope(&scope)?;
             client::permission_respond(
                 paths,
                 murmur_protocol::PermissionRespondPayload {
@@ -1355,12 +1469,209 @@ async fn dispatch_permission(
                     behavior,
                     message: None,
                     interrupt: false,
+                    scope,
                 },
             )
             .await?;
             println!("ok");
             Ok(())
         }
+        PermissionCommand::Log {
+            agent,
+            project,
+            tool,
+            decision,
+            decider,
+            since,
+            grep,
+            limit,
+        } => {
+            let filter = murmur_core::permission_log::PermissionLogFilter {
+                agent_id: agent,
+                project,
+                tool_name: tool,
+                action: decision
+                    .as_deref()
+                    .map(|d| parse_permission_behavior(d).map(permission_action))
+                    .transpose()?,
+                decider: decider
+                    .as_deref()
+                    .map(|d| {
+                        murmur_core::permission_log::Decider::parse(d).ok_or_else(|| {
+                            anyhow!(
+                                "invalid decider: {d} (expected rule|llm|manager|human|timeout)"
+                            )
+                        })
+                    })
+                    .transpose()?,
+                since_ms: since
+                    .as_deref()
+                    .map(|s| {
+                        murmur_core::permission_log::parse_age_ms(s)
+                            .map(|age| now_ms().saturating_sub(age))
+                            .map_err(|e| anyhow!(e))
+                    })
+                    .transpose()?,
+                contains: grep,
+            };
+
+            let mut entries = murmur::permission_log::read(paths).await?;
+            entries.retain(|e| filter.matches(e));
+            if let Some(limit) = limit {
+                let skip = entries.len().saturating_sub(limit);
+                entries.drain(..skip);
+            }
+            if entries.is_empty() {
+                println!("No permission decisions recorded.");
+                return Ok(());
+            }
+
+            println!("TIME\tDECISION\tDECIDER\tAGENT\tPROJECT\tTOOL\tCALL\tRATIONALE");
+            for e in entries {
+                let at = time::OffsetDateTime::from_unix_timestamp_nanos(
+                    i128::from(e.at_ms) * 1_000_000,
+                )
+                .ok()
+                .and_then(|ts| murmur::stats::format_rfc3339(ts).ok())
+                .unwrap_or_else(|| e.at_ms.to_string());
+                let action = match e.action {
+                    murmur_core::permissions::Action::Allow => "allow",
+                    murmur_core::permissions::Action::Deny => "deny",
+                    murmur_core::permissions::Action::Pass => "pass",
+                };
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    at,
+                    action,
+                    e.decider.as_str(),
+                    e.agent_id,
+                    e.project,
+                    e.tool_name,
+                    e.primary_field.replace(['\t', '\n'], " "),
+                    e.rationale.unwrap_or_default().replace(['\t', '\n'], " ")
+                );
+            }
+            Ok(())
+        }
+        PermissionCommand::Test {
+            tool,
+            input,
+            project,
+            role,
+            cwd,
+        } => {
+            let role = murmur_core::agent::AgentRole::parse(&role).ok_or_else(|| {
+                anyhow!("invalid role: {role} (expected coding|planner|manager|director)")
+            })?;
+            permission_test(paths, &tool, &input, project.as_deref(), role, cwd).await
+        }
+        PermissionCommand::Lint { project } => permission_lint(paths, project).await,
+    }
+}
+
+async fn permission_test(
+    paths: &MurmurPaths,
+    tool: &str,
+    input: &str,
+    project: Option<&str>,
+    role: murmur_core::agent::AgentRole,
+    cwd: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    use murmur_core::permissions::{evaluate_rules, trace_rules, Action};
+
+    let tool_input: serde_json::Value =
+        serde_json::from_str(input).context("parse --input as JSON")?;
+    let cwd = match cwd {
+        Some(cwd) => cwd,
+        None => env::current_dir().context("get working directory")?,
+    };
+    let cwd = cwd.to_string_lossy();
+    let home_dir = BaseDirs::new()
+        .ok_or_else(|| anyhow!("could not determine home directory"))?
+        .home_dir()
+        .to_string_lossy()
+        .to_string();
+
+    let mut sourced = murmur::permissions::load_sourced_rules(paths, project).await?;
+    sourced.retain(|(rule, _)| rule.applies_to(role));
+    let rules: Vec<_> = sourced.iter().map(|(rule, _)| rule.clone()).collect();
+
+    let traces = match trace_rules(&rules, tool, &tool_input, &cwd, &home_dir) {
+        Ok(traces) => traces,
+        Err(err) => {
+            println!("command could not be parsed: {err}");
+            println!("result: pass (the request goes to the daemon)");
+            return Ok(());
+        }
+    };
+    for trace in traces {
+        let call = format!("{} {:?}", trace.check.tool_name, trace.check.primary_field);
+        for index in trace.skipped_scripts {
+            let (rule, source) = &sourced[index];
+            println!(
+                "{call}\tscript\t{source}\t{} (not run; the hook runs it)",
+                rule.summary()
+            );
+        }
+        match trace.rule {
+            Some(index) => {
+                let (rule, source) = &sourced[index];
+                let action = if rule.action == Action::Allow {
+                    "allow"
+                } else {
+                    "deny"
+                };
+                println!("{call}\t{action}\t{source}\t{}", rule.summary());
+            }
+            None => println!("{call}\tpass\t-\tno rule decides"),
+        }
+    }
+
+    let result = match evaluate_rules(&rules, tool, &tool_input, &cwd, &home_dir) {
+        (Action::Allow, true) => "allow",
+        (Action::Deny, true) => "deny",
+        _ => "pass (the request goes to the daemon)",
+    };
+    println!("result: {result}");
+    Ok(())
+}
+
+async fn permission_lint(paths: &MurmurPaths, project: Option<String>) -> anyhow::Result<()> {
+    let mut scopes = vec![project.clone()];
+    if project.is_none() {
+        scopes.extend(
+            murmur::permissions::projects_with_rules(paths)
+                .await?
+                .into_iter()
+                .map(Some),
+        );
+    }
+
+    let mut count = 0;
+    for scope in scopes {
+        for finding in murmur::permissions::lint(paths, scope.as_deref()).await? {
+            println!(
+                "{} ({}): {}",
+                finding.source,
+                finding.rule.summary(),
+                finding.message
+            );
+            count += 1;
+        }
+    }
+    if count > 0 {
+        return Err(anyhow!("{count} problem(s) found"));
+    }
+    println!("No problems found.");
+    Ok(())
+}
+
+fn permission_action(
+    behavior: murmur_protocol::PermissionBehavior,
+) -> murmur_core::permissions::Action {
+    match behavior {
+        murmur_protocol::PermissionBehavior::Allow => murmur_core::permissions::Action::Allow,
+        murmur_protocol::PermissionBehavior::Deny => murmur_core::permissions::Action::Deny,
     }
 }
 
@@ -1406,6 +1717,25 @@ fn parse_permission_behavior(s: &str) -> anyhow::Result<murmur_protocol::Permiss
     }
 }
 
+fn format_permission_behavior(behavior: murmur_protocol::PermissionBehavior) -> &'static str {
+    match behavior {
+        murmur_protocol::PermissionBehavior::Allow => "allow",
+        murmur_protocol::PermissionBehavior::Deny => "deny",
+    }
+}
+
+fn parse_permission_scope(s: &str) -> anyhow::Result<murmur_protocol::PermissionScope> {
+    match s.trim().to_lowercase().as_str() {
+        "once" => Ok(murmur_protocol::PermissionScope::Once),
+        "agent" => Ok(murmur_protocol::PermissionScope::Agent),
+        "project" => Ok(murmur_protocol::PermissionScope::Project),
+        "global" | "forever" => Ok(murmur_protocol::PermissionScope::Global),
+        other => Err(anyhow!(
+            "invalid scope: {other} (expected once|agent|project|global)"
+        )),
+    }
+}
+
 fn parse_question_answers(s: &str) -> anyhow::Result<std::collections::BTreeMap<String, String>> {
     let trimmed = s.trim();
     let parsed: std::collections::BTreeMap<String, String> =
@@ -1653,6 +1983,36 @@ async fn dispatch_project(command: ProjectCommand, paths: &MurmurPaths) -> anyho
             } else {
                 println!("user_intervention\tno activity recorded");
             }
+            // Global agent pool
+            match status.max_total_agents {
+                Some(max) => println!(
+                    "global_agents\t{}/{} (weight: {})",
+                    status.total_active_agents, max, status.weight
+                ),
+                None => println!("global_agents\t{} (no limit)", status.total_active_agents),
+            }
+            if status.waiting_for_slots > 0 {
+                println!(
+                    "fair_share\twaiting for {} global slot(s)",
+                    status.waiting_for_slots
+                );
+            }
+            // Scheduling order of ready issues
+            if let Some(err) = status.schedule_error {
+                println!("schedule\tunavailable ({err})");
+            } else {
+                println!("schedule\t{} ready", status.schedule.len());
+                for (rank, issue) in status.schedule.iter().enumerate() {
+                    println!(
+                        "  {}\t{}\t{}\t{}\t{}",
+                        rank + 1,
+                        issue.issue_id,
+                        issue.decision,
+                        issue.reason,
+                        issue.title
+                    );
+                }
+            }
             Ok(())
         }
         ProjectCommand::Config { command } => dispatch_project_config(command, paths).await,
@@ -1707,7 +2067,7 @@ async fn dispatch_agent(command: AgentCommand, paths: &MurmurPaths) -> anyhow::R
             let agents: Vec<_> = resp
                 .agents
                 .into_iter()
-                .filter(|a| project.as_ref().map_or(true, |p| p == &a.project))
+                .filter(|a| project.as_ref().is_none_or(|p| p == &a.project))
                 .collect();
 
             if agents.is_empty() {
@@ -1944,6 +2304,43 @@ async fn dispatch_commit(command: CommitCommand, paths: &MurmurPaths) -> anyhow:
     }
 }
 
+async fn dispatch_merge(command: MergeCommand, paths: &MurmurPaths) -> anyhow::Result<()> {
+    match command {
+        MergeCommand::Queue { project } => {
+            let resp = client::merge_queue(paths, project).await?;
+            if resp.entries.is_empty() {
+                println!("Merge queue is empty.");
+                return Ok(());
+            }
+            let now = now_ms();
+            println!("PROJECT\tPOS\tAGENT\tISSUE\tSTATE\tPRIORITY\tWAITING");
+            for e in resp.entries {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    e.project,
+                    e.position,
+                    e.agent_id,
+                    e.issue_id,
+                    e.state,
+                    e.priority,
+                    format_duration_ms(now.saturating_sub(e.enqueued_at_ms))
+                );
+            }
+            Ok(())
+        }
+        MergeCommand::Cancel { agent_id } => {
+            client::merge_cancel(paths, agent_id.clone()).await?;
+            println!("Removed {agent_id} from the merge queue.");
+            Ok(())
+        }
+        MergeCommand::Prioritize { agent_id, priority } => {
+            client::merge_prioritize(paths, agent_id.clone(), priority).await?;
+            println!("Set merge priority of {agent_id} to {priority}.");
+            Ok(())
+        }
+    }
+}
+
 async fn branch_cleanup(dry_run: bool, local: bool) -> anyhow::Result<()> {
     let cwd = env::current_dir().context("get working directory")?;
 
@@ -2126,7 +2523,7 @@ async fn dispatch_issue(args: IssueArgs, paths: &MurmurPaths) -> anyhow::Result<
         }
         IssueCommand::Show { id } | IssueCommand::Get { id } => {
             let resp = client::issue_get(paths, project, id).await?;
-            let iss = resp.issue;
+            let iss = &resp.issue;
             println!("id\t{}", iss.id);
             println!(
                 "status\t{}",
@@ -2139,6 +2536,7 @@ async fn dispatch_issue(args: IssueArgs, paths: &MurmurPaths) -> anyhow::Result<
             println!("deps\t{}", iss.dependencies.join(","));
             println!("labels\t{}", iss.labels.join(","));
             println!("links\t{}", iss.links.join(","));
+            print_issue_attempts(&resp);
             println!();
             if iss.description.trim().is_empty() {
                 println!("No description");
@@ -2541,7 +2939,7 @@ fn plan_list_stored(paths: &MurmurPaths) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    plans.sort_by(|a, b| b.1.cmp(&a.1));
+    plans.sort_by_key(|p| std::cmp::Reverse(p.1));
 
     let fmt = time::format_description::parse("[year]-[month]-[day] [hour]:[minute]").unwrap();
     println!("ID\tMODIFIED");
@@ -2721,6 +3119,40 @@ async fn status(paths: &MurmurPaths, show_agents: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn print_issue_attempts(resp: &murmur_protocol::IssueGetResponse) {
+    if resp.attempts.is_empty() {
+        return;
+    }
+    let mut summary = if resp.max_attempts == 0 {
+        format!("{} failed", resp.failures)
+    } else {
+        format!("{}/{} failed", resp.failures, resp.max_attempts)
+    };
+    if resp.needs_human {
+        summary.push_str(" (needs human)");
+    } else if let Some(at) = resp.retry_at_ms {
+        let wait = at.saturating_sub(now_ms());
+        summary.push_str(&format!(" (retry in {})", format_duration_ms(wait)));
+    }
+    println!("attempts\t{summary}");
+
+    let now = now_ms();
+    for a in &resp.attempts {
+        let code = a
+            .exit_code
+            .map(|c| format!(" (code {c})"))
+            .unwrap_or_default();
+        let reset = if a.reset { "\t(reset)" } else { "" };
+        let took = a.ended_at_ms.unwrap_or(now).saturating_sub(a.started_at_ms);
+        println!(
+            "  {}\t{}{code}\t{}{reset}",
+            a.agent_id,
+            a.outcome,
+            format_duration_ms(took)
+        );
+    }
+}
+
 fn format_duration_ms(ms: u64) -> String {
     if ms < 1000 {
         return format!("{ms}ms");
@@ -2818,6 +3250,18 @@ fn print_event(evt: murmur_protocol::Event) -> anyhow::Result<()> {
                 req.project, req.agent_id, req.tool_name, primary
             );
         }
+        murmur_protocol::EVT_PERMISSION_EXPIRED => {
+            let evt: murmur_protocol::PermissionExpiredEvent =
+                serde_json::from_value(evt.payload).context("parse permission expired event")?;
+            println!(
+                "[{}:{}]\tpermission-expired\t{}\t{}\tafter={}s",
+                evt.project,
+                evt.agent_id,
+                evt.id,
+                format_permission_behavior(evt.behavior),
+                evt.timeout_secs
+            );
+        }
         murmur_protocol::EVT_USER_QUESTION => {
             let req: murmur_protocol::UserQuestion =
                 serde_json::from_value(evt.payload).context("parse user question")?;
@@ -2836,6 +3280,14 @@ fn print_event(evt: murmur_protocol::Event) -> anyhow::Result<()> {
                 .unwrap_or("-");
             println!("[{}:{}]\tidle", project, agent_id);
         }
+        murmur_protocol::EVT_AGENT_WATCHDOG => {
+            let wd: murmur_protocol::AgentWatchdogEvent =
+                serde_json::from_value(evt.payload).context("parse watchdog event")?;
+            println!(
+                "[{}:{}]\twatchdog\t{}\tstalled={}s\t{}",
+                wd.project, wd.agent_id, wd.action, wd.stalled_secs, wd.issue_id
+            );
+        }
         murmur_protocol::EVT_ORCHESTRATION_TICK_REQUESTED => {
             let tick: murmur_protocol::OrchestrationTickRequestedEvent =
                 serde_json::from_value(evt.payload).context("parse tick event")?;
@@ -0,0 +1,49 @@
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use fs2::FileExt as _;
+use murmur_core::paths::MurmurPaths;
+use murmur_core::permission_log::{parse_permission_log, PermissionLogEntry, PERMISSION_LOG_FILE};
+
+pub fn log_path(paths: &MurmurPaths) -> PathBuf {
+    paths.runtime_dir.join(PERMISSION_LOG_FILE)
+}
+
+/// Appends one entry as a JSON line. Hooks and the daemon write to the same
+/// file, so the write holds an exclusive lock.
+pub async fn append(paths: &MurmurPaths, entry: &PermissionLogEntry) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(entry).context("serialize permission log entry")?;
+    line.push(b'\n');
+
+    tokio::fs::create_dir_all(&paths.runtime_dir)
+        .await
+        .with_context(|| format!("create runtime dir: {}", paths.runtime_dir.display()))?;
+    let path = log_path(paths);
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("open {}", path.display()))?;
+        file.lock_exclusive()
+            .with_context(|| format!("lock {}", path.display()))?;
+        let result = file
+            .write_all(&line)
+            .with_context(|| format!("write {}", path.display()));
+        let _ = file.unlock();
+        result
+    })
+    .await
+    .context("append permission log")?
+}
+
+/// Every entry in the log, oldest first.
+pub async fn read(paths: &MurmurPaths) -> anyhow::Result<Vec<PermissionLogEntry>> {
+    let path = log_path(paths);
+    match tokio::fs::read_to_string(&path).await {
+        Ok(text) => Ok(parse_permission_log(&text)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err).with_context(|| format!("read {}", path.display())),
+    }
+}
@@ -1,28 +1,171 @@
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
 
 use anyhow::{Context as _, Result};
+use murmur_core::agent::AgentRole;
 use murmur_core::paths::{safe_join, MurmurPaths};
-use murmur_core::permissions::{Action, PermissionsFile, Rule};
+use murmur_core::permissions::{
+    lint_rules, parse_script_verdict, permission_checks, rule_matches, validate_rule, Action,
+    PermissionCheck, PermissionsFile, Rule, RuleProblem, ScriptVerdict,
+};
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::Pid;
+use serde_json::Value;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+/// Outcome of walking the rules for one tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decision {
+    pub action: Action,
+    pub matched: bool,
+    pub reason: Option<String>,
+    /// [`Rule::summary`] of each rule that decided a check.
+    pub rules: Vec<String>,
+}
+
+/// Where a loaded rule came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleSource {
+    File { path: PathBuf, index: usize },
+    BuiltIn { index: usize },
+}
+
+impl fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleSource::File { path, index } => write!(f, "{}: rules[{index}]", path.display()),
+            RuleSource::BuiltIn { index } => write!(f, "built-in rules[{index}]"),
+        }
+    }
+}
+
+/// The rules that apply to an agent with this role: the project's, then
+/// the global ones, then the built-in defaults.
+pub async fn load_rules(
+    paths: &MurmurPaths,
+    project: Option<&str>,
+    role: AgentRole,
+) -> Result<Vec<Rule>> {
+    Ok(load_sourced_rules(paths, project)
+        .await?
+        .into_iter()
+        .map(|(rule, _)| rule)
+        .filter(|rule| rule.applies_to(role))
+        .collect())
+}
 
-pub async fn load_rules(paths: &MurmurPaths, project: Option<&str>) -> Result<Vec<Rule>> {
+/// Every rule [`load_rules`] might return, for any role, with where each
+/// rule came from.
+pub async fn load_sourced_rules(
+    paths: &MurmurPaths,
+    project: Option<&str>,
+) -> Result<Vec<(Rule, RuleSource)>> {
     let mut rules = Vec::new();
+    let mut extend = |path: PathBuf, cfg: PermissionsFile| {
+        rules.extend(cfg.rules.into_iter().enumerate().map(|(index, rule)| {
+            let source = RuleSource::File {
+                path: path.clone(),
+                index,
+            };
+            (rule, source)
+        }));
+    };
 
     if let Some(project) = project.map(str::trim).filter(|s| !s.is_empty()) {
         let path = project_permissions_path(paths, project)?;
         if let Some(cfg) = load_permissions_file(&path).await? {
-            rules.extend(cfg.rules);
+            extend(path, cfg);
         }
     }
 
     if let Some(cfg) = load_permissions_file(&paths.permissions_file).await? {
-        rules.extend(cfg.rules);
+        extend(paths.permissions_file.clone(), cfg);
     }
 
-    rules.extend(default_rules());
+    rules.extend(
+        default_rules()
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| (rule, RuleSource::BuiltIn { index })),
+    );
 
     Ok(rules)
 }
 
+/// A problem [`lint`] found, with the rule and where it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub source: RuleSource,
+    pub rule: Rule,
+    pub message: String,
+}
+
+/// Lints the global rules, or a project's rules as they apply ahead of the
+/// global ones. Built-in rules are not reported, and a project lint only
+/// reports the project's own rules.
+pub async fn lint(paths: &MurmurPaths, project: Option<&str>) -> Result<Vec<LintFinding>> {
+    let rules = load_sourced_rules(paths, project).await?;
+    let only = match project {
+        Some(project) => project_permissions_path(paths, project)?,
+        None => paths.permissions_file.clone(),
+    };
+
+    let plain: Vec<Rule> = rules.iter().map(|(rule, _)| rule.clone()).collect();
+    let findings = lint_rules(&plain)
+        .into_iter()
+        .filter_map(|lint| {
+            let (rule, source) = &rules[lint.index];
+            if !matches!(source, RuleSource::File { path, .. } if *path == only) {
+                return None;
+            }
+            let message = match lint.problem {
+                RuleProblem::Unreachable { shadowed_by } => {
+                    let (earlier, earlier_source) = &rules[shadowed_by];
+                    format!(
+                        "unreachable: {earlier_source} ({}) always decides first",
+                        earlier.summary()
+                    )
+                }
+                problem => problem.message().to_owned(),
+            };
+            Some(LintFinding {
+                source: source.clone(),
+                rule: rule.clone(),
+                message,
+            })
+        })
+        .collect();
+    Ok(findings)
+}
+
+/// Projects that have their own permissions file.
+pub async fn projects_with_rules(paths: &MurmurPaths) -> Result<Vec<String>> {
+    let mut entries = match tokio::fs::read_dir(&paths.projects_dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("read {}", paths.projects_dir.display()))
+        }
+    };
+
+    let mut projects = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+            continue;
+        };
+        if tokio::fs::try_exists(entry.path().join("permissions.toml"))
+            .await
+            .unwrap_or(false)
+        {
+            projects.push(name);
+        }
+    }
+    projects.sort();
+    Ok(projects)
+}
+
 pub async fn load_manager_allowed_patterns(paths: &MurmurPaths) -> Result<Vec<String>> {
     const DEFAULT: &[&str] = &["mm:*"];
 
@@ -73,6 +216,165 @@ pub async fn load_director_allowed_patterns(paths: &MurmurPaths) -> Result<Vec<S
     }
 }
 
+/// Evaluates rules in order for each of the call's permission checks,
+/// running script rules with the hook input JSON on stdin (plus a `check`
+/// object naming the tool and value being checked). A script that fails or
+/// times out denies the call. The first denied check denies the call; it is
+/// allowed only if every check is.
+pub async fn evaluate(
+    rules: &[Rule],
+    tool_name: &str,
+    tool_input: &Value,
+    cwd: &str,
+    home_dir: &str,
+    hook_input: &Value,
+) -> Decision {
+    let pass = Decision {
+        action: Action::Pass,
+        matched: false,
+        reason: None,
+        rules: Vec::new(),
+    };
+    // Commands that cannot be parsed go to the approval path.
+    let Ok(checks) = permission_checks(tool_name, tool_input, cwd, home_dir) else {
+        return pass;
+    };
+
+    let mut reasons = Vec::new();
+    let mut decided_by = Vec::new();
+    let mut all_allowed = true;
+    for check in &checks {
+        let decision = evaluate_check(rules, check, cwd, home_dir, hook_input).await;
+        match decision.action {
+            Action::Deny => return decision,
+            Action::Allow => {
+                reasons.extend(decision.reason);
+                for rule in decision.rules {
+                    if !decided_by.contains(&rule) {
+                        decided_by.push(rule);
+                    }
+                }
+            }
+            Action::Pass => all_allowed = false,
+        }
+    }
+
+    if all_allowed {
+        Decision {
+            action: Action::Allow,
+            matched: true,
+            reason: Some(reasons.join("; ")).filter(|s| !s.is_empty()),
+            rules: decided_by,
+        }
+    } else {
+        pass
+    }
+}
+
+async fn evaluate_check(
+    rules: &[Rule],
+    check: &PermissionCheck,
+    cwd: &str,
+    home_dir: &str,
+    hook_input: &Value,
+) -> Decision {
+    for rule in rules {
+        if !rule_matches(rule, &check.tool_name, &check.primary_field, cwd, home_dir) {
+            continue;
+        }
+
+        let (action, reason) = match rule.script() {
+            Some(script) => {
+                let mut input = hook_input.clone();
+                if let Some(obj) = input.as_object_mut() {
+                    obj.insert(
+                        "check".to_owned(),
+                        serde_json::json!({
+                            "tool_name": check.tool_name,
+                            "value": check.primary_field,
+                        }),
+                    );
+                }
+                match run_script(script, rule.script_timeout_secs(), cwd, &input).await {
+                    Ok(ScriptVerdict { action, reason }) => (action, reason),
+                    Err(err) => (
+                        Action::Deny,
+                        Some(format!("permission script `{script}` failed: {err:#}")),
+                    ),
+                }
+            }
+            None => (rule.action, None),
+        };
+
+        if action == Action::Pass {
+            continue;
+        }
+        return Decision {
+            action,
+            matched: true,
+            reason,
+            rules: vec![rule.summary()],
+        };
+    }
+
+    Decision {
+        action: Action::Pass,
+        matched: false,
+        reason: None,
+        rules: Vec::new(),
+    }
+}
+
+async fn run_script(
+    script: &str,
+    timeout_secs: u64,
+    cwd: &str,
+    hook_input: &Value,
+) -> Result<ScriptVerdict> {
+    // Own process group, so a timeout also takes down whatever the script
+    // started.
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.arg("-c")
+        .arg(script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .process_group(0)
+        .kill_on_drop(true);
+    if !cwd.is_empty() && Path::new(cwd).is_dir() {
+        cmd.current_dir(cwd);
+    }
+    let mut child = cmd.spawn().context("spawn")?;
+    let mut stdin = child.stdin.take().context("script stdin")?;
+    let mut stdout = child.stdout.take().context("script stdout")?;
+
+    let input = serde_json::to_vec(hook_input).context("serialize hook input")?;
+    let mut out = Vec::new();
+    // The input is written inside the timeout: a script that neither reads it
+    // nor exits would otherwise block a large write forever.
+    let finished = tokio::time::timeout(Duration::from_secs(timeout_secs), async {
+        let write = async move {
+            // A script that decides without reading stdin closes the pipe early.
+            let _ = stdin.write_all(&input).await;
+        };
+        let (_, _, status) = tokio::join!(write, stdout.read_to_end(&mut out), child.wait());
+        status
+    })
+    .await;
+    let status = match finished {
+        Ok(status) => status.context("wait")?,
+        Err(_) => {
+            if let Some(pid) = child.id() {
+                let _ = killpg(Pid::from_raw(pid as i32), Signal::SIGKILL);
+            }
+            let _ = child.wait().await;
+            anyhow::bail!("timed out after {timeout_secs}s");
+        }
+    };
+
+    parse_script_verdict(status.code(), &String::from_utf8_lossy(&out)).map_err(anyhow::Error::msg)
+}
+
 fn default_rules() -> Vec<Rule> {
     fn rule(tool: &str, action: Action, patterns: &[&str]) -> Rule {
         Rule {
@@ -80,7 +382,10 @@ fn default_rules() -> Vec<Rule> {
             action,
             pattern: None,
             patterns: patterns.iter().map(|s| s.to_string()).collect(),
+            match_kind: None,
             script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
         }
     }
 
@@ -169,6 +474,51 @@ fn default_rules() -> Vec<Rule> {
     ]
 }
 
+/// Appends rules to the project's permissions file, or to the global one
+/// when `project` is `None`, under a `# note` comment. Existing content is
+/// kept as written. Returns the file's path.
+pub async fn append_rules(
+    paths: &MurmurPaths,
+    project: Option<&str>,
+    rules: &[Rule],
+    note: &str,
+) -> Result<PathBuf> {
+    let path = match project {
+        Some(project) => project_permissions_path(paths, project)?,
+        None => paths.permissions_file.clone(),
+    };
+
+    let mut content = match tokio::fs::read_to_string(&path).await {
+        Ok(s) => s,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+    };
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(&format!("# {note}\n"));
+    content.push_str(
+        &toml::to_string(&PermissionsFile {
+            rules: rules.to_vec(),
+            ..PermissionsFile::default()
+        })
+        .context("serialize rules")?,
+    );
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("create {}", parent.display()))?;
+    }
+    tokio::fs::write(&path, content)
+        .await
+        .with_context(|| format!("write {}", path.display()))?;
+    Ok(path)
+}
+
 fn project_permissions_path(paths: &MurmurPaths, project: &str) -> Result<PathBuf> {
     let project_dir = safe_join(&paths.projects_dir, project)
         .map_err(|e| anyhow::anyhow!("invalid project name {project:?}: {e}"))?;
@@ -180,6 +530,11 @@ async fn load_permissions_file(path: &Path) -> Result<Option<PermissionsFile>> {
         Ok(s) => {
             let cfg: PermissionsFile =
                 toml::from_str(&s).with_context(|| format!("parse {}", path.display()))?;
+            for (index, rule) in cfg.rules.iter().enumerate() {
+                validate_rule(rule).map_err(|err| {
+                    anyhow::anyhow!("{}: rules[{index}] ({}): {err}", path.display(), rule.tool)
+                })?;
+            }
             Ok(Some(cfg))
         }
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -237,19 +592,148 @@ pattern = "rm :*"
         .await
         .unwrap();
 
-        let rules = load_rules(&paths, Some("demo")).await.unwrap();
+        let rules = load_rules(&paths, Some("demo"), AgentRole::Coding)
+            .await
+            .unwrap();
         assert!(rules.len() >= 2);
         assert_eq!(rules[0].tool, "Bash");
         assert_eq!(rules[0].action, murmur_core::permissions::Action::Deny);
         assert_eq!(rules[1].action, murmur_core::permissions::Action::Allow);
     }
 
+    #[tokio::test]
+    async fn loads_rules_for_the_agent_role() {
+        let dir = TempDir::new().unwrap();
+        let paths = test_paths(&dir);
+
+        tokio::fs::create_dir_all(paths.permissions_file.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(
+            &paths.permissions_file,
+            r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "mm project:*"
+roles = ["coding"]
+
+[[rules]]
+tool = "Write"
+action = "deny"
+roles = ["planner", "manager"]
+"#,
+        )
+        .await
+        .unwrap();
+
+        let first = |role| {
+            let paths = paths.clone();
+            async move { load_rules(&paths, None, role).await.unwrap().remove(0) }
+        };
+        assert_eq!(first(AgentRole::Coding).await.tool, "Bash");
+        assert_eq!(first(AgentRole::Planner).await.tool, "Write");
+        assert_eq!(first(AgentRole::Manager).await.tool, "Write");
+        assert_eq!(first(AgentRole::Director).await.tool, "TodoWrite");
+
+        let rules = load_sourced_rules(&paths, None).await.unwrap();
+        assert_eq!(rules[1].0.roles, [AgentRole::Planner, AgentRole::Manager]);
+        assert_eq!(
+            rules[1].1,
+            RuleSource::File {
+                path: paths.permissions_file.clone(),
+                index: 1
+            }
+        );
+        assert_eq!(rules[1].0.summary(), "Write deny for planner, manager");
+    }
+
+    #[tokio::test]
+    async fn invalid_patterns_fail_to_load() {
+        let dir = TempDir::new().unwrap();
+        let paths = test_paths(&dir);
+
+        tokio::fs::create_dir_all(paths.permissions_file.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(
+            &paths.permissions_file,
+            r#"
+[[rules]]
+tool = "Bash"
+action = "allow"
+match = "regex"
+pattern = "cargo (build"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let err = load_rules(&paths, None, AgentRole::Coding)
+            .await
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("rules[0] (Bash): invalid pattern"),
+            "{err:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn append_rules_keeps_existing_content() {
+        let dir = TempDir::new().unwrap();
+        let paths = test_paths(&dir);
+        let rule = |pattern: &str| Rule {
+            tool: "Bash".to_owned(),
+            action: Action::Allow,
+            pattern: Some(pattern.to_owned()),
+            patterns: Vec::new(),
+            match_kind: None,
+            script: None,
+            timeout_secs: None,
+            roles: Vec::new(),
+        };
+
+        let path = append_rules(&paths, Some("demo"), &[rule("make build")], "first")
+            .await
+            .unwrap();
+        assert_eq!(path, project_permissions_path(&paths, "demo").unwrap());
+
+        let existing = "# keep me\n[manager]\nallowed_patterns = [\"mm:*\"]";
+        tokio::fs::create_dir_all(paths.permissions_file.parent().unwrap())
+            .await
+            .unwrap();
+        tokio::fs::write(&paths.permissions_file, existing)
+            .await
+            .unwrap();
+        append_rules(&paths, None, &[rule("make lint")], "second")
+            .await
+            .unwrap();
+
+        let global = tokio::fs::read_to_string(&paths.permissions_file)
+            .await
+            .unwrap();
+        assert!(global.starts_with(existing), "{global}");
+        assert!(global.contains("# second\n[[rules]]"), "{global}");
+
+        let rules = load_rules(&paths, Some("demo"), AgentRole::Coding)
+            .await
+            .unwrap();
+        assert_eq!(rules[0], rule("make build"));
+        assert_eq!(rules[1], rule("make lint"));
+        assert_eq!(
+            load_manager_allowed_patterns(&paths).await.unwrap(),
+            ["mm:*"]
+        );
+    }
+
     #[tokio::test]
     async fn missing_files_are_ok() {
         let dir = TempDir::new().unwrap();
         let paths = test_paths(&dir);
 
-        let rules = load_rules(&paths, Some("demo")).await.unwrap();
+        let rules = load_rules(&paths, Some("demo"), AgentRole::Coding)
+            .await
+            .unwrap();
         assert!(
             rules
                 .iter()
@@ -258,6 +742,90 @@ pattern = "rm :*"
         );
     }
 
+    #[tokio::test]
+    async fn script_rules_decide_with_hook_input() {
+        let dir = TempDir::new().unwrap();
+        let script = dir.path().join("check-push.sh");
+        tokio::fs::write(
+            &script,
+            "input=$(cat)\n\
+             case \"$input\" in\n\
+             *--force*murmur/*) echo allow ;;\n\
+             *--force*) echo 'deny force push outside murmur/*' ;;\n\
+             *) exit 2 ;;\n\
+             esac\n",
+        )
+        .await
+        .unwrap();
+
+        let script_rule = |script: String, timeout_secs: Option<u64>| Rule {
+            tool: "Bash".to_owned(),
+            action: Action::Deny,
+            pattern: Some("git push:*".to_owned()),
+            patterns: Vec::new(),
+            match_kind: None,
+            script: Some(script),
+            timeout_secs,
+            roles: Vec::new(),
+        };
+        let mut rules = vec![script_rule(format!("sh {}", script.display()), None)];
+        rules.extend(default_rules());
+
+        let cwd = dir.path().to_string_lossy().into_owned();
+        let decide = |rules: Vec<Rule>, command: &'static str| {
+            let cwd = cwd.clone();
+            async move {
+                let input = serde_json::json!({"command": command});
+                let hook = serde_json::json!({"tool_name": "Bash", "tool_input": input});
+                evaluate(&rules, "Bash", &input, &cwd, "/tmp", &hook).await
+            }
+        };
+
+        let got = decide(rules.clone(), "git push --force origin murmur/a-1").await;
+        assert_eq!((got.action, got.matched), (Action::Allow, true));
+
+        let got = decide(rules.clone(), "git push --force origin main").await;
+        assert_eq!(got.action, Action::Deny);
+        assert_eq!(got.reason.as_deref(), Some("force push outside murmur/*"));
+
+        // `pass` falls through to the built-in `git push` deny.
+        let got = decide(rules.clone(), "git push origin main").await;
+        assert_eq!((got.action, got.reason), (Action::Deny, None));
+
+        let got = decide(rules.clone(), "git status").await;
+        assert_eq!(got.action, Action::Allow);
+
+        // Each command in a list is checked on its own.
+        let got = decide(rules, "git status && git push --force origin main").await;
+        assert_eq!(got.action, Action::Deny);
+        assert_eq!(got.reason.as_deref(), Some("force push outside murmur/*"));
+
+        let got = decide(vec![script_rule("sleep 5".to_owned(), Some(1))], "git push").await;
+        assert_eq!(got.action, Action::Deny);
+        assert!(got.reason.unwrap().contains("timed out after 1s"));
+
+        let got = decide(vec![script_rule("exit 7".to_owned(), None)], "git push").await;
+        assert_eq!(got.action, Action::Deny);
+        assert!(got.reason.unwrap().contains("exited with code 7"));
+    }
+
+    #[tokio::test]
+    async fn script_timeout_covers_unread_input_and_the_process_group() {
+        let dir = TempDir::new().unwrap();
+        let marker = dir.path().join("survived");
+        // Larger than a pipe buffer, as a big `Write` payload would be.
+        let hook = serde_json::json!({"tool_input": {"content": "x".repeat(1 << 20)}});
+        let script = format!("(sleep 3; touch {}) & sleep 30", marker.display());
+
+        let started = std::time::Instant::now();
+        let err = run_script(&script, 1, "", &hook).await.unwrap_err();
+        assert!(err.to_string().contains("timed out after 1s"), "{err:#}");
+        assert!(started.elapsed() < Duration::from_secs(3));
+
+        tokio::time::sleep(Duration::from_secs(4)).await;
+        assert!(!marker.exists(), "background job outlived the timeout");
+    }
+
     #[tokio::test]
     async fn manager_allowed_patterns_defaults_when_missing() {
         let dir = TempDir::new().unwrap();
@@ -293,7 +861,7 @@ allowed_patterns = ["mm:*", "git :*"]
     async fn default_rules_deny_dangerous_mm_commands() {
         let dir = TempDir::new().unwrap();
         let paths = test_paths(&dir);
-        let rules = load_rules(&paths, None).await.unwrap();
+        let rules = load_rules(&paths, None, AgentRole::Coding).await.unwrap();
 
         // Verify dangerous commands are denied
         let dangerous_commands = [
@@ -326,7 +894,7 @@ allowed_patterns = ["mm:*", "git :*"]
     async fn default_rules_allow_safe_mm_commands() {
         let dir = TempDir::new().unwrap();
         let paths = test_paths(&dir);
-        let rules = load_rules(&paths, None).await.unwrap();
+        let rules = load_rules(&paths, None, AgentRole::Coding).await.unwrap();
 
         // Verify safe commands are still allowed
         let safe_commands = [
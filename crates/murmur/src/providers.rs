@@ -3,6 +3,7 @@ use std::env;
 use murmur_core::config::ConfigFile;
 
 const DEFAULT_GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
+const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
 const DEFAULT_LINEAR_GRAPHQL_URL: &str = "https://api.linear.app/graphql";
 const DEFAULT_ANTHROPIC_API_URL: &str = "https://api.anthropic.com";
 const DEFAULT_OPENAI_API_URL: &str = "https://api.openai.com";
@@ -20,6 +21,54 @@ pub fn github_graphql_url(config: &ConfigFile) -> String {
         .unwrap_or_else(|| DEFAULT_GITHUB_GRAPHQL_URL.to_owned())
 }
 
+pub fn gitea_token(config: &ConfigFile) -> Option<String> {
+    provider_string(config, "gitea", &["token", "api-key", "api_key"])
+        .or_else(|| env::var("GITEA_TOKEN").ok())
+}
+
+/// Gitea and Forgejo are self-hosted only, so there is no default instance.
+pub fn gitea_url(config: &ConfigFile) -> Option<String> {
+    env::var("GITEA_URL")
+        .ok()
+        .or_else(|| provider_string(config, "gitea", &["url", "base-url", "base_url"]))
+        .filter(|s| !s.trim().is_empty())
+}
+
+pub fn gitlab_token(config: &ConfigFile) -> Option<String> {
+    provider_string(config, "gitlab", &["token", "api-key", "api_key"])
+        .or_else(|| env::var("GITLAB_TOKEN").ok())
+}
+
+/// The instance root, not the API endpoint; self-hosted GitLab sets this.
+pub fn gitlab_url(config: &ConfigFile) -> String {
+    env::var("GITLAB_URL")
+        .ok()
+        .or_else(|| provider_string(config, "gitlab", &["url", "base-url", "base_url"]))
+        .unwrap_or_else(|| DEFAULT_GITLAB_URL.to_owned())
+}
+
+/// Jira Cloud sites and Data Center instances differ per team, so there is
+/// no default.
+pub fn jira_url(config: &ConfigFile) -> Option<String> {
+    env::var("JIRA_URL")
+        .ok()
+        .or_else(|| provider_string(config, "jira", &["url", "base-url", "base_url"]))
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Jira Cloud pairs the API token with the account email; without one the
+/// token is sent as a bearer token.
+pub fn jira_email(config: &ConfigFile) -> Option<String> {
+    provider_string(config, "jira", &["email", "user"])
+        .or_else(|| env::var("JIRA_EMAIL").ok())
+        .filter(|s| !s.trim().is_empty())
+}
+
+pub fn jira_token(config: &ConfigFile) -> Option<String> {
+    provider_string(config, "jira", &["token", "api-token", "api_token"])
+        .or_else(|| env::var("JIRA_API_TOKEN").ok())
+}
+
 pub fn linear_api_key(config: &ConfigFile) -> Option<String> {
     provider_string(config, "linear", &["api-key", "api_key", "token"])
         .or_else(|| env::var("LINEAR_API_KEY").ok())
@@ -56,6 +105,19 @@ pub fn openai_api_url(config: &ConfigFile) -> String {
         .unwrap_or_else(|| DEFAULT_OPENAI_API_URL.to_owned())
 }
 
+/// The `local` provider has no default endpoint.
+pub fn local_llm_api_url(config: &ConfigFile) -> Option<String> {
+    env::var("LOCAL_LLM_API_URL")
+        .ok()
+        .or_else(|| provider_string(config, "local", &["api-url", "api_url", "url"]))
+        .filter(|s| !s.trim().is_empty())
+}
+
+pub fn local_llm_api_key(config: &ConfigFile) -> Option<String> {
+    provider_string(config, "local", &["api-key", "api_key", "token"])
+        .or_else(|| env::var("LOCAL_LLM_API_KEY").ok())
+}
+
 pub fn llm_auth_provider(config: &ConfigFile) -> Option<String> {
     llm_auth_string(config, &["provider"])
 }
@@ -64,6 +126,18 @@ pub fn llm_auth_model(config: &ConfigFile) -> Option<String> {
     llm_auth_string(config, &["model"])
 }
 
+pub fn llm_auth_constraint(config: &ConfigFile) -> Option<String> {
+    llm_auth_string(config, &["constraint"])
+}
+
+pub fn llm_auth_timeout_secs(config: &ConfigFile) -> Option<u64> {
+    llm_auth_integer(config, &["timeout-secs", "timeout_secs"])
+}
+
+pub fn llm_auth_retries(config: &ConfigFile) -> Option<u32> {
+    llm_auth_integer(config, &["retries"]).and_then(|n| u32::try_from(n).ok())
+}
+
 fn provider_string(config: &ConfigFile, provider: &str, keys: &[&str]) -> Option<String> {
     let table = config.providers.get(provider)?.as_table()?;
     keys.iter()
@@ -83,6 +157,12 @@ fn llm_auth_string(config: &ConfigFile, keys: &[&str]) -> Option<String> {
         .and_then(|s| (!s.trim().is_empty()).then_some(s))
 }
 
+fn llm_auth_integer(config: &ConfigFile, keys: &[&str]) -> Option<u64> {
+    keys.iter()
+        .find_map(|key| config.llm_auth.get(*key).and_then(|v| v.as_integer()))
+        .and_then(|n| u64::try_from(n).ok())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -201,4 +281,37 @@ mod tests {
 
         assert_eq!(llm_auth_provider(&cfg).as_deref(), Some("openai"));
     }
+
+    #[test]
+    fn llm_auth_reads_local_provider_settings() {
+        let raw = r#"
+[llm_auth]
+provider = "local"
+model = "qwen2.5-7b-instruct"
+constraint = "grammar"
+timeout-secs = 5
+retries = 2
+
+[providers.local]
+api-url = "http://127.0.0.1:8081"
+"#;
+        let cfg: ConfigFile = toml::from_str(raw).unwrap();
+        assert_eq!(llm_auth_constraint(&cfg).as_deref(), Some("grammar"));
+        assert_eq!(llm_auth_timeout_secs(&cfg), Some(5));
+        assert_eq!(llm_auth_retries(&cfg), Some(2));
+        assert_eq!(llm_auth_retries(&ConfigFile::default()), None);
+
+        with_env_lock(|| {
+            let prev = env::var("LOCAL_LLM_API_URL").ok();
+            env::remove_var("LOCAL_LLM_API_URL");
+
+            assert_eq!(
+                local_llm_api_url(&cfg).as_deref(),
+                Some("http://127.0.0.1:8081")
+            );
+            assert_eq!(local_llm_api_url(&ConfigFile::default()), None);
+
+            restore_env("LOCAL_LLM_API_URL", prev);
+        });
+    }
 }
@@ -1,4 +1,5 @@
 use anyhow::Context as _;
+use murmur_core::attempts::AttemptLog;
 use murmur_core::paths::MurmurPaths;
 use murmur_protocol::AgentInfo;
 
@@ -6,6 +7,16 @@ pub async fn save_agents(
     paths: &MurmurPaths,
     agents_json: &serde_json::Value,
 ) -> anyhow::Result<()> {
+    let data = serde_json::to_vec_pretty(agents_json).context("serialize agents runtime json")?;
+    write_runtime_file(paths, "agents.json", &data).await
+}
+
+pub async fn save_attempts(paths: &MurmurPaths, log: &AttemptLog) -> anyhow::Result<()> {
+    let data = serde_json::to_vec_pretty(log).context("serialize attempts json")?;
+    write_runtime_file(paths, "attempts.json", &data).await
+}
+
+async fn write_runtime_file(paths: &MurmurPaths, name: &str, data: &[u8]) -> anyhow::Result<()> {
     tokio::fs::create_dir_all(&paths.runtime_dir)
         .await
         .with_context(|| format!("create runtime dir: {}", paths.runtime_dir.display()))?;
@@ -16,12 +27,10 @@ pub async fn save_agents(
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_nanos();
-    let tmp = paths.runtime_dir.join(format!("agents.json.{}.tmp", nonce));
-    let dest = paths.runtime_dir.join("agents.json");
-
-    let data = serde_json::to_vec_pretty(agents_json).context("serialize agents runtime json")?;
+    let tmp = paths.runtime_dir.join(format!("{name}.{nonce}.tmp"));
+    let dest = paths.runtime_dir.join(name);
 
-    tokio::fs::write(&tmp, &data)
+    tokio::fs::write(&tmp, data)
         .await
         .with_context(|| format!("write {}", tmp.display()))?;
     tokio::fs::rename(&tmp, &dest)
@@ -42,3 +51,14 @@ pub async fn load_agents(paths: &MurmurPaths) -> anyhow::Result<Vec<AgentInfo>>
     let infos: Vec<AgentInfo> = serde_json::from_str(&data).with_context(|| "parse agents.json")?;
     Ok(infos)
 }
+
+pub async fn load_attempts(paths: &MurmurPaths) -> anyhow::Result<AttemptLog> {
+    let path = paths.runtime_dir.join("attempts.json");
+    if !path.exists() {
+        return Ok(AttemptLog::default());
+    }
+    let data = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| "parse attempts.json")
+}
@@ -5,7 +5,7 @@ use tokio::task::JoinHandle;
 
 use murmur_protocol::{
     AgentChatHistoryResponse, AgentListResponse, CommitListResponse, PermissionBehavior,
-    PermissionListResponse, PlanStartResponse, ProjectListResponse, StatsResponse,
+    PermissionListResponse, PermissionScope, PlanStartResponse, ProjectListResponse, StatsResponse,
     UserQuestionListResponse,
 };
 use std::collections::BTreeMap;
@@ -53,7 +53,12 @@ pub trait TuiClient: Send + Sync {
     ) -> Result<CommitListResponse>;
     async fn stats(&self, project: Option<String>) -> Result<StatsResponse>;
     async fn permission_list(&self, project: Option<String>) -> Result<PermissionListResponse>;
-    async fn permission_respond(&self, id: String, behavior: PermissionBehavior) -> Result<()>;
+    async fn permission_respond(
+        &self,
+        id: String,
+        behavior: PermissionBehavior,
+        scope: PermissionScope,
+    ) -> Result<()>;
     async fn question_list(&self, project: Option<String>) -> Result<UserQuestionListResponse>;
     async fn question_respond(&self, id: String, answers: BTreeMap<String, String>) -> Result<()>;
     async fn plan_start(
@@ -2,7 +2,9 @@ use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroUsize;
 
 use lru::LruCache;
-use murmur_protocol::{AgentInfo, PermissionBehavior, PermissionRequest, UserQuestion};
+use murmur_protocol::{
+    AgentInfo, PermissionBehavior, PermissionRequest, PermissionScope, UserQuestion,
+};
 
 use super::chat::{self, ChatBuffer};
 use super::editor::Editor;
@@ -212,6 +214,7 @@ pub enum Msg {
     AgentListLoaded(Result<Vec<AgentInfo>, String>),
     AgentCreated(murmur_protocol::AgentCreatedEvent),
     AgentDeleted(murmur_protocol::AgentDeletedEvent),
+    AgentWatchdog(murmur_protocol::AgentWatchdogEvent),
     AgentChatReceived(murmur_protocol::AgentChatEvent),
     AgentChatHistoryLoaded {
         agent_id: String,
@@ -226,6 +229,7 @@ pub enum Msg {
     ProjectListLoaded(Result<Vec<murmur_protocol::ProjectInfo>, String>),
     PermissionListLoaded(Result<Vec<PermissionRequest>, String>),
     PermissionRequested(PermissionRequest),
+    PermissionExpired(murmur_protocol::PermissionExpiredEvent),
     PermissionRespondFinished {
         id: String,
         result: Result<(), String>,
@@ -283,6 +287,7 @@ pub enum Effect {
     RespondPermission {
         id: String,
         behavior: PermissionBehavior,
+        scope: PermissionScope,
     },
     FetchQuestionList {
         project: Option<String>,
@@ -354,6 +359,14 @@ pub fn reduce(mut model: Model, msg: Msg) -> (Model, Vec<Effect>) {
             if old_frame != new_frame && has_running_agents {
                 model.dirty = true;
             }
+            // Permission expiry countdowns tick once a second
+            let has_countdown = model
+                .pending_permissions
+                .iter()
+                .any(|p| p.expires_at_ms.is_some());
+            if has_countdown && model.now_ms / 1000 != now_ms / 1000 {
+                model.dirty = true;
+            }
 
             model.now_ms = now_ms;
             if model.next_refresh_ms == 0 && model.now_ms > 0 {
@@ -833,6 +846,16 @@ pub fn reduce(mut model: Model, msg: Msg) -> (Model, Vec<Effect>) {
             // When an agent is deleted, refresh the agent list
             effects.push(Effect::FetchAgentList);
         }
+        Msg::AgentWatchdog(evt) => {
+            model.status = Some(format!(
+                "watchdog: {} {} after {}m without progress",
+                evt.action,
+                evt.agent_id,
+                evt.stalled_secs / 60
+            ));
+            // The action may have changed the agent's state or removed it
+            effects.push(Effect::FetchAgentList);
+        }
         Msg::AgentChatReceived(evt) => {
             let (chat_width, chat_height) = chat_viewport(&model);
             let buf = model.chats.get_or_insert_mut(evt.agent_id, ChatBuffer::new);
@@ -918,6 +941,23 @@ pub fn reduce(mut model: Model, msg: Msg) -> (Model, Vec<Effect>) {
                 model.pending_permissions.push(req);
             }
         }
+        Msg::PermissionExpired(evt) => {
+            model.pending_permissions.retain(|p| p.id != evt.id);
+            rebuild_permission_index(&mut model);
+            let behavior = match evt.behavior {
+                PermissionBehavior::Allow => "allowed",
+                PermissionBehavior::Deny => "denied",
+            };
+            let limit = if evt.hook_limit {
+                " (agent hook limit)"
+            } else {
+                ""
+            };
+            model.status = Some(format!(
+                "permission: {} {} for {} after {}s without a response{}",
+                behavior, evt.tool_name, evt.agent_id, evt.timeout_secs, limit
+            ));
+        }
         Msg::PermissionRespondFinished { id, result } => match result {
             Ok(()) => {
                 model.pending_permissions.retain(|p| p.id != id);
@@ -1340,9 +1380,12 @@ fn next_attention(model: &Model) -> Option<PendingAttention> {
 }
 
 fn handle_permission_key(model: &mut Model, effects: &mut Vec<Effect>, ch: char) -> bool {
-    let behavior = match ch {
-        'y' => PermissionBehavior::Allow,
-        'n' => PermissionBehavior::Deny,
+    let (behavior, scope) = match ch {
+        'y' => (PermissionBehavior::Allow, PermissionScope::Once),
+        'a' => (PermissionBehavior::Allow, PermissionScope::Agent),
+        'P' => (PermissionBehavior::Allow, PermissionScope::Project),
+        'A' => (PermissionBehavior::Allow, PermissionScope::Global),
+        'n' => (PermissionBehavior::Deny, PermissionScope::Once),
         _ => return false,
     };
 
@@ -1353,6 +1396,7 @@ fn handle_permission_key(model: &mut Model, effects: &mut Vec<Effect>, ch: char)
     effects.push(Effect::RespondPermission {
         id: req.id,
         behavior,
+        scope,
     });
     true
 }
@@ -1786,6 +1830,8 @@ mod tests {
                 tool_input: serde_json::json!({"command":"echo new"}),
                 tool_use_id: None,
                 requested_at_ms: 20,
+                expires_at_ms: None,
+                timeout_behavior: None,
             },
             PermissionRequest {
                 id: "perm-old".to_owned(),
@@ -1795,15 +1841,28 @@ mod tests {
                 tool_input: serde_json::json!({"command":"echo old"}),
                 tool_use_id: None,
                 requested_at_ms: 10,
+                expires_at_ms: None,
+                timeout_behavior: None,
             },
         ];
 
-        let (_model, effects) = reduce(model, Msg::Action(Action::Char('y')));
+        let (model, effects) = reduce(model, Msg::Action(Action::Char('y')));
+        assert_eq!(
+            effects,
+            vec![Effect::RespondPermission {
+                id: "perm-old".to_owned(),
+                behavior: PermissionBehavior::Allow,
+                scope: PermissionScope::Once,
+            }]
+        );
+
+        let (_model, effects) = reduce(model, Msg::Action(Action::Char('P')));
         assert_eq!(
             effects,
             vec![Effect::RespondPermission {
                 id: "perm-old".to_owned(),
                 behavior: PermissionBehavior::Allow,
+                scope: PermissionScope::Project,
             }]
         );
     }
@@ -1996,4 +2055,61 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn watchdog_event_sets_status_and_refreshes_agents() {
+        let model = Model::new();
+        let (model, effects) = reduce(
+            model,
+            Msg::AgentWatchdog(murmur_protocol::AgentWatchdogEvent {
+                agent_id: "a-3".to_owned(),
+                project: "demo".to_owned(),
+                issue_id: "ISSUE-7".to_owned(),
+                action: "nudge".to_owned(),
+                stalled_secs: 3_600,
+            }),
+        );
+        assert_eq!(
+            model.status.as_deref(),
+            Some("watchdog: nudge a-3 after 60m without progress")
+        );
+        assert_eq!(effects, vec![Effect::FetchAgentList]);
+    }
+
+    #[test]
+    fn permission_expired_event_drops_the_request() {
+        let mut model = Model::new();
+        model.pending_permissions = vec![PermissionRequest {
+            id: "perm-1".to_owned(),
+            agent_id: "a-1".to_owned(),
+            project: "demo".to_owned(),
+            tool_name: "Bash".to_owned(),
+            tool_input: serde_json::json!({"command":"make deploy"}),
+            tool_use_id: None,
+            requested_at_ms: 10,
+            expires_at_ms: Some(60_010),
+            timeout_behavior: Some(PermissionBehavior::Deny),
+        }];
+        model.agents_with_permissions.insert("a-1".to_owned());
+
+        let (model, effects) = reduce(
+            model,
+            Msg::PermissionExpired(murmur_protocol::PermissionExpiredEvent {
+                id: "perm-1".to_owned(),
+                agent_id: "a-1".to_owned(),
+                project: "demo".to_owned(),
+                tool_name: "Bash".to_owned(),
+                behavior: PermissionBehavior::Deny,
+                timeout_secs: 60,
+                hook_limit: false,
+            }),
+        );
+        assert!(model.pending_permissions.is_empty());
+        assert!(model.agents_with_permissions.is_empty());
+        assert_eq!(
+            model.status.as_deref(),
+            Some("permission: denied Bash for a-1 after 60s without a response")
+        );
+        assert!(effects.is_empty());
+    }
 }
@@ -157,6 +157,7 @@ impl TuiClient for DaemonTuiClient {
         &self,
         id: String,
         behavior: murmur_protocol::PermissionBehavior,
+        scope: murmur_protocol::PermissionScope,
     ) -> anyhow::Result<()> {
         crate::client::permission_respond(
             &self.paths,
@@ -165,6 +166,7 @@ impl TuiClient for DaemonTuiClient {
                 behavior,
                 message: None,
                 interrupt: false,
+                scope,
             },
         )
         .await
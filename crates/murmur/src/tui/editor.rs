@@ -96,7 +96,7 @@ pub fn visual_lines(buffer: &str, width: usize) -> usize {
             // Count characters (not bytes) for proper Unicode handling
             let char_count = line.chars().count();
             // Each line takes at least 1 visual line, plus additional lines for wrapping
-            total += (char_count + width - 1) / width;
+            total += char_count.div_ceil(width);
         }
     }
     total.max(1)
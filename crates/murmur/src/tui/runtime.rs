@@ -158,12 +158,24 @@ pub async fn run(client: Arc<dyn TuiClient>) -> Result<()> {
                                     Err(_) => None,
                                 }
                             }
+                            murmur_protocol::EVT_AGENT_WATCHDOG => {
+                                match serde_json::from_value::<murmur_protocol::AgentWatchdogEvent>(event.payload) {
+                                    Ok(evt) => Some(Msg::AgentWatchdog(evt)),
+                                    Err(_) => None,
+                                }
+                            }
                             murmur_protocol::EVT_PERMISSION_REQUEST => {
                                 match serde_json::from_value::<murmur_protocol::PermissionRequest>(event.payload) {
                                     Ok(req) => Some(Msg::PermissionRequested(req)),
                                     Err(_) => None,
                                 }
                             }
+                            murmur_protocol::EVT_PERMISSION_EXPIRED => {
+                                match serde_json::from_value::<murmur_protocol::PermissionExpiredEvent>(event.payload) {
+                                    Ok(evt) => Some(Msg::PermissionExpired(evt)),
+                                    Err(_) => None,
+                                }
+                            }
                             murmur_protocol::EVT_USER_QUESTION => {
                                 match serde_json::from_value::<murmur_protocol::UserQuestion>(event.payload) {
                                     Ok(req) => Some(Msg::QuestionRequested(req)),
@@ -447,12 +459,16 @@ async fn apply_effects(
                     let _ = tx.send(Msg::PlanStopFinished { plan_id, result });
                 });
             }
-            Effect::RespondPermission { id, behavior } => {
+            Effect::RespondPermission {
+                id,
+                behavior,
+                scope,
+            } => {
                 let client = client.clone();
                 let tx = effect_tx.clone();
                 tokio::spawn(async move {
                     let result = client
-                        .permission_respond(id.clone(), behavior)
+                        .permission_respond(id.clone(), behavior, scope)
                         .await
                         .map_err(|err| err.to_string());
                     let _ = tx.send(Msg::PermissionRespondFinished { id, result });
@@ -588,7 +588,7 @@ fn draw_attention_overlay(frame: &mut Frame<'_>, model: &Model, area: Rect) {
             let Some(req) = model.pending_permission_for_selected() else {
                 return;
             };
-            draw_permission_overlay(frame, overlay, req);
+            draw_permission_overlay(frame, model, overlay, req);
         }
         AttentionKind::Question => {
             let Some(req) = model.pending_question_for_selected() else {
@@ -701,6 +701,7 @@ fn draw_planner_picker_overlay(frame: &mut Frame<'_>, model: &Model, area: Rect)
 
 fn draw_permission_overlay(
     frame: &mut Frame<'_>,
+    model: &Model,
     area: Rect,
     req: &murmur_protocol::PermissionRequest,
 ) {
@@ -712,7 +713,7 @@ fn draw_permission_overlay(
     frame.render_widget(block, area);
 
     let summary = permission_summary(req);
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("Agent ", Style::default().fg(Color::Gray)),
             Span::styled(&req.agent_id, Style::default().add_modifier(Modifier::BOLD)),
@@ -727,7 +728,28 @@ fn draw_permission_overlay(
             Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(" deny"),
         ]),
+        Line::from(vec![
+            Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" allow for agent   "),
+            Span::styled("P", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" allow for project   "),
+            Span::styled("A", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" always allow"),
+        ]),
     ];
+    if let (Some(expires_at_ms), Some(behavior)) = (req.expires_at_ms, req.timeout_behavior) {
+        let action = match behavior {
+            murmur_protocol::PermissionBehavior::Allow => "allow",
+            murmur_protocol::PermissionBehavior::Deny => "deny",
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Auto-{action} in {}",
+                format_duration_compact(expires_at_ms.saturating_sub(model.now_ms))
+            ),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
 
     frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
 }
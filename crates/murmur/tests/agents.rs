@@ -10,6 +10,7 @@ use std::time::{Duration, Instant};
 use assert_cmd::cargo::cargo_bin_cmd;
 use nix::sys::signal::{kill, Signal};
 use nix::unistd::Pid;
+use predicates::prelude::*;
 use tempfile::TempDir;
 
 fn read_to_string_best_effort(path: &Path) -> String {
@@ -128,6 +129,14 @@ fn wait_for_daemon_ready(dir: &TempDir) {
 }
 
 fn spawn_daemon(dir: &TempDir, bin_dir: &Path) -> std::process::Child {
+    spawn_daemon_with_env(dir, bin_dir, &[])
+}
+
+fn spawn_daemon_with_env(
+    dir: &TempDir,
+    bin_dir: &Path,
+    envs: &[(&str, &str)],
+) -> std::process::Child {
     let path = {
         let mut parts = Vec::new();
         parts.push(bin_dir.to_string_lossy().to_string());
@@ -140,6 +149,7 @@ fn spawn_daemon(dir: &TempDir, bin_dir: &Path) -> std::process::Child {
     let child = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
         .env("MURMUR_DIR", dir.path())
         .env("PATH", path)
+        .envs(envs.iter().copied())
         .args(["server", "start", "--foreground"])
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -454,3 +464,157 @@ fn agent_codex_backend_resumes_and_records_chat() {
 
     shutdown_daemon(&murmur_dir, daemon);
 }
+
+#[test]
+fn watchdog_nudges_then_flags_a_silent_agent() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = TempDir::new().unwrap();
+    let bin_dir = bins.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    write_executable(
+        &bin_dir.join("claude"),
+        "#!/usr/bin/env bash\ncat > /dev/null\n",
+    );
+    let daemon = spawn_daemon_with_env(
+        &murmur_dir,
+        &bin_dir,
+        &[("FUGUE_WATCHDOG_INTERVAL_MS", "100")],
+    );
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+        "--backend",
+        "claude",
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args([
+        "project",
+        "config",
+        "set",
+        "demo",
+        "watchdog-running-secs",
+        "1",
+    ]);
+    set.assert().success();
+
+    let mut create = cargo_bin_cmd!("mm");
+    create.env("MURMUR_DIR", murmur_dir.path());
+    create.args(["agent", "create", "demo", "ISSUE-1"]);
+    create.assert().success().stdout("a-1\n");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let mut list = cargo_bin_cmd!("mm");
+        list.env("MURMUR_DIR", murmur_dir.path());
+        list.args(["agent", "list"]);
+        let assert = list.assert().success();
+        let out = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+        if out.contains("a-1\tdemo\tcoding\tneeds_resolution") {
+            break;
+        }
+        if Instant::now() > deadline {
+            panic!("timed out waiting for watchdog; agent list was:\n{out}");
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let mut hist = cargo_bin_cmd!("mm");
+    hist.env("MURMUR_DIR", murmur_dir.path());
+    hist.args(["agent", "chat-history", "a-1", "--limit", "50"]);
+    let assert = hist.assert().success();
+    let out = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(
+        out.contains("You have not made progress in a while"),
+        "nudge missing from history:\n{out}"
+    );
+    assert!(
+        out.contains("Watchdog: no progress for"),
+        "needs-resolution note missing from history:\n{out}"
+    );
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn aborted_attempt_backs_off_and_is_listed_by_issue_show() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"));
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+        "--backend",
+        "claude",
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let mut create_issue = cargo_bin_cmd!("mm");
+    create_issue.env("MURMUR_DIR", murmur_dir.path());
+    create_issue.args(["issue", "create", "-p", "demo", "Flaky work"]);
+    let out = create_issue.assert().success().get_output().stdout.clone();
+    let issue_id = String::from_utf8_lossy(&out).trim().to_owned();
+
+    let mut create = cargo_bin_cmd!("mm");
+    create.env("MURMUR_DIR", murmur_dir.path());
+    create.args(["agent", "create", "demo", &issue_id]);
+    create.assert().success().stdout("a-1\n");
+
+    let mut show = cargo_bin_cmd!("mm");
+    show.env("MURMUR_DIR", murmur_dir.path());
+    show.args(["issue", "show", "-p", "demo", &issue_id]);
+    show.assert()
+        .success()
+        .stdout(predicates::str::contains("attempts\t0/3 failed\n"))
+        .stdout(predicates::str::contains("  a-1\trunning\t"));
+
+    let mut abort = cargo_bin_cmd!("mm");
+    abort.env("MURMUR_DIR", murmur_dir.path());
+    abort.args(["agent", "abort", "--yes", "a-1"]);
+    abort.assert().success().stdout("ok\n");
+
+    let mut show = cargo_bin_cmd!("mm");
+    show.env("MURMUR_DIR", murmur_dir.path());
+    show.args(["issue", "show", "-p", "demo", &issue_id]);
+    show.assert()
+        .success()
+        .stdout(predicates::str::contains("attempts\t1/3 failed (retry in "))
+        .stdout(predicates::str::contains("  a-1\taborted\t"));
+
+    let mut ready = cargo_bin_cmd!("mm");
+    ready.env("MURMUR_DIR", murmur_dir.path());
+    ready.args(["issue", "ready", "-p", "demo"]);
+    ready
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(issue_id.as_str()).not());
+
+    let attempts_json =
+        read_to_string_best_effort(&murmur_dir.path().join("runtime").join("attempts.json"));
+    assert!(
+        attempts_json.contains("\"outcome\": \"aborted\""),
+        "attempts.json missing aborted attempt; file was:\n{attempts_json}"
+    );
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
@@ -19,6 +19,7 @@ fn help_includes_top_level_commands() {
         .stdout(has_cmd("director"))
         .stdout(has_cmd("issue"))
         .stdout(has_cmd("manager"))
+        .stdout(has_cmd("merge"))
         .stdout(has_cmd("plan"))
         .stdout(has_cmd("project"))
         .stdout(has_cmd("server"))
@@ -26,6 +26,16 @@ fn run_git(cwd: &Path, args: &[&str]) {
     assert!(status.success(), "git {:?} failed", args);
 }
 
+fn git_stdout(cwd: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
 fn init_local_remote_with_head_main(base: &Path) -> PathBuf {
     let origin = base.join("origin.git");
     run_git(base, &["init", "--bare", origin.to_str().unwrap()]);
@@ -394,6 +404,342 @@ fn agent_done_conflict_marks_needs_resolution_and_keeps_worktree() {
     shutdown_daemon(&murmur_dir, daemon);
 }
 
+#[test]
+fn agent_done_conflict_starts_resolution_turn_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, home_dir.path(), &bins.path().join("bin"));
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    for (key, value) in [
+        ("conflict-resolution", "agent"),
+        ("conflict-max-attempts", "1"),
+    ] {
+        let mut set = cargo_bin_cmd!("mm");
+        set.env("MURMUR_DIR", murmur_dir.path());
+        set.args(["project", "config", "set", "demo", key, value]);
+        set.assert().success();
+    }
+
+    let repo_dir = murmur_dir.path().join("projects").join("demo").join("repo");
+    run_git(&repo_dir, &["config", "user.name", "Test"]);
+    run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+    let mut issue = cargo_bin_cmd!("mm");
+    issue.env("MURMUR_DIR", murmur_dir.path());
+    issue.args(["issue", "create", "-p", "demo", "Conflicting issue"]);
+    let issue_id = String::from_utf8_lossy(&issue.assert().success().get_output().stdout)
+        .trim()
+        .to_owned();
+
+    let mut create = cargo_bin_cmd!("mm");
+    create.env("MURMUR_DIR", murmur_dir.path());
+    create.args(["agent", "create", "demo", &issue_id]);
+    create.assert().success().stdout("a-1\n");
+
+    let worktree_dir = murmur_dir
+        .path()
+        .join("projects")
+        .join("demo")
+        .join("worktrees")
+        .join("wt-a-1");
+    fs::write(worktree_dir.join("README.md"), "agent edit\n").unwrap();
+    run_git(&worktree_dir, &["add", "README.md"]);
+    run_git(&worktree_dir, &["commit", "-m", "agent: edit readme"]);
+
+    let upstream = tmp.path().join("upstream");
+    run_git(
+        tmp.path(),
+        &[
+            "clone",
+            origin.to_str().unwrap(),
+            upstream.to_str().unwrap(),
+        ],
+    );
+    run_git(&upstream, &["checkout", "main"]);
+    run_git(&upstream, &["config", "user.name", "Test"]);
+    run_git(&upstream, &["config", "user.email", "test@example.com"]);
+    fs::write(upstream.join("README.md"), "upstream edit\n").unwrap();
+    run_git(&upstream, &["add", "README.md"]);
+    run_git(&upstream, &["commit", "-m", "upstream: edit readme"]);
+    run_git(&upstream, &["push", "origin", "main"]);
+
+    let mut done = cargo_bin_cmd!("mm");
+    done.env("MURMUR_DIR", murmur_dir.path());
+    done.env("MURMUR_AGENT_ID", "a-1");
+    done.args(["agent", "done"]);
+    done.assert().failure().stderr(predicates::str::contains(
+        "merge conflict on murmur/a-1 (resolution attempt 1/1; sent to agent)",
+    ));
+
+    let mut list = cargo_bin_cmd!("mm");
+    list.env("MURMUR_DIR", murmur_dir.path());
+    list.args(["agent", "list"]);
+    list.assert()
+        .success()
+        .stdout(predicates::str::contains("needs_resolution").not());
+
+    let mut hist = cargo_bin_cmd!("mm");
+    hist.env("MURMUR_DIR", murmur_dir.path());
+    hist.args(["agent", "chat-history", "a-1", "--limit", "50"]);
+    hist.assert()
+        .success()
+        .stdout(predicates::str::contains("- README.md"))
+        .stdout(predicates::str::contains("upstream: edit readme"));
+
+    // Out of attempts: the next conflict waits for a human.
+    let mut done = cargo_bin_cmd!("mm");
+    done.env("MURMUR_DIR", murmur_dir.path());
+    done.env("MURMUR_AGENT_ID", "a-1");
+    done.args(["agent", "done"]);
+    done.assert().failure().stderr(predicates::str::contains(
+        "merge conflict (agent needs resolution)",
+    ));
+
+    let mut list = cargo_bin_cmd!("mm");
+    list.env("MURMUR_DIR", murmur_dir.path());
+    list.args(["agent", "list"]);
+    list.assert().success().stdout(predicates::str::contains(
+        "a-1\tdemo\tcoding\tneeds_resolution\t",
+    ));
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn agent_done_rejects_merge_when_verification_fails() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, home_dir.path(), &bins.path().join("bin"));
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args([
+        "project",
+        "config",
+        "set",
+        "demo",
+        "verify-commands",
+        "echo checking; test -f agent.txt",
+    ]);
+    set.assert().success();
+
+    let repo_dir = murmur_dir.path().join("projects").join("demo").join("repo");
+    run_git(&repo_dir, &["config", "user.name", "Test"]);
+    run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+    let mut issue = cargo_bin_cmd!("mm");
+    issue.env("MURMUR_DIR", murmur_dir.path());
+    issue.args(["issue", "create", "-p", "demo", "Verified issue"]);
+    let issue_id = String::from_utf8_lossy(&issue.assert().success().get_output().stdout)
+        .trim()
+        .to_owned();
+
+    let mut create = cargo_bin_cmd!("mm");
+    create.env("MURMUR_DIR", murmur_dir.path());
+    create.args(["agent", "create", "demo", &issue_id]);
+    create.assert().success().stdout("a-1\n");
+
+    let worktree_dir = murmur_dir
+        .path()
+        .join("projects")
+        .join("demo")
+        .join("worktrees")
+        .join("wt-a-1");
+    fs::write(worktree_dir.join("other.txt"), "wrong file\n").unwrap();
+    run_git(&worktree_dir, &["add", "."]);
+    run_git(&worktree_dir, &["commit", "-m", "agent: add other.txt"]);
+
+    let mut done = cargo_bin_cmd!("mm");
+    done.env("MURMUR_DIR", murmur_dir.path());
+    done.env("MURMUR_AGENT_ID", "a-1");
+    done.args(["agent", "done"]);
+    done.assert().failure().stderr(predicates::str::contains(
+        "verification failed: `test -f agent.txt` exited with code 1 (round 1/3",
+    ));
+
+    let mut list = cargo_bin_cmd!("mm");
+    list.env("MURMUR_DIR", murmur_dir.path());
+    list.args(["agent", "list"]);
+    list.assert()
+        .success()
+        .stdout(predicates::str::contains("needs_resolution").not());
+
+    let mut hist = cargo_bin_cmd!("mm");
+    hist.env("MURMUR_DIR", murmur_dir.path());
+    hist.args(["agent", "chat-history", "a-1", "--limit", "50"]);
+    hist.assert().success().stdout(predicates::str::contains(
+        "Merge rejected: verification failed (round 1/3)",
+    ));
+
+    fs::write(worktree_dir.join("agent.txt"), "from agent\n").unwrap();
+    run_git(&worktree_dir, &["add", "."]);
+    run_git(&worktree_dir, &["commit", "-m", "agent: add agent.txt"]);
+
+    let mut done = cargo_bin_cmd!("mm");
+    done.env("MURMUR_DIR", murmur_dir.path());
+    done.env("MURMUR_AGENT_ID", "a-1");
+    done.args(["agent", "done"]);
+    done.assert().success().stdout("ok\n");
+
+    let inspect = tmp.path().join("inspect");
+    run_git(
+        tmp.path(),
+        &["clone", origin.to_str().unwrap(), inspect.to_str().unwrap()],
+    );
+    run_git(&inspect, &["checkout", "main"]);
+    assert!(inspect.join("agent.txt").exists());
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn agent_done_squashes_or_merges_with_templated_message() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, home_dir.path(), &bins.path().join("bin"));
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let repo_dir = murmur_dir.path().join("projects").join("demo").join("repo");
+    run_git(&repo_dir, &["config", "user.name", "Test"]);
+    run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args([
+        "project",
+        "config",
+        "set",
+        "demo",
+        "merge-message-template",
+        "{issue_id}: {title}\n\nAgent: {agent_id}",
+    ]);
+    set.assert().success();
+
+    let inspect = tmp.path().join("inspect");
+    run_git(
+        tmp.path(),
+        &["clone", origin.to_str().unwrap(), inspect.to_str().unwrap()],
+    );
+
+    for (n, strategy) in [(1, "squash"), (2, "merge-commit")] {
+        let mut set = cargo_bin_cmd!("mm");
+        set.env("MURMUR_DIR", murmur_dir.path());
+        set.args([
+            "project",
+            "config",
+            "set",
+            "demo",
+            "merge-strategy",
+            strategy,
+        ]);
+        set.assert().success();
+
+        let mut issue = cargo_bin_cmd!("mm");
+        issue.env("MURMUR_DIR", murmur_dir.path());
+        issue.args([
+            "issue",
+            "create",
+            "-p",
+            "demo",
+            &format!("Land via {strategy}"),
+        ]);
+        let issue_id = String::from_utf8_lossy(&issue.assert().success().get_output().stdout)
+            .trim()
+            .to_owned();
+
+        let agent_id = format!("a-{n}");
+        let mut create = cargo_bin_cmd!("mm");
+        create.env("MURMUR_DIR", murmur_dir.path());
+        create.args(["agent", "create", "demo", &issue_id]);
+        create.assert().success().stdout(format!("{agent_id}\n"));
+
+        let worktree_dir = murmur_dir
+            .path()
+            .join("projects")
+            .join("demo")
+            .join("worktrees")
+            .join(format!("wt-{agent_id}"));
+        for part in ["one", "two"] {
+            fs::write(worktree_dir.join(format!("{strategy}-{part}.txt")), part).unwrap();
+            run_git(&worktree_dir, &["add", "."]);
+            run_git(&worktree_dir, &["commit", "-m", &format!("agent: {part}")]);
+        }
+
+        let mut done = cargo_bin_cmd!("mm");
+        done.env("MURMUR_DIR", murmur_dir.path());
+        done.env("MURMUR_AGENT_ID", &agent_id);
+        done.args(["agent", "done"]);
+        done.assert().success().stdout("ok\n");
+
+        run_git(&inspect, &["fetch", "origin"]);
+        let subject = format!("{issue_id}: Land via {strategy}");
+        let log = git_stdout(&inspect, &["log", "--format=%H %P%x09%s", "origin/main"]);
+        let line = log
+            .lines()
+            .find(|l| l.ends_with(&format!("\t{subject}")))
+            .unwrap_or_else(|| panic!("no {strategy} commit in:\n{log}"));
+        let sha = line.split(' ').next().unwrap();
+        let parents = line.split('\t').next().unwrap().split(' ').count() - 1;
+        assert_eq!(parents, if strategy == "squash" { 1 } else { 2 });
+
+        let body = git_stdout(&inspect, &["log", "-1", "--format=%B", sha]);
+        assert_eq!(body.trim(), format!("{subject}\n\nAgent: {agent_id}"));
+
+        let branch = format!("murmur/{agent_id}");
+        let agent_commits = git_stdout(
+            &repo_dir,
+            &["log", "--format=%s", &format!("{sha}~1..{branch}")],
+        );
+        assert_eq!(agent_commits, "agent: two\nagent: one\n");
+    }
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
 #[test]
 fn concurrent_agent_done_is_serialized_per_project() {
     use std::sync::{Arc, Barrier};
@@ -525,3 +871,159 @@ fn concurrent_agent_done_is_serialized_per_project() {
 
     shutdown_daemon(&murmur_dir, daemon);
 }
+
+fn merge_queue_output(murmur_dir: &Path) -> String {
+    let mut queue = cargo_bin_cmd!("mm");
+    queue.env("MURMUR_DIR", murmur_dir);
+    queue.args(["merge", "queue", "-p", "demo"]);
+    String::from_utf8_lossy(&queue.assert().success().get_output().stdout).into_owned()
+}
+
+fn wait_for_merge_queue(murmur_dir: &Path, ready: impl Fn(&str) -> bool) -> String {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let out = merge_queue_output(murmur_dir);
+        if ready(&out) {
+            return out;
+        }
+        assert!(Instant::now() < deadline, "merge queue never ready:\n{out}");
+        std::thread::sleep(Duration::from_millis(25));
+    }
+}
+
+fn queue_state(out: &str, agent_id: &str) -> Option<String> {
+    out.lines()
+        .map(|l| l.split('\t').collect::<Vec<_>>())
+        .find(|cols| cols.get(2) == Some(&agent_id))
+        .map(|cols| cols[4].to_owned())
+}
+
+#[test]
+fn merge_queue_reorders_cancels_and_batches_waiting_agents() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon =
+        spawn_daemon_with_merge_delay(&murmur_dir, home_dir.path(), &bins.path().join("bin"), 1500);
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args(["project", "config", "set", "demo", "merge-batch-size", "2"]);
+    set.assert().success();
+
+    let repo_dir = murmur_dir.path().join("projects").join("demo").join("repo");
+    run_git(&repo_dir, &["config", "user.name", "Test"]);
+    run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+    for n in 1..=4 {
+        let mut issue = cargo_bin_cmd!("mm");
+        issue.env("MURMUR_DIR", murmur_dir.path());
+        issue.args(["issue", "create", "-p", "demo", &format!("Issue {n}")]);
+        let issue_id = String::from_utf8_lossy(&issue.assert().success().get_output().stdout)
+            .trim()
+            .to_owned();
+
+        let mut create = cargo_bin_cmd!("mm");
+        create.env("MURMUR_DIR", murmur_dir.path());
+        create.args(["agent", "create", "demo", &issue_id]);
+        create.assert().success().stdout(format!("a-{n}\n"));
+
+        let wt = murmur_dir
+            .path()
+            .join("projects")
+            .join("demo")
+            .join("worktrees")
+            .join(format!("wt-a-{n}"));
+        fs::write(wt.join(format!("a{n}.txt")), format!("a{n}\n")).unwrap();
+        run_git(&wt, &["add", "."]);
+        run_git(&wt, &["commit", "-m", &format!("agent{n}")]);
+    }
+
+    let done = |agent_id: &str| {
+        let dir = murmur_dir.path().to_path_buf();
+        let agent_id = agent_id.to_owned();
+        std::thread::spawn(move || {
+            let mut done = cargo_bin_cmd!("mm");
+            done.env("MURMUR_DIR", &dir);
+            done.env("MURMUR_AGENT_ID", &agent_id);
+            done.args(["agent", "done"]);
+            done.output().unwrap()
+        })
+    };
+
+    let t1 = done("a-1");
+    wait_for_merge_queue(murmur_dir.path(), |out| {
+        queue_state(out, "a-1").as_deref() == Some("merging")
+    });
+    let t2 = done("a-2");
+    wait_for_merge_queue(murmur_dir.path(), |out| queue_state(out, "a-2").is_some());
+    let t3 = done("a-3");
+    wait_for_merge_queue(murmur_dir.path(), |out| queue_state(out, "a-3").is_some());
+    let t4 = done("a-4");
+    wait_for_merge_queue(murmur_dir.path(), |out| queue_state(out, "a-4").is_some());
+
+    let mut prioritize = cargo_bin_cmd!("mm");
+    prioritize.env("MURMUR_DIR", murmur_dir.path());
+    prioritize.args(["merge", "prioritize", "a-3", "5"]);
+    prioritize.assert().success();
+
+    let order: Vec<String> = merge_queue_output(murmur_dir.path())
+        .lines()
+        .skip(1)
+        .map(|l| l.split('\t').nth(2).unwrap().to_owned())
+        .collect();
+    assert_eq!(order, ["a-1", "a-3", "a-2", "a-4"]);
+
+    let mut cancel = cargo_bin_cmd!("mm");
+    cancel.env("MURMUR_DIR", murmur_dir.path());
+    cancel.args(["merge", "cancel", "a-4"]);
+    cancel.assert().success();
+    let out4 = t4.join().unwrap();
+    assert!(!out4.status.success());
+    assert!(String::from_utf8_lossy(&out4.stderr).contains("removed from merge queue"));
+
+    // a-3 and a-2 land together once a-1 is through.
+    wait_for_merge_queue(murmur_dir.path(), |out| {
+        queue_state(out, "a-3").as_deref() == Some("merging")
+            && queue_state(out, "a-2").as_deref() == Some("merging")
+    });
+    for t in [t1, t2, t3] {
+        let out = t.join().unwrap();
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+    assert_eq!(
+        merge_queue_output(murmur_dir.path()),
+        "Merge queue is empty.\n"
+    );
+
+    let inspect = tmp.path().join("inspect");
+    run_git(
+        tmp.path(),
+        &["clone", origin.to_str().unwrap(), inspect.to_str().unwrap()],
+    );
+    run_git(&inspect, &["checkout", "main"]);
+    for n in 1..=3 {
+        assert!(inspect.join(format!("a{n}.txt")).exists());
+    }
+    assert!(!inspect.join("a4.txt").exists());
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
@@ -0,0 +1,377 @@
+use murmur::gitea::{DependencyCache, GiteaBackend};
+use murmur_core::issue::{CreateParams, ListFilter, Status, UpdateParams};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const REPO: &str = "/api/v1/repos/owner/repo";
+
+fn gitea_issue(
+    number: i64,
+    title: &str,
+    state: &str,
+    author: &str,
+    labels: &[&str],
+) -> serde_json::Value {
+    json!({
+        "id": 1000 + number,
+        "number": number,
+        "title": title,
+        "body": format!("body {number}"),
+        "state": state,
+        "created_at": "2026-01-20T00:00:00Z",
+        "user": { "login": author },
+        "labels": labels.iter().enumerate().map(|(i, l)| json!({"id": i, "name": l})).collect::<Vec<_>>(),
+    })
+}
+
+async fn mount_dependencies(server: &MockServer, number: i64, deps: &[(i64, &str)]) {
+    let body: Vec<_> = deps
+        .iter()
+        .map(|(n, s)| gitea_issue(*n, "dep", s, "owner", &[]))
+        .collect();
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues/{number}/dependencies")))
+        .and(header("authorization", "token test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+/// Ends a page walk over `endpoint` after page 1.
+async fn mount_last_page(server: &MockServer, endpoint: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/{endpoint}")))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(server)
+        .await;
+}
+
+fn backend(server: &MockServer) -> GiteaBackend {
+    GiteaBackend::new(
+        "owner".to_owned(),
+        "repo".to_owned(),
+        "test-token".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn gitea_list_maps_labels_and_dependencies() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues")))
+        .and(query_param("page", "1"))
+        .and(header("authorization", "token test-token"))
+        .and(query_param("type", "issues"))
+        .and(query_param("state", "all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            gitea_issue(
+                1,
+                "Blocked bug",
+                "open",
+                "owner",
+                &["type:bug", "priority:2", "blocked", "ui"]
+            ),
+            gitea_issue(2, "Closed task", "closed", "owner", &["priority:1"]),
+        ])))
+        .mount(&server)
+        .await;
+    mount_last_page(&server, "issues").await;
+    mount_dependencies(&server, 1, &[(99, "open")]).await;
+    // Dependencies turned off for the repository.
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues/2/dependencies")))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server).list(ListFilter::default()).await.unwrap();
+    assert_eq!(issues.len(), 2);
+
+    let a = &issues[0];
+    assert_eq!(a.id, "1");
+    assert_eq!(a.status, Status::Blocked);
+    assert_eq!(a.issue_type, "bug");
+    assert_eq!(a.priority, 2);
+    assert_eq!(a.labels, vec!["ui".to_owned()]);
+    assert_eq!(a.dependencies, vec!["99".to_owned()]);
+    assert!(a.created_at_ms > 0);
+
+    let b = &issues[1];
+    assert_eq!(b.id, "2");
+    assert_eq!(b.status, Status::Closed);
+    assert_eq!(b.issue_type, "task");
+    assert_eq!(b.priority, 1);
+    assert!(b.dependencies.is_empty());
+}
+
+#[tokio::test]
+async fn gitea_ready_filters_blocked_authors_and_open_blockers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues")))
+        .and(query_param("page", "1"))
+        .and(query_param("state", "open"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            gitea_issue(1, "open blocker", "open", "owner", &["priority:1"]),
+            gitea_issue(2, "blocked", "open", "owner", &["blocked"]),
+            gitea_issue(3, "wrong author", "open", "someone", &[]),
+            gitea_issue(4, "ready", "open", "owner", &[]),
+        ])))
+        .mount(&server)
+        .await;
+    mount_last_page(&server, "issues").await;
+    mount_dependencies(&server, 1, &[(9, "open")]).await;
+    mount_dependencies(&server, 4, &[(10, "closed")]).await;
+
+    let issues = backend(&server).ready().await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].id, "4");
+    assert_eq!(issues[0].dependencies, vec!["10".to_owned()]);
+}
+
+#[tokio::test]
+async fn gitea_ready_reuses_cached_dependencies_until_the_issue_changes() {
+    let server = MockServer::start().await;
+
+    let mut issue = gitea_issue(4, "ready", "open", "owner", &[]);
+    issue["updated_at"] = json!("2026-01-21T00:00:00Z");
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues")))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([issue.clone()])))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    issue["updated_at"] = json!("2026-01-22T00:00:00Z");
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues")))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([issue])))
+        .mount(&server)
+        .await;
+    mount_last_page(&server, "issues").await;
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues/4/dependencies")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([gitea_issue(
+            10,
+            "dep",
+            "closed",
+            "owner",
+            &[]
+        )])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    // A fresh backend per tick, as the orchestrator opens one.
+    let cache = DependencyCache::default();
+    for _ in 0..3 {
+        let issues = backend(&server)
+            .with_dependency_cache(cache.clone())
+            .ready()
+            .await
+            .unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].dependencies, vec!["10".to_owned()]);
+    }
+}
+
+#[tokio::test]
+async fn gitea_list_walks_short_pages_until_an_empty_one() {
+    let server = MockServer::start().await;
+
+    // An instance with `MAX_RESPONSE_ITEMS = 30` returns short pages.
+    for (page, numbers) in [("1", 1..=30), ("2", 31..=45)] {
+        let body: Vec<_> = numbers
+            .map(|n| gitea_issue(n, "issue", "closed", "owner", &[]))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path(format!("{REPO}/issues")))
+            .and(query_param("limit", "50"))
+            .and(query_param("page", page))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues")))
+        .and(query_param("page", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"/issues/\d+/dependencies$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server).list(ListFilter::default()).await.unwrap();
+    assert_eq!(issues.len(), 45);
+}
+
+#[tokio::test]
+async fn gitea_create_update_close_and_comment_smoke() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/labels")))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "id": 1, "name": "type:task" },
+            { "id": 2, "name": "priority:1" },
+        ])))
+        .mount(&server)
+        .await;
+    mount_last_page(&server, "labels").await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{REPO}/labels")))
+        .and(body_string_contains("\"name\":\"ui\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 3, "name": "ui" })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{REPO}/issues")))
+        .and(header("authorization", "token test-token"))
+        .and(body_string_contains("\"labels\":[1,2,3]"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(gitea_issue(
+            7,
+            "Created",
+            "open",
+            "owner",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{REPO}/issues/7/dependencies")))
+        .and(body_string_contains("\"index\":3"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_dependencies(&server, 7, &[(3, "open")]).await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{REPO}/issues/7")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(gitea_issue(
+            7,
+            "Created",
+            "open",
+            "owner",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("{REPO}/issues/7")))
+        .and(body_string_contains("\"title\":\"Renamed\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(gitea_issue(
+            7,
+            "Renamed",
+            "open",
+            "owner",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(format!("{REPO}/issues/7/labels")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("{REPO}/issues/7")))
+        .and(body_string_contains("\"state\":\"closed\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(gitea_issue(
+            7,
+            "Renamed",
+            "closed",
+            "owner",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{REPO}/issues/7/comments")))
+        .and(body_string_contains("\"body\":\"hello\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 1 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server);
+
+    let created = backend
+        .create(CreateParams {
+            title: "Created".to_owned(),
+            description: "hello".to_owned(),
+            issue_type: "task".to_owned(),
+            priority: 1,
+            labels: vec!["ui".to_owned()],
+            dependencies: vec!["3".to_owned()],
+            links: vec![],
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.id, "7");
+    assert_eq!(created.dependencies, vec!["3".to_owned()]);
+
+    let updated = backend
+        .update(
+            "7",
+            UpdateParams {
+                title: Some("Renamed".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Renamed");
+
+    backend.close("7").await.unwrap();
+    backend.comment("7", "hello").await.unwrap();
+}
+
+#[tokio::test]
+async fn gitea_create_pull_request_returns_html_url() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{REPO}/pulls")))
+        .and(header("authorization", "token test-token"))
+        .and(body_string_contains("\"head\":\"murmur/a-1\""))
+        .and(body_string_contains("\"base\":\"main\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "number": 1,
+            "html_url": "https://git.example.com/owner/repo/pulls/1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = backend(&server)
+        .create_pull_request("main", "murmur/a-1", "Agent a-1 changes", "Closes #7")
+        .await
+        .unwrap();
+    assert_eq!(url, "https://git.example.com/owner/repo/pulls/1");
+}
@@ -278,3 +278,137 @@ async fn github_create_update_close_and_comment_smoke() {
     backend.close("7").await.unwrap();
     backend.comment("7", "hello").await.unwrap();
 }
+
+#[tokio::test]
+async fn github_ready_pages_through_cursors_with_label_filter() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("ListIssuesForReady"))
+        .and(body_string_contains(r#""labels":["agent-ready"]"#))
+        .and(body_string_contains(r#""after":null"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "repository": {
+                    "issues": {
+                        "pageInfo": { "hasNextPage": true, "endCursor": "cursor-1" },
+                        "nodes": [
+                            github_issue_node(1, "first page", "OPEN", "owner", &["agent-ready"], &[]),
+                        ]
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("ListIssuesForReady"))
+        .and(body_string_contains(r#""after":"cursor-1""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "repository": {
+                    "issues": {
+                        "pageInfo": { "hasNextPage": false, "endCursor": "cursor-2" },
+                        "nodes": [
+                            // Moved pages after an update; reported once.
+                            github_issue_node(1, "first page", "OPEN", "owner", &["agent-ready"], &[]),
+                            github_issue_node(2, "second page", "OPEN", "owner", &["agent-ready"], &[]),
+                        ]
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = GithubBackend::new(
+        "owner".to_owned(),
+        "repo".to_owned(),
+        "test-token".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap()
+    .with_ready_label(Some("agent-ready".to_owned()));
+
+    let ids = backend
+        .ready()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["1", "2"]);
+}
+
+#[tokio::test]
+async fn github_list_comments_pages_through_cursors() {
+    let server = MockServer::start().await;
+
+    let comment = |id: &str, created_at: &str| json!({ "id": id, "body": id, "createdAt": created_at, "author": { "login": "dev" } });
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("GetIssueComments"))
+        .and(body_string_contains(r#""after":null"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "repository": {
+                    "issue": {
+                        "comments": {
+                            "pageInfo": { "hasNextPage": true, "endCursor": "cursor-1" },
+                            "nodes": [comment("c-1", "2026-01-20T00:00:00Z")]
+                        }
+                    }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("GetIssueComments"))
+        .and(body_string_contains(r#""after":"cursor-1""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "repository": {
+                    "issue": {
+                        "comments": {
+                            "pageInfo": { "hasNextPage": false, "endCursor": null },
+                            "nodes": [comment("c-2", "2026-01-21T00:00:00Z")]
+                        }
+                    }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let backend = GithubBackend::new(
+        "owner".to_owned(),
+        "repo".to_owned(),
+        "test-token".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap();
+
+    let all = backend.list_comments("7", None).await.unwrap();
+    assert_eq!(
+        all.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(),
+        ["c-1", "c-2"]
+    );
+
+    let newer = backend
+        .list_comments("7", Some(all[0].created_at_ms))
+        .await
+        .unwrap();
+    assert_eq!(newer.len(), 1);
+    assert_eq!(newer[0].id, "c-2");
+}
@@ -0,0 +1,432 @@
+use murmur::gitlab::GitlabBackend;
+use murmur_core::issue::{CreateParams, ListFilter, Status, UpdateParams};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const PROJECT: &str = "/api/v4/projects/group%2Frepo";
+
+fn gitlab_issue(
+    iid: i64,
+    title: &str,
+    state: &str,
+    author: &str,
+    labels: &[&str],
+) -> serde_json::Value {
+    json!({
+        "id": 1000 + iid,
+        "iid": iid,
+        "project_id": 42,
+        "title": title,
+        "description": format!("body {iid}"),
+        "state": state,
+        "created_at": "2026-01-20T00:00:00.000Z",
+        "author": { "username": author },
+        "labels": labels,
+    })
+}
+
+fn gitlab_link(iid: i64, state: &str, link_type: &str) -> serde_json::Value {
+    json!({
+        "iid": iid,
+        "project_id": 42,
+        "title": format!("linked {iid}"),
+        "state": state,
+        "link_type": link_type,
+    })
+}
+
+/// An issue node of the GraphQL issues query.
+fn graphql_issue(
+    iid: i64,
+    title: &str,
+    state: &str,
+    author: &str,
+    labels: &[&str],
+    blocked_by: &[(i64, &str)],
+) -> serde_json::Value {
+    json!({
+        "iid": iid.to_string(),
+        "projectId": 42,
+        "title": title,
+        "description": format!("body {iid}"),
+        "state": state,
+        "createdAt": "2026-01-20T00:00:00Z",
+        "author": { "username": author },
+        "labels": { "nodes": labels.iter().map(|l| json!({ "title": l })).collect::<Vec<_>>() },
+        "blockedByIssues": {
+            "nodes": blocked_by
+                .iter()
+                .map(|(iid, state)| json!({ "iid": iid.to_string(), "state": state }))
+                .collect::<Vec<_>>(),
+        },
+    })
+}
+
+fn issues_page(nodes: Vec<serde_json::Value>, next: Option<&str>) -> serde_json::Value {
+    json!({
+        "data": {
+            "project": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": next.is_some(), "endCursor": next },
+                    "nodes": nodes,
+                },
+            },
+        },
+    })
+}
+
+async fn mount_links(server: &MockServer, iid: i64, links: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path(format!("{PROJECT}/issues/{iid}/links")))
+        .and(header("private-token", "test-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(links))
+        .mount(server)
+        .await;
+}
+
+fn backend(server: &MockServer) -> GitlabBackend {
+    GitlabBackend::new(
+        "group/repo".to_owned(),
+        "test-token".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn gitlab_list_maps_labels_and_dependencies() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .and(header("authorization", "Bearer test-token"))
+        .and(body_string_contains(r#""project":"group/repo""#))
+        .and(body_string_contains(r#""state":"all""#))
+        .and(body_string_contains(r#""sort":"UPDATED_DESC""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issues_page(
+            vec![
+                graphql_issue(
+                    1,
+                    "Blocked bug",
+                    "opened",
+                    "group",
+                    &["type:bug", "priority:2", "blocked", "ui"],
+                    &[(99, "opened")],
+                ),
+                graphql_issue(2, "Closed task", "closed", "group", &["priority:1"], &[]),
+            ],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server).list(ListFilter::default()).await.unwrap();
+    assert_eq!(issues.len(), 2);
+
+    let a = &issues[0];
+    assert_eq!(a.id, "1");
+    assert_eq!(a.status, Status::Blocked);
+    assert_eq!(a.issue_type, "bug");
+    assert_eq!(a.priority, 2);
+    assert_eq!(a.labels, vec!["ui".to_owned()]);
+    assert_eq!(a.dependencies, vec!["99".to_owned()]);
+    assert_eq!(a.description, "body 1");
+    assert!(a.created_at_ms > 0);
+
+    let b = &issues[1];
+    assert_eq!(b.id, "2");
+    assert_eq!(b.status, Status::Closed);
+    assert_eq!(b.issue_type, "task");
+    assert_eq!(b.priority, 1);
+    assert!(b.dependencies.is_empty());
+}
+
+#[tokio::test]
+async fn gitlab_ready_filters_blocked_authors_and_open_blockers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .and(body_string_contains(r#""state":"opened""#))
+        .and(body_string_contains(r#""sort":"CREATED_ASC""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issues_page(
+            vec![
+                graphql_issue(
+                    1,
+                    "open blocker",
+                    "opened",
+                    "group",
+                    &["priority:1"],
+                    &[(9, "opened")],
+                ),
+                graphql_issue(2, "blocked", "opened", "group", &["blocked"], &[]),
+                graphql_issue(3, "wrong author", "opened", "someone", &[], &[]),
+                graphql_issue(4, "ready", "opened", "group", &[], &[(10, "closed")]),
+            ],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // Blockers come with the issues; no per-issue links requests.
+    Mock::given(method("GET"))
+        .and(path_regex(r"/issues/\d+/links$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server).ready().await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].id, "4");
+    assert_eq!(issues[0].dependencies, vec!["10".to_owned()]);
+}
+
+#[tokio::test]
+async fn gitlab_ready_falls_back_without_blockers_on_ce() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .and(body_string_contains("blockedByIssues"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "errors": [{ "message": "Field 'blockedByIssues' doesn't exist on type 'Issue'" }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut issue = graphql_issue(4, "ready", "opened", "group", &[], &[]);
+    issue.as_object_mut().unwrap().remove("blockedByIssues");
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issues_page(vec![issue], None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server).ready().await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].id, "4");
+    assert!(issues[0].dependencies.is_empty());
+}
+
+#[tokio::test]
+async fn gitlab_create_update_close_and_comment_smoke() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{PROJECT}/issues")))
+        .and(header("private-token", "test-token"))
+        .and(body_string_contains(
+            "\"labels\":\"type:task,priority:1,ui\"",
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(gitlab_issue(
+            7,
+            "Created",
+            "opened",
+            "group",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{PROJECT}/issues/7/links")))
+        .and(body_string_contains("\"link_type\":\"is_blocked_by\""))
+        .and(body_string_contains("\"target_issue_iid\":3"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_links(
+        &server,
+        7,
+        json!([gitlab_link(3, "opened", "is_blocked_by")]),
+    )
+    .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{PROJECT}/issues/7")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(gitlab_issue(
+            7,
+            "Created",
+            "opened",
+            "group",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(format!("{PROJECT}/issues/7")))
+        .and(body_string_contains("\"title\":\"Renamed\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(gitlab_issue(
+            7,
+            "Renamed",
+            "opened",
+            "group",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(format!("{PROJECT}/issues/7")))
+        .and(body_string_contains("\"state_event\":\"close\""))
+        .respond_with(ResponseTemplate::new(200).set_body_json(gitlab_issue(
+            7,
+            "Renamed",
+            "closed",
+            "group",
+            &["type:task", "priority:1", "ui"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{PROJECT}/issues/7/notes")))
+        .and(body_string_contains("\"body\":\"hello\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 1 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server);
+
+    let created = backend
+        .create(CreateParams {
+            title: "Created".to_owned(),
+            description: "hello".to_owned(),
+            issue_type: "task".to_owned(),
+            priority: 1,
+            labels: vec!["ui".to_owned()],
+            dependencies: vec!["3".to_owned()],
+            links: vec![],
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.id, "7");
+    assert_eq!(created.dependencies, vec!["3".to_owned()]);
+
+    let updated = backend
+        .update(
+            "7",
+            UpdateParams {
+                title: Some("Renamed".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Renamed");
+
+    backend.close("7").await.unwrap();
+    backend.comment("7", "hello").await.unwrap();
+}
+
+#[tokio::test]
+async fn gitlab_list_comments_skips_system_notes() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{PROJECT}/issues/7/notes")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            { "id": 1, "body": "added ~blocked label", "created_at": "2026-01-20T00:00:00Z", "author": { "username": "group" }, "system": true },
+            { "id": 2, "body": "old", "created_at": "2026-01-20T00:00:00Z", "author": { "username": "group" }, "system": false },
+            { "id": 3, "body": "new", "created_at": "2026-01-21T00:00:00Z", "author": { "username": "dev" }, "system": false },
+        ])))
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server);
+
+    let all = backend.list_comments("7", None).await.unwrap();
+    assert_eq!(
+        all.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(),
+        ["2", "3"]
+    );
+
+    let since = all[0].created_at_ms;
+    let newer = backend.list_comments("7", Some(since)).await.unwrap();
+    assert_eq!(newer.len(), 1);
+    assert_eq!(newer[0].author, "dev");
+    assert_eq!(newer[0].body, "new");
+}
+
+#[tokio::test]
+async fn gitlab_create_merge_request_returns_web_url() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{PROJECT}/merge_requests")))
+        .and(header("private-token", "test-token"))
+        .and(body_string_contains("\"source_branch\":\"murmur/a-1\""))
+        .and(body_string_contains("\"target_branch\":\"main\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "iid": 1,
+            "web_url": "https://gitlab.example.com/group/repo/-/merge_requests/1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = backend(&server)
+        .create_merge_request("main", "murmur/a-1", "Agent a-1 changes", "Closes #7")
+        .await
+        .unwrap();
+    assert_eq!(
+        url,
+        "https://gitlab.example.com/group/repo/-/merge_requests/1"
+    );
+}
+
+#[tokio::test]
+async fn gitlab_ready_walks_pages_with_label_filter() {
+    let server = MockServer::start().await;
+
+    let first_page: Vec<_> = (1..=100)
+        .map(|iid| graphql_issue(iid, "candidate", "opened", "group", &["agent"], &[]))
+        .collect();
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .and(body_string_contains(r#""labels":["agent"]"#))
+        .and(body_string_contains(r#""after":"cursor-1""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(issues_page(
+            vec![graphql_issue(
+                101,
+                "last",
+                "opened",
+                "group",
+                &["agent"],
+                &[],
+            )],
+            None,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/graphql"))
+        .and(body_string_contains(r#""labels":["agent"]"#))
+        .and(body_string_contains(r#""after":null"#))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(issues_page(first_page, Some("cursor-1"))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server)
+        .with_ready_label(Some("agent".to_owned()))
+        .ready()
+        .await
+        .unwrap();
+    assert_eq!(issues.len(), 101);
+    assert_eq!(issues[100].id, "101");
+}
@@ -219,6 +219,21 @@ async fn backend_selection_routes_tk_and_linear() {
     ]);
     set_backend.assert().success().stdout("ok\n");
 
+    let mut set_unknown = cargo_bin_cmd!("mm");
+    set_unknown.env("MURMUR_DIR", murmur_dir.path());
+    set_unknown.args([
+        "project",
+        "config",
+        "set",
+        "linproj",
+        "issue-backend",
+        "youtrack",
+    ]);
+    set_unknown
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown issue backend: youtrack"));
+
     let mut create = cargo_bin_cmd!("mm");
     create.env("MURMUR_DIR", murmur_dir.path());
     create.args(["issue", "create", "-p", "tkproj", "Tk issue"]);
@@ -86,8 +86,37 @@ fn wait_for_daemon_ready(dir: &TempDir) {
 }
 
 fn spawn_daemon(dir: &TempDir) -> std::process::Child {
+    spawn_daemon_with_path(dir, &std::env::var("PATH").unwrap_or_default())
+}
+
+/// A daemon whose agents run a `claude` that answers every message with "ok".
+fn spawn_daemon_with_fake_claude(dir: &TempDir, bins: &TempDir) -> std::process::Child {
+    let claude = bins.path().join("claude");
+    fs::write(
+        &claude,
+        r#"#!/usr/bin/env bash
+while IFS= read -r line; do
+  echo '{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"ok"}]}}'
+done
+"#,
+    )
+    .unwrap();
+    let mut perms = fs::metadata(&claude).unwrap().permissions();
+    std::os::unix::fs::PermissionsExt::set_mode(&mut perms, 0o755);
+    fs::set_permissions(&claude, perms).unwrap();
+
+    let path = format!(
+        "{}:{}",
+        bins.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    spawn_daemon_with_path(dir, &path)
+}
+
+fn spawn_daemon_with_path(dir: &TempDir, path: &str) -> std::process::Child {
     let child = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
         .env("MURMUR_DIR", dir.path())
+        .env("PATH", path)
         .args(["server", "start", "--foreground"])
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -478,3 +507,97 @@ fn tk_issue_create_commit_flag_pushes() {
 
     shutdown_daemon(&murmur_dir, daemon);
 }
+
+#[test]
+fn tk_issue_ready_follows_scheduling_policy() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = TempDir::new().unwrap();
+    let daemon = spawn_daemon_with_fake_claude(&murmur_dir, &bins);
+
+    let mut add = cargo_bin_cmd!("mm");
+    add.env("MURMUR_DIR", murmur_dir.path());
+    add.args([
+        "project",
+        "add",
+        "demo",
+        "--remote-url",
+        origin.to_str().unwrap(),
+    ]);
+    add.assert().success().stdout("ok\n");
+
+    let create = |title: &str, extra: &[&str]| -> String {
+        let mut cmd = cargo_bin_cmd!("mm");
+        cmd.env("MURMUR_DIR", murmur_dir.path());
+        cmd.args(["issue", "create", "-p", "demo", title]);
+        cmd.args(extra);
+        let out = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8_lossy(&out).trim().to_owned()
+    };
+    let low = create("Low priority", &["--priority", "0"]);
+    let high = create("High priority", &["--priority", "2"]);
+    let boosted = create("Boosted", &["--priority", "0", "--label", "urgent"]);
+
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args([
+        "project",
+        "config",
+        "set",
+        "demo",
+        "label-boosts",
+        "urgent=5",
+    ]);
+    set.assert().success();
+
+    let mut ready = cargo_bin_cmd!("mm");
+    ready.env("MURMUR_DIR", murmur_dir.path());
+    ready.args(["issue", "ready", "-p", "demo"]);
+    let out = ready.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out);
+    let pos = |id: &str| {
+        out.find(id)
+            .unwrap_or_else(|| panic!("{id} missing: {out}"))
+    };
+    assert!(pos(&boosted) < pos(&high), "{out}");
+    assert!(pos(&high) < pos(&low), "{out}");
+
+    let status = || {
+        let mut cmd = cargo_bin_cmd!("mm");
+        cmd.env("MURMUR_DIR", murmur_dir.path());
+        cmd.args(["project", "status", "demo"]);
+        let out = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8_lossy(&out).into_owned()
+    };
+    // The schedule comes from the orchestrator's last tick, not a fresh query.
+    let out = status();
+    assert!(
+        out.contains("schedule\tunavailable (orchestrator not running)"),
+        "{out}"
+    );
+
+    let mut start = cargo_bin_cmd!("mm");
+    start.env("MURMUR_DIR", murmur_dir.path());
+    start.args(["project", "start", "demo"]);
+    start.assert().success();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let out = loop {
+        let out = status();
+        if out.contains("schedule\t3 ready") {
+            break out;
+        }
+        assert!(Instant::now() < deadline, "no schedule: {out}");
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    assert!(
+        out.contains(&format!("  1\t{boosted}\t")) && out.contains("priority 0 +5 (urgent)"),
+        "{out}"
+    );
+    assert!(out.contains(&format!("  2\t{high}\t")), "{out}");
+    assert!(out.contains(&format!("  3\t{low}\t")), "{out}");
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
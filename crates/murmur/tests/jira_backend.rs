@@ -0,0 +1,327 @@
+use murmur::jira::JiraBackend;
+use murmur_core::issue::{CreateParams, ListFilter, Status, UpdateParams};
+use serde_json::json;
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API: &str = "/rest/api/2";
+
+// base64("dev@example.com:test-token")
+const BASIC_AUTH: &str = "Basic ZGV2QGV4YW1wbGUuY29tOnRlc3QtdG9rZW4=";
+
+fn blocked_by(key: &str, category: &str) -> serde_json::Value {
+    json!({
+        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+        "inwardIssue": {
+            "key": key,
+            "fields": { "status": { "name": "Status", "statusCategory": { "key": category } } },
+        },
+    })
+}
+
+fn blocks(key: &str) -> serde_json::Value {
+    json!({
+        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+        "outwardIssue": {
+            "key": key,
+            "fields": { "status": { "name": "To Do", "statusCategory": { "key": "new" } } },
+        },
+    })
+}
+
+fn jira_issue(
+    key: &str,
+    summary: &str,
+    status: (&str, &str),
+    priority: &str,
+    labels: &[&str],
+    links: Vec<serde_json::Value>,
+) -> serde_json::Value {
+    json!({
+        "id": "10001",
+        "key": key,
+        "fields": {
+            "summary": summary,
+            "description": format!("body {key}"),
+            "status": { "name": status.0, "statusCategory": { "key": status.1 } },
+            "priority": { "name": priority },
+            "issuetype": { "name": "Bug" },
+            "labels": labels,
+            "issuelinks": links,
+            "created": "2026-01-20T00:00:00.000+0000",
+        },
+    })
+}
+
+fn backend(server: &MockServer, ready_jql: Option<&str>) -> JiraBackend {
+    JiraBackend::new(
+        server.uri(),
+        "app".to_owned(),
+        ready_jql.map(str::to_owned),
+        Some("dev@example.com".to_owned()),
+        "test-token".to_owned(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn jira_list_maps_status_priority_and_blockers() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/search/jql")))
+        .and(header("authorization", BASIC_AUTH))
+        .and(body_string_contains(
+            r#"project = \"APP\" ORDER BY updated DESC"#,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "issues": [
+                jira_issue(
+                    "APP-1",
+                    "Blocked bug",
+                    ("In Progress", "indeterminate"),
+                    "High",
+                    &["blocked", "ui"],
+                    vec![blocked_by("APP-9", "new"), blocks("APP-5")],
+                ),
+                jira_issue("APP-2", "Done", ("Done", "done"), "Lowest", &[], vec![]),
+                jira_issue("APP-3", "Stuck", ("Blocked", "indeterminate"), "Medium", &[], vec![]),
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    let issues = backend(&server, None)
+        .list(ListFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(issues.len(), 3);
+
+    let a = &issues[0];
+    assert_eq!(a.id, "APP-1");
+    assert_eq!(a.status, Status::Blocked);
+    assert_eq!(a.issue_type, "bug");
+    assert_eq!(a.priority, 2);
+    assert_eq!(a.labels, vec!["ui".to_owned()]);
+    assert_eq!(a.dependencies, vec!["APP-9".to_owned()]);
+    assert_eq!(a.description, "body APP-1");
+    assert!(a.created_at_ms > 0);
+
+    assert_eq!(issues[1].status, Status::Closed);
+    assert_eq!(issues[1].priority, 0);
+    assert_eq!(issues[2].status, Status::Blocked);
+}
+
+#[tokio::test]
+async fn jira_ready_runs_configured_jql_and_drops_blocked() {
+    let server = MockServer::start().await;
+
+    // Data Center has no `search/jql`.
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/search/jql")))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/search")))
+        .and(body_string_contains(r#"labels = agent"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "issues": [
+                jira_issue("APP-1", "open blocker", ("To Do", "new"), "Medium", &[], vec![blocked_by("APP-9", "indeterminate")]),
+                jira_issue("APP-2", "blocked", ("To Do", "new"), "Medium", &["blocked"], vec![]),
+                jira_issue("APP-4", "ready", ("To Do", "new"), "Highest", &[], vec![blocked_by("APP-10", "done")]),
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server, Some("project = APP AND labels = agent"));
+    let issues = backend.ready().await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].id, "APP-4");
+    assert_eq!(issues[0].priority, 3);
+    assert_eq!(issues[0].dependencies, vec!["APP-10".to_owned()]);
+}
+
+#[tokio::test]
+async fn jira_create_update_close_and_comment_smoke() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/issue")))
+        .and(header("authorization", BASIC_AUTH))
+        .and(body_string_contains(r#""project":{"key":"APP"}"#))
+        .and(body_string_contains(r#""issuetype":{"name":"Task"}"#))
+        .and(body_string_contains(r#""priority":{"name":"Medium"}"#))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "10007",
+            "key": "APP-7",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/issueLink")))
+        .and(body_string_contains(r#""inwardIssue":{"key":"APP-3"}"#))
+        .and(body_string_contains(r#""outwardIssue":{"key":"APP-7"}"#))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{API}/issue/APP-7")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(jira_issue(
+            "APP-7",
+            "Created",
+            ("To Do", "new"),
+            "Medium",
+            &["ui"],
+            vec![blocked_by("APP-3", "new")],
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(format!("{API}/issue/APP-7")))
+        .and(body_string_contains(r#""summary":"Renamed""#))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{API}/issue/APP-7/transitions")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "transitions": [
+                { "id": "21", "name": "Start", "to": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } } },
+                { "id": "31", "name": "Finish", "to": { "name": "Done", "statusCategory": { "key": "done" } } },
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/issue/APP-7/transitions")))
+        .and(body_string_contains(r#""id":"31""#))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/issue/APP-7/comment")))
+        .and(body_string_contains(r#""body":"hello""#))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": "1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server, None);
+
+    let created = backend
+        .create(CreateParams {
+            title: "Created".to_owned(),
+            description: "hello".to_owned(),
+            issue_type: "task".to_owned(),
+            priority: 1,
+            labels: vec!["ui".to_owned()],
+            dependencies: vec!["3".to_owned()],
+            links: vec![],
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.id, "APP-7");
+    assert_eq!(created.dependencies, vec!["APP-3".to_owned()]);
+
+    backend
+        .update(
+            "app-7",
+            UpdateParams {
+                title: Some("Renamed".to_owned()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    backend.close("APP-7").await.unwrap();
+    backend.comment("7", "hello").await.unwrap();
+}
+
+#[tokio::test]
+async fn jira_list_comments_filters_by_time() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("{API}/issue/APP-7/comment")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "comments": [
+                { "id": "1", "body": "old", "created": "2026-01-20T00:00:00.000+0000", "author": { "displayName": "Ops" } },
+                { "id": "2", "body": "new", "created": "2026-01-21T00:00:00.000+0100", "author": { "displayName": "Dev" } },
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server, None);
+
+    let all = backend.list_comments("APP-7", None).await.unwrap();
+    assert_eq!(all.len(), 2);
+
+    let newer = backend
+        .list_comments("APP-7", Some(all[0].created_at_ms))
+        .await
+        .unwrap();
+    assert_eq!(newer.len(), 1);
+    assert_eq!(newer[0].author, "Dev");
+    assert_eq!(newer[0].body, "new");
+}
+
+#[tokio::test]
+async fn jira_search_follows_page_tokens_and_ready_label() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/search/jql")))
+        .and(body_string_contains(r#"labels = \"agent\""#))
+        .and(body_string_contains(r#""nextPageToken":"page-2""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "issues": [
+                jira_issue("APP-2", "second", ("To Do", "new"), "Medium", &["agent"], vec![]),
+            ],
+            "isLast": true,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{API}/search/jql")))
+        .and(body_string_contains(r#"labels = \"agent\""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "issues": [
+                jira_issue("APP-1", "first", ("To Do", "new"), "Medium", &["agent"], vec![]),
+            ],
+            "nextPageToken": "page-2",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = backend(&server, None).with_ready_label(Some("agent".to_owned()));
+    assert_eq!(
+        backend.ready_jql(),
+        r#"project = "APP" AND labels = "agent" AND statusCategory != Done ORDER BY priority DESC, created ASC"#
+    );
+
+    let ids = backend
+        .ready()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["APP-1", "APP-2"]);
+}
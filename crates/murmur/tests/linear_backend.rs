@@ -257,3 +257,130 @@ async fn linear_create_update_close_and_comment_smoke() {
     backend.close("ENG-10").await.unwrap();
     backend.comment("ENG-10", "hello").await.unwrap();
 }
+
+#[tokio::test]
+async fn linear_ready_pages_with_label_filter_and_checks_parent_state() {
+    let server = MockServer::start().await;
+
+    let mut child = linear_issue_node("ENG-5", "Open parent", "backlog", 3, &["agent"], None);
+    child["parent"] = json!({ "identifier": "ENG-0", "state": { "type": "started" } });
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("query Issues"))
+        .and(body_string_contains(
+            r#""labels":{"some":{"name":{"eq":"agent"}}}"#,
+        ))
+        .and(body_string_contains(r#""after":null"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "cursor-1" },
+                    "nodes": [
+                        linear_issue_node("ENG-1", "A", "backlog", 3, &["agent"], None),
+                        child,
+                    ]
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("query Issues"))
+        .and(body_string_contains(r#""after":"cursor-1""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "issues": {
+                    "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    "nodes": [
+                        linear_issue_node("ENG-6", "B", "unstarted", 3, &["agent"], None),
+                    ]
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let backend = LinearBackend::new(
+        "team-1".to_owned(),
+        None,
+        "lin-key".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap()
+    .with_ready_label(Some("agent".to_owned()));
+
+    let ids = backend
+        .ready()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|i| i.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["ENG-1", "ENG-6"]);
+}
+
+#[tokio::test]
+async fn linear_list_comments_pages_through_cursors() {
+    let server = MockServer::start().await;
+
+    let comment = |id: &str, created_at: &str| json!({ "id": id, "body": id, "createdAt": created_at, "user": { "name": "Dev" } });
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("query IssueComments"))
+        .and(body_string_contains(r#""after":null"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "issue": {
+                    "comments": {
+                        "pageInfo": { "hasNextPage": true, "endCursor": "cursor-1" },
+                        "nodes": [comment("c-1", "2026-01-20T00:00:00Z")]
+                    }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_string_contains("query IssueComments"))
+        .and(body_string_contains(r#""after":"cursor-1""#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "issue": {
+                    "comments": {
+                        "pageInfo": { "hasNextPage": false, "endCursor": null },
+                        "nodes": [comment("c-2", "2026-01-21T00:00:00Z")]
+                    }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let backend = LinearBackend::new(
+        "team-1".to_owned(),
+        None,
+        "lin-key".to_owned(),
+        vec![],
+        server.uri(),
+    )
+    .unwrap();
+
+    let comments = backend
+        .list_comments("5b7c1d2e-0000-4000-8000-000000000001", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        comments.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(),
+        ["c-1", "c-2"]
+    );
+    assert_eq!(comments[1].author, "Dev");
+}
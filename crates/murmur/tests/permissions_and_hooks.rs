@@ -88,7 +88,7 @@ if [[ -n "${FAKE_CLAUDE_SETTINGS_OUT:-}" ]]; then
 fi
 
 if [[ -n "${FAKE_CLAUDE_ENV_OUT:-}" ]]; then
-  printf "%s" "${MURMUR_DIR:-}" > "${FAKE_CLAUDE_ENV_OUT}"
+  printf "%s\n%s" "${MURMUR_DIR:-}" "${MURMUR_AGENT_ROLE:-}" > "${FAKE_CLAUDE_ENV_OUT}"
 fi
 
 while IFS= read -r line; do
@@ -332,25 +332,21 @@ fn spawned_agents_receive_fugue_dir_env() {
     }
 
     let got = read_to_string_best_effort(&env_out);
-    assert_eq!(got.trim(), murmur_dir.path().to_string_lossy());
+    let lines: Vec<&str> = got.lines().collect();
+    assert_eq!(
+        lines,
+        [murmur_dir.path().to_string_lossy().as_ref(), "coding"]
+    );
 
     shutdown_daemon(&murmur_dir, daemon);
 }
 
-#[test]
-fn hook_pre_tool_use_blocks_until_permission_respond() {
-    let tmp = TempDir::new().unwrap();
-    let origin = init_local_remote(tmp.path());
-
-    let murmur_dir = TempDir::new().unwrap();
-    let bins = setup_fake_binaries();
-    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"));
-
-    add_project(&murmur_dir, &origin);
-    let agent_id = create_agent(&murmur_dir);
-    assert_eq!(agent_id, "a-1");
-    let cwd = worktree_dir(&murmur_dir, &agent_id);
-
+fn spawn_pre_tool_use_hook(
+    murmur_dir: &TempDir,
+    agent_id: &str,
+    cwd: &Path,
+    command: &str,
+) -> std::process::Child {
     let input = serde_json::json!({
         "session_id": "s-1",
         "transcript_path": "",
@@ -358,13 +354,13 @@ fn hook_pre_tool_use_blocks_until_permission_respond() {
         "permission_mode": "default",
         "hook_event_name": "PreToolUse",
         "tool_name": "Bash",
-        "tool_input": { "command": "echo hello" },
+        "tool_input": { "command": command },
         "tool_use_id": "tu-1"
     });
 
     let mut hook = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
         .env("MURMUR_DIR", murmur_dir.path())
-        .env("MURMUR_AGENT_ID", &agent_id)
+        .env("MURMUR_AGENT_ID", agent_id)
         .env("MURMUR_PROJECT", "demo")
         .args(["hook", "PreToolUse"])
         .stdin(Stdio::piped())
@@ -377,9 +373,12 @@ fn hook_pre_tool_use_blocks_until_permission_respond() {
         let mut stdin = hook.stdin.take().unwrap();
         stdin.write_all(format!("{input}\n").as_bytes()).unwrap();
     }
+    hook
+}
 
+fn wait_for_permission_request(murmur_dir: &TempDir) -> String {
     let deadline = Instant::now() + Duration::from_secs(5);
-    let request_id = loop {
+    loop {
         if Instant::now() > deadline {
             panic!("timed out waiting for permission request to appear");
         }
@@ -390,28 +389,505 @@ fn hook_pre_tool_use_blocks_until_permission_respond() {
         let out = list.assert().success().get_output().stdout.clone();
         let out = String::from_utf8_lossy(&out).to_string();
         if let Some(line) = out.lines().next().filter(|l| !l.trim().is_empty()) {
-            break line.split('\t').next().unwrap().to_owned();
+            return line.split('\t').next().unwrap().to_owned();
         }
 
         std::thread::sleep(Duration::from_millis(50));
-    };
+    }
+}
+
+fn hook_decision(hook: std::process::Child) -> String {
+    let output = hook.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["hookSpecificOutput"]["permissionDecision"]
+        .as_str()
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn hook_pre_tool_use_blocks_until_permission_respond() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"));
+
+    add_project(&murmur_dir, &origin);
+    let agent_id = create_agent(&murmur_dir);
+    assert_eq!(agent_id, "a-1");
+    let cwd = worktree_dir(&murmur_dir, &agent_id);
+
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "echo hello");
+    let request_id = wait_for_permission_request(&murmur_dir);
 
     let mut respond = cargo_bin_cmd!("mm");
     respond.env("MURMUR_DIR", murmur_dir.path());
     respond.args(["permission", "respond", &request_id, "allow"]);
     respond.assert().success().stdout("ok\n");
 
+    assert_eq!(hook_decision(hook), "allow");
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn unanswered_permission_request_expires_with_timeout_action() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"));
+
+    add_project(&murmur_dir, &origin);
+    let mut set = cargo_bin_cmd!("mm");
+    set.env("MURMUR_DIR", murmur_dir.path());
+    set.args([
+        "project",
+        "config",
+        "set",
+        "demo",
+        "permission-timeout-secs",
+        "3",
+    ]);
+    set.assert().success().stdout("ok\n");
+    let agent_id = create_agent(&murmur_dir);
+    let cwd = worktree_dir(&murmur_dir, &agent_id);
+
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "make deploy");
+    wait_for_permission_request(&murmur_dir);
+
+    let mut list = cargo_bin_cmd!("mm");
+    list.env("MURMUR_DIR", murmur_dir.path());
+    list.args(["permission", "list"]);
+    let out = list.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out).to_string();
+    let expiry = out.lines().next().unwrap().split('\t').nth(5).unwrap();
+    assert!(expiry.starts_with("deny in "), "{out}");
+
     let output = hook.wait_with_output().unwrap();
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    let decision = json["hookSpecificOutput"]["permissionDecision"]
+    assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "deny");
+    let reason = json["hookSpecificOutput"]["permissionDecisionReason"]
         .as_str()
         .unwrap();
-    assert_eq!(decision, "allow");
+    assert!(reason.starts_with("no response within 3s"), "{reason}");
+
+    let mut list = cargo_bin_cmd!("mm");
+    list.env("MURMUR_DIR", murmur_dir.path());
+    list.args(["permission", "list"]);
+    list.assert().success().stdout("");
+
+    let mut log = cargo_bin_cmd!("mm");
+    log.env("MURMUR_DIR", murmur_dir.path());
+    log.args(["permission", "log", "--decider", "timeout"]);
+    let out = log.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out).to_string();
+    assert!(
+        out.contains("\tdeny\ttimeout\ta-1\tdemo\tBash\tmake deploy\t"),
+        "{out}"
+    );
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn permission_respond_scopes_cover_later_requests() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"));
+
+    add_project(&murmur_dir, &origin);
+    let agent_id = create_agent(&murmur_dir);
+    let cwd = worktree_dir(&murmur_dir, &agent_id);
+
+    let respond = |request_id: &str, scope: &str| {
+        let mut respond = cargo_bin_cmd!("mm");
+        respond.env("MURMUR_DIR", murmur_dir.path());
+        respond.args([
+            "permission",
+            "respond",
+            request_id,
+            "allow",
+            "--scope",
+            scope,
+        ]);
+        respond.assert().success().stdout("ok\n");
+    };
+
+    // `agent`: the daemon answers the same command for this agent itself.
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "make build");
+    respond(&wait_for_permission_request(&murmur_dir), "agent");
+    assert_eq!(hook_decision(hook), "allow");
+
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "make build");
+    assert_eq!(hook_decision(hook), "allow");
+
+    // `project`: a rule lands in the project's permissions file, covering
+    // each new command of a compound one.
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "make lint && make test");
+    respond(&wait_for_permission_request(&murmur_dir), "project");
+    assert_eq!(hook_decision(hook), "allow");
+
+    let permissions = read_to_string_best_effort(
+        &murmur_dir
+            .path()
+            .join("projects")
+            .join("demo")
+            .join("permissions.toml"),
+    );
+    assert!(
+        permissions.contains("pattern = \"make lint\""),
+        "{permissions}"
+    );
+    assert!(
+        permissions.contains("pattern = \"make test\""),
+        "{permissions}"
+    );
+
+    let hook = spawn_pre_tool_use_hook(&murmur_dir, &agent_id, &cwd, "make test; make lint");
+    assert_eq!(hook_decision(hook), "allow");
+
+    let mut log = cargo_bin_cmd!("mm");
+    log.env("MURMUR_DIR", murmur_dir.path());
+    log.args(["permission", "log", "--agent", &agent_id, "--grep", "make"]);
+    let out = log.assert().success().get_output().stdout.clone();
+    let deciders: Vec<(String, String)> = String::from_utf8_lossy(&out)
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            (fields[2].to_owned(), fields[7].to_owned())
+        })
+        .collect();
+    assert_eq!(
+        deciders,
+        [
+            ("human".to_owned(), "scope: agent".to_owned()),
+            ("rule".to_owned(), "agent-scoped grant".to_owned()),
+            ("human".to_owned(), "scope: project".to_owned()),
+            (
+                "rule".to_owned(),
+                "Bash allow \"make test\"; Bash allow \"make lint\"".to_owned()
+            ),
+        ]
+    );
 
     shutdown_daemon(&murmur_dir, daemon);
 }
 
+#[test]
+fn hook_pre_tool_use_runs_script_rules() {
+    let murmur_dir = TempDir::new().unwrap();
+    let project_dir = murmur_dir.path().join("projects").join("demo");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::write(
+        project_dir.join("permissions.toml"),
+        r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "git push:*"
+script = "grep -q 'murmur/' && echo allow || echo 'deny push outside murmur/* branches'"
+"#,
+    )
+    .unwrap();
+
+    let run_hook = |command: &str| -> serde_json::Value {
+        let input = serde_json::json!({
+            "cwd": murmur_dir.path().to_string_lossy(),
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "tool_input": { "command": command },
+            "tool_use_id": "tu-1"
+        });
+        let mut hook = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
+            .env("MURMUR_DIR", murmur_dir.path())
+            .env("MURMUR_PROJECT", "demo")
+            .args(["hook", "PreToolUse"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        hook.stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{input}\n").as_bytes())
+            .unwrap();
+        let output = hook.wait_with_output().unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()["hookSpecificOutput"]
+            .clone()
+    };
+
+    let out = run_hook("git push origin murmur/a-1");
+    assert_eq!(out["permissionDecision"], "allow");
+
+    let out = run_hook("git push --force origin main");
+    assert_eq!(out["permissionDecision"], "deny");
+    assert_eq!(
+        out["permissionDecisionReason"],
+        "push outside murmur/* branches"
+    );
+
+    let mut log = cargo_bin_cmd!("mm");
+    log.env("MURMUR_DIR", murmur_dir.path());
+    log.args(["permission", "log", "--decision", "deny"]);
+    let out = log.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2, "{out}");
+    assert!(lines[0].starts_with("TIME\tDECISION"), "{out}");
+    let fields: Vec<&str> = lines[1].split('\t').collect();
+    assert_eq!(
+        fields[1..7],
+        [
+            "deny",
+            "rule",
+            "",
+            "demo",
+            "Bash",
+            "git push --force origin main"
+        ]
+    );
+    assert!(
+        fields[7].ends_with(": push outside murmur/* branches"),
+        "{out}"
+    );
+}
+
+#[test]
+fn hook_pre_tool_use_applies_rules_for_the_agent_role() {
+    let murmur_dir = TempDir::new().unwrap();
+    let global = murmur_dir.path().join("config").join("permissions.toml");
+    fs::create_dir_all(global.parent().unwrap()).unwrap();
+    fs::write(
+        &global,
+        r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "mm project:*"
+roles = ["coding"]
+"#,
+    )
+    .unwrap();
+
+    let run_hook = |role: Option<&str>| -> String {
+        let input = serde_json::json!({
+            "cwd": murmur_dir.path().to_string_lossy(),
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "tool_input": { "command": "mm project list" },
+            "tool_use_id": "tu-1"
+        });
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("mm"));
+        cmd.env("MURMUR_DIR", murmur_dir.path())
+            .env_remove("MURMUR_AGENT_ROLE")
+            .args(["hook", "PreToolUse"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        if let Some(role) = role {
+            cmd.env("MURMUR_AGENT_ROLE", role);
+        }
+        let mut hook = cmd.spawn().unwrap();
+        hook.stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{input}\n").as_bytes())
+            .unwrap();
+        let output = hook.wait_with_output().unwrap();
+        assert!(output.status.success());
+        let out: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        out["hookSpecificOutput"]["permissionDecision"]
+            .as_str()
+            .unwrap()
+            .to_owned()
+    };
+
+    assert_eq!(run_hook(Some("coding")), "deny");
+    assert_eq!(run_hook(None), "deny");
+    // The built-in `mm :*` allow applies to everyone else.
+    assert_eq!(run_hook(Some("manager")), "allow");
+    assert_eq!(run_hook(Some("planner")), "allow");
+}
+
+#[test]
+fn hook_pre_tool_use_confines_writes_to_the_worktree() {
+    let murmur_dir = TempDir::new().unwrap();
+    let global = murmur_dir.path().join("config").join("permissions.toml");
+    fs::create_dir_all(global.parent().unwrap()).unwrap();
+    fs::write(
+        &global,
+        r#"
+[[rules]]
+tool = "Write"
+action = "allow"
+"#,
+    )
+    .unwrap();
+    let worktree = murmur_dir.path().join("worktree");
+    fs::create_dir_all(&worktree).unwrap();
+    std::os::unix::fs::symlink("/etc", worktree.join("etc")).unwrap();
+
+    let run_hook = |file_path: &str| -> serde_json::Value {
+        let input = serde_json::json!({
+            "cwd": worktree.to_string_lossy(),
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Write",
+            "tool_input": { "file_path": file_path, "content": "x" },
+            "tool_use_id": "tu-1"
+        });
+        let mut hook = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
+            .env("MURMUR_DIR", murmur_dir.path())
+            .env("MURMUR_PROJECT", "demo")
+            .env("MURMUR_WORKTREE_DIR", &worktree)
+            .args(["hook", "PreToolUse"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        hook.stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{input}\n").as_bytes())
+            .unwrap();
+        let output = hook.wait_with_output().unwrap();
+        assert!(output.status.success());
+        let out: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        out["hookSpecificOutput"].clone()
+    };
+
+    assert_eq!(run_hook("src/main.rs")["permissionDecision"], "allow");
+
+    let out = run_hook("/etc/murmur-test");
+    assert_eq!(out["permissionDecision"], "deny");
+    let reason = out["permissionDecisionReason"].as_str().unwrap();
+    assert!(
+        reason.starts_with("write confinement: Write to /etc/murmur-test is outside"),
+        "{reason}"
+    );
+
+    let out = run_hook("etc/murmur-test");
+    assert_eq!(out["permissionDecision"], "deny");
+    let reason = out["permissionDecisionReason"].as_str().unwrap();
+    assert!(
+        reason.contains("(resolves to /etc/murmur-test)"),
+        "{reason}"
+    );
+
+    fs::write(
+        murmur_dir.path().join("config").join("config.toml"),
+        r#"
+[[projects]]
+name = "demo"
+remote-url = "file:///tmp/demo.git"
+
+[projects.confinement]
+allowed-dirs = ["/etc"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(run_hook("etc/murmur-test")["permissionDecision"], "allow");
+}
+
+#[test]
+fn permission_test_and_lint_explain_rules() {
+    let murmur_dir = TempDir::new().unwrap();
+    let global = murmur_dir.path().join("config").join("permissions.toml");
+    fs::create_dir_all(global.parent().unwrap()).unwrap();
+    fs::write(
+        &global,
+        r#"
+[[rules]]
+tool = "Bash"
+action = "allow"
+pattern = "git :*"
+
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "git push:*"
+"#,
+    )
+    .unwrap();
+    let project = murmur_dir.path().join("projects").join("demo");
+    fs::create_dir_all(&project).unwrap();
+    fs::write(
+        project.join("permissions.toml"),
+        r#"
+[[rules]]
+tool = "Bash"
+action = "deny"
+pattern = "git push:*"
+script = "./check-push.sh"
+
+[[rules]]
+tool = "Shell"
+action = "allow"
+"#,
+    )
+    .unwrap();
+
+    let mut test = cargo_bin_cmd!("mm");
+    test.env("MURMUR_DIR", murmur_dir.path());
+    test.args([
+        "permission",
+        "test",
+        "--tool",
+        "Bash",
+        "--input",
+        r#"{"command":"git push && rm -rf build"}"#,
+        "--project",
+        "demo",
+        "--cwd",
+        "/tmp",
+    ]);
+    let out = test.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out);
+    let lines: Vec<Vec<&str>> = out.lines().map(|l| l.split('\t').collect()).collect();
+    assert_eq!(lines.len(), 4, "{out}");
+    assert_eq!(lines[0][..2], ["Bash \"git push\"", "script"], "{out}");
+    assert!(
+        lines[0][2].ends_with("demo/permissions.toml: rules[0]"),
+        "{out}"
+    );
+    assert_eq!(lines[1][..2], ["Bash \"git push\"", "allow"], "{out}");
+    assert_eq!(
+        lines[1][2],
+        format!("{}: rules[0]", global.display()),
+        "{out}"
+    );
+    assert_eq!(lines[2][..2], ["Bash \"rm -rf build\"", "deny"], "{out}");
+    assert!(lines[2][2].starts_with("built-in rules["), "{out}");
+    assert_eq!(lines[3], ["result: deny"], "{out}");
+
+    let mut lint = cargo_bin_cmd!("mm");
+    lint.env("MURMUR_DIR", murmur_dir.path());
+    lint.args(["permission", "lint"]);
+    let out = lint.assert().failure().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2, "{out}");
+    assert!(
+        lines[0].starts_with(&format!("{}: rules[1] (Bash deny", global.display())),
+        "{out}"
+    );
+    assert!(lines[0].contains("unreachable:"), "{out}");
+    assert!(lines[1].contains("rules[1] (Shell allow)"), "{out}");
+    assert!(lines[1].ends_with("unknown tool name"), "{out}");
+}
+
 #[test]
 fn hook_ask_user_question_updates_input_with_answers() {
     let tmp = TempDir::new().unwrap();
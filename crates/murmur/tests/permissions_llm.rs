@@ -78,6 +78,12 @@ while IFS= read -r line; do
   if [[ -z "${line// }" ]]; then
     continue
   fi
+  # As the manager, allow every escalated permission request.
+  if [[ "$line" =~ Permission\ request\ ([0-9a-f]+) ]]; then
+    id="${BASH_REMATCH[1]}"
+    echo '{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"{\"permission\":\"'"$id"'\",\"decision\":\"allow\",\"reason\":\"part of the task\"}"}]}}'
+    continue
+  fi
   echo '{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"(fake claude) ok"}]}}'
 done
 "#,
@@ -376,3 +382,101 @@ async fn hook_pre_tool_use_denies_when_llm_unsure() {
 
     shutdown_daemon(&murmur_dir, daemon);
 }
+
+#[tokio::test]
+async fn unsure_llm_escalates_to_the_manager_agent() {
+    let openai = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{
+                "message": {
+                    "tool_calls": [{
+                        "function": {
+                            "arguments": "{\"decision\":\"unsure\",\"rationale\":\"need review\"}"
+                        }
+                    }]
+                }
+            }]
+        })))
+        .mount(&openai)
+        .await;
+
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote(tmp.path());
+
+    let murmur_dir = TempDir::new().unwrap();
+    write_llm_config(&murmur_dir);
+
+    let bins = setup_fake_binaries();
+    let daemon = spawn_daemon(&murmur_dir, &bins.path().join("bin"), &openai.uri());
+
+    add_project(&murmur_dir, &origin);
+    for (key, value) in [
+        ("agent-backend", "claude"),
+        ("escalation", "llm,manager:10,human"),
+    ] {
+        let mut set = cargo_bin_cmd!("mm");
+        set.env("MURMUR_DIR", murmur_dir.path());
+        set.args(["project", "config", "set", "demo", key, value]);
+        set.assert().success().stdout("ok\n");
+    }
+
+    let mut start = cargo_bin_cmd!("mm");
+    start.env("MURMUR_DIR", murmur_dir.path());
+    start.args(["manager", "start", "demo"]);
+    start.assert().success().stdout("ok\n");
+
+    let agent_id = create_agent(&murmur_dir);
+    let cwd = worktree_dir(&murmur_dir, &agent_id);
+
+    let input = serde_json::json!({
+        "session_id": "s-1",
+        "transcript_path": "",
+        "cwd": cwd.to_string_lossy(),
+        "permission_mode": "default",
+        "hook_event_name": "PreToolUse",
+        "tool_name": "Bash",
+        "tool_input": { "command": "make deploy" },
+        "tool_use_id": "tu-1"
+    });
+
+    let mut hook = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
+        .env("MURMUR_DIR", murmur_dir.path())
+        .env("MURMUR_AGENT_ID", &agent_id)
+        .env("MURMUR_PROJECT", "demo")
+        .args(["hook", "PreToolUse"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    {
+        let mut stdin = hook.stdin.take().unwrap();
+        stdin.write_all(format!("{input}\n").as_bytes()).unwrap();
+    }
+
+    let out = hook.wait_with_output().unwrap();
+    assert!(out.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json["hookSpecificOutput"]["permissionDecision"], "allow");
+
+    let mut log = cargo_bin_cmd!("mm");
+    log.env("MURMUR_DIR", murmur_dir.path());
+    log.args(["permission", "log", "--decider", "manager"]);
+    let out = log.assert().success().get_output().stdout.clone();
+    let out = String::from_utf8_lossy(&out).to_string();
+    assert!(
+        out.contains("\tallow\tmanager\ta-1\tdemo\tBash\tmake deploy\tpart of the task"),
+        "{out}"
+    );
+
+    let mut hist = cargo_bin_cmd!("mm");
+    hist.env("MURMUR_DIR", murmur_dir.path());
+    hist.args(["manager", "chat-history", "demo", "--limit", "50"]);
+    hist.assert()
+        .success()
+        .stdout(predicates::str::contains("Permission request "));
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
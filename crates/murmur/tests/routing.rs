@@ -0,0 +1,327 @@
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use tempfile::TempDir;
+
+fn read_to_string_best_effort(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+fn run_git(cwd: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn init_local_remote_with_head_main(base: &Path) -> PathBuf {
+    let origin = base.join("origin.git");
+    run_git(base, &["init", "--bare", origin.to_str().unwrap()]);
+
+    let seed = base.join("seed");
+    run_git(
+        base,
+        &["clone", origin.to_str().unwrap(), seed.to_str().unwrap()],
+    );
+    run_git(&seed, &["checkout", "-b", "main"]);
+    fs::write(seed.join("README.md"), "hello\n").unwrap();
+    run_git(&seed, &["add", "."]);
+    run_git(
+        &seed,
+        &[
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-m",
+            "init",
+        ],
+    );
+    run_git(&seed, &["push", "-u", "origin", "main"]);
+    run_git(
+        base,
+        &[
+            "-C",
+            origin.to_str().unwrap(),
+            "symbolic-ref",
+            "HEAD",
+            "refs/heads/main",
+        ],
+    );
+
+    origin
+}
+
+fn write_executable(path: &Path, contents: &str) {
+    fs::write(path, contents).unwrap();
+    let mut perms = fs::metadata(path).unwrap().permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(path, perms).unwrap();
+}
+
+/// `claude` and `codex` stand-ins that answer without doing anything.
+fn setup_fake_binaries() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let bin_dir = dir.path().join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+
+    write_executable(
+        &bin_dir.join("claude"),
+        r#"#!/usr/bin/env bash
+while IFS= read -r line; do
+  echo '{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"(fake claude) ok"}]}}'
+done
+"#,
+    );
+    write_executable(
+        &bin_dir.join("codex"),
+        r#"#!/usr/bin/env bash
+echo '{"type":"thread.started","thread_id":"t-123"}'
+echo '{"type":"item.completed","item":{"id":"i-1","type":"agent_message","text":"(fake codex) ok"}}'
+echo '{"type":"turn.completed","usage":{"input_tokens":1,"cached_input_tokens":0,"output_tokens":1}}'
+"#,
+    );
+
+    dir
+}
+
+fn wait_for_daemon_ready(dir: &TempDir) {
+    let log_path = dir.path().join("murmur.log");
+    let sock_path = dir.path().join("murmur.sock");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if Instant::now() > deadline {
+            let log = read_to_string_best_effort(&log_path);
+            panic!("timed out waiting for daemon ready; log was: {log}");
+        }
+
+        if sock_path.exists() {
+            let log = read_to_string_best_effort(&log_path);
+            if log.contains("daemon ready") {
+                return;
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn spawn_daemon(dir: &TempDir, bins: &TempDir) -> std::process::Child {
+    let mut path = bins.path().join("bin").to_string_lossy().into_owned();
+    if let Some(existing) = env::var_os("PATH") {
+        path.push(':');
+        path.push_str(&existing.to_string_lossy());
+    }
+
+    let child = Command::new(assert_cmd::cargo::cargo_bin!("mm"))
+        .env("MURMUR_DIR", dir.path())
+        .env("PATH", path)
+        .args(["server", "start", "--foreground"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    wait_for_daemon_ready(dir);
+    child
+}
+
+fn shutdown_daemon(dir: &TempDir, mut child: std::process::Child) {
+    let mut shutdown = cargo_bin_cmd!("mm");
+    shutdown.env("MURMUR_DIR", dir.path());
+    shutdown.args(["server", "shutdown"]);
+    shutdown.assert().success();
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            assert!(status.success(), "status: {status:?}");
+            break;
+        }
+        if Instant::now() > deadline {
+            let pid = Pid::from_raw(child.id() as i32);
+            let _ = kill(pid, Signal::SIGKILL);
+            panic!("timed out waiting for daemon to exit after shutdown");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn mm(murmur_dir: &TempDir, args: &[&str]) -> String {
+    let mut cmd = cargo_bin_cmd!("mm");
+    cmd.env("MURMUR_DIR", murmur_dir.path());
+    cmd.args(args);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    String::from_utf8_lossy(&out).trim().to_owned()
+}
+
+/// Adds project `demo`, on claude agents, with a `needs-plan` issue routed to
+/// a planner and an `ml` issue routed to codex coding agents. Routing rules live only in
+/// `config.toml`, so the daemon is restarted to load them. Returns the
+/// restarted daemon and the two issue ids.
+fn setup_routed_project(
+    murmur_dir: &TempDir,
+    bins: &TempDir,
+    origin: &Path,
+) -> (std::process::Child, String, String) {
+    let daemon = spawn_daemon(murmur_dir, bins);
+    mm(
+        murmur_dir,
+        &[
+            "project",
+            "add",
+            "demo",
+            "--remote-url",
+            origin.to_str().unwrap(),
+        ],
+    );
+    let planned = mm(
+        murmur_dir,
+        &[
+            "issue",
+            "create",
+            "-p",
+            "demo",
+            "Plan me",
+            "--label",
+            "needs-plan",
+        ],
+    );
+    let ml = mm(
+        murmur_dir,
+        &["issue", "create", "-p", "demo", "Train it", "--label", "ml"],
+    );
+    shutdown_daemon(murmur_dir, daemon);
+
+    let config_path = murmur_dir.path().join("config").join("config.toml");
+    let mut config: toml::Table =
+        toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    let project = config["projects"].as_array_mut().unwrap()[0]
+        .as_table_mut()
+        .unwrap();
+    project.insert("agent-backend".to_owned(), "claude".into());
+    project.insert(
+        "routing".to_owned(),
+        toml::from_str::<toml::Table>(
+            r#"
+routing = [
+  { label = "needs-plan", role = "planner" },
+  { label = "ml", backend = "codex" },
+]
+"#,
+        )
+        .unwrap()["routing"]
+            .clone(),
+    );
+    fs::write(&config_path, toml::to_string(&config).unwrap()).unwrap();
+
+    (spawn_daemon(murmur_dir, bins), planned, ml)
+}
+
+/// Runtime records of the daemon's agents, as persisted in `agents.json`.
+fn agents(murmur_dir: &TempDir) -> Vec<serde_json::Value> {
+    let path = murmur_dir.path().join("runtime").join("agents.json");
+    serde_json::from_str(&read_to_string_best_effort(&path)).unwrap_or_default()
+}
+
+fn agent_on<'a>(agents: &'a [serde_json::Value], issue_id: &str) -> Option<&'a serde_json::Value> {
+    agents.iter().find(|a| a["issue_id"] == issue_id)
+}
+
+#[test]
+fn orchestrator_spawns_routed_roles_and_clears_the_planner_label() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let (daemon, planned, ml) = setup_routed_project(&murmur_dir, &bins, &origin);
+
+    mm(&murmur_dir, &["project", "start", "demo"]);
+
+    let deadline = Instant::now() + Duration::from_secs(15);
+    let (planner, coder) = loop {
+        let agents = agents(&murmur_dir);
+        if let (Some(p), Some(c)) = (agent_on(&agents, &planned), agent_on(&agents, &ml)) {
+            break (p.clone(), c.clone());
+        }
+        assert!(
+            Instant::now() < deadline,
+            "routed agents not spawned: {agents:?}"
+        );
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    assert_eq!(planner["role"], "planner", "{planner}");
+    assert_eq!(planner["backend"], "claude", "{planner}");
+    assert_eq!(coder["role"], "coding", "{coder}");
+    assert_eq!(coder["backend"], "codex", "{coder}");
+
+    mm(&murmur_dir, &["project", "stop", "demo"]);
+
+    let mut done = cargo_bin_cmd!("mm");
+    done.env("MURMUR_DIR", murmur_dir.path());
+    done.env("MURMUR_AGENT_ID", planner["id"].as_str().unwrap());
+    done.args(["agent", "done"]);
+    done.assert().success();
+
+    let show = mm(&murmur_dir, &["issue", "show", "-p", "demo", &planned]);
+    let labels = show
+        .lines()
+        .find_map(|l| l.strip_prefix("labels\t"))
+        .unwrap_or_default();
+    assert!(!labels.contains("needs-plan"), "{show}");
+    let agents = agents(&murmur_dir);
+    assert!(agent_on(&agents, &planned).is_none(), "{agents:?}");
+
+    shutdown_daemon(&murmur_dir, daemon);
+}
+
+#[test]
+fn agent_claim_rejects_issues_routed_elsewhere() {
+    let tmp = TempDir::new().unwrap();
+    let origin = init_local_remote_with_head_main(tmp.path());
+    let murmur_dir = TempDir::new().unwrap();
+    let bins = setup_fake_binaries();
+    let (daemon, planned, ml) = setup_routed_project(&murmur_dir, &bins, &origin);
+
+    let claude = mm(&murmur_dir, &["agent", "create", "demo", "scratch-1"]);
+    let codex = mm(
+        &murmur_dir,
+        &["agent", "create", "demo", "scratch-2", "--backend", "codex"],
+    );
+    let claim = |agent_id: &str, issue_id: &str| {
+        let mut cmd = cargo_bin_cmd!("mm");
+        cmd.env("MURMUR_DIR", murmur_dir.path());
+        cmd.env("MURMUR_AGENT_ID", agent_id);
+        cmd.args(["agent", "claim", issue_id]);
+        cmd.assert()
+    };
+
+    let out = claim(&claude, &ml).failure().get_output().stderr.clone();
+    let err = String::from_utf8_lossy(&out);
+    assert!(err.contains("routed to codex agents"), "{err}");
+
+    let out = claim(&codex, &planned)
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let err = String::from_utf8_lossy(&out);
+    assert!(err.contains("routed to a planner first"), "{err}");
+
+    claim(&codex, &ml).success();
+
+    shutdown_daemon(&murmur_dir, daemon);
+}